@@ -17,7 +17,7 @@ pub const BEACON_MULTICAST_GROUP: Ipv4Addr = Ipv4Addr::new(224, 0, 0, 42);
 pub const BEACON_PORT: u16 = 4242;
 pub const BEACON_HEADER: &[u8; 6] = b"BEACON";
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct AlivenessState {
     pub hostname: String,
     pub interface_name: String,
@@ -1,2 +1,3 @@
 pub mod microphone_recorder;
+pub mod whistle_classifier;
 pub mod whistle_detection;
@@ -0,0 +1,407 @@
+use std::{
+    f32::consts::PI,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use color_eyre::{eyre::bail, Result};
+use compiled_nn::CompiledNN;
+use context_attribute::context;
+use framework::{deserialize_not_implemented, AdditionalOutput, MainOutput};
+use hardware::PathsInterface;
+use log::warn;
+use rustfft::{num_complex::Complex32, num_traits::Zero, Fft, FftPlanner};
+use serde::{Deserialize, Serialize};
+use types::{
+    parameters::{WhistleClassifierParameters, WhistleDetector},
+    samples::Samples,
+    whistle::Whistle,
+};
+
+use crate::whistle_detection::{AUDIO_SAMPLE_RATE, NUMBER_OF_AUDIO_SAMPLES};
+
+// The feature extraction has to match tools/machine-learning/whistle_classifier/train.py
+const FRAME_LENGTH: usize = 512;
+const HOP_LENGTH: usize = 256;
+const NUMBER_OF_FRAMES: usize = (NUMBER_OF_AUDIO_SAMPLES - FRAME_LENGTH) / HOP_LENGTH + 1;
+const NUMBER_OF_FREQUENCY_BINS: usize = FRAME_LENGTH / 2 + 1;
+const NUMBER_OF_MEL_BANDS: usize = 40;
+const MINIMUM_FREQUENCY: f32 = 500.0;
+const MAXIMUM_FREQUENCY: f32 = 10000.0;
+const LOG_OFFSET: f32 = 1e-6;
+
+struct NeuralNetwork {
+    path: PathBuf,
+    network: CompiledNN,
+}
+
+unsafe impl Send for NeuralNetwork {}
+
+#[derive(Deserialize, Serialize)]
+pub struct WhistleClassifier {
+    neural_networks: PathBuf,
+    #[serde(skip)]
+    neural_network: Option<NeuralNetwork>,
+    /// Network which could not be loaded, not retried until the parameter changes
+    failed_path: Option<PathBuf>,
+    #[serde(skip, default = "deserialize_not_implemented")]
+    fft: Arc<dyn Fft<f32>>,
+    #[serde(skip)]
+    scratch: Vec<Complex32>,
+    mel_filter_bank: Vec<Vec<f32>>,
+}
+
+#[context]
+pub struct CreationContext {
+    hardware_interface: HardwareInterface,
+}
+
+#[context]
+pub struct CycleContext {
+    detector: Parameter<WhistleDetector, "whistle_detection.detector">,
+    parameters: Parameter<WhistleClassifierParameters, "whistle_classifier">,
+
+    samples: Input<Samples, "samples">,
+    whistle_probabilities: AdditionalOutput<Vec<f32>, "whistle_probabilities">,
+}
+
+#[context]
+#[derive(Default)]
+pub struct MainOutputs {
+    pub classified_whistle: MainOutput<Option<Whistle>>,
+}
+
+impl WhistleClassifier {
+    pub fn new(context: CreationContext<impl PathsInterface>) -> Result<Self> {
+        let paths = context.hardware_interface.get_paths();
+        Ok(Self::with_neural_networks(paths.neural_networks))
+    }
+
+    fn with_neural_networks(neural_networks: PathBuf) -> Self {
+        let fft = FftPlanner::new().plan_fft_forward(FRAME_LENGTH);
+        let scratch = vec![Complex32::zero(); fft.get_inplace_scratch_len()];
+        Self {
+            neural_networks,
+            neural_network: None,
+            failed_path: None,
+            fft,
+            scratch,
+            mel_filter_bank: mel_filter_bank(
+                NUMBER_OF_MEL_BANDS,
+                FRAME_LENGTH,
+                AUDIO_SAMPLE_RATE as f32,
+                MINIMUM_FREQUENCY,
+                MAXIMUM_FREQUENCY,
+            ),
+        }
+    }
+
+    pub fn cycle(&mut self, mut context: CycleContext) -> Result<MainOutputs> {
+        if *context.detector != WhistleDetector::Learned {
+            self.neural_network = None;
+            self.failed_path = None;
+            return Ok(MainOutputs::default());
+        }
+
+        // without a network, the whistle detection falls back to the spectral detector
+        if self
+            .neural_network(&context.parameters.neural_network)
+            .is_none()
+        {
+            return Ok(MainOutputs::default());
+        }
+        let features: Vec<_> = context
+            .samples
+            .channels_of_samples
+            .iter()
+            .map(|buffer| self.log_mel_spectrogram(buffer))
+            .collect();
+        let Some(NeuralNetwork { network, .. }) = self.neural_network.as_mut() else {
+            return Ok(MainOutputs::default());
+        };
+        let probabilities: Vec<_> = features
+            .iter()
+            .map(|features| {
+                for (input, &feature) in network.input_mut(0).data.iter_mut().zip(features) {
+                    *input = feature;
+                }
+                network.apply();
+                network.output(0).data[0]
+            })
+            .collect();
+        context
+            .whistle_probabilities
+            .fill_if_subscribed(|| probabilities.clone());
+
+        let is_detected = probabilities
+            .iter()
+            .map(|&probability| probability > context.parameters.detection_threshold)
+            .collect();
+        Ok(MainOutputs {
+            classified_whistle: Some(Whistle {
+                is_detected,
+                time_differences_of_arrival: Vec::new(),
+            })
+            .into(),
+        })
+    }
+
+    fn neural_network(&mut self, file_name: &Path) -> Option<&mut CompiledNN> {
+        let path = self.neural_networks.join(file_name);
+        if self.failed_path.as_ref() == Some(&path) {
+            return None;
+        }
+        if let Err(error) = self.load_neural_network(path.clone()) {
+            warn!("falling back to spectral whistle detection: {error:#}");
+            self.neural_network = None;
+            self.failed_path = Some(path);
+            return None;
+        }
+        self.failed_path = None;
+        self.neural_network
+            .as_mut()
+            .map(|neural_network| &mut neural_network.network)
+    }
+
+    fn load_neural_network(&mut self, path: PathBuf) -> Result<()> {
+        if self
+            .neural_network
+            .as_ref()
+            .is_some_and(|neural_network| neural_network.path == path)
+        {
+            return Ok(());
+        }
+        if !path.exists() {
+            bail!("whistle classifier {} does not exist", path.display());
+        }
+        let mut network = CompiledNN::default();
+        network.compile(path.clone());
+        let input_size = network.input_mut(0).data.len();
+        let feature_size = NUMBER_OF_FRAMES * NUMBER_OF_MEL_BANDS;
+        if input_size != feature_size {
+            bail!(
+                "whistle classifier {} expects {input_size} inputs but the features have {feature_size}",
+                path.display()
+            );
+        }
+        self.neural_network = Some(NeuralNetwork { path, network });
+        Ok(())
+    }
+
+    /// Log mel band energies of overlapping Hann windowed frames, ordered frame by frame
+    fn log_mel_spectrogram(&mut self, buffer: &[f32]) -> Vec<f32> {
+        let mut features = Vec::with_capacity(NUMBER_OF_FRAMES * NUMBER_OF_MEL_BANDS);
+        for frame in buffer.windows(FRAME_LENGTH).step_by(HOP_LENGTH) {
+            let mut frame: Vec<_> = frame
+                .iter()
+                .enumerate()
+                .map(|(i, &sample)| {
+                    let hann = (PI * i as f32 / FRAME_LENGTH as f32).sin().powi(2);
+                    Complex32::new(hann * sample, 0.0)
+                })
+                .collect();
+            self.fft.process_with_scratch(&mut frame, &mut self.scratch);
+            let power_spectrum: Vec<_> = frame
+                .iter()
+                .take(NUMBER_OF_FREQUENCY_BINS)
+                .map(|value| value.norm_sqr() / FRAME_LENGTH as f32)
+                .collect();
+            features.extend(self.mel_filter_bank.iter().map(|filter| {
+                let energy: f32 = filter
+                    .iter()
+                    .zip(&power_spectrum)
+                    .map(|(weight, power)| weight * power)
+                    .sum();
+                (energy + LOG_OFFSET).ln()
+            }));
+        }
+        features
+    }
+}
+
+fn hertz_to_mel(frequency: f32) -> f32 {
+    2595.0 * (1.0 + frequency / 700.0).log10()
+}
+
+fn mel_to_hertz(mel: f32) -> f32 {
+    700.0 * (10.0_f32.powf(mel / 2595.0) - 1.0)
+}
+
+/// Triangular filters equally spaced on the mel scale, each with one weight per frequency bin
+fn mel_filter_bank(
+    number_of_bands: usize,
+    frame_length: usize,
+    sample_rate: f32,
+    minimum_frequency: f32,
+    maximum_frequency: f32,
+) -> Vec<Vec<f32>> {
+    let minimum_mel = hertz_to_mel(minimum_frequency);
+    let maximum_mel = hertz_to_mel(maximum_frequency);
+    let corner_frequencies: Vec<_> = (0..number_of_bands + 2)
+        .map(|index| {
+            mel_to_hertz(
+                minimum_mel
+                    + (maximum_mel - minimum_mel) * index as f32 / (number_of_bands + 1) as f32,
+            )
+        })
+        .collect();
+    let frequency_resolution = sample_rate / frame_length as f32;
+    corner_frequencies
+        .windows(3)
+        .map(|corners| {
+            let (lower, center, upper) = (corners[0], corners[1], corners[2]);
+            (0..frame_length / 2 + 1)
+                .map(|bin| {
+                    let frequency = bin as f32 * frequency_resolution;
+                    let rising = (frequency - lower) / (center - lower);
+                    let falling = (upper - frequency) / (upper - center);
+                    rising.min(falling).max(0.0)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use types::parameters::WhistleDetectionParameters;
+
+    use crate::whistle_detection::{self, WhistleDetection};
+
+    use super::*;
+
+    #[test]
+    fn tone_excites_mel_band_around_its_frequency() {
+        let filter_bank = mel_filter_bank(
+            NUMBER_OF_MEL_BANDS,
+            FRAME_LENGTH,
+            AUDIO_SAMPLE_RATE as f32,
+            MINIMUM_FREQUENCY,
+            MAXIMUM_FREQUENCY,
+        );
+        let frequency_resolution = AUDIO_SAMPLE_RATE as f32 / FRAME_LENGTH as f32;
+        let tone_bin = (3000.0 / frequency_resolution).round() as usize;
+
+        let strongest_band = filter_bank
+            .iter()
+            .enumerate()
+            .max_by(|(_, left), (_, right)| left[tone_bin].total_cmp(&right[tone_bin]))
+            .map(|(index, _)| index)
+            .unwrap();
+
+        let minimum_mel = hertz_to_mel(MINIMUM_FREQUENCY);
+        let maximum_mel = hertz_to_mel(MAXIMUM_FREQUENCY);
+        let center_of_strongest_band = mel_to_hertz(
+            minimum_mel
+                + (maximum_mel - minimum_mel) * (strongest_band + 1) as f32
+                    / (NUMBER_OF_MEL_BANDS + 1) as f32,
+        );
+        assert!((center_of_strongest_band - 3000.0).abs() < 200.0);
+        assert_eq!(filter_bank.len(), NUMBER_OF_MEL_BANDS);
+        assert!(filter_bank
+            .iter()
+            .all(|filter| filter.len() == NUMBER_OF_FREQUENCY_BINS));
+    }
+
+    #[test]
+    fn whistle_features_peak_in_every_frame() {
+        let mut classifier = WhistleClassifier::with_neural_networks(PathBuf::new());
+        let buffer: Vec<_> = (0..NUMBER_OF_AUDIO_SAMPLES)
+            .map(|index| (2.0 * PI * 3000.0 * index as f32 / AUDIO_SAMPLE_RATE as f32).sin())
+            .collect();
+
+        let features = classifier.log_mel_spectrogram(&buffer);
+
+        assert_eq!(features.len(), NUMBER_OF_FRAMES * NUMBER_OF_MEL_BANDS);
+        let strongest_bands: Vec<_> = features
+            .chunks(NUMBER_OF_MEL_BANDS)
+            .map(|frame| {
+                frame
+                    .iter()
+                    .enumerate()
+                    .max_by(|(_, left), (_, right)| left.total_cmp(right))
+                    .map(|(index, _)| index)
+                    .unwrap()
+            })
+            .collect();
+        assert!(strongest_bands
+            .iter()
+            .all(|&band| band == strongest_bands[0]));
+        let minimum_mel = hertz_to_mel(MINIMUM_FREQUENCY);
+        let maximum_mel = hertz_to_mel(MAXIMUM_FREQUENCY);
+        let center_of_strongest_band = mel_to_hertz(
+            minimum_mel
+                + (maximum_mel - minimum_mel) * (strongest_bands[0] + 1) as f32
+                    / (NUMBER_OF_MEL_BANDS + 1) as f32,
+        );
+        assert!((center_of_strongest_band - 3000.0).abs() < 200.0);
+    }
+
+    #[test]
+    fn cycle_without_neural_network_falls_back_to_spectral_detection() {
+        let mut classifier = WhistleClassifier::with_neural_networks(PathBuf::from("/nonexistent"));
+        let tone: Vec<_> = (0..NUMBER_OF_AUDIO_SAMPLES)
+            .map(|index| (2.0 * PI * 3000.0 * index as f32 / AUDIO_SAMPLE_RATE as f32).sin())
+            .collect();
+        let samples = Samples {
+            rate: AUDIO_SAMPLE_RATE,
+            channels_of_samples: Arc::new(vec![tone; 4]),
+        };
+        let classifier_parameters = WhistleClassifierParameters {
+            neural_network: PathBuf::from("whistle_classifier.hdf5"),
+            detection_threshold: 0.8,
+        };
+        let mut whistle_probabilities = None;
+
+        let classified_whistle = classifier
+            .cycle(CycleContext::new(
+                &WhistleDetector::Learned,
+                &classifier_parameters,
+                &samples,
+                AdditionalOutput::new(true, &mut whistle_probabilities),
+            ))
+            .unwrap()
+            .classified_whistle
+            .value;
+
+        assert!(classified_whistle.is_none());
+        assert!(whistle_probabilities.is_none());
+
+        let detection_parameters = WhistleDetectionParameters {
+            detector: WhistleDetector::Learned,
+            detection_band: 2000.0..4000.0,
+            background_noise_scaling: 1.6,
+            whistle_scaling: 3.8,
+            number_of_chunks: 16,
+        };
+        let mut audio_spectrums = None;
+        let mut detection_infos = None;
+        let detected_whistle = WhistleDetection::new(whistle_detection::CreationContext {})
+            .unwrap()
+            .cycle(whistle_detection::CycleContext::new(
+                &detection_parameters,
+                &samples,
+                classified_whistle.as_ref(),
+                AdditionalOutput::new(false, &mut audio_spectrums),
+                AdditionalOutput::new(false, &mut detection_infos),
+            ))
+            .unwrap()
+            .detected_whistle
+            .value;
+
+        assert_eq!(detected_whistle.is_detected, vec![true; 4]);
+    }
+
+    #[test]
+    fn missing_neural_network_falls_back_to_spectral_detection() {
+        let mut classifier = WhistleClassifier::with_neural_networks(PathBuf::from("/nonexistent"));
+
+        assert!(classifier
+            .neural_network(Path::new("whistle_classifier.hdf5"))
+            .is_none());
+        assert!(classifier.failed_path.is_some());
+        assert!(classifier
+            .neural_network(Path::new("whistle_classifier.hdf5"))
+            .is_none());
+    }
+}
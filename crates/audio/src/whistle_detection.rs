@@ -1,4 +1,4 @@
-use std::{f32::consts::PI, sync::Arc};
+use std::{f32::consts::PI, ops::Range, sync::Arc};
 
 use color_eyre::Result;
 use context_attribute::context;
@@ -11,20 +11,24 @@ use rustfft::{
 };
 use serde::{Deserialize, Serialize};
 use types::{
-    parameters::WhistleDetectionParameters,
+    parameters::{WhistleDetectionParameters, WhistleDetector},
     samples::Samples,
-    whistle::{DetectionInfo, Whistle},
+    whistle::{DetectionInfo, TimeDifferenceOfArrival, Whistle},
 };
 
 pub const AUDIO_SAMPLE_RATE: u32 = 44100;
 pub const NUMBER_OF_AUDIO_CHANNELS: usize = 4;
 pub const NUMBER_OF_AUDIO_SAMPLES: usize = 2048;
 const NUMBER_OF_FREQUENCY_SAMPLES: usize = NUMBER_OF_AUDIO_SAMPLES / 2;
+/// The head microphones are at most about 12cm apart, which sound travels in about 16 samples
+const MAXIMUM_DELAY_IN_SAMPLES: isize = 20;
 
 #[derive(Deserialize, Serialize)]
 pub struct WhistleDetection {
     #[serde(skip, default = "deserialize_not_implemented")]
     fft: Arc<dyn Fft<f32>>,
+    #[serde(skip, default = "deserialize_not_implemented")]
+    inverse_fft: Arc<dyn Fft<f32>>,
     #[serde(skip)]
     scratch: Vec<Complex32>,
 }
@@ -37,6 +41,7 @@ pub struct CycleContext {
     parameters: Parameter<WhistleDetectionParameters, "whistle_detection">,
 
     samples: Input<Samples, "samples">,
+    classified_whistle: Input<Option<Whistle>, "classified_whistle?">,
     audio_spectrums: AdditionalOutput<Vec<Vec<(f32, f32)>>, "audio_spectrums">,
     detection_infos: AdditionalOutput<Vec<DetectionInfo>, "detection_infos">,
 }
@@ -51,39 +56,59 @@ impl WhistleDetection {
     pub fn new(_context: CreationContext) -> Result<Self> {
         let mut planner = FftPlanner::new();
         let fft = planner.plan_fft_forward(NUMBER_OF_AUDIO_SAMPLES);
-        let scratch = vec![Complex32::zero(); fft.get_inplace_scratch_len()];
-        Ok(Self { fft, scratch })
+        let inverse_fft = planner.plan_fft_inverse(NUMBER_OF_AUDIO_SAMPLES);
+        let scratch_length = fft
+            .get_inplace_scratch_len()
+            .max(inverse_fft.get_inplace_scratch_len());
+        let scratch = vec![Complex32::zero(); scratch_length];
+        Ok(Self {
+            fft,
+            inverse_fft,
+            scratch,
+        })
     }
 
     pub fn cycle(&mut self, mut context: CycleContext) -> Result<MainOutputs> {
         context.audio_spectrums.fill_if_subscribed(Vec::new);
         context.detection_infos.fill_if_subscribed(Vec::new);
-        let is_detected = context
+        let spectrums: Vec<_> = context
             .samples
             .channels_of_samples
             .iter()
-            .map(|buffer| {
-                self.is_whistle_detected_in_buffer(
-                    buffer,
+            .map(|buffer| self.spectrum(buffer))
+            .collect();
+        let spectral_detections: Vec<_> = spectrums
+            .iter()
+            .map(|spectrum| {
+                is_whistle_detected_in_spectrum(
+                    spectrum,
                     context.parameters,
                     &mut context.audio_spectrums,
                     &mut context.detection_infos,
                 )
             })
             .collect();
+        let is_detected = match (context.parameters.detector, context.classified_whistle) {
+            (WhistleDetector::Learned, Some(classified_whistle)) => {
+                classified_whistle.is_detected.clone()
+            }
+            _ => spectral_detections,
+        };
+        let time_differences_of_arrival = if is_detected.contains(&true) {
+            self.time_differences_of_arrival(&spectrums, &context.parameters.detection_band)
+        } else {
+            Vec::new()
+        };
         Ok(MainOutputs {
-            detected_whistle: Whistle { is_detected }.into(),
+            detected_whistle: Whistle {
+                is_detected,
+                time_differences_of_arrival,
+            }
+            .into(),
         })
     }
 
-    fn is_whistle_detected_in_buffer(
-        &mut self,
-        buffer: &[f32],
-        detection_parameters: &WhistleDetectionParameters,
-        audio_spectrums: &mut AdditionalOutput<Vec<Vec<(f32, f32)>>>,
-        detection_infos: &mut AdditionalOutput<Vec<DetectionInfo>>,
-    ) -> bool {
-        let frequency_resolution = AUDIO_SAMPLE_RATE as f32 / NUMBER_OF_AUDIO_SAMPLES as f32;
+    fn spectrum(&mut self, buffer: &[f32]) -> Vec<Complex32> {
         let mut buffer: Vec<_> = buffer
             .iter()
             .enumerate()
@@ -96,35 +121,121 @@ impl WhistleDetection {
             .collect();
         self.fft
             .process_with_scratch(&mut buffer, &mut self.scratch);
-        let absolute_values: Vec<_> = buffer
-            .iter()
-            .take(NUMBER_OF_FREQUENCY_SAMPLES)
-            .map(|sample| {
-                let normalized_sample = sample * 1.0 / (NUMBER_OF_FREQUENCY_SAMPLES as f32).sqrt();
-                normalized_sample.abs()
-            })
-            .collect();
-        audio_spectrums.mutate_if_subscribed(|spectrums| {
-            let spectrum = absolute_values
-                .iter()
-                .enumerate()
-                .map(|(i, &value)| (i as f32 * frequency_resolution, value))
-                .collect();
-            if let Some(spectrums) = spectrums {
-                spectrums.push(spectrum);
+        buffer
+    }
+
+    /// Estimates the delays between all pairs of microphones using the generalized cross
+    /// correlation with phase transform (GCC-PHAT) restricted to the whistle band
+    fn time_differences_of_arrival(
+        &mut self,
+        spectrums: &[Vec<Complex32>],
+        detection_band: &Range<f32>,
+    ) -> Vec<TimeDifferenceOfArrival> {
+        let mut time_differences_of_arrival = Vec::new();
+        for first_channel in 0..spectrums.len() {
+            for second_channel in first_channel + 1..spectrums.len() {
+                if let Some((delay, coherence)) = self.phase_transform_delay(
+                    &spectrums[first_channel],
+                    &spectrums[second_channel],
+                    detection_band,
+                ) {
+                    time_differences_of_arrival.push(TimeDifferenceOfArrival {
+                        first_channel,
+                        second_channel,
+                        delay,
+                        coherence,
+                    });
+                }
             }
-        });
-        let (detected, detection_info) =
-            spectrum_contains_whistle(&absolute_values, detection_parameters, frequency_resolution);
-        detection_infos.mutate_if_subscribed(|infos| {
-            if let Some(infos) = infos {
-                infos.push(detection_info);
+        }
+        time_differences_of_arrival
+    }
+
+    fn phase_transform_delay(
+        &mut self,
+        first_spectrum: &[Complex32],
+        second_spectrum: &[Complex32],
+        detection_band: &Range<f32>,
+    ) -> Option<(f32, f32)> {
+        let frequency_resolution = AUDIO_SAMPLE_RATE as f32 / NUMBER_OF_AUDIO_SAMPLES as f32;
+        let min_frequency_index = ((detection_band.start / frequency_resolution).ceil() as usize)
+            .clamp(1, NUMBER_OF_FREQUENCY_SAMPLES);
+        let max_frequency_index = ((detection_band.end / frequency_resolution).ceil() as usize)
+            .clamp(min_frequency_index, NUMBER_OF_FREQUENCY_SAMPLES);
+
+        let mut cross_spectrum = vec![Complex32::zero(); NUMBER_OF_AUDIO_SAMPLES];
+        let mut number_of_bins = 0;
+        for index in min_frequency_index..max_frequency_index {
+            let cross_power = first_spectrum[index] * second_spectrum[index].conj();
+            let magnitude = cross_power.norm();
+            if magnitude > f32::EPSILON {
+                let weighted = cross_power / magnitude;
+                cross_spectrum[index] = weighted;
+                cross_spectrum[NUMBER_OF_AUDIO_SAMPLES - index] = weighted.conj();
+                number_of_bins += 2;
             }
-        });
-        detected
+        }
+        if number_of_bins == 0 {
+            return None;
+        }
+        self.inverse_fft
+            .process_with_scratch(&mut cross_spectrum, &mut self.scratch);
+
+        let correlation_at = |lag: isize| {
+            cross_spectrum[lag.rem_euclid(NUMBER_OF_AUDIO_SAMPLES as isize) as usize].re
+                / number_of_bins as f32
+        };
+        let best_lag = (-MAXIMUM_DELAY_IN_SAMPLES..=MAXIMUM_DELAY_IN_SAMPLES)
+            .max_by(|&left, &right| correlation_at(left).total_cmp(&correlation_at(right)))?;
+        let peak = correlation_at(best_lag);
+        let previous = correlation_at(best_lag - 1);
+        let next = correlation_at(best_lag + 1);
+        let curvature = previous - 2.0 * peak + next;
+        let sub_sample_offset = if curvature < 0.0 {
+            (0.5 * (previous - next) / curvature).clamp(-0.5, 0.5)
+        } else {
+            0.0
+        };
+        let delay = (best_lag as f32 + sub_sample_offset) / AUDIO_SAMPLE_RATE as f32;
+        Some((delay, peak))
     }
 }
 
+fn is_whistle_detected_in_spectrum(
+    spectrum: &[Complex32],
+    detection_parameters: &WhistleDetectionParameters,
+    audio_spectrums: &mut AdditionalOutput<Vec<Vec<(f32, f32)>>>,
+    detection_infos: &mut AdditionalOutput<Vec<DetectionInfo>>,
+) -> bool {
+    let frequency_resolution = AUDIO_SAMPLE_RATE as f32 / NUMBER_OF_AUDIO_SAMPLES as f32;
+    let absolute_values: Vec<_> = spectrum
+        .iter()
+        .take(NUMBER_OF_FREQUENCY_SAMPLES)
+        .map(|sample| {
+            let normalized_sample = sample * 1.0 / (NUMBER_OF_FREQUENCY_SAMPLES as f32).sqrt();
+            normalized_sample.abs()
+        })
+        .collect();
+    audio_spectrums.mutate_if_subscribed(|spectrums| {
+        let spectrum = absolute_values
+            .iter()
+            .enumerate()
+            .map(|(i, &value)| (i as f32 * frequency_resolution, value))
+            .collect();
+        if let Some(spectrums) = spectrums {
+            spectrums.push(spectrum);
+        }
+    });
+    let (detected, detection_info) =
+        spectrum_contains_whistle(&absolute_values, detection_parameters, frequency_resolution);
+    detection_infos.mutate_if_subscribed(|infos| {
+        if let Some(infos) = infos {
+            infos.push(detection_info);
+        }
+    });
+    detected
+}
+
 fn spectrum_contains_whistle(
     absolute_values: &[f32],
     detection_parameters: &WhistleDetectionParameters,
@@ -135,6 +246,7 @@ fn spectrum_contains_whistle(
         background_noise_scaling,
         whistle_scaling,
         number_of_chunks,
+        ..
     } = detection_parameters;
     let overall_mean = mean(absolute_values);
     let overall_standard_deviation = standard_deviation(absolute_values, overall_mean);
@@ -215,3 +327,45 @@ fn spectrum_contains_whistle(
     detection_info.whistle_mean = Some(whistle_mean);
     (whistle_mean > whistle_threshold, detection_info)
 }
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaChaRng;
+
+    use super::*;
+
+    #[test]
+    fn delays_of_shifted_noise_are_recovered() {
+        let delays_in_samples = [0, 3, -5, 12];
+        let mut random_number_generator = ChaChaRng::seed_from_u64(42);
+        let noise: Vec<f32> = (0..NUMBER_OF_AUDIO_SAMPLES + 2 * MAXIMUM_DELAY_IN_SAMPLES as usize)
+            .map(|_| random_number_generator.random_range(-1.0..1.0))
+            .collect();
+        let mut detection = WhistleDetection::new(CreationContext {}).unwrap();
+        let spectrums: Vec<_> = delays_in_samples
+            .iter()
+            .map(|&delay| {
+                let start = (MAXIMUM_DELAY_IN_SAMPLES - delay) as usize;
+                detection.spectrum(&noise[start..start + NUMBER_OF_AUDIO_SAMPLES])
+            })
+            .collect();
+
+        let time_differences_of_arrival =
+            detection.time_differences_of_arrival(&spectrums, &(2000.0..4000.0));
+
+        assert_eq!(time_differences_of_arrival.len(), 6);
+        for time_difference_of_arrival in time_differences_of_arrival {
+            let expected_delay = (delays_in_samples[time_difference_of_arrival.first_channel]
+                - delays_in_samples[time_difference_of_arrival.second_channel])
+                as f32
+                / AUDIO_SAMPLE_RATE as f32;
+            assert!(
+                (time_difference_of_arrival.delay - expected_delay).abs()
+                    < 0.5 / AUDIO_SAMPLE_RATE as f32,
+                "{time_difference_of_arrival:?} should have a delay of {expected_delay}"
+            );
+            assert!(time_difference_of_arrival.coherence > 0.5);
+        }
+    }
+}
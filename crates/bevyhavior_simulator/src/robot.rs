@@ -1,7 +1,9 @@
 use std::{
     convert::Into,
     f32::consts::FRAC_PI_2,
+    fs::{read_to_string, File},
     mem::take,
+    path::Path,
     sync::{mpsc, Arc},
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
@@ -26,15 +28,23 @@ use linear_algebra::{
     point, vector, Isometry2, Isometry3, Orientation2, Orientation3, Point2, Pose2, Pose3,
     Rotation2, Vector2,
 };
-use parameters::directory::deserialize;
+use parameters::{
+    directory::deserialize,
+    json::{prune_empty_objects, prune_equal_branches},
+};
 use projection::intrinsic::Intrinsic;
+use serde_json::Value;
 use spl_network_messages::{HulkMessage, PlayerNumber};
 use types::{
     ball_position::BallPosition,
+    behavior_fixture::{
+        BehaviorCalibrationController, BehaviorFixture, BehaviorFixtureParameters, BehaviorInputs,
+        BehaviorLocalization, BehaviorPoseDetection, BehaviorWalkSpeeds,
+    },
     filtered_whistle::FilteredWhistle,
     joints::Joints,
     messages::{IncomingMessage, OutgoingMessage},
-    motion_command::{HeadMotion, KickVariant},
+    motion_command::{HeadMotion, KickVariant, MotionCommand},
     motion_selection::MotionSafeExits,
     pose_kinds::PoseKind,
     robot_dimensions::RobotDimensions,
@@ -65,6 +75,7 @@ pub struct Robot {
     pub simulator_parameters: SimulatedRobotParameters,
     pub anchor: Pose2<Field>,
     pub anchor_side: Option<Side>,
+    last_motion_command: MotionCommand,
 
     pub cycler: Cycler<Interfake>,
     control_receiver: Receiver<(SystemTime, Database)>,
@@ -147,6 +158,7 @@ impl Robot {
             simulator_parameters,
             anchor: Pose2::zero(),
             anchor_side: None,
+            last_motion_command: MotionCommand::default(),
 
             cycler,
             control_receiver,
@@ -185,6 +197,7 @@ impl Robot {
         .main_outputs = self.database.main_outputs.clone();
         *self.parameters_sender.borrow_mut() = (SystemTime::now(), self.parameters.clone());
 
+        self.last_motion_command = self.database.main_outputs.motion_command.clone();
         self.cycler.cycle()?;
 
         let (_, database) = &*self.control_receiver.borrow_and_mark_as_seen();
@@ -193,6 +206,73 @@ impl Robot {
         Ok(())
     }
 
+    /// Captures the inputs and output of the last behavior cycle for fixture-based behavior tests
+    pub fn behavior_fixture(&self) -> Result<BehaviorFixture> {
+        let main_outputs = &self.database.main_outputs;
+        let parameters = &self.parameters;
+        let mut parameter_overrides = serde_json::to_value(BehaviorFixtureParameters {
+            behavior: parameters.behavior.clone(),
+            kick_selector: parameters.kick_selector.clone(),
+            in_walk_kicks: parameters.in_walk_kicks.clone(),
+            field_dimensions: parameters.field_dimensions,
+            pose_detection: BehaviorPoseDetection {
+                enable: parameters.pose_detection.enable,
+            },
+            keeper_motion: parameters.keeper_motion.clone(),
+            calibration_controller: BehaviorCalibrationController {
+                use_stand_head_unstiff_calibration: parameters
+                    .calibration_controller
+                    .use_stand_head_unstiff_calibration,
+            },
+            localization: BehaviorLocalization {
+                initial_poses: parameters.localization.initial_poses.clone(),
+            },
+            walk_speed: BehaviorWalkSpeeds {
+                defend: parameters.walk_speed.defend,
+                dribble: parameters.walk_speed.dribble,
+                intercept_ball: parameters.walk_speed.intercept_ball,
+                lost_ball: parameters.walk_speed.lost_ball,
+                search: parameters.walk_speed.search,
+                support: parameters.walk_speed.support,
+                walk_to_kickoff: parameters.walk_speed.walk_to_kickoff,
+                walk_to_penalty_kick: parameters.walk_speed.walk_to_penalty_kick,
+            },
+        })
+        .wrap_err("failed to serialize behavior parameters")?;
+        let default_parameters: Value = serde_json::from_str(
+            &read_to_string("etc/parameters/default.json")
+                .wrap_err("failed to read default parameters")?,
+        )
+        .wrap_err("failed to parse default parameters")?;
+        prune_equal_branches(&mut parameter_overrides, &default_parameters);
+        prune_empty_objects(&mut parameter_overrides);
+
+        Ok(BehaviorFixture {
+            inputs: BehaviorInputs {
+                has_ground_contact: main_outputs.has_ground_contact,
+                world_state: main_outputs.world_state.clone(),
+                dribble_path_plan: main_outputs.dribble_path_plan.clone(),
+                cycle_time: main_outputs.cycle_time,
+                is_localization_converged: main_outputs.is_localization_converged,
+                expected_referee_position: main_outputs.expected_referee_position,
+                last_motion_command: self.last_motion_command.clone(),
+            },
+            state: self
+                .database
+                .additional_outputs
+                .behavior_state
+                .unwrap_or_default(),
+            parameter_overrides,
+            expected_motion_command: main_outputs.motion_command.clone(),
+        })
+    }
+
+    pub fn save_behavior_fixture(&self, path: impl AsRef<Path>) -> Result<()> {
+        let file = File::create(path).wrap_err("failed to create fixture file")?;
+        serde_json::to_writer_pretty(file, &self.behavior_fixture()?)
+            .wrap_err("failed to serialize behavior fixture")
+    }
+
     pub fn field_of_view(&self) -> f32 {
         let image_size = vector![640.0, 480.0];
         let focal_lengths = self
@@ -484,6 +564,7 @@ pub fn cycle_robots(
             last_detection: whistle
                 .last_whistle
                 .map(|last_whistle| SystemTime::UNIX_EPOCH + last_whistle),
+            bearing: None,
         };
         let visual_referee_pose_kind = if matches!(
             robot.database.main_outputs.motion_command.head_motion(),
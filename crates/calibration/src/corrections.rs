@@ -1,13 +1,25 @@
-use nalgebra::{vector, Rotation3, SVector, UnitQuaternion};
+use std::ops::Range;
+
+use nalgebra::{vector, Rotation3, SVector, UnitQuaternion, Vector2};
 use serde::{Deserialize, Serialize};
 
 use approx_derive::{AbsDiffEq, RelativeEq};
 use linear_algebra::IntoTransform;
 use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
 use projection::camera_matrix::CameraMatrix;
-use types::camera_position::CameraPosition;
+use types::{
+    camera_position::CameraPosition, joints::Joints, lens_distortion::LensDistortion,
+    support_foot::Side,
+};
+
+use crate::joint_offsets::{correct_joint_offsets, JointOffsets};
 
-pub const AMOUNT_OF_PARAMETERS: usize = 9;
+pub const AMOUNT_OF_PARAMETERS: usize = 19;
+/// Yaw of the robot rotation correction, rotating the head like the head yaw offset
+pub const ROBOT_YAW_PARAMETER: usize = 2;
+/// Parameters before these are the rotations of robot and cameras
+pub const LENS_DISTORTION_PARAMETERS: Range<usize> = 9..13;
+pub const JOINT_OFFSET_PARAMETERS: Range<usize> = 13..AMOUNT_OF_PARAMETERS;
 
 #[derive(
     Clone,
@@ -28,6 +40,11 @@ pub struct Corrections {
     pub correction_in_robot: Rotation3<f32>,
     pub correction_in_camera_top: Rotation3<f32>,
     pub correction_in_camera_bottom: Rotation3<f32>,
+    /// Added to the radial coefficients `k1`, `k2` of the top camera lens distortion
+    pub radial_distortion_top: Vector2<f32>,
+    /// Added to the radial coefficients `k1`, `k2` of the bottom camera lens distortion
+    pub radial_distortion_bottom: Vector2<f32>,
+    pub joint_offsets: JointOffsets,
 }
 
 impl From<&SVector<f32, AMOUNT_OF_PARAMETERS>> for Corrections {
@@ -48,6 +65,11 @@ impl From<&SVector<f32, AMOUNT_OF_PARAMETERS>> for Corrections {
                 parameters[7],
                 parameters[8],
             ),
+            radial_distortion_top: vector![parameters[9], parameters[10]],
+            radial_distortion_bottom: vector![parameters[11], parameters[12]],
+            joint_offsets: JointOffsets::from_slice(
+                &parameters.as_slice()[JOINT_OFFSET_PARAMETERS],
+            ),
         }
     }
 }
@@ -59,7 +81,7 @@ impl From<&Corrections> for SVector<f32, AMOUNT_OF_PARAMETERS> {
             parameters.correction_in_camera_top.euler_angles();
         let (camera_bottom_roll, camera_bottom_pitch, camera_bottom_yaw) =
             parameters.correction_in_camera_bottom.euler_angles();
-        vector![
+        let rotations_and_lens_distortion = [
             robot_roll,
             robot_pitch,
             robot_yaw,
@@ -68,27 +90,91 @@ impl From<&Corrections> for SVector<f32, AMOUNT_OF_PARAMETERS> {
             camera_top_yaw,
             camera_bottom_roll,
             camera_bottom_pitch,
-            camera_bottom_yaw
-        ]
+            camera_bottom_yaw,
+            parameters.radial_distortion_top.x,
+            parameters.radial_distortion_top.y,
+            parameters.radial_distortion_bottom.x,
+            parameters.radial_distortion_bottom.y,
+        ];
+        SVector::from_iterator(
+            rotations_and_lens_distortion
+                .into_iter()
+                .chain(parameters.joint_offsets.as_array()),
+        )
     }
 }
 
+/// Joint offsets are only applied if the joint positions and support side during the measurement
+/// are known
 pub(crate) fn get_corrected_camera_matrix(
     input_matrix: &CameraMatrix,
     position: CameraPosition,
+    joint_positions: Option<(&Joints<f32>, Side)>,
     parameters: &Corrections,
 ) -> CameraMatrix {
-    input_matrix.to_corrected(
+    let joint_corrected_matrix;
+    let input_matrix = match joint_positions {
+        Some((joint_positions, support_side))
+            if parameters.joint_offsets != JointOffsets::default() =>
+        {
+            joint_corrected_matrix = correct_joint_offsets(
+                input_matrix,
+                joint_positions,
+                support_side,
+                &parameters.joint_offsets,
+            );
+            &joint_corrected_matrix
+        }
+        _ => input_matrix,
+    };
+    let (correction_in_camera, radial_distortion) = match position {
+        CameraPosition::Top => (
+            parameters.correction_in_camera_top,
+            parameters.radial_distortion_top,
+        ),
+        CameraPosition::Bottom => (
+            parameters.correction_in_camera_bottom,
+            parameters.radial_distortion_bottom,
+        ),
+    };
+    let corrected = input_matrix.to_corrected(
         UnitQuaternion::from_rotation_matrix(&parameters.correction_in_robot).framed_transform(),
-        match position {
-            CameraPosition::Top => {
-                UnitQuaternion::from_rotation_matrix(&parameters.correction_in_camera_top)
-                    .framed_transform()
-            }
-            CameraPosition::Bottom => {
-                UnitQuaternion::from_rotation_matrix(&parameters.correction_in_camera_bottom)
-                    .framed_transform()
+        UnitQuaternion::from_rotation_matrix(&correction_in_camera).framed_transform(),
+    );
+    if radial_distortion == Vector2::zeros() {
+        return corrected;
+    }
+    let lens_distortion =
+        correct_radial_distortion(corrected.intrinsics.distortion, radial_distortion);
+    corrected.with_lens_distortion(lens_distortion)
+}
+
+pub fn correct_radial_distortion(
+    lens_distortion: LensDistortion,
+    correction: Vector2<f32>,
+) -> LensDistortion {
+    match lens_distortion {
+        LensDistortion::None => LensDistortion::RadialTangential {
+            k1: correction.x,
+            k2: correction.y,
+            k3: 0.0,
+            p1: 0.0,
+            p2: 0.0,
+        },
+        LensDistortion::RadialTangential { k1, k2, k3, p1, p2 } => {
+            LensDistortion::RadialTangential {
+                k1: k1 + correction.x,
+                k2: k2 + correction.y,
+                k3,
+                p1,
+                p2,
             }
+        }
+        LensDistortion::Fisheye { k1, k2, k3, k4 } => LensDistortion::Fisheye {
+            k1: k1 + correction.x,
+            k2: k2 + correction.y,
+            k3,
+            k4,
         },
-    )
+    }
 }
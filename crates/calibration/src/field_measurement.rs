@@ -0,0 +1,268 @@
+use levenberg_marquardt::{LeastSquaresProblem, LevenbergMarquardt};
+use nalgebra::{vector, Const, DVector, Dyn, Matrix, Owned, SVector};
+use serde::{Deserialize, Serialize};
+
+use coordinate_systems::{Field, Ground};
+use geometry::line_segment::LineSegment;
+use linear_algebra::{distance, Isometry2};
+use types::{
+    field_dimensions::FieldDimensions,
+    field_marks::{field_marks_from_field_dimensions, FieldMark},
+};
+
+use crate::residuals::{ResidualVector, ResidualVectorStorage};
+
+/// Length, width, penalty area length and width, and center circle diameter
+pub const AMOUNT_OF_FIELD_PARAMETERS: usize = 5;
+
+pub type FieldJacobian = Matrix<f32, Dyn, Const<AMOUNT_OF_FIELD_PARAMETERS>, FieldJacobianStorage>;
+pub type FieldJacobianStorage = Owned<f32, Dyn, Const<AMOUNT_OF_FIELD_PARAMETERS>>;
+
+const EPSILON: f32 = 0.0001;
+
+/// Lines seen while the robot stands at a known pose
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Measurement {
+    pub ground_to_field: Isometry2<Ground, Field>,
+    pub lines: Vec<LineSegment<Ground>>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Estimate {
+    pub field_dimensions: FieldDimensions,
+    /// Distances of the measured line end points to their associated field marks in meters
+    pub residuals: Vec<f32>,
+    pub root_mean_square_error: f32,
+    pub associated_lines: usize,
+    pub unassociated_lines: usize,
+}
+
+/// Estimates field length, width, penalty area and center circle from measured lines
+///
+/// Lines are associated once with the closest field mark of the initial dimensions, lines further
+/// away than `maximum_association_distance` are ignored.
+pub fn estimate_field_dimensions(
+    initial_field_dimensions: FieldDimensions,
+    measurements: &[Measurement],
+    maximum_association_distance: f32,
+) -> Option<Estimate> {
+    let field_marks = field_marks_from_field_dimensions(&initial_field_dimensions);
+    let lines_in_field = measurements.iter().flat_map(|measurement| {
+        measurement.lines.iter().map(|line| {
+            LineSegment(
+                measurement.ground_to_field * line.0,
+                measurement.ground_to_field * line.1,
+            )
+        })
+    });
+    let mut unassociated_lines = 0;
+    let associations: Vec<_> = lines_in_field
+        .filter_map(|line| {
+            let association = field_marks
+                .iter()
+                .enumerate()
+                .map(|(index, field_mark)| (index, mean_distance(field_mark, line)))
+                .min_by(|(_, left), (_, right)| left.total_cmp(right))
+                .filter(|(_, distance)| *distance < maximum_association_distance)
+                .map(|(index, _)| Association {
+                    field_mark_index: index,
+                    line,
+                });
+            if association.is_none() {
+                unassociated_lines += 1;
+            }
+            association
+        })
+        .collect();
+    if associations.is_empty() {
+        return None;
+    }
+
+    let problem = FieldDimensionsProblem {
+        field_dimensions: initial_field_dimensions,
+        associations,
+    };
+    let (result, report) = LevenbergMarquardt::new().minimize(problem);
+    if !report.termination.was_successful() {
+        return None;
+    }
+    let residuals: Vec<f32> = result.residuals()?.iter().copied().collect();
+    let root_mean_square_error = (residuals
+        .iter()
+        .map(|residual| residual.powi(2))
+        .sum::<f32>()
+        / residuals.len() as f32)
+        .sqrt();
+
+    Some(Estimate {
+        field_dimensions: result.field_dimensions,
+        residuals,
+        root_mean_square_error,
+        associated_lines: result.associations.len(),
+        unassociated_lines,
+    })
+}
+
+fn mean_distance(field_mark: &FieldMark, line: LineSegment<Field>) -> f32 {
+    let distance_to_point = |point| match *field_mark {
+        FieldMark::Line { line, .. } => distance(point, line.closest_point(point)),
+        FieldMark::Circle { center, radius } => (distance(point, center) - radius).abs(),
+    };
+    (distance_to_point(line.0) + distance_to_point(line.1)) / 2.0
+}
+
+struct Association {
+    field_mark_index: usize,
+    line: LineSegment<Field>,
+}
+
+struct FieldDimensionsProblem {
+    field_dimensions: FieldDimensions,
+    associations: Vec<Association>,
+}
+
+impl FieldDimensionsProblem {
+    fn residuals_for(&self, field_dimensions: &FieldDimensions) -> ResidualVector {
+        let field_marks = field_marks_from_field_dimensions(field_dimensions);
+        DVector::from_iterator(
+            self.associations.len() * 2,
+            self.associations.iter().flat_map(|association| {
+                let line = association.line;
+                match field_marks[association.field_mark_index] {
+                    FieldMark::Line {
+                        line: reference, ..
+                    } => [
+                        reference.signed_distance_to_point(line.0),
+                        reference.signed_distance_to_point(line.1),
+                    ],
+                    FieldMark::Circle { center, radius } => [
+                        distance(line.0, center) - radius,
+                        distance(line.1, center) - radius,
+                    ],
+                }
+            }),
+        )
+    }
+}
+
+fn to_parameters(field_dimensions: &FieldDimensions) -> SVector<f32, AMOUNT_OF_FIELD_PARAMETERS> {
+    vector![
+        field_dimensions.length,
+        field_dimensions.width,
+        field_dimensions.penalty_area_length,
+        field_dimensions.penalty_area_width,
+        field_dimensions.center_circle_diameter
+    ]
+}
+
+fn with_parameters(
+    field_dimensions: FieldDimensions,
+    parameters: &SVector<f32, AMOUNT_OF_FIELD_PARAMETERS>,
+) -> FieldDimensions {
+    FieldDimensions {
+        length: parameters[0],
+        width: parameters[1],
+        penalty_area_length: parameters[2],
+        penalty_area_width: parameters[3],
+        center_circle_diameter: parameters[4],
+        ..field_dimensions
+    }
+}
+
+impl LeastSquaresProblem<f32, Dyn, Const<AMOUNT_OF_FIELD_PARAMETERS>> for FieldDimensionsProblem {
+    type ResidualStorage = ResidualVectorStorage;
+    type JacobianStorage = FieldJacobianStorage;
+    type ParameterStorage = Owned<f32, Const<AMOUNT_OF_FIELD_PARAMETERS>>;
+
+    fn set_params(&mut self, parameters: &SVector<f32, AMOUNT_OF_FIELD_PARAMETERS>) {
+        self.field_dimensions = with_parameters(self.field_dimensions, parameters);
+    }
+
+    fn params(&self) -> SVector<f32, AMOUNT_OF_FIELD_PARAMETERS> {
+        to_parameters(&self.field_dimensions)
+    }
+
+    fn residuals(&self) -> Option<ResidualVector> {
+        Some(self.residuals_for(&self.field_dimensions))
+    }
+
+    fn jacobian(&self) -> Option<FieldJacobian> {
+        let parameters = self.params();
+        let columns: Vec<_> = (0..AMOUNT_OF_FIELD_PARAMETERS)
+            .map(|index| {
+                let mut epsilon_vector = SVector::<f32, AMOUNT_OF_FIELD_PARAMETERS>::zeros();
+                epsilon_vector[index] = EPSILON;
+                let upper = with_parameters(self.field_dimensions, &(parameters + epsilon_vector));
+                let lower = with_parameters(self.field_dimensions, &(parameters - epsilon_vector));
+                (self.residuals_for(&upper) - self.residuals_for(&lower)) / (2.0 * EPSILON)
+            })
+            .collect();
+        Some(Matrix::from_columns(&columns))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+    use linear_algebra::{point, vector, Isometry2};
+
+    use super::*;
+
+    #[test]
+    fn recovers_field_dimensions_from_lines_of_a_larger_field() {
+        let venue = FieldDimensions {
+            length: 9.04,
+            width: 5.97,
+            penalty_area_length: 1.62,
+            penalty_area_width: 4.03,
+            center_circle_diameter: 1.52,
+            ..FieldDimensions::SPL_2025
+        };
+        let ground_to_field = Isometry2::<Ground, Field>::from_parts(vector![-1.0, 0.5], 0.2);
+        let field_to_ground = ground_to_field.inverse();
+        let lines = field_marks_from_field_dimensions(&venue)
+            .into_iter()
+            .map(|field_mark| match field_mark {
+                FieldMark::Line { line, .. } => {
+                    LineSegment(field_to_ground * line.0, field_to_ground * line.1)
+                }
+                FieldMark::Circle { center, radius } => LineSegment(
+                    field_to_ground * (center + vector![radius, 0.0]),
+                    field_to_ground * (center + vector![0.0, radius]),
+                ),
+            })
+            .chain([LineSegment(point![20.0, 20.0], point![21.0, 20.0])])
+            .collect();
+        let measurements = [Measurement {
+            ground_to_field,
+            lines,
+        }];
+
+        let estimate =
+            estimate_field_dimensions(FieldDimensions::SPL_2025, &measurements, 0.3).unwrap();
+
+        assert_eq!(estimate.unassociated_lines, 1);
+        assert_relative_eq!(
+            estimate.field_dimensions.length,
+            venue.length,
+            epsilon = 1e-3
+        );
+        assert_relative_eq!(estimate.field_dimensions.width, venue.width, epsilon = 1e-3);
+        assert_relative_eq!(
+            estimate.field_dimensions.penalty_area_length,
+            venue.penalty_area_length,
+            epsilon = 1e-3
+        );
+        assert_relative_eq!(
+            estimate.field_dimensions.penalty_area_width,
+            venue.penalty_area_width,
+            epsilon = 1e-3
+        );
+        assert_relative_eq!(
+            estimate.field_dimensions.center_circle_diameter,
+            venue.center_circle_diameter,
+            epsilon = 1e-3
+        );
+        assert!(estimate.root_mean_square_error < 1e-3);
+    }
+}
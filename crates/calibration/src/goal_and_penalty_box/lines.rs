@@ -114,6 +114,7 @@ impl CalculateResiduals for Residuals {
         let camera_matrix = get_corrected_camera_matrix(
             &measurement.camera_matrix,
             measurement.position,
+            None,
             parameters,
         );
 
@@ -12,7 +12,10 @@ mod tests {
     use projection::camera_matrix::CameraMatrix;
     use types::{camera_position::CameraPosition, field_dimensions::FieldDimensions};
 
-    use crate::{corrections::Corrections, problem::CalibrationProblem};
+    use crate::{
+        corrections::Corrections,
+        problem::{CalibrationProblem, EstimatedParameters},
+    };
 
     use super::{LineType, Measurement, Residuals};
 
@@ -51,8 +54,12 @@ mod tests {
         };
 
         let field_dimensions = FieldDimensions::SPL_2025;
-        let problem =
-            CalibrationProblem::<Residuals>::new(corrections, vec![measurement], field_dimensions);
+        let problem = CalibrationProblem::<Residuals>::new(
+            corrections,
+            vec![measurement],
+            field_dimensions,
+            EstimatedParameters::default(),
+        );
         let (result, _report) = LevenbergMarquardt::new().minimize(problem);
         assert_relative_eq!(result.get_corrections(), corrections);
     }
@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use coordinate_systems::Pixel;
 use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
 use projection::camera_matrix::CameraMatrix;
-use types::camera_position::CameraPosition;
+use types::{camera_position::CameraPosition, joints::Joints, support_foot::Side};
 
 use super::lines::Lines;
 
@@ -14,4 +14,8 @@ pub struct Measurement {
     pub position: CameraPosition,
     pub matrix: CameraMatrix,
     pub lines: Lines<Pixel>,
+    /// Measured joint positions while capturing, used to estimate joint offsets
+    pub joint_positions: Joints<f32>,
+    /// Support side while capturing, the ground of the camera matrix follows its sole
+    pub support_side: Side,
 }
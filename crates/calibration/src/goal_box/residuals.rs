@@ -3,6 +3,7 @@ use types::field_dimensions::FieldDimensions;
 
 use crate::{
     corrections::{get_corrected_camera_matrix, Corrections},
+    joint_offsets::SoleResiduals,
     residuals::CalculateResiduals,
 };
 
@@ -14,6 +15,7 @@ pub struct GoalBoxResiduals {
     pub distance_between_parallel_line_start_points: f32,
     pub distance_between_parallel_line_center_points: f32,
     pub distance_between_parallel_line_end_points: f32,
+    pub sole_residuals: SoleResiduals,
 }
 
 impl CalculateResiduals for GoalBoxResiduals {
@@ -25,8 +27,12 @@ impl CalculateResiduals for GoalBoxResiduals {
         measurement: &Measurement,
         field_dimensions: &FieldDimensions,
     ) -> Result<Self, Self::Error> {
-        let corrected =
-            get_corrected_camera_matrix(&measurement.matrix, measurement.position, parameters);
+        let corrected = get_corrected_camera_matrix(
+            &measurement.matrix,
+            measurement.position,
+            Some((&measurement.joint_positions, measurement.support_side)),
+            parameters,
+        );
 
         let projected_lines = measurement
             .lines
@@ -49,6 +55,12 @@ impl CalculateResiduals for GoalBoxResiduals {
             .border_line
             .distance_to(projected_lines.goal_box_line.1);
 
+        let sole_residuals = SoleResiduals::calculate_from(
+            &measurement.matrix,
+            &measurement.joint_positions,
+            &parameters.joint_offsets,
+        );
+
         Ok(GoalBoxResiduals {
             border_to_connecting_angle,
             connecting_to_goal_box_angle,
@@ -58,6 +70,7 @@ impl CalculateResiduals for GoalBoxResiduals {
                 distance_between_parallel_line_center_points - field_dimensions.goal_box_area_length,
             distance_between_parallel_line_end_points: distance_between_parallel_line_end_points
                 - field_dimensions.goal_box_area_length,
+            sole_residuals,
         })
     }
 }
@@ -70,6 +83,9 @@ impl From<GoalBoxResiduals> for Vec<f32> {
             residuals.distance_between_parallel_line_start_points,
             residuals.distance_between_parallel_line_center_points,
             residuals.distance_between_parallel_line_end_points,
+            residuals.sole_residuals.left_sole_pitch,
+            residuals.sole_residuals.right_sole_pitch,
+            residuals.sole_residuals.height_difference,
         ]
     }
 }
@@ -10,7 +10,8 @@ use crate::{
 pub type Jacobian = Matrix<f32, Dyn, Const<AMOUNT_OF_PARAMETERS>, JacobianStorage>;
 pub type JacobianStorage = Owned<f32, Dyn, Const<AMOUNT_OF_PARAMETERS>>;
 
-const EPSILON: f32 = 0.000001;
+// central differences with smaller steps drown in the f32 rounding of the residuals
+const EPSILON: f32 = 0.0001;
 
 pub fn calculate_jacobian_from_parameters<MeasurementResidualsType>(
     parameters: &Corrections,
@@ -0,0 +1,417 @@
+use serde::{Deserialize, Serialize};
+
+use approx_derive::{AbsDiffEq, RelativeEq};
+use coordinate_systems::Robot;
+use kinematics::forward::{head_to_neck, left_sole_to_robot, neck_to_robot, right_sole_to_robot};
+use linear_algebra::Isometry3;
+use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
+use projection::camera_matrix::CameraMatrix;
+use types::{
+    joints::{head::HeadJoints, leg::LegJoints, Joints},
+    support_foot::Side,
+};
+
+/// Encoder offsets of the joints influencing the camera pose, subtracted from measured positions
+/// like `joint_calibration_offsets`
+///
+/// Knee offsets move the support sole and therefore the camera height in bent poses, the flat
+/// soles determine the sum of the hip, knee and ankle offsets (see [`SoleResiduals`]). Hip offsets
+/// only move the soles by the horizontal distance between hip and ankle, which almost vanishes
+/// while the robot balances over its feet, so their share of the sum is attributed to the ankles.
+/// The head yaw offset rotates the head about the same axis as the yaw of the robot rotation
+/// correction, it becomes observable from the different torso tilts of multiple capture poses.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Serialize,
+    Deserialize,
+    PathDeserialize,
+    PathSerialize,
+    PathIntrospect,
+    PartialEq,
+    AbsDiffEq,
+    RelativeEq,
+)]
+#[abs_diff_eq(epsilon_type = f32)]
+pub struct JointOffsets {
+    pub head_yaw: f32,
+    pub head_pitch: f32,
+    pub left_knee_pitch: f32,
+    pub left_ankle_pitch: f32,
+    pub right_knee_pitch: f32,
+    pub right_ankle_pitch: f32,
+}
+
+impl JointOffsets {
+    pub const AMOUNT_OF_OFFSETS: usize = 6;
+
+    pub fn from_slice(offsets: &[f32]) -> Self {
+        Self {
+            head_yaw: offsets[0],
+            head_pitch: offsets[1],
+            left_knee_pitch: offsets[2],
+            left_ankle_pitch: offsets[3],
+            right_knee_pitch: offsets[4],
+            right_ankle_pitch: offsets[5],
+        }
+    }
+
+    pub fn as_array(&self) -> [f32; Self::AMOUNT_OF_OFFSETS] {
+        [
+            self.head_yaw,
+            self.head_pitch,
+            self.left_knee_pitch,
+            self.left_ankle_pitch,
+            self.right_knee_pitch,
+            self.right_ankle_pitch,
+        ]
+    }
+
+    /// Offsets in the layout of the `joint_calibration_offsets` parameter
+    pub fn to_joints(&self) -> Joints<f32> {
+        Joints {
+            head: HeadJoints {
+                yaw: self.head_yaw,
+                pitch: self.head_pitch,
+            },
+            left_leg: LegJoints {
+                knee_pitch: self.left_knee_pitch,
+                ankle_pitch: self.left_ankle_pitch,
+                ..Default::default()
+            },
+            right_leg: LegJoints {
+                knee_pitch: self.right_knee_pitch,
+                ankle_pitch: self.right_ankle_pitch,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+}
+
+/// Rebuilds the kinematic chain of the camera matrix with offset corrected joint positions
+///
+/// Like the ground provider, the ground follows the sole of the support side.
+pub(crate) fn correct_joint_offsets(
+    camera_matrix: &CameraMatrix,
+    joint_positions: &Joints<f32>,
+    support_side: Side,
+    offsets: &JointOffsets,
+) -> CameraMatrix {
+    let corrected_positions = *joint_positions - offsets.to_joints();
+
+    let head_to_robot =
+        neck_to_robot(&corrected_positions.head) * head_to_neck(&corrected_positions.head);
+
+    let sole_displacement = match support_side {
+        Side::Left => {
+            left_sole_to_robot(&corrected_positions.left_leg).translation()
+                - left_sole_to_robot(&joint_positions.left_leg).translation()
+        }
+        Side::Right => {
+            right_sole_to_robot(&corrected_positions.right_leg).translation()
+                - right_sole_to_robot(&joint_positions.right_leg).translation()
+        }
+    };
+    let ground_displacement = Isometry3::<Robot, Robot>::from(sole_displacement);
+
+    let mut corrected = camera_matrix.clone();
+    corrected.robot_to_head = head_to_robot.inverse();
+    corrected.ground_to_robot = ground_displacement * camera_matrix.ground_to_robot;
+    corrected.compute_memoized();
+    corrected
+}
+
+/// Constraints of both soles standing flat on the ground while capturing
+///
+/// The orientation of the uncorrected camera matrix is the measured IMU orientation, so these
+/// residuals only depend on the joint offsets and leave the robot rotation correction to the
+/// field lines. The sole pitches tie the ankle offsets to the knee offsets, the height
+/// difference ties the leg which is not supporting to the one determining the camera height.
+pub struct SoleResiduals {
+    pub left_sole_pitch: f32,
+    pub right_sole_pitch: f32,
+    pub height_difference: f32,
+}
+
+impl SoleResiduals {
+    pub fn calculate_from(
+        camera_matrix: &CameraMatrix,
+        joint_positions: &Joints<f32>,
+        offsets: &JointOffsets,
+    ) -> Self {
+        let corrected_positions = *joint_positions - offsets.to_joints();
+        let robot_to_ground = camera_matrix.ground_to_robot.inverse();
+
+        let left_sole_to_ground =
+            (robot_to_ground * left_sole_to_robot(&corrected_positions.left_leg)).inner;
+        let right_sole_to_ground =
+            (robot_to_ground * right_sole_to_robot(&corrected_positions.right_leg)).inner;
+        let (_, left_sole_pitch, _) = left_sole_to_ground.rotation.euler_angles();
+        let (_, right_sole_pitch, _) = right_sole_to_ground.rotation.euler_angles();
+
+        Self {
+            left_sole_pitch,
+            right_sole_pitch,
+            height_difference: left_sole_to_ground.translation.z
+                - right_sole_to_ground.translation.z,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use approx::assert_relative_eq;
+    use coordinate_systems::Ground;
+    use geometry::line_segment::LineSegment;
+    use levenberg_marquardt::LevenbergMarquardt;
+    use linear_algebra::{point, vector, IntoTransform, Point2};
+    use nalgebra::Rotation3;
+    use projection::Projection;
+    use types::{
+        camera_position::CameraPosition, field_dimensions::FieldDimensions,
+        robot_dimensions::RobotDimensions,
+    };
+
+    use crate::{
+        corrections::{get_corrected_camera_matrix, Corrections},
+        goal_box::{lines::Lines, measurement::Measurement, residuals::GoalBoxResiduals},
+        problem::{CalibrationProblem, EstimatedParameters},
+    };
+
+    use super::*;
+
+    fn leg(hip_pitch: f32, knee_pitch: f32, ankle_pitch: f32) -> LegJoints<f32> {
+        LegJoints {
+            hip_pitch,
+            knee_pitch,
+            ankle_pitch,
+            ..Default::default()
+        }
+    }
+
+    /// Camera matrix like the camera matrix calculator computes it from the measured joints and
+    /// an IMU orientation of a robot standing flat on its soles
+    fn measured_camera_matrix(
+        actual_positions: &Joints<f32>,
+        measured_positions: &Joints<f32>,
+        support_side: Side,
+    ) -> CameraMatrix {
+        let imu_orientation = left_sole_to_robot(&actual_positions.left_leg).rotation();
+        let support_sole_in_robot = match support_side {
+            Side::Left => left_sole_to_robot(&measured_positions.left_leg).translation(),
+            Side::Right => right_sole_to_robot(&measured_positions.right_leg).translation(),
+        };
+        let ground_to_robot = nalgebra::Isometry3::from_parts(
+            support_sole_in_robot.inner.coords.into(),
+            imu_orientation.inner,
+        )
+        .framed_transform();
+
+        let head_to_robot =
+            neck_to_robot(&measured_positions.head) * head_to_neck(&measured_positions.head);
+        let head_to_top_camera =
+            (nalgebra::Isometry3::rotation(nalgebra::Vector3::x() * 1.2_f32.to_radians())
+                * nalgebra::Isometry3::rotation(nalgebra::Vector3::y() * -FRAC_PI_2)
+                * nalgebra::Isometry3::rotation(nalgebra::Vector3::x() * FRAC_PI_2)
+                * nalgebra::Isometry3::from(-RobotDimensions::HEAD_TO_TOP_CAMERA.inner))
+            .framed_transform();
+        CameraMatrix::from_normalized_focal_and_center(
+            nalgebra::vector![0.96666, 1.28574],
+            nalgebra::point![0.5, 0.5],
+            vector![640.0, 480.0],
+            ground_to_robot,
+            head_to_robot.inverse(),
+            head_to_top_camera,
+        )
+    }
+
+    #[test]
+    fn head_pitch_offset_tilts_camera() {
+        let joint_positions = Joints {
+            head: HeadJoints {
+                yaw: 0.0,
+                pitch: 0.3,
+            },
+            ..Default::default()
+        };
+        let camera_matrix = measured_camera_matrix(&joint_positions, &joint_positions, Side::Left);
+        let offsets = JointOffsets {
+            head_pitch: 0.05,
+            ..Default::default()
+        };
+
+        let unchanged = correct_joint_offsets(
+            &camera_matrix,
+            &joint_positions,
+            Side::Left,
+            &Default::default(),
+        );
+        let corrected =
+            correct_joint_offsets(&camera_matrix, &joint_positions, Side::Left, &offsets);
+
+        let point_in_ground = point![1.0, 0.0, 0.0];
+        assert_relative_eq!(
+            unchanged.ground_to_camera * point_in_ground,
+            camera_matrix.ground_to_camera * point_in_ground,
+            epsilon = 1e-5
+        );
+        let expected_head_to_robot = neck_to_robot(&HeadJoints {
+            yaw: 0.0,
+            pitch: 0.25,
+        }) * head_to_neck(&HeadJoints {
+            yaw: 0.0,
+            pitch: 0.25,
+        });
+        assert_relative_eq!(
+            corrected.robot_to_head.inverse() * point![0.1, 0.0, 0.0],
+            expected_head_to_robot * point![0.1, 0.0, 0.0],
+            epsilon = 1e-5
+        );
+    }
+
+    #[test]
+    fn leg_offsets_move_ground_with_support_sole() {
+        let joint_positions = Joints {
+            left_leg: leg(-0.5, 1.0, -0.5),
+            right_leg: leg(-0.5, 1.0, -0.5),
+            ..Default::default()
+        };
+        let camera_matrix = measured_camera_matrix(&joint_positions, &joint_positions, Side::Left);
+        let offsets = JointOffsets {
+            right_knee_pitch: 0.05,
+            ..Default::default()
+        };
+
+        let left_supported =
+            correct_joint_offsets(&camera_matrix, &joint_positions, Side::Left, &offsets);
+        let right_supported =
+            correct_joint_offsets(&camera_matrix, &joint_positions, Side::Right, &offsets);
+
+        let ground_origin = point![0.0, 0.0, 0.0];
+        assert_relative_eq!(
+            left_supported.ground_to_robot * ground_origin,
+            camera_matrix.ground_to_robot * ground_origin,
+            epsilon = 1e-6
+        );
+        let straighter_knee = right_sole_to_robot(&leg(-0.5, 0.95, -0.5)).translation()
+            - right_sole_to_robot(&joint_positions.right_leg).translation();
+        assert_relative_eq!(
+            right_supported.ground_to_robot * ground_origin,
+            camera_matrix.ground_to_robot * ground_origin + straighter_knee,
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn solver_recovers_joint_offsets_from_multiple_poses() {
+        let field_dimensions = FieldDimensions::SPL_2025;
+        let true_corrections = Corrections {
+            correction_in_camera_top: Rotation3::from_euler_angles(0.01, 0.02, 0.0),
+            joint_offsets: JointOffsets {
+                head_yaw: -0.02,
+                head_pitch: 0.05,
+                left_knee_pitch: -0.03,
+                left_ankle_pitch: 0.01,
+                right_knee_pitch: 0.04,
+                right_ankle_pitch: -0.02,
+            },
+            ..Default::default()
+        };
+        let offsets = true_corrections.joint_offsets.to_joints();
+
+        // the initial and stand pose captured by the calibration controller
+        let poses = [leg(0.09, -0.06, 0.01), leg(-0.45, 0.87, -0.445)];
+        let measurements = poses
+            .into_iter()
+            .flat_map(|pose| {
+                [-0.6, -0.3, 0.0, 0.3, 0.6]
+                    .into_iter()
+                    .flat_map(move |yaw: f32| {
+                        [1.5, 2.5, 3.5].map(|distance: f32| (pose, yaw, distance))
+                    })
+            })
+            .enumerate()
+            .map(|(index, (pose, yaw, distance))| {
+                let actual_positions = Joints {
+                    head: HeadJoints {
+                        yaw,
+                        pitch: (0.58 / distance).atan(),
+                    },
+                    left_leg: pose,
+                    right_leg: pose,
+                    ..Default::default()
+                };
+                let joint_positions = actual_positions + offsets;
+                let support_side = if index % 2 == 0 {
+                    Side::Left
+                } else {
+                    Side::Right
+                };
+                let matrix =
+                    measured_camera_matrix(&actual_positions, &joint_positions, support_side);
+                let true_matrix = get_corrected_camera_matrix(
+                    &matrix,
+                    CameraPosition::Top,
+                    Some((&joint_positions, support_side)),
+                    &true_corrections,
+                );
+
+                // goal box corner in front of the camera, rotated into the look direction
+                let (sin, cos) = yaw.sin_cos();
+                let to_ground = |x: f32, y: f32| -> Point2<Ground> {
+                    let (x, y) = (x - 0.5 + distance, y - 0.3);
+                    point![cos * x - sin * y, sin * x + cos * y]
+                };
+                let to_pixel = |line: LineSegment<Ground>| {
+                    LineSegment(
+                        true_matrix.ground_to_pixel(line.0).unwrap(),
+                        true_matrix.ground_to_pixel(line.1).unwrap(),
+                    )
+                };
+                let goal_box_y = field_dimensions.goal_box_area_length;
+                Measurement {
+                    position: CameraPosition::Top,
+                    matrix,
+                    lines: Lines {
+                        border_line: to_pixel(LineSegment(
+                            to_ground(0.0, 0.0),
+                            to_ground(2.0, 0.0),
+                        )),
+                        goal_box_line: to_pixel(LineSegment(
+                            to_ground(0.5, goal_box_y),
+                            to_ground(1.5, goal_box_y),
+                        )),
+                        connecting_line: to_pixel(LineSegment(
+                            to_ground(0.5, goal_box_y),
+                            to_ground(0.5, 0.0),
+                        )),
+                    },
+                    joint_positions,
+                    support_side,
+                }
+            })
+            .collect();
+
+        let problem = CalibrationProblem::<GoalBoxResiduals>::new(
+            Corrections::default(),
+            measurements,
+            field_dimensions,
+            EstimatedParameters {
+                lens_distortion: false,
+                joint_offsets: true,
+            },
+        );
+        let (result, _report) = LevenbergMarquardt::new().minimize(problem);
+
+        assert_relative_eq!(
+            result.get_corrections().joint_offsets,
+            true_corrections.joint_offsets,
+            epsilon = 1e-3
+        );
+    }
+}
@@ -3,14 +3,16 @@ use levenberg_marquardt::LevenbergMarquardt;
 use types::field_dimensions::FieldDimensions;
 
 use corrections::Corrections;
-use problem::CalibrationProblem;
+use problem::{CalibrationProblem, EstimatedParameters};
 use residuals::CalculateResiduals;
 
 pub mod center_circle;
 pub mod corrections;
+pub mod field_measurement;
 pub mod goal_and_penalty_box;
 pub mod goal_box;
 pub mod jacobian;
+pub mod joint_offsets;
 pub mod problem;
 pub mod residuals;
 
@@ -18,6 +20,7 @@ pub fn solve<MeasurementResidualsType>(
     initial_corrections: Corrections,
     measurements: Vec<MeasurementResidualsType::Measurement>,
     field_dimensions: FieldDimensions,
+    estimated_parameters: EstimatedParameters,
 ) -> Corrections
 where
     MeasurementResidualsType: CalculateResiduals,
@@ -27,6 +30,7 @@ where
         initial_corrections,
         measurements,
         field_dimensions,
+        estimated_parameters,
     );
     let (result, report) = LevenbergMarquardt::new().minimize(problem);
     println!("Report: {report:?}");
@@ -4,7 +4,10 @@ use nalgebra::{Const, Dyn, Owned, SVector};
 use types::field_dimensions::FieldDimensions;
 
 use crate::{
-    corrections::{Corrections, AMOUNT_OF_PARAMETERS},
+    corrections::{
+        Corrections, AMOUNT_OF_PARAMETERS, JOINT_OFFSET_PARAMETERS, LENS_DISTORTION_PARAMETERS,
+        ROBOT_YAW_PARAMETER,
+    },
     jacobian::{calculate_jacobian_from_parameters, Jacobian, JacobianStorage},
     residuals::{
         calculate_residuals_from_parameters, CalculateResiduals, ResidualVector,
@@ -12,6 +15,13 @@ use crate::{
     },
 };
 
+/// Optional parameters to estimate in addition to the rotations of robot and cameras
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EstimatedParameters {
+    pub lens_distortion: bool,
+    pub joint_offsets: bool,
+}
+
 pub struct CalibrationProblem<MeasurementResidualsType>
 where
     MeasurementResidualsType: CalculateResiduals,
@@ -19,6 +29,7 @@ where
     parameters: Corrections,
     measurements: Vec<MeasurementResidualsType::Measurement>,
     field_dimensions: FieldDimensions,
+    estimated_parameters: EstimatedParameters,
 }
 
 impl<MeasurementResidualsType> CalibrationProblem<MeasurementResidualsType>
@@ -29,11 +40,13 @@ where
         initial_corrections: Corrections,
         measurements: Vec<MeasurementResidualsType::Measurement>,
         field_dimensions: FieldDimensions,
+        estimated_parameters: EstimatedParameters,
     ) -> Self {
         Self {
             parameters: initial_corrections,
             measurements,
             field_dimensions,
+            estimated_parameters,
         }
     }
 
@@ -69,10 +82,28 @@ where
     }
 
     fn jacobian(&self) -> Option<Jacobian> {
-        calculate_jacobian_from_parameters::<MeasurementResidualsType>(
+        let mut jacobian = calculate_jacobian_from_parameters::<MeasurementResidualsType>(
             &self.parameters,
             &self.measurements,
             &self.field_dimensions,
-        )
+        )?;
+        // without gradient the solver keeps the initial corrections of these parameters
+        if !self.estimated_parameters.lens_distortion {
+            jacobian
+                .columns_mut(
+                    LENS_DISTORTION_PARAMETERS.start,
+                    LENS_DISTORTION_PARAMETERS.len(),
+                )
+                .fill(0.0);
+        }
+        if self.estimated_parameters.joint_offsets {
+            // indistinguishable from the head yaw offset, which is estimated instead
+            jacobian.column_mut(ROBOT_YAW_PARAMETER).fill(0.0);
+        } else {
+            jacobian
+                .columns_mut(JOINT_OFFSET_PARAMETERS.start, JOINT_OFFSET_PARAMETERS.len())
+                .fill(0.0);
+        }
+        Some(jacobian)
     }
 }
@@ -56,6 +56,10 @@ fn generate_module(cycler: &Cycler, cyclers: &Cyclers, mode: CyclerMode) -> Toke
     let database_struct = generate_database_struct(cycler);
     let cycler_struct = generate_struct(cycler, cyclers, mode);
     let cycler_implementation = generate_implementation(cycler, cyclers, mode);
+    let recorded_outputs_visitation = match mode {
+        CyclerMode::Run => Default::default(),
+        CyclerMode::Replay => generate_recorded_outputs_visitation(cycler),
+    };
 
     quote! {
         #[allow(dead_code, unused_mut, unused_variables,clippy::explicit_auto_deref, clippy::too_many_arguments, clippy::needless_question_mark, clippy::borrow_deref_ref)]
@@ -67,6 +71,7 @@ fn generate_module(cycler: &Cycler, cyclers: &Cyclers, mode: CyclerMode) -> Toke
             #database_struct
             #cycler_struct
             #cycler_implementation
+            #recorded_outputs_visitation
         }
     }
 }
@@ -832,6 +837,82 @@ fn generate_cross_inputs_extraction(cross_inputs: impl IntoIterator<Item = Field
     }
 }
 
+fn generate_recorded_outputs_visitation(cycler: &Cycler) -> TokenStream {
+    let setup_node_outputs = cycler
+        .setup_nodes
+        .iter()
+        .flat_map(|node| node.contexts.main_outputs.iter())
+        .filter_map(|field| match field {
+            Field::MainOutput { data_type, name } => {
+                let error_message = format!("failed to extract {name}");
+                let path = format!("main_outputs.{name}");
+                Some(quote! {
+                    {
+                        let value: #data_type = bincode::deserialize_from(&mut recording_frame).wrap_err(#error_message)?;
+                        visitor.visit(&instance_name, #path, &value)?;
+                    }
+                })
+            }
+            _ => None,
+        });
+    let cross_inputs = get_cross_input_fields(cycler).into_iter().map(|field| {
+        let error_message = match &field {
+            Field::CyclerState { name, .. } => format!("failed to extract cycler state {name}"),
+            Field::HistoricInput { name, .. } => format!("failed to extract historic input {name}"),
+            Field::Input { cycler_instance: Some(_), name, .. } => format!("failed to extract input {name}"),
+            Field::PerceptionInput { name, .. } => format!("failed to extract perception input {name}"),
+            Field::RequiredInput { cycler_instance: Some(_), name, .. } => format!("failed to extract required input {name}"),
+            _ => panic!("unexpected field {field:?}"),
+        };
+        match field {
+            Field::CyclerState { data_type, .. } => quote! {
+                bincode::deserialize_from::<_, #data_type>(&mut recording_frame).wrap_err(#error_message)?;
+            },
+            Field::HistoricInput { data_type, .. } => quote! {
+                bincode::deserialize_from::<_, std::collections::BTreeMap<std::time::SystemTime, #data_type>>(&mut recording_frame).wrap_err(#error_message)?;
+            },
+            Field::PerceptionInput { data_type, .. } => quote! {
+                bincode::deserialize_from::<_, [std::collections::BTreeMap<std::time::SystemTime, Vec<#data_type>>; 2]>(&mut recording_frame).wrap_err(#error_message)?;
+            },
+            Field::Input {
+                cycler_instance: Some(cycler_instance),
+                data_type,
+                path,
+                ..
+            }
+            | Field::RequiredInput {
+                cycler_instance: Some(cycler_instance),
+                data_type,
+                path,
+                ..
+            } => {
+                let cycler_instance = cycler_instance.to_case(Case::Pascal);
+                let path = format!("main_outputs.{}", path.to_segments().join("."));
+                quote! {
+                    {
+                        let value: #data_type = bincode::deserialize_from(&mut recording_frame).wrap_err(#error_message)?;
+                        visitor.visit(#cycler_instance, #path, &value)?;
+                    }
+                }
+            }
+            _ => panic!("unexpected field {field:?}"),
+        }
+    });
+
+    quote! {
+        pub fn visit_recorded_outputs(
+            instance: CyclerInstance,
+            mut recording_frame: &[u8],
+            visitor: &mut impl framework::RecordedOutputVisitor,
+        ) -> color_eyre::Result<()> {
+            let instance_name = format!("{instance:?}");
+            #(#setup_node_outputs)*
+            #(#cross_inputs)*
+            Ok(())
+        }
+    }
+}
+
 fn path_to_extraction_variable_name(cycler_instance: &str, path: &Path, suffix: &str) -> Ident {
     format_ident!(
         "replay_extraction_{}_{}_{}",
@@ -25,6 +25,7 @@ pub fn generate_run_function(cyclers: &Cyclers) -> TokenStream {
             hardware_ids: hula_types::hardware::Ids,
             keep_running: tokio_util::sync::CancellationToken,
             recording_intervals: std::collections::HashMap<String, usize>,
+            hot_reload_parameters: bool,
         ) -> color_eyre::Result<()>
         {
             use color_eyre::eyre::WrapErr;
@@ -41,12 +42,16 @@ pub fn generate_run_function(cyclers: &Cyclers) -> TokenStream {
             #construct_buffered_watch_channels
             #construct_future_queues
 
+            let parameters_directory = parameters_directory.as_ref().to_path_buf();
             let parameters_from_disk: crate::structs::Parameters =
                 parameters::directory::deserialize(
-                    parameters_directory,
+                    &parameters_directory,
                     &hardware_ids,
                     false,
                 ).wrap_err("failed to parse initial parameters")?;
+            if hot_reload_parameters && addresses.is_none() {
+                eprintln!("parameter hot reload requires communication, parameters are not reloaded");
+            }
             let initial_parameters = parameters_from_disk;
             let (parameters_sender, parameters_receiver) =
                 buffered_watch::channel((std::time::SystemTime::now(), initial_parameters));
@@ -72,6 +77,14 @@ pub fn generate_run_function(cyclers: &Cyclers) -> TokenStream {
                             let (parameters_subscriptions, _) = buffered_watch::channel(Default::default());
                             communication_server.expose_source("parameters", parameters_receiver, parameters_subscriptions)?;
                             communication_server.expose_sink("parameters", parameters_sender)?;
+                            if hot_reload_parameters {
+                                communication_server.watch_parameters::<crate::structs::Parameters>(
+                                    "parameters",
+                                    parameters_directory,
+                                    hardware_ids.clone(),
+                                    keep_running.clone(),
+                                )?;
+                            }
                             let (_, ids_receiver) = buffered_watch::channel((std::time::SystemTime::now(), hardware_ids));
                             let (ids_subscriptions, _) = buffered_watch::channel(Default::default());
                             communication_server.expose_source("hardware_ids", ids_receiver, ids_subscriptions)?;
@@ -177,6 +190,7 @@ pub fn generate_replayer_struct(cyclers: &Cyclers, with_communication: bool) ->
     let recording_index_entries_mut =
         generate_recording_index_entries(cyclers, ReferenceKind::Mutable);
     let cycler_replays = generate_cycler_replays(cyclers);
+    let recorded_outputs_visitations = generate_recorded_outputs_visitations(cyclers);
 
     quote! {
         pub struct Replayer<Hardware> {
@@ -266,6 +280,15 @@ pub fn generate_replayer_struct(cyclers: &Cyclers, with_communication: bool) ->
                 Ok(())
             }
 
+            pub fn visit_recorded_outputs(&self, cycler_instance_name: &str, data: &[u8], visitor: &mut impl framework::RecordedOutputVisitor) -> color_eyre::Result<()> {
+                use color_eyre::eyre::{bail, WrapErr};
+
+                match cycler_instance_name {
+                    #recorded_outputs_visitations
+                    _ => bail!("unexpected cycler instance name {cycler_instance_name}"),
+                }
+            }
+
             #accessors
         }
     }
@@ -651,3 +674,21 @@ fn generate_cycler_replays(cyclers: &Cyclers) -> TokenStream {
         })
         .collect()
 }
+
+fn generate_recorded_outputs_visitations(cyclers: &Cyclers) -> TokenStream {
+    cyclers
+        .instances()
+        .map(|(cycler, instance)| {
+            let cycler_module_name = format_ident!("{}", cycler.name.to_case(Case::Snake));
+            let instance_name = format_ident!("{}", instance);
+            let error_message = format!("failed to visit recorded outputs of {instance}");
+            quote! {
+                #instance => crate::cyclers::#cycler_module_name::visit_recorded_outputs(
+                    crate::cyclers::#cycler_module_name::CyclerInstance::#instance_name,
+                    data,
+                    visitor,
+                ).wrap_err(#error_message),
+            }
+        })
+        .collect()
+}
@@ -1,13 +1,15 @@
 use std::{collections::BTreeMap, time::SystemTime};
 
+use path_serde::schema::FieldSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-#[derive(Default, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Default, Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[non_exhaustive]
 pub struct Entry {
     pub is_readable: bool,
     pub is_writable: bool,
+    pub schema: Option<FieldSchema>,
 }
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -45,7 +47,7 @@ pub enum RequestKind {
     Write { path: Path, value: TextOrBinary },
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[non_exhaustive]
 pub struct Response {
     pub id: RequestId,
@@ -54,7 +56,7 @@ pub struct Response {
 
 pub type Paths = BTreeMap<Path, Entry>;
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[non_exhaustive]
 pub enum ResponseKind {
     Paths {
@@ -1,5 +1,6 @@
 mod acceptor;
 mod connection;
+mod parameters_watcher;
 mod router;
 mod sink;
 mod source;
@@ -8,12 +9,14 @@ use std::{
     collections::{BTreeMap, HashMap, HashSet},
     io,
     marker::{Send, Sync},
+    path::PathBuf,
     time::SystemTime,
 };
 
+use hula_types::hardware::Ids;
 use log::info;
-use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
-use serde::{Deserialize, Serialize};
+use path_serde::{schema::FieldSchema, PathDeserialize, PathIntrospect, PathSerialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use thiserror::Error;
 use tokio::{
     net::{TcpListener, ToSocketAddrs},
@@ -28,6 +31,7 @@ use crate::{
 };
 
 use self::{
+    parameters_watcher::watch_parameters,
     sink::{Sink, SinkHandle},
     source::{Source, SourceHandle},
 };
@@ -37,6 +41,8 @@ use self::{
 pub enum RegistrationError {
     #[error("conflicting path, existing prefix: {prefix}")]
     ConflictingPath { prefix: Path },
+    #[error("no sink exposed at {path}")]
+    SinkNotExposed { path: Path },
 }
 
 #[derive(Default)]
@@ -56,7 +62,12 @@ impl Tree {
         }
     }
 
-    fn add_sink(&mut self, prefix: &Path, paths: impl Iterator<Item = Path>) {
+    fn add_sink(
+        &mut self,
+        prefix: &Path,
+        paths: impl Iterator<Item = Path>,
+        schema: BTreeMap<Path, FieldSchema>,
+    ) {
         self.paths
             .entry(prefix.to_string())
             .or_default()
@@ -65,6 +76,10 @@ impl Tree {
             let concatenated_path = format!("{prefix}.{path}");
             self.paths.entry(concatenated_path).or_default().is_writable = true;
         }
+        for (path, field_schema) in schema {
+            let concatenated_path = format!("{prefix}.{path}");
+            self.paths.entry(concatenated_path).or_default().schema = Some(field_schema);
+        }
     }
 }
 
@@ -126,7 +141,7 @@ impl Server {
         data: buffered_watch::Sender<(SystemTime, T)>,
     ) -> Result<(), RegistrationError>
     where
-        T: Clone + PathDeserialize + PathIntrospect + Send + Sync + 'static,
+        T: Clone + Serialize + PathDeserialize + PathIntrospect + Send + Sync + 'static,
         for<'de> T: Deserialize<'de>,
     {
         let path = path.into();
@@ -135,10 +150,35 @@ impl Server {
                 prefix: prefix.clone(),
             });
         }
-        self.tree.add_sink(&path, T::get_fields().into_iter());
+        self.tree
+            .add_sink(&path, T::get_fields().into_iter(), T::get_schema());
         let (sink, handle) = Sink::new(data);
         self.sinks.insert(path, handle);
         self.tasks.spawn(sink.run());
         Ok(())
     }
+
+    /// Writes the parameters to the sink at `path` whenever their files change on disk
+    pub fn watch_parameters<T>(
+        &mut self,
+        path: impl Into<String>,
+        parameters_directory: PathBuf,
+        hardware_ids: Ids,
+        cancellation_token: CancellationToken,
+    ) -> Result<(), RegistrationError>
+    where
+        T: DeserializeOwned + Serialize + Send + 'static,
+    {
+        let path = path.into();
+        let Some(sink) = self.sinks.get(&path).cloned() else {
+            return Err(RegistrationError::SinkNotExposed { path });
+        };
+        self.tasks.spawn(watch_parameters::<T>(
+            sink,
+            parameters_directory,
+            hardware_ids,
+            cancellation_token,
+        ));
+        Ok(())
+    }
 }
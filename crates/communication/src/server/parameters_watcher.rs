@@ -0,0 +1,146 @@
+use std::{
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
+
+use color_eyre::Report;
+use log::{error, info};
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::{select, time::interval};
+use tokio_util::sync::CancellationToken;
+
+use hula_types::hardware::Ids;
+use parameters::{directory::deserialize, watcher::LayerWatcher};
+
+use crate::{messages::TextOrBinary, server::sink::SinkHandle};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Re-merges the parameter files on changes and writes them to the sink like a client would
+///
+/// Files which fail to parse are reported and the running parameters are kept.
+pub async fn watch_parameters<Parameters>(
+    sink: SinkHandle,
+    parameters_directory: PathBuf,
+    hardware_ids: Ids,
+    cancellation_token: CancellationToken,
+) where
+    Parameters: DeserializeOwned + Serialize + Send,
+{
+    let mut watcher = LayerWatcher::new(&parameters_directory, &hardware_ids);
+    let mut interval = interval(POLL_INTERVAL);
+    loop {
+        select! {
+            _ = cancellation_token.cancelled() => break,
+            _ = interval.tick() => {},
+        }
+        if !watcher.has_changed() {
+            continue;
+        }
+        let parameters: Parameters = match deserialize(&parameters_directory, &hardware_ids, false)
+        {
+            Ok(parameters) => parameters,
+            Err(error) => {
+                error!("failed to reload parameters: {:#}", Report::new(error));
+                continue;
+            }
+        };
+        let value = match serde_json::to_value(parameters) {
+            Ok(value) => value,
+            Err(error) => {
+                error!("failed to serialize reloaded parameters: {error}");
+                continue;
+            }
+        };
+        match sink
+            .write("", SystemTime::now(), TextOrBinary::Text(value))
+            .await
+        {
+            Ok(()) => info!(
+                "reloaded parameters from {}",
+                parameters_directory.display()
+            ),
+            Err(error) => error!("failed to apply reloaded parameters: {error}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs::{write, File},
+        path::Path,
+        time::UNIX_EPOCH,
+    };
+
+    use path_serde::{PathDeserialize, PathIntrospect};
+    use serde::Deserialize;
+    use tokio::time::{sleep, timeout};
+
+    use crate::server::sink::Sink;
+
+    use super::*;
+
+    #[derive(Deserialize, Serialize, PathDeserialize, PathIntrospect, Clone)]
+    struct Data {
+        foo: usize,
+    }
+
+    fn write_default(parameters_directory: &Path, contents: &str, modified: SystemTime) {
+        let file_path = parameters_directory.join("default.json");
+        write(&file_path, contents).unwrap();
+        File::options()
+            .write(true)
+            .open(file_path)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn changed_file_reaches_sink_and_broken_file_keeps_parameters() {
+        let parameters_directory = tempfile::tempdir().unwrap();
+        let now = SystemTime::now();
+        write_default(parameters_directory.path(), r#"{ "foo": 42 }"#, now);
+
+        let (data_sender, mut data_receiver) =
+            buffered_watch::channel((UNIX_EPOCH, Data { foo: 42 }));
+        let (sink, handle) = Sink::new(data_sender);
+        let sink_task = tokio::spawn(sink.run());
+        let cancellation_token = CancellationToken::new();
+        let watcher_task = tokio::spawn(watch_parameters::<Data>(
+            handle,
+            parameters_directory.path().to_path_buf(),
+            Ids {
+                body_id: String::new(),
+                head_id: String::new(),
+            },
+            cancellation_token.clone(),
+        ));
+
+        // let the watcher record the initial modification times
+        sleep(POLL_INTERVAL).await;
+        write_default(
+            parameters_directory.path(),
+            r#"{ "foo": 1337 }"#,
+            now + Duration::from_secs(1),
+        );
+        timeout(10 * POLL_INTERVAL, data_receiver.wait_for_change())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(data_receiver.borrow_and_mark_as_seen().1.foo, 1337);
+
+        write_default(
+            parameters_directory.path(),
+            r#"{ "foo": "#,
+            now + Duration::from_secs(2),
+        );
+        sleep(3 * POLL_INTERVAL).await;
+        assert_eq!(data_receiver.borrow().1.foo, 1337);
+
+        cancellation_token.cancel();
+        watcher_task.await.unwrap();
+        sink_task.await.unwrap();
+    }
+}
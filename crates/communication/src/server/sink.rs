@@ -1,8 +1,11 @@
-use std::time::SystemTime;
+use std::{collections::BTreeMap, time::SystemTime};
 
 use bincode::{DefaultOptions, Deserializer, Options};
-use path_serde::PathDeserialize;
-use serde::Deserialize;
+use path_serde::{
+    schema::{validate, FieldSchema, Violation},
+    PathDeserialize, PathIntrospect,
+};
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tokio::sync::{mpsc, oneshot};
 
@@ -14,6 +17,18 @@ pub enum Error {
     Json(#[from] path_serde::deserialize::Error<serde_json::Error>),
     #[error(transparent)]
     Bincode(#[from] path_serde::deserialize::Error<bincode::Error>),
+    #[error("failed to serialize written data for validation")]
+    ValidationNotSerialized(#[source] serde_json::Error),
+    #[error("out of range: {}", format_violations(.violations))]
+    OutOfRange { violations: Vec<Violation> },
+}
+
+fn format_violations(violations: &[Violation]) -> String {
+    violations
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
 pub enum Event {
@@ -25,6 +40,7 @@ pub enum Event {
     },
 }
 
+#[derive(Clone)]
 pub struct SinkHandle {
     command_sender: mpsc::Sender<Event>,
 }
@@ -53,17 +69,19 @@ impl SinkHandle {
 pub struct Sink<T> {
     data_sender: buffered_watch::Sender<(SystemTime, T)>,
     command_receiver: mpsc::Receiver<Event>,
+    schema: BTreeMap<String, FieldSchema>,
 }
 
 impl<T> Sink<T>
 where
-    for<'de> T: Deserialize<'de> + PathDeserialize + Clone,
+    for<'de> T: Deserialize<'de> + Serialize + PathDeserialize + PathIntrospect + Clone,
 {
     pub fn new(data_sender: buffered_watch::Sender<(SystemTime, T)>) -> (Self, SinkHandle) {
         let (command_sender, command_receiver) = mpsc::channel(1);
         let task = Self {
             data_sender,
             command_receiver,
+            schema: T::get_schema(),
         };
         let handle = SinkHandle { command_sender };
         (task, handle)
@@ -122,26 +140,65 @@ where
             };
             data
         };
+        self.validate(path, &data)?;
 
         *self.data_sender.borrow_mut() = (timestamp, data);
         Ok(())
     }
+
+    /// Rejects the write if a bounded value at or below the written path, or the field it is bounded
+    /// by, is out of range
+    fn validate(&self, path: &str, data: &T) -> Result<(), Error> {
+        if !self.schema.values().any(FieldSchema::is_bounded) {
+            return Ok(());
+        }
+        let value = serde_json::to_value(data).map_err(Error::ValidationNotSerialized)?;
+        let violations: Vec<_> = validate(&self.schema, &value)
+            .into_iter()
+            .filter(|violation| {
+                let touches = |violation_path: &str| {
+                    violation_path == path
+                        || violation_path.starts_with(&format!("{path}."))
+                        || path.starts_with(&format!("{violation_path}."))
+                };
+                path.is_empty()
+                    || touches(&violation.path)
+                    || violation
+                        .minimum_field
+                        .as_ref()
+                        .is_some_and(|(minimum_path, _)| touches(minimum_path))
+            })
+            .collect();
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::OutOfRange { violations })
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::time::{SystemTime, UNIX_EPOCH};
 
-    use path_serde::PathDeserialize;
+    use path_serde::{PathDeserialize, PathIntrospect};
     use serde_json::json;
 
     use super::*;
 
-    #[derive(Deserialize, PathDeserialize, Clone)]
+    #[derive(Deserialize, Serialize, PathDeserialize, PathIntrospect, Clone)]
     struct Data {
+        #[path_serde(maximum = 2000)]
         foo: usize,
     }
 
+    #[derive(Deserialize, Serialize, PathDeserialize, PathIntrospect, Clone)]
+    struct Interval {
+        minimum: f32,
+        #[path_serde(minimum_field = "minimum")]
+        maximum: f32,
+    }
+
     #[tokio::test]
     async fn write_text() {
         let (data_sender, mut data_receiver) =
@@ -199,4 +256,49 @@ mod tests {
         drop(handle);
         task.await.unwrap();
     }
+
+    #[tokio::test]
+    async fn write_out_of_range() {
+        let (data_sender, mut data_receiver) =
+            buffered_watch::channel((UNIX_EPOCH, Data { foo: 42 }));
+        let (sink, handle) = Sink::new(data_sender);
+        let task = tokio::spawn(sink.run());
+
+        let path = Path::from("foo");
+        let value = TextOrBinary::Text(json!(4096));
+        let result = handle.write(path, SystemTime::now(), value).await;
+
+        assert!(matches!(result, Err(Error::OutOfRange { .. })));
+
+        drop(handle);
+        task.await.unwrap();
+
+        let (_, data) = &*data_receiver.borrow();
+        assert_eq!(data.foo, 42);
+    }
+
+    #[tokio::test]
+    async fn write_above_bounded_field() {
+        let (data_sender, mut data_receiver) = buffered_watch::channel((
+            UNIX_EPOCH,
+            Interval {
+                minimum: 1.0,
+                maximum: 2.0,
+            },
+        ));
+        let (sink, handle) = Sink::new(data_sender);
+        let task = tokio::spawn(sink.run());
+
+        let path = Path::from("minimum");
+        let value = TextOrBinary::Text(json!(3.0));
+        let result = handle.write(path, SystemTime::now(), value).await;
+
+        assert!(matches!(result, Err(Error::OutOfRange { .. })));
+
+        drop(handle);
+        task.await.unwrap();
+
+        let (_, data) = &*data_receiver.borrow();
+        assert_eq!(data.minimum, 1.0);
+    }
 }
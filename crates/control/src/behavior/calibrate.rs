@@ -1,5 +1,6 @@
 use types::{
-    calibration::CalibrationCommand,
+    calibration::{CalibrationCommand, CalibrationPose},
+    decision_trace::Precondition,
     motion_command::{HeadMotion, ImageRegion, MotionCommand},
     primary_state::PrimaryState,
     world_state::WorldState,
@@ -8,25 +9,34 @@ use types::{
 pub fn execute(
     world_state: &WorldState,
     use_stand_head_unstiff_calibration: bool,
-) -> Option<MotionCommand> {
+) -> Result<MotionCommand, Precondition> {
     if PrimaryState::Calibration != world_state.robot.primary_state {
-        return None;
+        return Err(Precondition::Situation);
     }
     if use_stand_head_unstiff_calibration {
-        return Some(MotionCommand::Stand {
+        return Ok(MotionCommand::Stand {
             head: HeadMotion::Unstiff,
         });
     }
 
-    let head =
-        if let Some(CalibrationCommand { target, camera, .. }) = world_state.calibration_command {
-            HeadMotion::LookAt {
-                target,
-                camera: Some(camera),
-                image_region_target: ImageRegion::Bottom,
-            }
-        } else {
-            HeadMotion::Unstiff
-        };
-    Some(MotionCommand::Stand { head })
+    let Some(CalibrationCommand {
+        target,
+        camera,
+        pose,
+        ..
+    }) = world_state.calibration_command
+    else {
+        return Ok(MotionCommand::Stand {
+            head: HeadMotion::Unstiff,
+        });
+    };
+    let head = HeadMotion::LookAt {
+        target,
+        camera: Some(camera),
+        image_region_target: ImageRegion::Bottom,
+    };
+    Ok(match pose {
+        CalibrationPose::Stand => MotionCommand::Stand { head },
+        CalibrationPose::Initial => MotionCommand::Initial { head },
+    })
 }
@@ -8,9 +8,10 @@ use geometry::{
     look_at::LookAt,
 };
 use linear_algebra::{distance, point, Point2, Pose2, Vector2};
-use serde::{Deserialize, Serialize};
 use spl_network_messages::{GamePhase, SubState, Team};
 use types::{
+    behavior_state::DefendMode,
+    decision_trace::Precondition,
     field_dimensions::{FieldDimensions, Side},
     filtered_game_controller_state::FilteredGameControllerState,
     motion_command::{JumpDirection, MotionCommand, WalkSpeed},
@@ -21,12 +22,6 @@ use types::{
 
 use super::{head::LookAction, walk_to_pose::WalkAndStand};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub enum DefendMode {
-    Aggressive,
-    Passive,
-}
-
 pub struct Defend<'cycle> {
     world_state: &'cycle WorldState,
     field_dimensions: &'cycle FieldDimensions,
@@ -62,7 +57,7 @@ impl<'cycle> Defend<'cycle> {
         walk_speed: WalkSpeed,
         distance_to_be_aligned: f32,
         hysteresis: nalgebra::Vector2<f32>,
-    ) -> Option<MotionCommand> {
+    ) -> Result<MotionCommand, Precondition> {
         self.walk_and_stand.execute(
             pose,
             self.look_action.execute(),
@@ -73,8 +68,11 @@ impl<'cycle> Defend<'cycle> {
         )
     }
 
-    pub fn keeper_motion(&self, parameters: KeeperMotionParameters) -> Option<MotionCommand> {
-        let ball = self.world_state.ball?;
+    pub fn keeper_motion(
+        &self,
+        parameters: KeeperMotionParameters,
+    ) -> Result<MotionCommand, Precondition> {
+        let ball = self.world_state.ball.ok_or(Precondition::Ball)?;
 
         let position = ball.ball_in_ground;
         let velocity = ball.ball_in_ground_velocity;
@@ -85,7 +83,7 @@ impl<'cycle> Defend<'cycle> {
             ball.ball_in_ground_velocity.x() < -parameters.minimum_ball_velocity;
 
         if !ball_is_in_front_of_robot || !ball_is_moving_towards_robot {
-            return None;
+            return Err(Precondition::Situation);
         }
 
         let horizontal_distance_to_intersection =
@@ -94,23 +92,23 @@ impl<'cycle> Defend<'cycle> {
         if (-parameters.action_radius_center..=parameters.action_radius_center)
             .contains(&horizontal_distance_to_intersection)
         {
-            Some(MotionCommand::KeeperMotion {
+            Ok(MotionCommand::KeeperMotion {
                 direction: JumpDirection::Center,
             })
         } else if (parameters.action_radius_center..parameters.action_radius_left)
             .contains(&horizontal_distance_to_intersection)
         {
-            Some(MotionCommand::KeeperMotion {
+            Ok(MotionCommand::KeeperMotion {
                 direction: JumpDirection::Left,
             })
         } else if (-parameters.action_radius_left..-parameters.action_radius_center)
             .contains(&horizontal_distance_to_intersection)
         {
-            Some(MotionCommand::KeeperMotion {
+            Ok(MotionCommand::KeeperMotion {
                 direction: JumpDirection::Right,
             })
         } else {
-            None
+            Err(Precondition::Situation)
         }
     }
 
@@ -119,7 +117,7 @@ impl<'cycle> Defend<'cycle> {
         path_obstacles_output: &mut AdditionalOutput<Vec<PathObstacle>>,
         walk_speed: WalkSpeed,
         distance_to_be_aligned: f32,
-    ) -> Option<MotionCommand> {
+    ) -> Result<MotionCommand, Precondition> {
         let pose = defend_pose(
             self.world_state,
             self.field_dimensions,
@@ -142,7 +140,7 @@ impl<'cycle> Defend<'cycle> {
         path_obstacles_output: &mut AdditionalOutput<Vec<PathObstacle>>,
         walk_speed: WalkSpeed,
         distance_to_be_aligned: f32,
-    ) -> Option<MotionCommand> {
+    ) -> Result<MotionCommand, Precondition> {
         let pose = defend_pose(
             self.world_state,
             self.field_dimensions,
@@ -166,7 +164,7 @@ impl<'cycle> Defend<'cycle> {
         walk_speed: WalkSpeed,
         field_side: Side,
         distance_to_be_aligned: f32,
-    ) -> Option<MotionCommand> {
+    ) -> Result<MotionCommand, Precondition> {
         let pose = defend_pose(
             self.world_state,
             self.field_dimensions,
@@ -189,7 +187,7 @@ impl<'cycle> Defend<'cycle> {
         path_obstacles_output: &mut AdditionalOutput<Vec<PathObstacle>>,
         walk_speed: WalkSpeed,
         distance_to_be_aligned: f32,
-    ) -> Option<MotionCommand> {
+    ) -> Result<MotionCommand, Precondition> {
         let pose =
             defend_penalty_kick(self.world_state, self.field_dimensions, self.role_positions)?;
         self.with_pose(
@@ -206,7 +204,7 @@ impl<'cycle> Defend<'cycle> {
         path_obstacles_output: &mut AdditionalOutput<Vec<PathObstacle>>,
         walk_speed: WalkSpeed,
         distance_to_be_aligned: f32,
-    ) -> Option<MotionCommand> {
+    ) -> Result<MotionCommand, Precondition> {
         let pose = defend_goal_pose(self.world_state, self.field_dimensions, self.role_positions)?;
         self.with_pose(
             pose,
@@ -222,7 +220,7 @@ impl<'cycle> Defend<'cycle> {
         path_obstacles_output: &mut AdditionalOutput<Vec<PathObstacle>>,
         walk_speed: WalkSpeed,
         distance_to_be_aligned: f32,
-    ) -> Option<MotionCommand> {
+    ) -> Result<MotionCommand, Precondition> {
         let pose =
             defend_kick_off_pose(self.world_state, self.field_dimensions, self.role_positions)?;
         self.with_pose(
@@ -242,8 +240,11 @@ fn defend_pose(
     x_offset: f32,
     field_side: Side,
     last_defender_mode: &mut DefendMode,
-) -> Option<Pose2<Ground>> {
-    let ground_to_field = world_state.robot.ground_to_field?;
+) -> Result<Pose2<Ground>, Precondition> {
+    let ground_to_field = world_state
+        .robot
+        .ground_to_field
+        .ok_or(Precondition::GroundToField)?;
     let ball = world_state
         .rule_ball
         .or(world_state.ball)
@@ -271,13 +272,11 @@ fn defend_pose(
     if mode == DefendMode::Passive {
         let passive_target_position = position_to_defend
             + (Vector2::x_axis() * role_positions.defender_aggressive_ring_radius);
-        return Some(
-            ground_to_field.inverse()
-                * Pose2::<Field>::new(
-                    passive_target_position,
-                    passive_target_position.look_at(&ball.ball_in_field).angle(),
-                ),
-        );
+        return Ok(ground_to_field.inverse()
+            * Pose2::<Field>::new(
+                passive_target_position,
+                passive_target_position.look_at(&ball.ball_in_field).angle(),
+            ));
     }
 
     let distance_to_target = if field_side == ball.field_side {
@@ -293,15 +292,18 @@ fn defend_pose(
     );
     let defend_pose = block_on_circle(ball.ball_in_field, position_to_defend, distance_to_target);
 
-    Some(ground_to_field.inverse() * defend_pose)
+    Ok(ground_to_field.inverse() * defend_pose)
 }
 
 fn defend_penalty_kick(
     world_state: &WorldState,
     field_dimensions: &FieldDimensions,
     role_positions: &RolePositionsParameters,
-) -> Option<Pose2<Ground>> {
-    let ground_to_field = world_state.robot.ground_to_field?;
+) -> Result<Pose2<Ground>, Precondition> {
+    let ground_to_field = world_state
+        .robot
+        .ground_to_field
+        .ok_or(Precondition::GroundToField)?;
     let ball = world_state
         .rule_ball
         .or(world_state.ball)
@@ -323,15 +325,18 @@ fn defend_penalty_kick(
     );
 
     let defend_pose = block_on_circle(ball.ball_in_field, position_to_defend, distance_to_target);
-    Some(ground_to_field.inverse() * defend_pose)
+    Ok(ground_to_field.inverse() * defend_pose)
 }
 
 fn defend_goal_pose(
     world_state: &WorldState,
     field_dimensions: &FieldDimensions,
     role_positions: &RolePositionsParameters,
-) -> Option<Pose2<Ground>> {
-    let ground_to_field = world_state.robot.ground_to_field?;
+) -> Result<Pose2<Ground>, Precondition> {
+    let ground_to_field = world_state
+        .robot
+        .ground_to_field
+        .ok_or(Precondition::GroundToField)?;
     let ball = world_state
         .rule_ball
         .or(world_state.ball)
@@ -358,9 +363,7 @@ fn defend_goal_pose(
     let passive_position_to_defend = point![-field_dimensions.length / 2.0 + 0.25, 0.0];
 
     if ball.ball_in_ground.coords().norm() >= role_positions.keeper_passive_distance {
-        return Some(
-            ground_to_field.inverse() * Pose2::<Field>::new(passive_position_to_defend, 0.0),
-        );
+        return Ok(ground_to_field.inverse() * Pose2::<Field>::new(passive_position_to_defend, 0.0));
     }
 
     let position_to_defend = point![-field_dimensions.length / 2.0 - 1.0, 0.0];
@@ -371,15 +374,18 @@ fn defend_goal_pose(
         -field_dimensions.length / 2.0 + keeper_x_offset,
         -0.7..0.7,
     );
-    Some(ground_to_field.inverse() * defend_pose)
+    Ok(ground_to_field.inverse() * defend_pose)
 }
 
 fn defend_kick_off_pose(
     world_state: &WorldState,
     field_dimensions: &FieldDimensions,
     role_positions: &RolePositionsParameters,
-) -> Option<Pose2<Ground>> {
-    let ground_to_field = world_state.robot.ground_to_field?;
+) -> Result<Pose2<Ground>, Precondition> {
+    let ground_to_field = world_state
+        .robot
+        .ground_to_field
+        .ok_or(Precondition::GroundToField)?;
     let absolute_ball_position = match world_state.ball {
         Some(ball) => ball.ball_in_field,
         None => Point2::origin(),
@@ -394,7 +400,7 @@ fn defend_kick_off_pose(
         position_to_defend,
         distance_to_target,
     );
-    Some(ground_to_field.inverse() * defend_pose)
+    Ok(ground_to_field.inverse() * defend_pose)
 }
 
 pub fn block_on_circle(
@@ -3,6 +3,7 @@ use linear_algebra::{Isometry2, Pose2};
 use spl_network_messages::GamePhase;
 use types::{
     camera_position::CameraPosition,
+    decision_trace::Precondition,
     dribble_path_plan::DribblePathPlan,
     filtered_game_controller_state::FilteredGameControllerState,
     motion_command::{ArmMotion, HeadMotion, ImageRegion, MotionCommand, WalkSpeed},
@@ -20,8 +21,8 @@ pub fn execute(
     parameters: &DribblingParameters,
     dribble_path_plan: Option<DribblePathPlan>,
     mut walk_speed: WalkSpeed,
-) -> Option<MotionCommand> {
-    let ball_position = world_state.ball?.ball_in_ground;
+) -> Result<MotionCommand, Precondition> {
+    let ball_position = world_state.ball.ok_or(Precondition::Ball)?.ball_in_ground;
     let distance_to_ball = ball_position.coords().norm();
     let head = if distance_to_ball < parameters.distance_to_look_directly_at_the_ball {
         HeadMotion::LookAt {
@@ -34,8 +35,14 @@ pub fn execute(
             target: ball_position,
         }
     };
-    let kick_decisions = world_state.kick_decisions.as_ref()?;
-    let instant_kick_decisions = world_state.instant_kick_decisions.as_ref()?;
+    let kick_decisions = world_state
+        .kick_decisions
+        .as_ref()
+        .ok_or(Precondition::KickDecisions)?;
+    let instant_kick_decisions = world_state
+        .instant_kick_decisions
+        .as_ref()
+        .ok_or(Precondition::KickDecisions)?;
 
     let available_kick = kick_decisions
         .iter()
@@ -56,7 +63,7 @@ pub fn execute(
             left_arm: ArmMotion::Swing,
             right_arm: ArmMotion::Swing,
         };
-        return Some(command);
+        return Ok(command);
     }
 
     if let Some(FilteredGameControllerState {
@@ -71,13 +78,13 @@ pub fn execute(
         Some(DribblePathPlan {
             orientation_mode,
             path,
-        }) => Some(walk_path_planner.walk_with_obstacle_avoiding_arms(
+        }) => Ok(walk_path_planner.walk_with_obstacle_avoiding_arms(
             head,
             orientation_mode,
             path,
             walk_speed,
         )),
-        None => Some(MotionCommand::Stand { head }),
+        None => Ok(MotionCommand::Stand { head }),
     }
 }
 
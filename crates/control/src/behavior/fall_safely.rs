@@ -1,10 +1,16 @@
-use types::{fall_state::FallState, motion_command::MotionCommand, world_state::WorldState};
+use types::{
+    decision_trace::Precondition, fall_state::FallState, motion_command::MotionCommand,
+    world_state::WorldState,
+};
 
-pub fn execute(world_state: &WorldState, has_ground_contact: bool) -> Option<MotionCommand> {
+pub fn execute(
+    world_state: &WorldState,
+    has_ground_contact: bool,
+) -> Result<MotionCommand, Precondition> {
     match (world_state.robot.fall_state, has_ground_contact) {
         (FallState::Falling { direction, .. }, true) => {
-            Some(MotionCommand::FallProtection { direction })
+            Ok(MotionCommand::FallProtection { direction })
         }
-        _ => None,
+        _ => Err(Precondition::Situation),
     }
 }
@@ -1,5 +1,6 @@
 use types::{
     camera_position::CameraPosition,
+    decision_trace::Precondition,
     field_dimensions::GlobalFieldSide,
     filtered_game_state::FilteredGameState,
     initial_pose::InitialPose,
@@ -14,20 +15,23 @@ pub fn execute(
     world_state: &WorldState,
     enable_pose_detection: bool,
     initial_poses: &Players<InitialPose>,
-) -> Option<MotionCommand> {
+) -> Result<MotionCommand, Precondition> {
     if world_state.robot.primary_state != PrimaryState::Initial
         && world_state.robot.primary_state != PrimaryState::Standby
     {
-        return None;
+        return Err(Precondition::Situation);
     }
 
     if world_state.robot.primary_state == PrimaryState::Initial {
-        return Some(MotionCommand::Initial {
+        return Ok(MotionCommand::Initial {
             head: HeadMotion::Center,
         });
     }
 
-    let filtered_game_controller_state = world_state.filtered_game_controller_state.clone()?;
+    let filtered_game_controller_state = world_state
+        .filtered_game_controller_state
+        .clone()
+        .ok_or(Precondition::GameControllerState)?;
 
     let should_pose_detection_be_active = world_state.robot.primary_state == PrimaryState::Standby
         && filtered_game_controller_state.game_state == FilteredGameState::Standby
@@ -43,7 +47,7 @@ pub fn execute(
         (Side::Right, GlobalFieldSide::Away) => false,
     };
 
-    Some(MotionCommand::Initial {
+    Ok(MotionCommand::Initial {
         head: match (
             should_pose_detection_be_active,
             initial_pose_should_look_for_referee,
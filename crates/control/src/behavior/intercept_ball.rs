@@ -4,6 +4,7 @@ use geometry::line_segment::LineSegment;
 use linear_algebra::{Isometry2, Orientation2, Point};
 use spl_network_messages::{GamePhase, SubState};
 use types::{
+    decision_trace::Precondition,
     filtered_game_controller_state::FilteredGameControllerState,
     filtered_game_state::FilteredGameState,
     motion_command::{HeadMotion, ImageRegion, MotionCommand, OrientationMode, WalkSpeed},
@@ -16,7 +17,7 @@ pub fn execute(
     world_state: &WorldState,
     parameters: InterceptBallParameters,
     walk_speed: WalkSpeed,
-) -> Option<MotionCommand> {
+) -> Result<MotionCommand, Precondition> {
     if let Some(
         FilteredGameControllerState {
             game_phase: GamePhase::PenaltyShootout { .. },
@@ -28,60 +29,58 @@ pub fn execute(
         },
     ) = world_state.filtered_game_controller_state
     {
-        return None;
+        return Err(Precondition::GamePhase);
     }
 
     let filtered_game_state = world_state
         .filtered_game_controller_state
         .as_ref()
         .map(|filtered_game_controller_state| filtered_game_controller_state.game_state);
-    match (
+    if !matches!(
         filtered_game_state,
-        world_state.ball,
-        world_state.robot.ground_to_field,
+        Some(FilteredGameState::Playing {
+            ball_is_free: true,
+            ..
+        }) | None
     ) {
-        (
-            Some(FilteredGameState::Playing {
-                ball_is_free: true, ..
-            })
-            | None,
-            Some(ball),
-            Some(ground_to_field),
-        ) => {
-            if !ball_is_interception_candidate(ball, ground_to_field, &parameters) {
-                return None;
-            }
+        return Err(Precondition::GamePhase);
+    }
+    let ball = world_state.ball.ok_or(Precondition::Ball)?;
+    let ground_to_field = world_state
+        .robot
+        .ground_to_field
+        .ok_or(Precondition::GroundToField)?;
+    if !ball_is_interception_candidate(ball, ground_to_field, &parameters) {
+        return Err(Precondition::Situation);
+    }
 
-            let ball_line = Line {
-                point: ball.ball_in_ground,
-                direction: ball.ball_in_ground_velocity,
-            };
-            let interception_point = ball_line.closest_point(Point::origin());
+    let ball_line = Line {
+        point: ball.ball_in_ground,
+        direction: ball.ball_in_ground_velocity,
+    };
+    let interception_point = ball_line.closest_point(Point::origin());
 
-            if interception_point.coords().norm() > parameters.maximum_intercept_distance {
-                return None;
-            }
+    if interception_point.coords().norm() > parameters.maximum_intercept_distance {
+        return Err(Precondition::Situation);
+    }
 
-            let path = vec![PathSegment::LineSegment(LineSegment(
-                Point::origin(),
-                interception_point,
-            ))];
+    let path = vec![PathSegment::LineSegment(LineSegment(
+        Point::origin(),
+        interception_point,
+    ))];
 
-            Some(MotionCommand::Walk {
-                head: HeadMotion::LookAt {
-                    target: ball.ball_in_ground,
-                    image_region_target: ImageRegion::Center,
-                    camera: None,
-                },
-                path,
-                left_arm: types::motion_command::ArmMotion::Swing,
-                right_arm: types::motion_command::ArmMotion::Swing,
-                orientation_mode: OrientationMode::Override(Orientation2::identity()),
-                speed: walk_speed,
-            })
-        }
-        _ => None,
-    }
+    Ok(MotionCommand::Walk {
+        head: HeadMotion::LookAt {
+            target: ball.ball_in_ground,
+            image_region_target: ImageRegion::Center,
+            camera: None,
+        },
+        path,
+        left_arm: types::motion_command::ArmMotion::Swing,
+        right_arm: types::motion_command::ArmMotion::Swing,
+        orientation_mode: OrientationMode::Override(Orientation2::identity()),
+        speed: walk_speed,
+    })
 }
 
 fn ball_is_interception_candidate(
@@ -1,12 +1,13 @@
 use spl_network_messages::GamePhase;
 use types::{
+    decision_trace::Precondition,
     filtered_game_controller_state::FilteredGameControllerState,
     motion_command::{HeadMotion, MotionCommand},
     primary_state::PrimaryState,
     world_state::WorldState,
 };
 
-pub fn execute(world_state: &WorldState) -> Option<MotionCommand> {
+pub fn execute(world_state: &WorldState) -> Result<MotionCommand, Precondition> {
     match (
         &world_state.filtered_game_controller_state,
         world_state.robot.primary_state,
@@ -17,12 +18,12 @@ pub fn execute(world_state: &WorldState) -> Option<MotionCommand> {
                 ..
             }),
             _,
-        ) => None,
+        ) => Err(Precondition::GamePhase),
         (_, PrimaryState::Ready | PrimaryState::Set | PrimaryState::Playing) => {
-            Some(MotionCommand::Stand {
+            Ok(MotionCommand::Stand {
                 head: HeadMotion::LookAround,
             })
         }
-        _ => None,
+        _ => Err(Precondition::Situation),
     }
 }
@@ -3,6 +3,7 @@ use framework::AdditionalOutput;
 use linear_algebra::{Point2, Pose2, Rotation2};
 use types::{
     camera_position::CameraPosition,
+    decision_trace::Precondition,
     motion_command::{HeadMotion, ImageRegion, MotionCommand, WalkSpeed},
     path_obstacles::PathObstacle,
     world_state::WorldState,
@@ -18,7 +19,7 @@ pub fn execute(
     path_obstacles_output: &mut AdditionalOutput<Vec<PathObstacle>>,
     walk_speed: WalkSpeed,
     distance_to_be_aligned: f32,
-) -> Option<MotionCommand> {
+) -> Result<MotionCommand, Precondition> {
     let pose_looking_at_referee = if let (Some(expected_referee_position), Some(ground_to_field)) =
         (expected_referee_position, world_state.robot.ground_to_field)
     {
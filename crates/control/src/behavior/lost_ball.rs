@@ -3,6 +3,7 @@ use framework::AdditionalOutput;
 use geometry::look_at::LookAt;
 use linear_algebra::Point2;
 use types::{
+    decision_trace::Precondition,
     motion_command::{HeadMotion, MotionCommand, OrientationMode, WalkSpeed},
     parameters::LostBallParameters,
     path_obstacles::PathObstacle,
@@ -18,8 +19,11 @@ pub fn execute(
     lost_ball_parameters: &LostBallParameters,
     path_obstacles_output: &mut AdditionalOutput<Vec<PathObstacle>>,
     walk_speed: WalkSpeed,
-) -> Option<MotionCommand> {
-    let ground_to_field = world_state.robot.ground_to_field?;
+) -> Result<MotionCommand, Precondition> {
+    let ground_to_field = world_state
+        .robot
+        .ground_to_field
+        .ok_or(Precondition::GroundToField)?;
     let walk_target = ground_to_field.inverse()
         * (absolute_last_known_ball_position - lost_ball_parameters.offset_to_last_ball_location);
     let relative_last_known_ball_position =
@@ -48,7 +52,7 @@ pub fn execute(
         },
         None => HeadMotion::SearchForLostBall,
     };
-    Some(walk_path_planner.walk_with_obstacle_avoiding_arms(
+    Ok(walk_path_planner.walk_with_obstacle_avoiding_arms(
         head,
         OrientationMode::Override(orientation),
         path,
@@ -1,13 +1,14 @@
 use types::{
+    decision_trace::Precondition,
     motion_command::{HeadMotion, MotionCommand},
     world_state::WorldState,
 };
 
-pub fn execute(world_state: &WorldState) -> Option<MotionCommand> {
+pub fn execute(world_state: &WorldState) -> Result<MotionCommand, Precondition> {
     if world_state.robot.has_ground_contact {
-        return None;
+        return Err(Precondition::Situation);
     }
-    Some(MotionCommand::Stand {
+    Ok(MotionCommand::Stand {
         head: HeadMotion::Center,
     })
 }
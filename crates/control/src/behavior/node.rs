@@ -1,23 +1,23 @@
-use std::time::SystemTime;
-
 use color_eyre::Result;
 use serde::{Deserialize, Serialize};
 
 use context_attribute::context;
 use coordinate_systems::Field;
 use framework::{AdditionalOutput, MainOutput};
-use linear_algebra::{point, Point2};
+use linear_algebra::Point2;
 use spl_network_messages::{GamePhase, PlayerNumber, SubState, Team};
 use types::{
     action::Action,
+    behavior_state::BehaviorState,
     cycle_time::CycleTime,
+    decision_trace::{ActionCandidate, ActionOutcome, DecisionTrace},
     dribble_path_plan::DribblePathPlan,
     field_dimensions::{FieldDimensions, GlobalFieldSide, Side},
     filtered_game_controller_state::FilteredGameControllerState,
     filtered_game_state::FilteredGameState,
     initial_pose::InitialPose,
     kick_decision::DecisionParameters,
-    motion_command::{MotionCommand, WalkSpeed},
+    motion_command::{HeadMotion, MotionCommand, WalkSpeed},
     parameters::{
         BehaviorParameters, InWalkKicksParameters, InterceptBallParameters, KeeperMotionParameters,
         LostBallParameters,
@@ -31,21 +31,18 @@ use types::{
 
 use super::{
     animation, calibrate,
-    defend::{Defend, DefendMode},
+    defend::Defend,
     dribble, fall_safely,
     head::LookAction,
     initial, intercept_ball, jump, look_around, look_at_referee, lost_ball, no_ground_contact,
-    penalize, prepare_jump, search, sit_down, stand, stand_up, support, unstiff, walk_to_kick_off,
-    walk_to_penalty_kick,
+    penalize, prepare_jump, receive_pass, search, sit_down, stand, stand_up, support, unstiff,
+    walk_to_kick_off, walk_to_penalty_kick,
     walk_to_pose::{WalkAndStand, WalkPathPlanner},
 };
 
 #[derive(Deserialize, Serialize)]
 pub struct Behavior {
-    last_known_ball_position: Point2<Field>,
-    active_since: Option<SystemTime>,
-    previous_role: Role,
-    last_defender_mode: DefendMode,
+    state: BehaviorState,
 }
 
 #[context]
@@ -83,6 +80,8 @@ pub struct CycleContext {
 
     path_obstacles_output: AdditionalOutput<Vec<PathObstacle>, "path_obstacles">,
     active_action_output: AdditionalOutput<Action, "active_action">,
+    decision_trace: AdditionalOutput<DecisionTrace, "decision_trace">,
+    behavior_state: AdditionalOutput<BehaviorState, "behavior_state">,
 
     last_motion_command: CyclerState<MotionCommand, "last_motion_command">,
 }
@@ -96,14 +95,12 @@ pub struct MainOutputs {
 impl Behavior {
     pub fn new(_context: CreationContext) -> Result<Self> {
         Ok(Self {
-            last_known_ball_position: point![0.0, 0.0],
-            active_since: None,
-            previous_role: Role::Searcher,
-            last_defender_mode: DefendMode::Passive,
+            state: BehaviorState::default(),
         })
     }
 
     pub fn cycle(&mut self, mut context: CycleContext) -> Result<MainOutputs> {
+        context.behavior_state.fill_if_subscribed(|| self.state);
         let world_state = context.world_state;
         if let Some(command) = &context.parameters.injected_motion_command {
             return Ok(MainOutputs {
@@ -112,25 +109,25 @@ impl Behavior {
         }
 
         if let Some(ball_state) = &world_state.ball {
-            self.last_known_ball_position = ball_state.ball_in_field;
+            self.state.last_known_ball_position = ball_state.ball_in_field;
         }
 
         let now = context.cycle_time.start_time;
-        match (self.active_since, world_state.robot.primary_state) {
+        match (self.state.active_since, world_state.robot.primary_state) {
             (None, PrimaryState::Ready | PrimaryState::Set | PrimaryState::Playing) => {
-                self.active_since = Some(now)
+                self.state.active_since = Some(now)
             }
             (None, _) => {}
             (Some(_), PrimaryState::Ready | PrimaryState::Set | PrimaryState::Playing) => {}
-            (Some(_), _) => self.active_since = None,
+            (Some(_), _) => self.state.active_since = None,
         }
 
-        if self.previous_role != context.world_state.robot.role
+        if self.state.previous_role != context.world_state.robot.role
             && context.world_state.robot.role != Role::Searcher
             && context.world_state.robot.role != Role::Loser
-            && self.previous_role != Role::Keeper
+            && self.state.previous_role != Role::Keeper
         {
-            self.previous_role = context.world_state.robot.role;
+            self.state.previous_role = context.world_state.robot.role;
         }
 
         let mut actions = vec![
@@ -146,7 +143,7 @@ impl Behavior {
             Action::Calibrate,
         ];
 
-        if let Some(active_since) = self.active_since {
+        if let Some(active_since) = self.state.active_since {
             let duration_active = now.duration_since(active_since)?;
             if !context.is_localization_converged
                 && (duration_active < context.parameters.maximum_lookaround_duration)
@@ -160,6 +157,15 @@ impl Behavior {
         }
         actions.push(Action::InterceptBall);
 
+        if world_state.incoming_pass.is_some()
+            && !matches!(
+                world_state.robot.role,
+                Role::Keeper | Role::ReplacementKeeper | Role::Striker
+            )
+        {
+            actions.push(Action::ReceivePass);
+        }
+
         match world_state.robot.role {
             Role::DefenderLeft if should_do_kick_in_pose_detection(world_state) => {
                 actions.push(Action::LookAtReferee);
@@ -268,251 +274,285 @@ impl Behavior {
             &context.parameters.role_positions,
             &walk_and_stand,
             &look_action,
-            &mut self.last_defender_mode,
+            &mut self.state.last_defender_mode,
         );
 
-        let (action, motion_command) = actions
-            .iter()
-            .find_map(|action| {
-                let motion_command = match action {
-                    Action::Animation => animation::execute(world_state),
-                    Action::Unstiff => unstiff::execute(world_state),
-                    Action::SitDown => sit_down::execute(world_state),
-                    Action::Penalize => penalize::execute(world_state),
-                    Action::Initial => initial::execute(
-                        world_state,
-                        *context.enable_pose_detection,
-                        context.initial_poses,
-                    ),
-                    Action::LookAtReferee => look_at_referee::execute(
-                        *context.enable_pose_detection,
-                        &walk_and_stand,
-                        context.expected_referee_position,
-                        context.world_state,
-                        &mut context.path_obstacles_output,
-                        *context.support_walk_speed,
-                        context
-                            .parameters
-                            .walk_and_stand
-                            .normal_distance_to_be_aligned,
-                    ),
-                    Action::FallSafely => {
-                        fall_safely::execute(world_state, *context.has_ground_contact)
-                    }
-                    Action::StandUp => {
-                        stand_up::execute(world_state, context.parameters.maximum_standup_attempts)
-                    }
-                    Action::NoGroundContact => no_ground_contact::execute(world_state),
-                    Action::LookAround => look_around::execute(world_state),
-                    Action::KeeperMotion => defend.keeper_motion(context.keeper_motion.clone()),
-                    Action::InterceptBall => intercept_ball::execute(
-                        world_state,
-                        *context.intercept_ball_parameters,
-                        *context.intercept_ball_walk_speed,
-                    ),
-                    Action::Calibrate => {
-                        calibrate::execute(world_state, *context.use_stand_head_unstiff_calibration)
-                    }
-                    Action::DefendGoal => defend.goal(
-                        &mut context.path_obstacles_output,
-                        *context.defend_walk_speed,
-                        context
-                            .parameters
-                            .walk_and_stand
-                            .defender_distance_to_be_aligned,
-                    ),
-                    Action::DefendKickOff => defend.kick_off(
-                        &mut context.path_obstacles_output,
-                        *context.defend_walk_speed,
-                        context
-                            .parameters
-                            .walk_and_stand
-                            .defender_distance_to_be_aligned,
-                    ),
-                    Action::DefendLeft => defend.left(
-                        &mut context.path_obstacles_output,
-                        *context.defend_walk_speed,
-                        context
-                            .parameters
-                            .walk_and_stand
-                            .defender_distance_to_be_aligned,
-                    ),
-                    Action::DefendRight => defend.right(
+        let mut evaluated_candidates = Vec::with_capacity(actions.len());
+        let chosen = actions.iter().find_map(|action| {
+            let motion_command = match action {
+                Action::Animation => animation::execute(world_state),
+                Action::Unstiff => unstiff::execute(world_state),
+                Action::SitDown => sit_down::execute(world_state),
+                Action::Penalize => penalize::execute(world_state),
+                Action::Initial => initial::execute(
+                    world_state,
+                    *context.enable_pose_detection,
+                    context.initial_poses,
+                ),
+                Action::LookAtReferee => look_at_referee::execute(
+                    *context.enable_pose_detection,
+                    &walk_and_stand,
+                    context.expected_referee_position,
+                    context.world_state,
+                    &mut context.path_obstacles_output,
+                    *context.support_walk_speed,
+                    context
+                        .parameters
+                        .walk_and_stand
+                        .normal_distance_to_be_aligned,
+                ),
+                Action::FallSafely => {
+                    fall_safely::execute(world_state, *context.has_ground_contact)
+                }
+                Action::StandUp => {
+                    stand_up::execute(world_state, context.parameters.maximum_standup_attempts)
+                }
+                Action::NoGroundContact => no_ground_contact::execute(world_state),
+                Action::LookAround => look_around::execute(world_state),
+                Action::KeeperMotion => defend.keeper_motion(context.keeper_motion.clone()),
+                Action::InterceptBall => intercept_ball::execute(
+                    world_state,
+                    *context.intercept_ball_parameters,
+                    *context.intercept_ball_walk_speed,
+                ),
+                Action::Calibrate => {
+                    calibrate::execute(world_state, *context.use_stand_head_unstiff_calibration)
+                }
+                Action::DefendGoal => defend.goal(
+                    &mut context.path_obstacles_output,
+                    *context.defend_walk_speed,
+                    context
+                        .parameters
+                        .walk_and_stand
+                        .defender_distance_to_be_aligned,
+                ),
+                Action::DefendKickOff => defend.kick_off(
+                    &mut context.path_obstacles_output,
+                    *context.defend_walk_speed,
+                    context
+                        .parameters
+                        .walk_and_stand
+                        .defender_distance_to_be_aligned,
+                ),
+                Action::DefendLeft => defend.left(
+                    &mut context.path_obstacles_output,
+                    *context.defend_walk_speed,
+                    context
+                        .parameters
+                        .walk_and_stand
+                        .defender_distance_to_be_aligned,
+                ),
+                Action::DefendRight => defend.right(
+                    &mut context.path_obstacles_output,
+                    *context.defend_walk_speed,
+                    context
+                        .parameters
+                        .walk_and_stand
+                        .defender_distance_to_be_aligned,
+                ),
+                Action::DefendPenaltyKick => defend.penalty_kick(
+                    &mut context.path_obstacles_output,
+                    *context.defend_walk_speed,
+                    context
+                        .parameters
+                        .walk_and_stand
+                        .defender_distance_to_be_aligned,
+                ),
+                Action::DefendOpponentCornerKick { side: Side::Left } => defend
+                    .opponent_corner_kick(
                         &mut context.path_obstacles_output,
                         *context.defend_walk_speed,
+                        Side::Left,
                         context
                             .parameters
                             .walk_and_stand
                             .defender_distance_to_be_aligned,
                     ),
-                    Action::DefendPenaltyKick => defend.penalty_kick(
+                Action::DefendOpponentCornerKick { side: Side::Right } => defend
+                    .opponent_corner_kick(
                         &mut context.path_obstacles_output,
                         *context.defend_walk_speed,
+                        Side::Right,
                         context
                             .parameters
                             .walk_and_stand
                             .defender_distance_to_be_aligned,
                     ),
-                    Action::DefendOpponentCornerKick { side: Side::Left } => defend
-                        .opponent_corner_kick(
-                            &mut context.path_obstacles_output,
-                            *context.defend_walk_speed,
-                            Side::Left,
-                            context
-                                .parameters
-                                .walk_and_stand
-                                .defender_distance_to_be_aligned,
-                        ),
-                    Action::DefendOpponentCornerKick { side: Side::Right } => defend
-                        .opponent_corner_kick(
-                            &mut context.path_obstacles_output,
-                            *context.defend_walk_speed,
-                            Side::Right,
-                            context
-                                .parameters
-                                .walk_and_stand
-                                .defender_distance_to_be_aligned,
-                        ),
-                    Action::Stand => stand::execute(
-                        world_state,
-                        context.field_dimensions,
-                        &context.world_state.robot.role,
-                    ),
-                    Action::Dribble => dribble::execute(
-                        world_state,
-                        &walk_path_planner,
-                        context.in_walk_kicks,
-                        &context.parameters.dribbling,
-                        context.dribble_path_plan.cloned(),
-                        *context.dribble_walk_speed,
-                    ),
-                    Action::Jump => jump::execute(world_state),
-                    Action::PrepareJump => prepare_jump::execute(world_state),
-                    Action::Search => search::execute(
-                        world_state,
-                        &walk_path_planner,
-                        &walk_and_stand,
-                        context.field_dimensions,
-                        &context.parameters.search,
-                        &mut context.path_obstacles_output,
-                        self.previous_role,
-                        *context.search_walk_speed,
-                        context
-                            .parameters
-                            .walk_and_stand
-                            .normal_distance_to_be_aligned,
-                    ),
-                    Action::SearchForLostBall => lost_ball::execute(
-                        world_state,
-                        self.last_known_ball_position,
-                        &walk_path_planner,
-                        context.lost_ball_parameters,
-                        &mut context.path_obstacles_output,
-                        *context.lost_ball_walk_speed,
-                    ),
-                    Action::SupportLeft => support::execute(
-                        world_state,
-                        context.field_dimensions,
-                        Some(Side::Left),
-                        context
-                            .parameters
-                            .role_positions
-                            .left_midfielder_distance_to_ball,
-                        context
-                            .parameters
-                            .role_positions
-                            .left_midfielder_maximum_x_in_ready_and_when_ball_is_not_free,
-                        context.parameters.role_positions.left_midfielder_minimum_x,
-                        &walk_and_stand,
-                        &look_action,
-                        &mut context.path_obstacles_output,
-                        *context.support_walk_speed,
-                        context
-                            .parameters
-                            .walk_and_stand
-                            .normal_distance_to_be_aligned,
-                    ),
-                    Action::SupportRight => support::execute(
-                        world_state,
-                        context.field_dimensions,
-                        Some(Side::Right),
-                        context
-                            .parameters
-                            .role_positions
-                            .right_midfielder_distance_to_ball,
-                        context
-                            .parameters
-                            .role_positions
-                            .right_midfielder_maximum_x_in_ready_and_when_ball_is_not_free,
-                        context.parameters.role_positions.right_midfielder_minimum_x,
-                        &walk_and_stand,
-                        &look_action,
-                        &mut context.path_obstacles_output,
-                        *context.support_walk_speed,
-                        context
-                            .parameters
-                            .walk_and_stand
-                            .normal_distance_to_be_aligned,
-                    ),
-                    Action::SupportStriker => support::execute(
-                        world_state,
-                        context.field_dimensions,
-                        None,
-                        context
-                            .parameters
-                            .role_positions
-                            .striker_supporter_distance_to_ball,
-                        context
-                            .parameters
-                            .role_positions
-                            .striker_supporter_maximum_x_in_ready_and_when_ball_is_not_free,
-                        context
-                            .parameters
-                            .role_positions
-                            .striker_supporter_minimum_x,
-                        &walk_and_stand,
-                        &look_action,
-                        &mut context.path_obstacles_output,
-                        *context.support_walk_speed,
-                        context
-                            .parameters
-                            .walk_and_stand
-                            .normal_distance_to_be_aligned,
-                    ),
-                    Action::WalkToKickOff => walk_to_kick_off::execute(
-                        world_state,
-                        &walk_and_stand,
-                        &look_action,
-                        &mut context.path_obstacles_output,
-                        context.parameters.role_positions.striker_kickoff_position,
-                        context.kick_decision_parameters.kick_off_angle,
-                        *context.walk_to_kickoff_walk_speed,
-                        context
-                            .parameters
-                            .walk_and_stand
-                            .normal_distance_to_be_aligned,
-                    ),
-                    Action::WalkToPenaltyKick => walk_to_penalty_kick::execute(
-                        world_state,
-                        &walk_and_stand,
-                        &look_action,
-                        &mut context.path_obstacles_output,
-                        context.field_dimensions,
-                        *context.walk_to_penalty_kick_walk_speed,
-                        context
-                            .parameters
-                            .walk_and_stand
-                            .normal_distance_to_be_aligned,
-                    ),
-                }?;
-                Some((action, motion_command))
-            })
-            .unwrap_or_else(|| {
-                panic!(
-                    "there has to be at least one action available, world_state: {world_state:#?}",
-                )
+                Action::Stand => stand::execute(
+                    world_state,
+                    context.field_dimensions,
+                    &context.world_state.robot.role,
+                ),
+                Action::Dribble => dribble::execute(
+                    world_state,
+                    &walk_path_planner,
+                    context.in_walk_kicks,
+                    &context.parameters.dribbling,
+                    context.dribble_path_plan.cloned(),
+                    *context.dribble_walk_speed,
+                ),
+                Action::Jump => jump::execute(world_state),
+                Action::PrepareJump => prepare_jump::execute(world_state),
+                Action::ReceivePass => receive_pass::execute(
+                    world_state,
+                    &walk_and_stand,
+                    &look_action,
+                    &mut context.path_obstacles_output,
+                    *context.support_walk_speed,
+                    context
+                        .parameters
+                        .walk_and_stand
+                        .normal_distance_to_be_aligned,
+                ),
+                Action::Search => search::execute(
+                    world_state,
+                    &walk_path_planner,
+                    &walk_and_stand,
+                    context.field_dimensions,
+                    &context.parameters.search,
+                    &mut context.path_obstacles_output,
+                    self.state.previous_role,
+                    *context.search_walk_speed,
+                    context
+                        .parameters
+                        .walk_and_stand
+                        .normal_distance_to_be_aligned,
+                ),
+                Action::SearchForLostBall => lost_ball::execute(
+                    world_state,
+                    self.state.last_known_ball_position,
+                    &walk_path_planner,
+                    context.lost_ball_parameters,
+                    &mut context.path_obstacles_output,
+                    *context.lost_ball_walk_speed,
+                ),
+                Action::SupportLeft => support::execute(
+                    world_state,
+                    context.field_dimensions,
+                    Some(Side::Left),
+                    context
+                        .parameters
+                        .role_positions
+                        .left_midfielder_distance_to_ball,
+                    context
+                        .parameters
+                        .role_positions
+                        .left_midfielder_maximum_x_in_ready_and_when_ball_is_not_free,
+                    context.parameters.role_positions.left_midfielder_minimum_x,
+                    &walk_and_stand,
+                    &look_action,
+                    &mut context.path_obstacles_output,
+                    *context.support_walk_speed,
+                    context
+                        .parameters
+                        .walk_and_stand
+                        .normal_distance_to_be_aligned,
+                ),
+                Action::SupportRight => support::execute(
+                    world_state,
+                    context.field_dimensions,
+                    Some(Side::Right),
+                    context
+                        .parameters
+                        .role_positions
+                        .right_midfielder_distance_to_ball,
+                    context
+                        .parameters
+                        .role_positions
+                        .right_midfielder_maximum_x_in_ready_and_when_ball_is_not_free,
+                    context.parameters.role_positions.right_midfielder_minimum_x,
+                    &walk_and_stand,
+                    &look_action,
+                    &mut context.path_obstacles_output,
+                    *context.support_walk_speed,
+                    context
+                        .parameters
+                        .walk_and_stand
+                        .normal_distance_to_be_aligned,
+                ),
+                Action::SupportStriker => support::execute(
+                    world_state,
+                    context.field_dimensions,
+                    None,
+                    context
+                        .parameters
+                        .role_positions
+                        .striker_supporter_distance_to_ball,
+                    context
+                        .parameters
+                        .role_positions
+                        .striker_supporter_maximum_x_in_ready_and_when_ball_is_not_free,
+                    context
+                        .parameters
+                        .role_positions
+                        .striker_supporter_minimum_x,
+                    &walk_and_stand,
+                    &look_action,
+                    &mut context.path_obstacles_output,
+                    *context.support_walk_speed,
+                    context
+                        .parameters
+                        .walk_and_stand
+                        .normal_distance_to_be_aligned,
+                ),
+                Action::WalkToKickOff => walk_to_kick_off::execute(
+                    world_state,
+                    &walk_and_stand,
+                    &look_action,
+                    &mut context.path_obstacles_output,
+                    context.parameters.role_positions.striker_kickoff_position,
+                    context.kick_decision_parameters.kick_off_angle,
+                    *context.walk_to_kickoff_walk_speed,
+                    context
+                        .parameters
+                        .walk_and_stand
+                        .normal_distance_to_be_aligned,
+                ),
+                Action::WalkToPenaltyKick => walk_to_penalty_kick::execute(
+                    world_state,
+                    &walk_and_stand,
+                    &look_action,
+                    &mut context.path_obstacles_output,
+                    context.field_dimensions,
+                    *context.walk_to_penalty_kick_walk_speed,
+                    context
+                        .parameters
+                        .walk_and_stand
+                        .normal_distance_to_be_aligned,
+                ),
+            };
+            evaluated_candidates.push(ActionCandidate {
+                action: *action,
+                outcome: match motion_command {
+                    Ok(_) => ActionOutcome::Chosen,
+                    Err(precondition) => ActionOutcome::PreconditionFailed { precondition },
+                },
             });
-        context.active_action_output.fill_if_subscribed(|| *action);
+            Some((action, motion_command.ok()?))
+        });
+        let motion_command = match chosen {
+            Some((action, motion_command)) => {
+                context.active_action_output.fill_if_subscribed(|| *action);
+                motion_command
+            }
+            // roles may lack an available action without inputs like the ball or the localization
+            None => MotionCommand::Stand {
+                head: HeadMotion::LookAround,
+            },
+        };
+        context.decision_trace.fill_if_subscribed(|| DecisionTrace {
+            role: world_state.robot.role,
+            candidates: evaluated_candidates
+                .iter()
+                .copied()
+                .chain(actions[evaluated_candidates.len()..].iter().map(|&action| {
+                    ActionCandidate {
+                        action,
+                        outcome: ActionOutcome::NotEvaluated,
+                    }
+                }))
+                .collect(),
+        });
 
         *context.last_motion_command = motion_command.clone();
 
@@ -567,3 +607,395 @@ pub fn should_do_kick_in_pose_detection(world_state: &WorldState) -> bool {
         false
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::Path, time::UNIX_EPOCH};
+
+    use color_eyre::eyre::WrapErr;
+    use serde_json::Value;
+
+    use linear_algebra::{point, vector, Isometry2};
+    use parameters::json::merge_json;
+    use types::{
+        behavior_fixture::{BehaviorFixture, BehaviorFixtureParameters},
+        decision_trace::Precondition,
+        world_state::BallState,
+    };
+
+    use super::*;
+
+    const FIXTURE_DIRECTORY: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/behavior");
+    const DEFAULT_PARAMETERS: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../etc/parameters/default.json"
+    );
+    const UPDATE_VARIABLE: &str = "UPDATE_BEHAVIOR_FIXTURES";
+    const TOLERANCE: f64 = 1e-4;
+
+    fn parameters(fixture: &BehaviorFixture) -> Result<BehaviorFixtureParameters> {
+        let mut parameters: Value = serde_json::from_str(
+            &fs::read_to_string(DEFAULT_PARAMETERS)
+                .wrap_err("failed to read default parameters")?,
+        )
+        .wrap_err("failed to parse default parameters")?;
+        merge_json(&mut parameters, &fixture.parameter_overrides);
+        serde_json::from_value(parameters).wrap_err("failed to deserialize behavior parameters")
+    }
+
+    fn replay(fixture: &BehaviorFixture) -> Result<(MotionCommand, DecisionTrace)> {
+        let inputs = &fixture.inputs;
+        let parameters = &parameters(fixture)?;
+        let mut path_obstacles = None;
+        let mut active_action = None;
+        let mut decision_trace = None;
+        let mut behavior_state = None;
+        let mut last_motion_command = inputs.last_motion_command.clone();
+
+        let context = CycleContext {
+            has_ground_contact: &inputs.has_ground_contact,
+            world_state: &inputs.world_state,
+            dribble_path_plan: inputs.dribble_path_plan.as_ref(),
+            cycle_time: &inputs.cycle_time,
+            is_localization_converged: &inputs.is_localization_converged,
+            expected_referee_position: inputs.expected_referee_position.as_ref(),
+            parameters: &parameters.behavior,
+            kick_decision_parameters: &parameters.kick_selector,
+            in_walk_kicks: &parameters.in_walk_kicks,
+            field_dimensions: &parameters.field_dimensions,
+            lost_ball_parameters: &parameters.behavior.lost_ball,
+            intercept_ball_parameters: &parameters.behavior.intercept_ball,
+            enable_pose_detection: &parameters.pose_detection.enable,
+            keeper_motion: &parameters.keeper_motion,
+            use_stand_head_unstiff_calibration: &parameters
+                .calibration_controller
+                .use_stand_head_unstiff_calibration,
+            dribble_walk_speed: &parameters.walk_speed.dribble,
+            initial_poses: &parameters.localization.initial_poses,
+            intercept_ball_walk_speed: &parameters.walk_speed.intercept_ball,
+            lost_ball_walk_speed: &parameters.walk_speed.lost_ball,
+            search_walk_speed: &parameters.walk_speed.search,
+            support_walk_speed: &parameters.walk_speed.support,
+            walk_to_kickoff_walk_speed: &parameters.walk_speed.walk_to_kickoff,
+            walk_to_penalty_kick_walk_speed: &parameters.walk_speed.walk_to_penalty_kick,
+            defend_walk_speed: &parameters.walk_speed.defend,
+            path_obstacles_output: AdditionalOutput::new(false, &mut path_obstacles),
+            active_action_output: AdditionalOutput::new(false, &mut active_action),
+            decision_trace: AdditionalOutput::new(true, &mut decision_trace),
+            behavior_state: AdditionalOutput::new(false, &mut behavior_state),
+            last_motion_command: &mut last_motion_command,
+        };
+        let mut behavior = Behavior {
+            state: fixture.state,
+        };
+        let motion_command = behavior.cycle(context)?.motion_command.value;
+        Ok((motion_command, decision_trace.unwrap_or_default()))
+    }
+
+    fn playing_fixture() -> Result<BehaviorFixture> {
+        let mut fixture: BehaviorFixture = serde_json::from_str(&fs::read_to_string(
+            Path::new(FIXTURE_DIRECTORY).join("penalized.json"),
+        )?)?;
+        let world_state = &mut fixture.inputs.world_state;
+        world_state.robot.primary_state = PrimaryState::Playing;
+        world_state.robot.ground_to_field = Some(Isometry2::from_parts(vector![-1.0, 0.0], 0.0));
+        world_state.filtered_game_controller_state = Some(FilteredGameControllerState {
+            game_state: FilteredGameState::Playing {
+                ball_is_free: true,
+                kick_off: false,
+            },
+            ..Default::default()
+        });
+        world_state.ball = Some(BallState {
+            ball_in_ground: point![1.0, 0.1],
+            ball_in_field: point![0.0, 0.1],
+            ball_in_ground_velocity: vector![-1.0, 0.0],
+            last_seen_ball: UNIX_EPOCH,
+            penalty_shot_direction: None,
+            field_side: Side::Left,
+        });
+        world_state.kick_decisions = Some(Vec::new());
+        world_state.instant_kick_decisions = Some(Vec::new());
+        fixture.inputs.last_motion_command = MotionCommand::Stand {
+            head: HeadMotion::Center,
+        };
+        Ok(fixture)
+    }
+
+    type Removal = (Precondition, fn(&mut WorldState));
+
+    const WITHOUT_GROUND_TO_FIELD: Removal = (Precondition::GroundToField, |world_state| {
+        world_state.robot.ground_to_field = None;
+    });
+    const WITHOUT_BALL: Removal = (Precondition::Ball, |world_state| {
+        world_state.ball = None;
+        world_state.rule_ball = None;
+    });
+    const WITHOUT_KICK_DECISIONS: Removal = (Precondition::KickDecisions, |world_state| {
+        world_state.kick_decisions = None;
+    });
+    const IN_PENALTY_SHOOTOUT: Removal = (Precondition::GamePhase, |world_state| {
+        if let Some(state) = world_state.filtered_game_controller_state.as_mut() {
+            state.game_phase = GamePhase::PenaltyShootout {
+                kicking_team: Team::Hulks,
+            };
+        }
+    });
+    const WITHOUT_FREE_BALL: Removal = (Precondition::GamePhase, |world_state| {
+        if let Some(state) = world_state.filtered_game_controller_state.as_mut() {
+            state.game_state = FilteredGameState::Playing {
+                ball_is_free: false,
+                kick_off: false,
+            };
+        }
+    });
+
+    #[test]
+    fn missing_inputs_are_reported_as_failed_preconditions() -> Result<()> {
+        type Scenario = (Action, fn(&mut WorldState), &'static [Removal]);
+        let scenarios: [Scenario; 9] = [
+            (
+                Action::KeeperMotion,
+                |world_state| {
+                    world_state.robot.player_number = PlayerNumber::One;
+                    world_state.robot.role = Role::Keeper;
+                },
+                &[WITHOUT_BALL],
+            ),
+            (
+                Action::InterceptBall,
+                |_| {},
+                &[
+                    WITHOUT_GROUND_TO_FIELD,
+                    WITHOUT_BALL,
+                    IN_PENALTY_SHOOTOUT,
+                    WITHOUT_FREE_BALL,
+                ],
+            ),
+            (
+                Action::Dribble,
+                |world_state| {
+                    world_state.ball.as_mut().unwrap().ball_in_ground_velocity = vector![0.0, 0.0];
+                },
+                &[WITHOUT_BALL, WITHOUT_KICK_DECISIONS],
+            ),
+            (
+                Action::ReceivePass,
+                |world_state| {
+                    world_state.robot.role = Role::MidfielderLeft;
+                    world_state.incoming_pass = Some(point![1.0, 1.0]);
+                    world_state.ball.as_mut().unwrap().ball_in_ground_velocity = vector![0.0, 0.0];
+                },
+                &[WITHOUT_GROUND_TO_FIELD, WITHOUT_BALL],
+            ),
+            (
+                Action::SupportLeft,
+                |world_state| {
+                    world_state.robot.role = Role::MidfielderLeft;
+                    world_state.ball.as_mut().unwrap().ball_in_ground_velocity = vector![0.0, 0.0];
+                },
+                &[WITHOUT_GROUND_TO_FIELD],
+            ),
+            (
+                Action::DefendLeft,
+                |world_state| {
+                    world_state.robot.role = Role::DefenderLeft;
+                    world_state.ball.as_mut().unwrap().ball_in_ground_velocity = vector![0.0, 0.0];
+                },
+                &[WITHOUT_GROUND_TO_FIELD],
+            ),
+            (
+                Action::DefendGoal,
+                |world_state| {
+                    world_state.robot.role = Role::Keeper;
+                    world_state.ball.as_mut().unwrap().ball_in_ground_velocity = vector![0.0, 0.0];
+                },
+                &[WITHOUT_GROUND_TO_FIELD],
+            ),
+            (
+                Action::Search,
+                |world_state| {
+                    world_state.robot.role = Role::Searcher;
+                    world_state.ball = None;
+                },
+                &[WITHOUT_GROUND_TO_FIELD],
+            ),
+            (
+                Action::SearchForLostBall,
+                |world_state| {
+                    world_state.robot.role = Role::Loser;
+                    world_state.ball = None;
+                },
+                &[WITHOUT_GROUND_TO_FIELD],
+            ),
+        ];
+
+        for (action, prepare, removals) in scenarios {
+            let mut fixture = playing_fixture()?;
+            prepare(&mut fixture.inputs.world_state);
+            let (_, trace) = replay(&fixture)?;
+            assert_eq!(trace.chosen_action(), Some(action), "{trace:#?}");
+
+            for (precondition, remove) in removals {
+                let mut fixture = fixture.clone();
+                remove(&mut fixture.inputs.world_state);
+                let (_, trace) = replay(&fixture)?;
+                let outcome = trace
+                    .candidates
+                    .iter()
+                    .find(|candidate| candidate.action == action)
+                    .map(|candidate| candidate.outcome);
+                assert_eq!(
+                    outcome,
+                    Some(ActionOutcome::PreconditionFailed {
+                        precondition: *precondition
+                    }),
+                    "{action:?} without {precondition:?}: {trace:#?}"
+                );
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn robot_without_available_action_stands_and_looks_around() -> Result<()> {
+        let mut fixture = playing_fixture()?;
+        WITHOUT_BALL.1(&mut fixture.inputs.world_state);
+
+        let (motion_command, trace) = replay(&fixture)?;
+
+        assert_eq!(trace.chosen_action(), None, "{trace:#?}");
+        assert_eq!(
+            motion_command,
+            MotionCommand::Stand {
+                head: HeadMotion::LookAround
+            }
+        );
+        Ok(())
+    }
+
+    fn collect_differences(
+        expected: &Value,
+        actual: &Value,
+        path: String,
+        differences: &mut Vec<String>,
+    ) {
+        match (expected, actual) {
+            (Value::Number(expected), Value::Number(actual)) => {
+                let expected = expected.as_f64().unwrap_or_default();
+                let actual = actual.as_f64().unwrap_or_default();
+                if (expected - actual).abs() > TOLERANCE {
+                    differences.push(format!(
+                        "{path}: expected {expected}, got {actual} ({:+})",
+                        actual - expected
+                    ));
+                }
+            }
+            (Value::Array(expected), Value::Array(actual)) if expected.len() == actual.len() => {
+                for (index, (expected, actual)) in expected.iter().zip(actual).enumerate() {
+                    collect_differences(expected, actual, format!("{path}[{index}]"), differences);
+                }
+            }
+            (Value::Object(expected), Value::Object(actual))
+                if expected.keys().eq(actual.keys()) =>
+            {
+                for (key, expected) in expected {
+                    collect_differences(
+                        expected,
+                        &actual[key],
+                        format!("{path}.{key}"),
+                        differences,
+                    );
+                }
+            }
+            _ if expected != actual => {
+                differences.push(format!(
+                    "{path}:\n    expected {expected}\n    got      {actual}"
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    fn check_fixture(path: &Path, update: bool) -> Result<Vec<String>> {
+        let mut fixture: BehaviorFixture =
+            serde_json::from_str(&fs::read_to_string(path).wrap_err("failed to read fixture")?)
+                .wrap_err("failed to parse fixture")?;
+        let (motion_command, _) = replay(&fixture)?;
+
+        if update {
+            fixture.expected_motion_command = motion_command;
+            let mut content = serde_json::to_string_pretty(&fixture)?;
+            content.push('\n');
+            fs::write(path, content).wrap_err("failed to write fixture")?;
+            return Ok(Vec::new());
+        }
+        let mut differences = Vec::new();
+        collect_differences(
+            &serde_json::to_value(&fixture.expected_motion_command)?,
+            &serde_json::to_value(&motion_command)?,
+            "motion_command".to_string(),
+            &mut differences,
+        );
+        Ok(differences)
+    }
+
+    #[test]
+    fn behavior_matches_fixtures() -> Result<()> {
+        let update = env::var_os(UPDATE_VARIABLE).is_some();
+        let mut paths = Vec::new();
+        for entry in fs::read_dir(FIXTURE_DIRECTORY)
+            .wrap_err_with(|| format!("failed to read {FIXTURE_DIRECTORY}"))?
+        {
+            let path = entry?.path();
+            if path
+                .extension()
+                .is_some_and(|extension| extension == "json")
+            {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+        assert!(
+            !paths.is_empty(),
+            "no fixtures found in {FIXTURE_DIRECTORY}"
+        );
+
+        let mut failures = Vec::new();
+        for path in paths {
+            let differences = check_fixture(&path, update)
+                .wrap_err_with(|| format!("failed to check {}", path.display()))?;
+            if !differences.is_empty() {
+                failures.push(format!(
+                    "{}:\n  {}",
+                    path.display(),
+                    differences.join("\n  ")
+                ));
+            }
+        }
+        assert!(
+            failures.is_empty(),
+            "motion commands differ from fixtures, rerun with {UPDATE_VARIABLE}=1 if intended\n{}",
+            failures.join("\n")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn small_numeric_differences_are_tolerated() {
+        let expected = serde_json::json!({"Walk": {"path": [[0.0, 1.0]], "speed": "Normal"}});
+        let actual = serde_json::json!({"Walk": {"path": [[0.00001, 1.5]], "speed": "Fast"}});
+        let mut differences = Vec::new();
+
+        collect_differences(
+            &expected,
+            &actual,
+            "motion_command".to_string(),
+            &mut differences,
+        );
+
+        assert_eq!(differences.len(), 2);
+        assert!(differences[0].starts_with("motion_command.Walk.path[0][1]"));
+        assert!(differences[1].starts_with("motion_command.Walk.speed"));
+    }
+}
@@ -1,5 +1,5 @@
-use types::{motion_command::MotionCommand, world_state::WorldState};
+use types::{decision_trace::Precondition, motion_command::MotionCommand, world_state::WorldState};
 
-pub fn execute(_world_state: &WorldState) -> Option<MotionCommand> {
-    Some(MotionCommand::ArmsUpSquat)
+pub fn execute(_world_state: &WorldState) -> Result<MotionCommand, Precondition> {
+    Ok(MotionCommand::ArmsUpSquat)
 }
@@ -0,0 +1,44 @@
+use framework::AdditionalOutput;
+use geometry::look_at::LookAt;
+use linear_algebra::Pose2;
+use types::{
+    decision_trace::Precondition,
+    motion_command::{MotionCommand, WalkSpeed},
+    path_obstacles::PathObstacle,
+    world_state::WorldState,
+};
+
+use super::{head::LookAction, walk_to_pose::WalkAndStand};
+
+pub fn execute(
+    world_state: &WorldState,
+    walk_and_stand: &WalkAndStand,
+    look_action: &LookAction,
+    path_obstacles_output: &mut AdditionalOutput<Vec<PathObstacle>>,
+    walk_speed: WalkSpeed,
+    distance_to_be_aligned: f32,
+) -> Result<MotionCommand, Precondition> {
+    let receive_position = world_state
+        .incoming_pass
+        .ok_or(Precondition::IncomingPass)?;
+    let ground_to_field = world_state
+        .robot
+        .ground_to_field
+        .ok_or(Precondition::GroundToField)?;
+    let ball = world_state
+        .rule_ball
+        .or(world_state.ball)
+        .ok_or(Precondition::Ball)?;
+    let receive_pose = Pose2::new(
+        receive_position,
+        receive_position.look_at(&ball.ball_in_field).angle(),
+    );
+    walk_and_stand.execute(
+        ground_to_field.inverse() * receive_pose,
+        look_action.execute(),
+        path_obstacles_output,
+        walk_speed,
+        distance_to_be_aligned,
+        walk_and_stand.parameters.hysteresis,
+    )
+}
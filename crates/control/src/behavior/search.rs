@@ -2,6 +2,7 @@ use coordinate_systems::{Field, Ground};
 use framework::AdditionalOutput;
 use linear_algebra::{point, Isometry2, Orientation2, Point2, Pose2};
 use types::{
+    decision_trace::Precondition,
     field_dimensions::FieldDimensions,
     motion_command::{HeadMotion, MotionCommand, OrientationMode, WalkSpeed},
     parameters::SearchParameters,
@@ -75,8 +76,11 @@ pub fn execute(
     previous_role: Role,
     walk_speed: WalkSpeed,
     distance_to_be_aligned: f32,
-) -> Option<MotionCommand> {
-    let ground_to_field = world_state.robot.ground_to_field?;
+) -> Result<MotionCommand, Precondition> {
+    let ground_to_field = world_state
+        .robot
+        .ground_to_field
+        .ok_or(Precondition::GroundToField)?;
     let search_role = assign_search_role(world_state);
     let search_position = match (world_state.suggested_search_position, previous_role) {
         (Some(_), Role::Striker | Role::StrikerSupporter) => {
@@ -127,7 +131,7 @@ pub fn execute(
         } else {
             OrientationMode::AlignWithPath
         };
-        Some(walk_path_planner.walk_with_obstacle_avoiding_arms(
+        Ok(walk_path_planner.walk_with_obstacle_avoiding_arms(
             head,
             orientation_mode,
             path,
@@ -1,14 +1,21 @@
 use types::{
+    decision_trace::Precondition,
     motion_command::{HeadMotion, MotionCommand},
     primary_state::PrimaryState,
     world_state::WorldState,
 };
 
-pub fn execute(world_state: &WorldState) -> Option<MotionCommand> {
+pub fn execute(world_state: &WorldState) -> Result<MotionCommand, Precondition> {
     match world_state.robot.primary_state {
-        PrimaryState::Finished => Some(MotionCommand::SitDown {
+        PrimaryState::Finished => Ok(MotionCommand::SitDown {
             head: HeadMotion::Unstiff,
         }),
-        _ => None,
+        // long stoppages are used to let hot joints cool down
+        PrimaryState::Initial | PrimaryState::Penalized if world_state.robot.needs_cooling => {
+            Ok(MotionCommand::SitDown {
+                head: HeadMotion::Unstiff,
+            })
+        }
+        _ => Err(Precondition::Situation),
     }
 }
@@ -1,17 +1,21 @@
 use types::{
+    decision_trace::Precondition,
     fall_state::{FallState, FallenKind, StandUpSpeed},
     motion_command::MotionCommand,
     world_state::WorldState,
 };
 
-pub fn execute(world_state: &WorldState, maximum_standup_attempts: u32) -> Option<MotionCommand> {
+pub fn execute(
+    world_state: &WorldState,
+    maximum_standup_attempts: u32,
+) -> Result<MotionCommand, Precondition> {
     if world_state.robot.stand_up_count > maximum_standup_attempts {
-        return Some(MotionCommand::Unstiff);
+        return Ok(MotionCommand::Unstiff);
     }
     let kind = match world_state.robot.fall_state {
         FallState::Fallen { kind } => kind,
         FallState::StandingUp { kind, .. } => kind,
-        _ => return None,
+        _ => return Err(Precondition::Situation),
     };
     let speed = match (kind, world_state.robot.stand_up_count) {
         (FallenKind::Sitting, 0) => StandUpSpeed::Default,
@@ -20,5 +24,5 @@ pub fn execute(world_state: &WorldState, maximum_standup_attempts: u32) -> Optio
         (FallenKind::FacingDown, _) => StandUpSpeed::Slow,
         (FallenKind::FacingUp, _) => StandUpSpeed::Default,
     };
-    Some(MotionCommand::StandUp { kind, speed })
+    Ok(MotionCommand::StandUp { kind, speed })
 }
@@ -6,6 +6,7 @@ use geometry::look_at::LookAt;
 use linear_algebra::{point, Pose2, Rotation2, Vector2};
 use spl_network_messages::SubState;
 use types::{
+    decision_trace::Precondition,
     field_dimensions::{FieldDimensions, Side},
     filtered_game_controller_state::FilteredGameControllerState,
     filtered_game_state::FilteredGameState,
@@ -29,7 +30,7 @@ pub fn execute(
     path_obstacles_output: &mut AdditionalOutput<Vec<PathObstacle>>,
     walk_speed: WalkSpeed,
     distance_to_be_aligned: f32,
-) -> Option<MotionCommand> {
+) -> Result<MotionCommand, Precondition> {
     let pose = support_pose(
         world_state,
         field_dimensions,
@@ -55,8 +56,11 @@ fn support_pose(
     distance_to_ball: f32,
     maximum_x_in_ready_and_when_ball_is_not_free: f32,
     minimum_x: f32,
-) -> Option<Pose2<Ground>> {
-    let ground_to_field = world_state.robot.ground_to_field?;
+) -> Result<Pose2<Ground>, Precondition> {
+    let ground_to_field = world_state
+        .robot
+        .ground_to_field
+        .ok_or(Precondition::GroundToField)?;
     let ball = world_state
         .rule_ball
         .or(world_state.ball)
@@ -150,5 +154,5 @@ fn support_pose(
         clamped_position,
         clamped_position.look_at(&ball.ball_in_field).angle(),
     );
-    Some(ground_to_field.inverse() * support_pose)
+    Ok(ground_to_field.inverse() * support_pose)
 }
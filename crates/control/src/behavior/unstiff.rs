@@ -1,8 +1,11 @@
-use types::{motion_command::MotionCommand, primary_state::PrimaryState, world_state::WorldState};
+use types::{
+    decision_trace::Precondition, motion_command::MotionCommand, primary_state::PrimaryState,
+    world_state::WorldState,
+};
 
-pub fn execute(world_state: &WorldState) -> Option<MotionCommand> {
+pub fn execute(world_state: &WorldState) -> Result<MotionCommand, Precondition> {
     match world_state.robot.primary_state {
-        PrimaryState::Unstiff => Some(MotionCommand::Unstiff),
-        _ => None,
+        PrimaryState::Unstiff => Ok(MotionCommand::Unstiff),
+        _ => Err(Precondition::Situation),
     }
 }
@@ -2,6 +2,7 @@ use coordinate_systems::Field;
 use framework::AdditionalOutput;
 use linear_algebra::{Point2, Pose2, Rotation2};
 use types::{
+    decision_trace::Precondition,
     motion_command::{MotionCommand, WalkSpeed},
     path_obstacles::PathObstacle,
     world_state::WorldState,
@@ -19,8 +20,11 @@ pub fn execute(
     kick_off_angle: f32,
     walk_speed: WalkSpeed,
     distance_to_be_aligned: f32,
-) -> Option<MotionCommand> {
-    let ground_to_field = world_state.robot.ground_to_field?;
+) -> Result<MotionCommand, Precondition> {
+    let ground_to_field = world_state
+        .robot
+        .ground_to_field
+        .ok_or(Precondition::GroundToField)?;
     let kick_off_pose =
         Rotation2::<Field, Field>::new(-kick_off_angle) * Pose2::from(kickoff_position);
     walk_and_stand.execute(
@@ -1,6 +1,7 @@
 use framework::AdditionalOutput;
 use linear_algebra::{point, Pose2};
 use types::{
+    decision_trace::Precondition,
     field_dimensions::FieldDimensions,
     motion_command::{MotionCommand, WalkSpeed},
     path_obstacles::PathObstacle,
@@ -17,8 +18,11 @@ pub fn execute(
     field_dimensions: &FieldDimensions,
     walk_speed: WalkSpeed,
     distance_to_be_aligned: f32,
-) -> Option<MotionCommand> {
-    let ground_to_field = world_state.robot.ground_to_field?;
+) -> Result<MotionCommand, Precondition> {
+    let ground_to_field = world_state
+        .robot
+        .ground_to_field
+        .ok_or(Precondition::GroundToField)?;
     let kick_off_pose = Pose2::from(point![
         field_dimensions.length / 2.0
             - field_dimensions.penalty_marker_distance
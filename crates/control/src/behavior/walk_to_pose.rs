@@ -5,6 +5,7 @@ use filtering::hysteresis::less_than_with_relative_hysteresis;
 use framework::AdditionalOutput;
 use linear_algebra::{point, Isometry2, Orientation2, Point, Point2, Pose2};
 use types::{
+    decision_trace::Precondition,
     field_dimensions::FieldDimensions,
     motion_command::{ArmMotion, HeadMotion, MotionCommand, OrientationMode, WalkSpeed},
     obstacles::Obstacle,
@@ -55,7 +56,17 @@ impl<'cycle> WalkPathPlanner<'cycle> {
             self.last_motion_command,
             self.parameters.rotation_penalty_factor,
         );
-        planner.with_obstacles(obstacles, self.parameters.robot_radius_at_hip_height);
+        if self.parameters.predict_obstacle_motion {
+            planner.with_moving_obstacles(
+                obstacles,
+                self.parameters.robot_radius_at_hip_height,
+                self.parameters.line_walking_speed,
+                self.parameters.obstacle_prediction_horizon,
+                self.parameters.maximum_obstacle_inflation,
+            );
+        } else {
+            planner.with_obstacles(obstacles, self.parameters.robot_radius_at_hip_height);
+        }
         planner.with_rule_obstacles(
             ground_to_field.inverse(),
             rule_obstacles,
@@ -162,8 +173,12 @@ impl<'cycle> WalkAndStand<'cycle> {
         walk_speed: WalkSpeed,
         distance_to_be_aligned: f32,
         hysteresis: nalgebra::Vector2<f32>,
-    ) -> Option<MotionCommand> {
-        let ground_to_field = self.world_state.robot.ground_to_field?;
+    ) -> Result<MotionCommand, Precondition> {
+        let ground_to_field = self
+            .world_state
+            .robot
+            .ground_to_field
+            .ok_or(Precondition::GroundToField)?;
         let distance_to_walk = target_pose.position().coords().norm();
         let angle_to_walk = target_pose.orientation().angle();
         let was_standing_last_cycle =
@@ -186,7 +201,7 @@ impl<'cycle> WalkAndStand<'cycle> {
         );
 
         if is_reached {
-            Some(MotionCommand::Stand { head })
+            Ok(MotionCommand::Stand { head })
         } else {
             let path = self.walk_path_planner.plan(
                 target_pose.position(),
@@ -197,7 +212,7 @@ impl<'cycle> WalkAndStand<'cycle> {
                 &self.world_state.rule_obstacles,
                 path_obstacles_output,
             );
-            Some(self.walk_path_planner.walk_with_obstacle_avoiding_arms(
+            Ok(self.walk_path_planner.walk_with_obstacle_avoiding_arms(
                 head,
                 orientation_mode,
                 path,
@@ -1,4 +1,7 @@
-use std::{time::Duration, vec};
+use std::{
+    time::{Duration, SystemTime},
+    vec,
+};
 
 use color_eyre::Result;
 use serde::{Deserialize, Serialize};
@@ -6,18 +9,22 @@ use serde::{Deserialize, Serialize};
 use calibration::{
     corrections::Corrections,
     goal_box::{measurement::Measurement, residuals::GoalBoxResiduals},
+    problem::EstimatedParameters,
     solve,
 };
 use context_attribute::context;
 use coordinate_systems::Ground;
-use framework::{AdditionalOutput, MainOutput, PerceptionInput};
+use framework::{AdditionalOutput, HistoricInput, MainOutput, PerceptionInput};
 use linear_algebra::{point, Point2};
 use types::{
-    calibration::{CalibrationCaptureResponse, CalibrationCommand},
+    calibration::{CalibrationCaptureResponse, CalibrationCommand, CalibrationPose},
     camera_position::CameraPosition,
     cycle_time::CycleTime,
     field_dimensions::FieldDimensions,
+    joints::Joints,
     primary_state::PrimaryState,
+    sensor_data::SensorData,
+    support_foot::SupportFoot,
 };
 
 #[derive(Deserialize, Serialize)]
@@ -34,6 +41,8 @@ pub struct CreationContext {}
 pub struct CycleContext {
     primary_state: Input<PrimaryState, "primary_state">,
     cycle_time: Input<CycleTime, "cycle_time">,
+    historic_sensor_data: HistoricInput<SensorData, "sensor_data">,
+    historic_support_foot: HistoricInput<SupportFoot, "support_foot">,
     measurement_bottom: PerceptionInput<
         Option<CalibrationCaptureResponse<Measurement>>,
         "VisionBottom",
@@ -48,9 +57,14 @@ pub struct CycleContext {
     look_at_dispatch_delay: Parameter<Duration, "calibration_controller.look_at_dispatch_delay">,
     stabilization_delay: Parameter<Duration, "calibration_controller.stabilization_delay">,
     max_retries_per_capture: Parameter<u32, "calibration_controller.max_retries_per_capture">,
+    estimate_lens_distortion: Parameter<bool, "calibration_controller.estimate_lens_distortion">,
+    estimate_joint_offsets: Parameter<bool, "calibration_controller.estimate_joint_offsets">,
+    joint_calibration_offsets: Parameter<Joints<f32>, "joint_calibration_offsets">,
 
     calibration_measurements: AdditionalOutput<Vec<Measurement>, "calibration_inner.measurements">,
     last_calibration_corrections: AdditionalOutput<Corrections, "last_calibration_corrections">,
+    calibrated_joint_calibration_offsets:
+        AdditionalOutput<Joints<f32>, "calibrated_joint_calibration_offsets">,
 }
 
 #[context]
@@ -77,11 +91,14 @@ enum CalibrationState {
     LookAt {
         target: Point2<Ground>,
         camera: CameraPosition,
+        pose: CalibrationPose,
+        pose_changed: bool,
         dispatch_time: CycleTime,
     },
     Capture {
         target: Point2<Ground>,
         camera: CameraPosition,
+        pose: CalibrationPose,
         dispatch_time: CycleTime,
     },
     Finish,
@@ -93,20 +110,25 @@ impl CalibrationState {
             CalibrationState::LookAt {
                 target,
                 camera,
+                pose,
                 dispatch_time,
+                ..
             } => Some(CalibrationCommand {
                 target,
                 camera,
+                pose,
                 dispatch_time,
                 capture: false,
             }),
             CalibrationState::Capture {
                 target,
                 camera,
+                pose,
                 dispatch_time,
             } => Some(CalibrationCommand {
                 target,
                 camera,
+                pose,
                 dispatch_time,
                 capture: true,
             }),
@@ -148,7 +170,7 @@ impl CalibrationController {
 
                 if waiting_duration >= *context.stabilization_delay {
                     self.inner_states.calibration_state = self
-                        .get_next_look_at(*context.cycle_time)
+                        .get_next_look_at(&context)
                         .unwrap_or(CalibrationState::Finish);
                 }
             }
@@ -156,18 +178,26 @@ impl CalibrationController {
                 dispatch_time,
                 camera,
                 target,
+                pose,
+                pose_changed,
             } => {
                 let time_diff = context
                     .cycle_time
                     .start_time
                     .duration_since(dispatch_time.start_time)
                     .unwrap_or_default();
+                let delay = if pose_changed {
+                    *context.stabilization_delay
+                } else {
+                    *context.look_at_dispatch_delay
+                };
 
-                if time_diff > *context.look_at_dispatch_delay {
+                if time_diff > delay {
                     self.inner_states.last_capture_retries = 0;
                     self.inner_states.calibration_state = CalibrationState::Capture {
                         target,
                         camera,
+                        pose,
                         dispatch_time: *context.cycle_time,
                     };
                 }
@@ -196,6 +226,19 @@ impl CalibrationController {
                 }
             });
 
+        context
+            .calibrated_joint_calibration_offsets
+            .mutate_if_subscribed(|data| {
+                if let Some(corrections) = self.corrections {
+                    // measured positions already have the current offsets subtracted
+                    data.replace(
+                        *context.joint_calibration_offsets + corrections.joint_offsets.to_joints(),
+                    );
+                } else {
+                    data.take();
+                }
+            });
+
         Ok(MainOutputs {
             calibration_command: self
                 .inner_states
@@ -219,9 +262,20 @@ impl CalibrationController {
             &dispatch_time,
         );
 
-        let goto_next_lookat = calibration_response.is_some_and(|response| {
+        let goto_next_lookat = calibration_response.is_some_and(|(image_timestamp, response)| {
             if let Some(measurement) = response.measurement {
-                self.inner_states.measurements.push(measurement);
+                // joint positions at the time the image was taken, not when it was processed
+                let joint_positions = context.historic_sensor_data.get(&image_timestamp).positions;
+                let support_side = context
+                    .historic_support_foot
+                    .get(&image_timestamp)
+                    .support_side
+                    .unwrap_or_default();
+                self.inner_states.measurements.push(Measurement {
+                    joint_positions,
+                    support_side,
+                    ..measurement
+                });
                 true
             } else {
                 self.inner_states.last_capture_retries += 1;
@@ -230,7 +284,7 @@ impl CalibrationController {
         });
         if goto_next_lookat {
             self.inner_states.calibration_state = self
-                .get_next_look_at(*context.cycle_time)
+                .get_next_look_at(context)
                 .unwrap_or_else(|| self.calibrate(context));
         }
     }
@@ -241,23 +295,36 @@ impl CalibrationController {
             Corrections::default(),
             self.inner_states.measurements.clone(),
             *context.field_dimensions,
+            EstimatedParameters {
+                lens_distortion: *context.estimate_lens_distortion,
+                joint_offsets: *context.estimate_joint_offsets,
+            },
         );
 
         self.corrections = Some(solved_result);
         CalibrationState::Finish
     }
 
-    fn get_next_look_at(&mut self, dispatch_time: CycleTime) -> Option<CalibrationState> {
+    /// Iterates the look-at list once per capture pose
+    fn get_next_look_at(&mut self, context: &CycleContext) -> Option<CalibrationState> {
+        let poses = capture_poses(*context.estimate_joint_offsets);
         let index = self.inner_states.look_at_index;
         self.inner_states.look_at_index += 1;
-        self.look_at_list
-            .get(index)
-            .copied()
-            .map(|(target, camera)| CalibrationState::LookAt {
-                camera,
-                target,
-                dispatch_time,
-            })
+
+        let pose = *poses.get(index / self.look_at_list.len())?;
+        let (target, camera) = self.look_at_list[index % self.look_at_list.len()];
+        let previous_pose = index
+            .checked_sub(1)
+            .map_or(CalibrationPose::default(), |previous_index| {
+                poses[previous_index / self.look_at_list.len()]
+            });
+        Some(CalibrationState::LookAt {
+            camera,
+            target,
+            pose,
+            pose_changed: pose != previous_pose,
+            dispatch_time: *context.cycle_time,
+        })
     }
 }
 
@@ -266,17 +333,30 @@ fn collect_filtered_values(
         Vec<Option<&CalibrationCaptureResponse<Measurement>>>,
     >,
     original_dispatch_time: &CycleTime,
-) -> Option<CalibrationCaptureResponse<Measurement>> {
+) -> Option<(SystemTime, CalibrationCaptureResponse<Measurement>)> {
     measurement_perception_input
         .persistent
         .iter()
-        .flat_map(|(_cycle_timestamp, measurements)| measurements.iter())
-        .flatten()
-        .find(|&measurement| {
+        .flat_map(|(cycle_timestamp, measurements)| {
+            measurements
+                .iter()
+                .flatten()
+                .map(move |measurement| (*cycle_timestamp, *measurement))
+        })
+        .find(|(_cycle_timestamp, measurement)| {
             original_dispatch_time.start_time == measurement.dispatch_time.start_time
         })
-        .cloned()
-        .cloned()
+        .map(|(cycle_timestamp, measurement)| (cycle_timestamp, measurement.clone()))
+}
+
+/// Joint offsets are only observable from captures in poses with different torso tilts and knee
+/// bends, the calibration starts in the stand pose
+fn capture_poses(estimate_joint_offsets: bool) -> &'static [CalibrationPose] {
+    if estimate_joint_offsets {
+        &[CalibrationPose::Stand, CalibrationPose::Initial]
+    } else {
+        &[CalibrationPose::Stand]
+    }
 }
 
 // TODO Add fancier logic to either set this via parameters OR detect the location, walk, etc
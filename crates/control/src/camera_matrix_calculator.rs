@@ -78,7 +78,8 @@ impl CameraMatrixCalculator {
             context.robot_to_ground.inverse(),
             context.robot_kinematics.head.head_to_robot.inverse(),
             head_to_top_camera,
-        );
+        )
+        .with_lens_distortion(context.top_camera_matrix_parameters.lens_distortion);
 
         let head_to_bottom_camera = head_to_camera(
             context
@@ -94,7 +95,8 @@ impl CameraMatrixCalculator {
             context.robot_to_ground.inverse(),
             context.robot_kinematics.head.head_to_robot.inverse(),
             head_to_bottom_camera,
-        );
+        )
+        .with_lens_distortion(context.bottom_camera_matrix_parameters.lens_distortion);
 
         let correction_in_robot = Rotation3::from_euler_angles(
             context.correction_in_robot.x,
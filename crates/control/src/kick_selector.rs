@@ -1,4 +1,4 @@
-use std::cmp::Ordering;
+use std::{cmp::Ordering, time::Duration};
 
 use color_eyre::Result;
 use itertools::iproduct;
@@ -12,15 +12,16 @@ use linear_algebra::{
     distance, point, vector, IntoFramed, Isometry2, Orientation2, Point, Point2, Pose2, Rotation2,
     Vector2,
 };
-use spl_network_messages::{GamePhase, SubState, Team};
+use spl_network_messages::{GamePhase, PlayerNumber, SubState, Team};
 use types::{
     field_dimensions::{self, FieldDimensions, Half},
     filtered_game_controller_state::FilteredGameControllerState,
     filtered_game_state::FilteredGameState,
-    kick_decision::{DecisionParameters, KickDecision, PlayingSituation},
+    kick_decision::{DecisionParameters, KickDecision, PassingParameters, PlayingSituation},
     motion_command::KickVariant,
     obstacles::Obstacle,
     parameters::{InWalkKickInfoParameters, InWalkKicksParameters},
+    players::Players,
     support_foot::Side,
     world_state::BallState,
 };
@@ -38,12 +39,15 @@ pub struct CycleContext {
     obstacles: Input<Vec<Obstacle>, "obstacles">,
     filtered_game_controller_state:
         Input<Option<FilteredGameControllerState>, "filtered_game_controller_state?">,
+    teammate_poses: Input<Players<Option<Pose2<Field>>>, "teammate_poses">,
+    teammate_pose_ages: Input<Players<Option<Duration>>, "teammate_pose_ages">,
     ground_to_upcoming_support:
         CyclerState<Isometry2<Ground, UpcomingSupport>, "ground_to_upcoming_support">,
 
     decision_parameters: Parameter<DecisionParameters, "kick_selector">,
     field_dimensions: Parameter<FieldDimensions, "field_dimensions">,
     in_walk_kicks: Parameter<InWalkKicksParameters, "in_walk_kicks">,
+    player_number: Parameter<PlayerNumber, "player_number">,
 
     playing_situation: AdditionalOutput<PlayingSituation, "playing_situation">,
 }
@@ -107,8 +111,64 @@ impl KickSelector {
             strength,
             ball_position,
             context.in_walk_kicks,
+            None,
         );
 
+        let passing = &context.decision_parameters.passing;
+        let is_shot_blocked = targets.iter().all(|&target| {
+            is_intersecting_with_an_obstacle(
+                context.obstacles,
+                ball_position,
+                target,
+                context.decision_parameters,
+            )
+        });
+        if passing.enabled
+            && matches!(playing_situation, PlayingSituation::Normal)
+            && is_shot_blocked
+        {
+            let keeper = context
+                .filtered_game_controller_state
+                .and_then(|game_controller_state| {
+                    game_controller_state
+                        .penalties
+                        .keeper(*context.player_number)
+                });
+            let mut teammates = Players::new(None);
+            for (player_number, pose) in context.teammate_poses.iter() {
+                teammates[player_number] = (*pose).zip(context.teammate_pose_ages[player_number]);
+            }
+            let obstacles: Vec<_> = context
+                .obstacles
+                .iter()
+                .map(|obstacle| {
+                    Circle::new(
+                        ground_to_field * obstacle.position,
+                        obstacle.radius_at_foot_height,
+                    )
+                })
+                .collect();
+            if let Some((receiver, receive_position)) = select_pass(
+                ground_to_field * ball_position,
+                *context.player_number,
+                keeper,
+                &teammates,
+                &obstacles,
+                context.field_dimensions,
+                passing,
+            ) {
+                kick_decisions.extend(kick_decisions_from_targets(
+                    &[ground_to_field.inverse() * receive_position],
+                    &variants,
+                    &sides,
+                    passing.kick_strength,
+                    ball_position,
+                    context.in_walk_kicks,
+                    Some(receiver),
+                ));
+            }
+        }
+
         kick_decisions.sort_by(|left, right| {
             compare_decisions(
                 left,
@@ -279,10 +339,11 @@ fn compare_decisions(
 ) -> Ordering {
     let left_in_obstacle = is_inside_any_obstacle(left.kick_pose, obstacles, parameters);
     let right_in_obstacle = is_inside_any_obstacle(right.kick_pose, obstacles, parameters);
-    let left_is_intersecting_with_obstacle =
-        is_intersecting_with_an_obstacle(obstacles, ball_position, left.target, parameters);
-    let right_is_intersecting_with_obstacle =
-        is_intersecting_with_an_obstacle(obstacles, ball_position, right.target, parameters);
+    // passes are only selected if they are unlikely to be intercepted
+    let left_is_intersecting_with_obstacle = left.pass_receiver.is_none()
+        && is_intersecting_with_an_obstacle(obstacles, ball_position, left.target, parameters);
+    let right_is_intersecting_with_obstacle = right.pass_receiver.is_none()
+        && is_intersecting_with_an_obstacle(obstacles, ball_position, right.target, parameters);
     let distance_to_left = distance_to_kick_pose(
         ground_to_upcoming_support * left.kick_pose,
         parameters.angle_distance_weight,
@@ -413,6 +474,7 @@ fn generate_decisions_for_instant_kicks(
                     kicking_side,
                     kick_pose,
                     strength: parameters.kick_off_kick_strength,
+                    pass_receiver: None,
                 })
             } else if !is_own_kick_off && (is_inside_field && is_strategic_target || scores_goal) {
                 let kick_pose = compute_kick_pose(ball_position, target, kick_info, kicking_side);
@@ -422,6 +484,7 @@ fn generate_decisions_for_instant_kicks(
                     kicking_side,
                     kick_pose,
                     strength: parameters.default_kick_strength,
+                    pass_receiver: None,
                 })
             } else {
                 None
@@ -457,6 +520,7 @@ fn kick_decisions_from_targets(
     strength: f32,
     ball_position: Point2<Ground>,
     in_walk_kicks: &InWalkKicksParameters,
+    pass_receiver: Option<PlayerNumber>,
 ) -> Vec<KickDecision> {
     targets_to_kick_to
         .iter()
@@ -470,12 +534,117 @@ fn kick_decisions_from_targets(
                     kicking_side,
                     kick_pose,
                     strength,
+                    pass_receiver,
                 }
             })
         })
         .collect()
 }
 
+/// Selects the teammate with the lowest combined interception risk, waiting time and pose age
+///
+/// The striker does not pass to itself and the keeper, which is chosen like in the role
+/// assignment.
+fn select_pass(
+    ball_position: Point2<Field>,
+    own_player_number: PlayerNumber,
+    keeper_player_number: Option<PlayerNumber>,
+    teammates: &Players<Option<(Pose2<Field>, Duration)>>,
+    obstacles: &[Circle<Field>],
+    field_dimensions: &FieldDimensions,
+    parameters: &PassingParameters,
+) -> Option<(PlayerNumber, Point2<Field>)> {
+    teammates
+        .iter()
+        .filter(|(player_number, _)| {
+            *player_number != own_player_number && Some(*player_number) != keeper_player_number
+        })
+        .filter_map(|(player_number, teammate)| {
+            let (receiver_pose, pose_age) = (*teammate)?;
+            if pose_age > parameters.maximum_receiver_pose_age {
+                return None;
+            }
+            let receiver_position = receiver_pose.position();
+            let receive_position = receiver_position + vector![parameters.lead_distance, 0.0];
+            let pass_distance = distance(ball_position, receive_position);
+            if !field_dimensions.is_inside_field(receive_position)
+                || pass_distance < parameters.minimum_pass_distance
+                || pass_distance > parameters.maximum_pass_distance
+                || receive_position.x() < ball_position.x() + parameters.minimum_progress
+            {
+                return None;
+            }
+
+            let risk = interception_risk(
+                ball_position,
+                receive_position,
+                receiver_position,
+                obstacles,
+                parameters,
+            );
+            if risk > parameters.maximum_interception_risk {
+                return None;
+            }
+            let ball_time = pass_distance / parameters.ball_speed;
+            let receiver_time =
+                receiver_time(receiver_pose, receive_position, ball_position, parameters);
+            let waiting_time = (receiver_time - ball_time).max(0.0);
+            let score = risk
+                + parameters.receiver_time_weight * waiting_time
+                + parameters.receiver_pose_age_weight * pose_age.as_secs_f32();
+            Some((score, player_number, receive_position))
+        })
+        .min_by(|(left, ..), (right, ..)| left.total_cmp(right))
+        .map(|(_, player_number, receive_position)| (player_number, receive_position))
+}
+
+/// Time the receiver needs to walk to the receive position and turn towards the ball
+fn receiver_time(
+    receiver_pose: Pose2<Field>,
+    receive_position: Point2<Field>,
+    ball_position: Point2<Field>,
+    parameters: &PassingParameters,
+) -> f32 {
+    let walking_time =
+        distance(receiver_pose.position(), receive_position) / parameters.receiver_walking_speed;
+    let turning_angle = receiver_pose
+        .orientation()
+        .rotation_to(Orientation2::from_vector(ball_position - receive_position))
+        .angle()
+        .abs();
+    walking_time + turning_angle / parameters.receiver_turning_speed
+}
+
+/// Ratio between the time the ball needs and the time the closest opponent needs to reach the
+/// pass line, i.e. 1.0 if an obstacle is able to reach the ball before it passes by
+fn interception_risk(
+    ball_position: Point2<Field>,
+    receive_position: Point2<Field>,
+    receiver_position: Point2<Field>,
+    obstacles: &[Circle<Field>],
+    parameters: &PassingParameters,
+) -> f32 {
+    let pass = LineSegment::new(ball_position, receive_position);
+    obstacles
+        .iter()
+        .filter(|obstacle| {
+            distance(obstacle.center, receiver_position) > parameters.receiver_obstacle_radius
+        })
+        .map(|obstacle| {
+            let interception_point = pass.closest_point(obstacle.center);
+            let ball_time = distance(ball_position, interception_point) / parameters.ball_speed;
+            let obstacle_time = (distance(obstacle.center, interception_point) - obstacle.radius)
+                .max(0.0)
+                / parameters.opponent_walking_speed;
+            if obstacle_time <= ball_time {
+                1.0
+            } else {
+                ball_time / obstacle_time
+            }
+        })
+        .fold(0.0, f32::max)
+}
+
 fn distance_to_kick_pose(kick_pose: Pose2<UpcomingSupport>, angle_distance_weight: f32) -> f32 {
     kick_pose.position().coords().norm()
         + angle_distance_weight * kick_pose.orientation().angle().abs()
@@ -535,3 +704,134 @@ pub fn is_inside_kick_off_target_region(position: Point2<Field>, field_width: f3
         && position.x().abs() < field_width / 2.0
         && position.x().abs() <= position.y().abs()
 }
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    fn parameters() -> PassingParameters {
+        PassingParameters {
+            enabled: true,
+            kick_strength: 0.6,
+            ball_speed: 1.0,
+            receiver_walking_speed: 0.25,
+            receiver_turning_speed: 0.5,
+            opponent_walking_speed: 0.3,
+            lead_distance: 0.5,
+            minimum_pass_distance: 1.0,
+            maximum_pass_distance: 4.0,
+            minimum_progress: 0.5,
+            receiver_obstacle_radius: 0.5,
+            maximum_interception_risk: 0.7,
+            receiver_time_weight: 0.2,
+            maximum_receiver_pose_age: Duration::from_secs(16),
+            receiver_pose_age_weight: 0.05,
+        }
+    }
+
+    fn facing(position: Point2<Field>, target: Point2<Field>) -> Pose2<Field> {
+        Pose2::from_parts(position, Orientation2::from_vector(target - position))
+    }
+
+    #[test]
+    fn obstacle_reaching_the_pass_line_first_intercepts() {
+        let ball = point![0.0, 0.0];
+        let receive_position = point![3.0, 0.0];
+        let receiver_position = point![2.5, 0.0];
+
+        let close_to_line = [Circle::new(point![1.5, 0.2], 0.1)];
+        let far_from_line = [Circle::new(point![1.5, 3.0], 0.1)];
+        let at_receiver = [Circle::new(point![2.6, 0.1], 0.1)];
+
+        assert_relative_eq!(
+            interception_risk(
+                ball,
+                receive_position,
+                receiver_position,
+                &close_to_line,
+                &parameters()
+            ),
+            1.0
+        );
+        let risk = interception_risk(
+            ball,
+            receive_position,
+            receiver_position,
+            &far_from_line,
+            &parameters(),
+        );
+        assert!(risk > 0.0 && risk < 1.0);
+        assert_relative_eq!(
+            interception_risk(
+                ball,
+                receive_position,
+                receiver_position,
+                &at_receiver,
+                &parameters()
+            ),
+            0.0
+        );
+    }
+
+    #[test]
+    fn receivers_outside_the_field_or_behind_the_ball_are_rejected() {
+        let ball = point![0.0, 0.0];
+        let teammates = Players {
+            two: Some((facing(point![4.3, 0.0], ball), Duration::ZERO)),
+            three: Some((facing(point![-2.0, 0.0], ball), Duration::ZERO)),
+            four: Some((facing(point![-0.5, 2.0], ball), Duration::ZERO)),
+            ..Players::new(None)
+        };
+
+        assert_eq!(
+            select_pass(
+                ball,
+                PlayerNumber::Five,
+                None,
+                &teammates,
+                &[],
+                &FieldDimensions::SPL_2025,
+                &parameters(),
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn receiver_with_least_waiting_time_is_chosen() {
+        let ball = point![0.0, 0.0];
+        let ready = facing(point![2.5, 1.0], ball);
+        let turned_away = Pose2::from_parts(point![0.5, -2.0], Orientation2::new(0.0));
+        let teammates = Players {
+            two: Some((ready, Duration::ZERO)),
+            three: Some((turned_away, Duration::ZERO)),
+            ..Players::new(None)
+        };
+        let select = |keeper, teammates: &Players<Option<(Pose2<Field>, Duration)>>| {
+            select_pass(
+                ball,
+                PlayerNumber::Five,
+                keeper,
+                teammates,
+                &[],
+                &FieldDimensions::SPL_2025,
+                &parameters(),
+            )
+            .map(|(receiver, _)| receiver)
+        };
+
+        assert_eq!(select(None, &teammates), Some(PlayerNumber::Two));
+        assert_eq!(
+            select(Some(PlayerNumber::Two), &teammates),
+            Some(PlayerNumber::Three)
+        );
+
+        let stale_teammates = Players {
+            two: Some((ready, Duration::from_secs(20))),
+            ..teammates
+        };
+        assert_eq!(select(None, &stale_teammates), Some(PlayerNumber::Three));
+    }
+}
@@ -30,6 +30,7 @@ pub mod penalty_shot_direction_estimation;
 pub mod primary_state_filter;
 pub mod ready_signal_detection_filter;
 pub mod referee_position_provider;
+pub mod role_allocation;
 pub mod role_assignment;
 pub mod rule_obstacle_composer;
 pub mod sacrificial_lamb;
@@ -39,6 +40,8 @@ pub mod sole_pressure_filter;
 pub mod sonar_filter;
 pub mod support_foot_estimation;
 pub mod team_ball_receiver;
+pub mod teammate_pose_receiver;
+pub mod thermal_model;
 pub mod time_to_reach_kick_position;
 pub mod whistle_filter;
 pub mod world_state_composer;
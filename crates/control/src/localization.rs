@@ -26,6 +26,7 @@ use types::{
     line_data::LineData,
     localization::{ScoredPose, Update},
     multivariate_normal_distribution::MultivariateNormalDistribution,
+    odometry::OdometryUncertainty,
     players::Players,
     primary_state::PrimaryState,
     stand_up::RemainingStandUpDuration,
@@ -60,6 +61,7 @@ pub struct CycleContext {
 
     current_odometry_to_last_odometry:
         HistoricInput<Option<nalgebra::Isometry2<f32>>, "current_odometry_to_last_odometry?">,
+    odometry_uncertainty: HistoricInput<Option<OdometryUncertainty>, "odometry_uncertainty?">,
 
     filtered_game_controller_state:
         Input<Option<FilteredGameControllerState>, "filtered_game_controller_state?">,
@@ -88,6 +90,7 @@ pub struct CycleContext {
         Parameter<usize, "localization.maximum_amount_of_outer_iterations">,
     minimum_fit_error: Parameter<f32, "localization.minimum_fit_error">,
     odometry_noise: Parameter<Vector3<f32>, "localization.odometry_noise">,
+    use_odometry_covariance: Parameter<bool, "localization.use_odometry_covariance">,
     player_number: Parameter<PlayerNumber, "player_number">,
     penalized_distance: Parameter<f32, "localization.penalized_distance">,
     penalized_hypothesis_covariance:
@@ -334,6 +337,11 @@ impl Localization {
             let current_odometry_to_last_odometry = context
                 .current_odometry_to_last_odometry
                 .get(line_data_top_timestamp);
+            let odometry_covariance = context
+                .odometry_uncertainty
+                .get(line_data_top_timestamp)
+                .filter(|_| *context.use_odometry_covariance)
+                .map(|uncertainty| uncertainty.covariance);
 
             let mut fit_errors_per_hypothesis = vec![];
             for (hypothesis_index, scored_state) in self.hypotheses.iter_mut().enumerate() {
@@ -342,6 +350,7 @@ impl Localization {
                         &mut scored_state.state,
                         current_odometry_to_last_odometry,
                         context.odometry_noise,
+                        odometry_covariance,
                     )
                     .wrap_err("failed to predict pose filter")?;
                     scored_state.score *= *context.hypothesis_prediction_score_reduction_factor;
@@ -714,15 +723,24 @@ fn predict(
     state: &mut MultivariateNormalDistribution<3>,
     current_odometry_to_last_odometry: &nalgebra::Isometry2<f32>,
     odometry_noise: &Vector3<f32>,
+    odometry_covariance: Option<Matrix3<f32>>,
 ) -> Result<()> {
     let current_orientation_angle = state.mean.z;
     // rotate odometry noise from robot frame to field frame
-    let rotated_noise = Rotation2::new(current_orientation_angle) * odometry_noise.xy();
-    let process_noise = Matrix::from_diagonal(&nalgebra::vector![
+    let rotation = Rotation2::new(current_orientation_angle);
+    let rotated_noise = rotation * odometry_noise.xy();
+    let mut process_noise = Matrix::from_diagonal(&nalgebra::vector![
         rotated_noise.x.abs(),
         rotated_noise.y.abs(),
         odometry_noise.z
     ]);
+    if let Some(odometry_covariance) = odometry_covariance {
+        let mut rotation_to_field = Matrix3::identity();
+        rotation_to_field
+            .fixed_view_mut::<2, 2>(0, 0)
+            .copy_from(rotation.matrix());
+        process_noise += rotation_to_field * odometry_covariance * rotation_to_field.transpose();
+    }
 
     state.predict(
         |state| {
@@ -14,8 +14,10 @@ use types::{
     },
     motion_selection::{MotionSelection, MotionType},
     motor_commands::MotorCommands,
+    parameters::ThermalModelParameters,
     primary_state::PrimaryState,
     sensor_data::SensorData,
+    thermal_state::ThermalState,
 };
 
 #[derive(Deserialize, Serialize)]
@@ -50,10 +52,12 @@ pub struct CycleContext {
     center_jump_positions: Input<Joints<f32>, "center_jump_positions">,
     walk_motor_commands: Input<MotorCommands<BodyJoints<f32>>, "walk_motor_commands">,
     cycle_time: Input<CycleTime, "cycle_time">,
+    thermal_state: Input<ThermalState, "thermal_state">,
     penalized_pose: Parameter<Joints<f32>, "penalized_pose">,
     initial_pose: Parameter<Joints<f32>, "initial_pose">,
     current_minimizer_parameters:
         Parameter<CurrentMinimizerParameters, "current_minimizer_parameters">,
+    thermal_model: Parameter<ThermalModelParameters, "thermal_model">,
     default_motion_stiffness_upper_body: Parameter<f32, "default_motion_stiffness_upper_body">,
     only_one_foot_has_ground_contact: Input<bool, "only_one_foot_has_ground_contact">,
     has_ground_contact: Input<bool, "has_ground_contact">,
@@ -97,6 +101,19 @@ impl MotorCommandCollector {
         let keeper_jump_left = context.keeper_jump_left_motor_commands;
         let keeper_jump_right = context.keeper_jump_right_motor_commands;
         let walk = context.walk_motor_commands;
+        let current_minimizer_parameters = if context.thermal_state.needs_cooling {
+            CurrentMinimizerParameters {
+                allowed_current: context.current_minimizer_parameters.allowed_current
+                    * context.thermal_model.hot_allowed_current_factor,
+                allowed_current_upper_threshold: context
+                    .current_minimizer_parameters
+                    .allowed_current_upper_threshold
+                    * context.thermal_model.hot_allowed_current_factor,
+                ..*context.current_minimizer_parameters
+            }
+        } else {
+            *context.current_minimizer_parameters
+        };
 
         let (positions, stiffnesses) = match motion_selection.current_motion {
             MotionType::Animation => (animation.positions, animation.stiffnesses),
@@ -120,7 +137,7 @@ impl MotorCommandCollector {
                     ),
                     context.sensor_data.positions,
                     *context.cycle_time,
-                    *context.current_minimizer_parameters,
+                    current_minimizer_parameters,
                     *context.has_ground_contact,
                 ),
                 Joints::from_head_and_body(
@@ -141,7 +158,7 @@ impl MotorCommandCollector {
                     *context.penalized_pose,
                     context.sensor_data.positions,
                     *context.cycle_time,
-                    *context.current_minimizer_parameters,
+                    current_minimizer_parameters,
                     *context.has_ground_contact,
                 ),
                 Joints::from_head_and_body(
@@ -159,7 +176,7 @@ impl MotorCommandCollector {
                     Joints::from_head_and_body(head_joints_command.positions, walk.positions),
                     context.sensor_data.positions,
                     *context.cycle_time,
-                    *context.current_minimizer_parameters,
+                    current_minimizer_parameters,
                     *context.has_ground_contact,
                 ),
                 Joints::from_head_and_body(head_joints_command.stiffnesses, walk.stiffnesses),
@@ -16,17 +16,24 @@ use types::{
     motion_selection::{MotionSafeExits, MotionType},
     motor_commands::MotorCommands,
     obstacle_avoiding_arms::{ArmCommand, ArmCommands},
+    parameters::ThermalModelParameters,
     sensor_data::SensorData,
     step::Step,
     support_foot::Side,
+    thermal_state::ThermalState,
     walk_command::WalkCommand,
 };
-use walking_engine::{kick_steps::KickSteps, mode::Mode, parameters::Parameters, Context, Engine};
+use walking_engine::{
+    kick_steps::KickSteps, mode::Mode, parameters::Parameters, preview_control::PreviewController,
+    Context, Engine,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WalkingEngine {
     engine: Engine,
     filtered_gyro: LowPassFilter<nalgebra::Vector3<f32>>,
+    #[serde(skip)]
+    preview_controller: Option<PreviewController>,
 }
 
 #[context]
@@ -40,6 +47,7 @@ pub struct CycleContext {
     parameters: Parameter<Parameters, "walking_engine">,
     max_step_size: Parameter<Step, "step_planner.max_step_size">,
     kick_steps: Parameter<KickSteps, "kick_steps">,
+    thermal_model: Parameter<ThermalModelParameters, "thermal_model">,
 
     motion_safe_exits: CyclerState<MotionSafeExits, "motion_safe_exits">,
     ground_to_upcoming_support:
@@ -50,6 +58,7 @@ pub struct CycleContext {
     cycle_time: Input<CycleTime, "cycle_time">,
     center_of_mass: Input<Point3<Robot>, "center_of_mass">,
     sensor_data: Input<SensorData, "sensor_data">,
+    thermal_state: Input<ThermalState, "thermal_state">,
     walk_command: Input<WalkCommand, "walk_command">,
     robot_to_ground: Input<Option<Isometry3<Robot, Ground>>, "robot_to_ground?">,
     obstacle_avoiding_arms: Input<ArmCommands, "obstacle_avoiding_arms">,
@@ -75,6 +84,7 @@ impl WalkingEngine {
                 nalgebra::Vector3::zeros(),
                 context.parameters.gyro_balancing.low_pass_factor,
             ),
+            preview_controller: None,
         })
     }
 
@@ -130,8 +140,19 @@ impl WalkingEngine {
         let orientation =
             Orientation3::from_euler_angles(imu.roll_pitch.x(), imu.roll_pitch.y(), 0.0);
 
+        let parameters = reduce_stiffness_of_hot_legs(
+            cycle_context.parameters,
+            cycle_context.thermal_state,
+            cycle_context.thermal_model,
+        );
+
+        let preview_controller = self
+            .preview_controller
+            .get_or_insert_with(|| PreviewController::new(&parameters.preview_control));
+        preview_controller.update(&parameters.preview_control);
+
         let context = Context {
-            parameters: cycle_context.parameters,
+            parameters: &parameters,
             max_step_size: cycle_context.max_step_size,
             kick_steps: cycle_context.kick_steps,
             cycle_time: cycle_context.cycle_time,
@@ -147,6 +168,7 @@ impl WalkingEngine {
             zero_moment_point: cycle_context.zero_moment_point,
             consecutive_cycles_zero_moment_point_outside_support_polygon: cycle_context
                 .consecutive_cycles_zero_moment_point_outside_support_polygon,
+            preview_controller,
         };
 
         match *cycle_context.walk_command {
@@ -232,6 +254,23 @@ fn ground_to_upcoming_support(
     Isometry2::from_parts(translation, yaw).inverse()
 }
 
+fn reduce_stiffness_of_hot_legs(
+    parameters: &Parameters,
+    thermal_state: &ThermalState,
+    thermal_model: &ThermalModelParameters,
+) -> Parameters {
+    if !thermal_model.enabled {
+        return parameters.clone();
+    }
+    let stiffness_factor = thermal_state
+        .leg_headroom(thermal_model.stiffness_reduction_margin)
+        .max(thermal_model.minimum_stiffness_factor);
+    let mut parameters = parameters.clone();
+    parameters.stiffnesses.leg_stiffness_walk *= stiffness_factor;
+    parameters.stiffnesses.leg_stiffness_stand *= stiffness_factor;
+    parameters
+}
+
 fn compensate_arm_motion_with_torso_tilt(
     arm_command: &ArmCommand,
     torso_tilt_compensation_factor: f32,
@@ -7,7 +7,7 @@ use filtering::kalman_filter::KalmanFilter;
 use framework::{AdditionalOutput, HistoricInput, MainOutput, PerceptionInput};
 use itertools::{chain, iproduct};
 use linear_algebra::{distance, point, IntoFramed, Isometry2, Point2};
-use nalgebra::Matrix2;
+use nalgebra::{Matrix2, Vector2};
 use serde::{Deserialize, Serialize};
 use types::{
     cycle_time::CycleTime,
@@ -120,6 +120,7 @@ impl ObstacleFilter {
                             .network_robot_measurement_noise,
                     ),
                     MeasurementKind::NetworkRobot,
+                    context.obstacle_filter_parameters,
                 );
             }
 
@@ -144,6 +145,7 @@ impl ObstacleFilter {
                             &context.obstacle_filter_parameters.feet_measurement_noise,
                         ),
                         MeasurementKind::Own,
+                        context.obstacle_filter_parameters,
                     );
                 }
             }
@@ -170,6 +172,7 @@ impl ObstacleFilter {
                             &context.obstacle_filter_parameters.sonar_measurement_noise,
                         ),
                         MeasurementKind::Own,
+                        context.obstacle_filter_parameters,
                     );
                 }
             }
@@ -191,6 +194,7 @@ impl ObstacleFilter {
                             &context.obstacle_filter_parameters.feet_measurement_noise,
                         ),
                         MeasurementKind::Own,
+                        context.obstacle_filter_parameters,
                     );
                 }
             }
@@ -241,6 +245,7 @@ impl ObstacleFilter {
                     kind: hypothesis.obstacle_kind,
                     radius_at_hip_height,
                     radius_at_foot_height,
+                    velocity: hypothesis.velocity.framed(),
                 }
             })
             .collect::<Vec<_>>();
@@ -277,10 +282,12 @@ impl ObstacleFilter {
                 control_input_model,
                 odometry_translation,
                 process_noise,
-            )
+            );
+            hypothesis.velocity = state_prediction * hypothesis.velocity;
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn update_hypotheses_with_measurement(
         &mut self,
         detected_position: Point2<Ground>,
@@ -289,6 +296,7 @@ impl ObstacleFilter {
         matching_distance: f32,
         measurement_noise: Matrix2<f32>,
         kind: MeasurementKind,
+        parameters: &ObstacleFilterParameters,
     ) {
         let mut matching_hypotheses = self
             .hypotheses
@@ -307,6 +315,7 @@ impl ObstacleFilter {
             return;
         }
         matching_hypotheses.for_each(|hypothesis| {
+            let previous_position = hypothesis.state.mean;
             hypothesis.state.update(
                 Matrix2::identity(),
                 detected_position.inner.coords,
@@ -321,6 +330,17 @@ impl ObstacleFilter {
                 ObstacleKind::Unknown => detected_obstacle_kind,
                 _ => panic!("Unexpected obstacle kind"),
             };
+            let elapsed = detection_time
+                .duration_since(hypothesis.last_update)
+                .unwrap_or_default()
+                .as_secs_f32();
+            if elapsed > 0.0 {
+                let measured_velocity = ((hypothesis.state.mean - previous_position) / elapsed)
+                    .cap_magnitude(parameters.maximum_velocity);
+                hypothesis.velocity = hypothesis
+                    .velocity
+                    .lerp(&measured_velocity, parameters.velocity_smoothing_factor);
+            }
             hypothesis.measurement_count += 1;
             hypothesis.last_update = detection_time;
         });
@@ -339,6 +359,7 @@ impl ObstacleFilter {
                 mean: initial_state,
                 covariance: initial_covariance,
             },
+            velocity: Vector2::zeros(),
             obstacle_kind,
             measurement_count: 1,
             last_update: detection_time,
@@ -50,6 +50,7 @@ impl ObstacleReceiver {
             let pose = match message {
                 HulkMessage::Striker(striker_message) => striker_message.pose,
                 HulkMessage::Loser(loser_message) => loser_message.pose,
+                HulkMessage::Pose(pose_message) => pose_message.pose,
                 HulkMessage::VisualReferee(_) => continue,
             };
             let sender_position = context.ground_to_field.inverse() * pose.position();
@@ -3,10 +3,13 @@ use context_attribute::context;
 use coordinate_systems::{Field, Robot};
 use framework::{AdditionalOutput, MainOutput};
 use linear_algebra::{Orientation2, Orientation3, Vector2};
-use nalgebra::{Isometry2, Translation2};
+use nalgebra::{Isometry2, Matrix3, Translation2, UnitComplex};
 use serde::{Deserialize, Serialize};
 use types::{
+    odometry::OdometryUncertainty,
+    parameters::OdometryFusionParameters,
     robot_kinematics::RobotKinematics,
+    sole_pressure::SolePressure,
     support_foot::{Side, SupportFoot},
 };
 
@@ -14,6 +17,7 @@ use types::{
 pub struct Odometry {
     last_orientation: Orientation2<Field>,
     last_left_sole_to_right_sole: Vector2<Robot>,
+    last_left_sole_to_right_sole_yaw: f32,
     accumulated_odometry: Isometry2<f32>,
 }
 
@@ -26,38 +30,35 @@ pub struct CycleContext {
 
     robot_kinematics: Input<RobotKinematics, "robot_kinematics">,
     robot_orientation: RequiredInput<Option<Orientation3<Field>>, "robot_orientation?">,
+    sole_pressure: Input<SolePressure, "sole_pressure">,
     support_foot: Input<SupportFoot, "support_foot">,
 
     odometry_scale_factor: Parameter<Vector2<Robot>, "odometry.odometry_scale_factor">,
+    fusion: Parameter<OdometryFusionParameters, "odometry.fusion">,
 }
 
 #[context]
 #[derive(Default)]
 pub struct MainOutputs {
     pub current_odometry_to_last_odometry: MainOutput<Option<Isometry2<f32>>>,
+    pub odometry_uncertainty: MainOutput<Option<OdometryUncertainty>>,
 }
 
 impl Odometry {
     pub fn new(_context: CreationContext) -> Result<Self> {
         Ok(Self {
             last_left_sole_to_right_sole: Vector2::zeros(),
+            last_left_sole_to_right_sole_yaw: 0.0,
             last_orientation: Orientation2::default(),
             accumulated_odometry: Isometry2::identity(),
         })
     }
 
     pub fn cycle(&mut self, mut context: CycleContext) -> Result<MainOutputs> {
-        let left_sole_to_right_sole = (context
-            .robot_kinematics
-            .right_leg
-            .sole_to_robot
-            .translation()
-            - context
-                .robot_kinematics
-                .left_leg
-                .sole_to_robot
-                .translation())
-        .xy();
+        let left_sole_to_robot = context.robot_kinematics.left_leg.sole_to_robot;
+        let right_sole_to_robot = context.robot_kinematics.right_leg.sole_to_robot;
+        let left_sole_to_right_sole =
+            (right_sole_to_robot.translation() - left_sole_to_robot.translation()).xy();
         let offset_to_last_position = calculate_offset_to_last_position(
             context.support_foot,
             left_sole_to_right_sole,
@@ -67,14 +68,42 @@ impl Odometry {
         let corrected_offset_to_last_position =
             offset_to_last_position.component_mul(context.odometry_scale_factor);
 
+        let (_, _, left_sole_yaw) = left_sole_to_robot.inner.rotation.euler_angles();
+        let (_, _, right_sole_yaw) = right_sole_to_robot.inner.rotation.euler_angles();
+        let left_sole_to_right_sole_yaw = right_sole_yaw - left_sole_yaw;
+        let kinematic_yaw_offset = calculate_yaw_offset_to_last_orientation(
+            context.support_foot,
+            left_sole_to_right_sole_yaw,
+            self.last_left_sole_to_right_sole_yaw,
+        );
+        self.last_left_sole_to_right_sole_yaw = left_sole_to_right_sole_yaw;
+
         let (_, _, yaw) = context.robot_orientation.inner.euler_angles();
         let orientation = Orientation2::new(yaw);
-        let orientation_offset = self.last_orientation.rotation_to(orientation);
+        let imu_orientation_offset = self.last_orientation.rotation_to(orientation);
         self.last_orientation = orientation;
 
+        let (orientation_offset, uncertainty) = if context.fusion.enabled {
+            let contact_confidence = contact_confidence(
+                context.support_foot,
+                context.sole_pressure,
+                context.fusion.full_contact_pressure,
+            );
+            let (yaw_offset, uncertainty) = fuse(
+                corrected_offset_to_last_position,
+                kinematic_yaw_offset,
+                imu_orientation_offset.inner.angle(),
+                contact_confidence,
+                context.fusion,
+            );
+            (UnitComplex::new(yaw_offset), Some(uncertainty))
+        } else {
+            (imu_orientation_offset.inner, None)
+        };
+
         let current_odometry_to_last_odometry = Isometry2::from_parts(
             Translation2::from(corrected_offset_to_last_position.inner),
-            orientation_offset.inner,
+            orientation_offset,
         );
         self.accumulated_odometry = current_odometry_to_last_odometry * self.accumulated_odometry;
         context
@@ -83,6 +112,7 @@ impl Odometry {
 
         Ok(MainOutputs {
             current_odometry_to_last_odometry: Some(current_odometry_to_last_odometry).into(),
+            odometry_uncertainty: uncertainty.into(),
         })
     }
 }
@@ -98,3 +128,178 @@ fn calculate_offset_to_last_position(
         None => Vector2::zeros(),
     }
 }
+
+fn calculate_yaw_offset_to_last_orientation(
+    support_foot: &SupportFoot,
+    left_sole_to_right_sole_yaw: f32,
+    last_left_sole_to_right_sole_yaw: f32,
+) -> f32 {
+    let difference =
+        Orientation2::<Robot>::new(left_sole_to_right_sole_yaw - last_left_sole_to_right_sole_yaw)
+            .angle();
+    match support_foot.support_side {
+        Some(Side::Left) => difference / 2.0,
+        Some(Side::Right) => -difference / 2.0,
+        None => 0.0,
+    }
+}
+
+fn contact_confidence(
+    support_foot: &SupportFoot,
+    sole_pressure: &SolePressure,
+    full_contact_pressure: f32,
+) -> f32 {
+    let support_sole_pressure = match support_foot.support_side {
+        Some(Side::Left) => sole_pressure.left,
+        Some(Side::Right) => sole_pressure.right,
+        None => return 0.0,
+    };
+    (support_sole_pressure / full_contact_pressure).clamp(0.0, 1.0)
+}
+
+/// Combines the kinematic and IMU yaw offsets weighted by their variances
+///
+/// The kinematic yaw offset is only trusted as much as the support foot is in contact. If the
+/// foot slips, i.e. the contact is weak or both offsets disagree, the IMU yaw offset is used
+/// alone and the translation variance is inflated.
+///
+/// A foot sliding without turning is not detected: neither the leg kinematics nor the IMU yaw
+/// observe the translation of the support foot, such slips are only covered by the relative
+/// translation noise.
+fn fuse(
+    offset_to_last_position: Vector2<Robot>,
+    kinematic_yaw_offset: f32,
+    imu_yaw_offset: f32,
+    contact_confidence: f32,
+    parameters: &OdometryFusionParameters,
+) -> (f32, OdometryUncertainty) {
+    let yaw_residual = Orientation2::<Robot>::new(imu_yaw_offset - kinematic_yaw_offset).angle();
+    let is_slipping = contact_confidence < parameters.minimum_contact_confidence
+        || yaw_residual.abs() > parameters.slip_yaw_threshold;
+
+    let imu_yaw_variance = parameters.imu_yaw_noise.powi(2);
+    let (yaw_offset, yaw_variance) = if is_slipping {
+        (imu_yaw_offset, imu_yaw_variance)
+    } else {
+        let kinematic_yaw_variance =
+            parameters.kinematic_yaw_noise.powi(2) / contact_confidence.max(f32::EPSILON);
+        let imu_weight = kinematic_yaw_variance / (kinematic_yaw_variance + imu_yaw_variance);
+        (
+            kinematic_yaw_offset + imu_weight * yaw_residual,
+            imu_weight * imu_yaw_variance,
+        )
+    };
+
+    let slip_factor = if is_slipping {
+        parameters.slip_noise_factor
+    } else {
+        1.0
+    };
+    let translation_standard_deviation = parameters.translation_noise
+        + offset_to_last_position.abs() * parameters.relative_translation_noise;
+
+    let uncertainty = OdometryUncertainty {
+        covariance: Matrix3::from_diagonal(&nalgebra::vector![
+            translation_standard_deviation.x().powi(2) * slip_factor,
+            translation_standard_deviation.y().powi(2) * slip_factor,
+            yaw_variance
+        ]),
+        contact_confidence,
+        yaw_residual,
+        is_slipping,
+    };
+    (yaw_offset, uncertainty)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+
+    use approx::assert_relative_eq;
+    use linear_algebra::vector;
+
+    use super::*;
+
+    fn parameters() -> OdometryFusionParameters {
+        OdometryFusionParameters {
+            enabled: true,
+            full_contact_pressure: 1.0,
+            minimum_contact_confidence: 0.3,
+            kinematic_yaw_noise: 0.01,
+            imu_yaw_noise: 0.01,
+            slip_yaw_threshold: 0.05,
+            translation_noise: vector![0.001, 0.001],
+            relative_translation_noise: 0.1,
+            slip_noise_factor: 10.0,
+        }
+    }
+
+    #[test]
+    fn agreeing_offsets_are_averaged_by_variance() {
+        let (yaw_offset, uncertainty) = fuse(Vector2::zeros(), 0.02, 0.04, 1.0, &parameters());
+
+        assert!(!uncertainty.is_slipping);
+        assert_relative_eq!(yaw_offset, 0.03);
+        assert_relative_eq!(uncertainty.covariance[(2, 2)], 0.00005);
+    }
+
+    #[test]
+    fn disagreeing_offsets_are_detected_as_slip() {
+        let offset = vector![0.01, 0.0];
+        let (yaw_offset, uncertainty) = fuse(offset, 0.0, 0.1, 1.0, &parameters());
+        let (_, firm_uncertainty) = fuse(offset, 0.0, 0.0, 1.0, &parameters());
+
+        assert!(uncertainty.is_slipping);
+        assert_relative_eq!(yaw_offset, 0.1);
+        assert_relative_eq!(
+            uncertainty.covariance[(0, 0)],
+            firm_uncertainty.covariance[(0, 0)] * 10.0
+        );
+    }
+
+    fn support(support_side: Option<Side>) -> SupportFoot {
+        SupportFoot {
+            support_side,
+            changed_this_cycle: false,
+        }
+    }
+
+    #[test]
+    fn contact_confidence_follows_support_sole_pressure() {
+        let sole_pressure = SolePressure {
+            left: 0.75,
+            right: 3.0,
+        };
+
+        assert_relative_eq!(
+            contact_confidence(&support(Some(Side::Left)), &sole_pressure, 1.5),
+            0.5
+        );
+        assert_relative_eq!(
+            contact_confidence(&support(Some(Side::Right)), &sole_pressure, 1.5),
+            1.0
+        );
+        assert_relative_eq!(contact_confidence(&support(None), &sole_pressure, 1.5), 0.0);
+    }
+
+    #[test]
+    fn yaw_offset_is_half_the_wrapped_sole_yaw_difference() {
+        assert_relative_eq!(
+            calculate_yaw_offset_to_last_orientation(&support(Some(Side::Left)), 0.2, 0.1),
+            0.05
+        );
+        assert_relative_eq!(
+            calculate_yaw_offset_to_last_orientation(&support(Some(Side::Right)), 0.2, 0.1),
+            -0.05
+        );
+        assert_relative_eq!(
+            calculate_yaw_offset_to_last_orientation(&support(Some(Side::Left)), -3.1, 3.1),
+            (2.0 * PI - 6.2) / 2.0,
+            epsilon = 1e-5
+        );
+        assert_relative_eq!(
+            calculate_yaw_offset_to_last_orientation(&support(None), 0.2, 0.1),
+            0.0
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use color_eyre::{eyre::eyre, Result};
 use geometry::{
     arc::Arc,
@@ -86,6 +88,34 @@ impl PathPlanner {
         self.obstacles.extend(new_obstacles);
     }
 
+    /// Adds obstacles inflated along their predicted motion
+    ///
+    /// Each obstacle is predicted until the time the own robot needs to walk to its current
+    /// position, but at most for the prediction horizon. The resulting circle covers the whole
+    /// swept area between the current and the predicted position, its radius grows by at most
+    /// `maximum_inflation`.
+    pub fn with_moving_obstacles(
+        &mut self,
+        obstacles: &[Obstacle],
+        own_robot_radius: f32,
+        walking_speed: f32,
+        prediction_horizon: Duration,
+        maximum_inflation: f32,
+    ) {
+        let new_obstacles = obstacles.iter().map(|obstacle| {
+            let time_to_reach = (obstacle.position.coords().norm() / walking_speed)
+                .min(prediction_horizon.as_secs_f32());
+            let displacement =
+                (obstacle.velocity * time_to_reach).cap_magnitude(2.0 * maximum_inflation);
+            let center = obstacle.position + displacement / 2.0;
+            let radius =
+                obstacle.radius_at_hip_height + own_robot_radius + displacement.norm() / 2.0;
+            PathObstacle::from(PathObstacleShape::Circle(Circle { center, radius }))
+        });
+
+        self.obstacles.extend(new_obstacles);
+    }
+
     pub fn with_rule_obstacles(
         &mut self,
         field_to_robot: Isometry2<Field, Ground>,
@@ -546,7 +576,7 @@ mod tests {
     use std::f32::consts::{FRAC_PI_3, PI};
 
     use approx::assert_relative_eq;
-    use linear_algebra::point;
+    use linear_algebra::{point, vector};
 
     use super::*;
 
@@ -804,6 +834,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn path_around_moving_obstacle() {
+        let mut static_planner = PathPlanner::default();
+        let mut moving_planner = PathPlanner::default();
+        let obstacle = Obstacle {
+            velocity: vector![0.0, -0.5],
+            ..Obstacle::robot(point![1.0, 1.0], 0.2, 0.2)
+        };
+        static_planner.with_obstacles(&[obstacle], 0.0);
+        moving_planner.with_moving_obstacles(&[obstacle], 0.0, 0.5, Duration::from_secs(3), 0.5);
+
+        let static_path = static_planner
+            .plan(Point2::origin(), point![2.0, 0.0])
+            .expect("Path error")
+            .expect("Path was none");
+        let moving_path = moving_planner
+            .plan(Point2::origin(), point![2.0, 0.0])
+            .expect("Path error")
+            .expect("Path was none");
+
+        assert_eq!(static_path.len(), 1);
+        assert!(moving_path.len() > 1);
+        assert!(
+            moving_path
+                .iter()
+                .map(|segment| segment.length())
+                .sum::<f32>()
+                > 2.0
+        );
+    }
+
     #[test]
     fn path_start_surrounded() {
         let mut map = PathPlanner::default();
@@ -0,0 +1,324 @@
+use std::time::{Duration, SystemTime};
+
+use linear_algebra::{distance, point, Point2, Pose2};
+use ndarray::Array2;
+use ordered_float::NotNan;
+use serde::{Deserialize, Serialize};
+
+use coordinate_systems::Field;
+use hungarian_algorithm::AssignmentProblem;
+use spl_network_messages::{HulkMessage, PlayerNumber};
+use types::{parameters::RoleAllocationParameters, players::Players, roles::Role};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Candidate {
+    pub player_number: PlayerNumber,
+    pub position: Point2<Field>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+struct TimedPose {
+    time: SystemTime,
+    pose: Pose2<Field>,
+}
+
+impl TimedPose {
+    fn age(&self, now: SystemTime) -> Duration {
+        now.duration_since(self.time).unwrap_or_default()
+    }
+}
+
+/// Latest poses the players broadcast in striker, loser and pose messages
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct TeammatePoses {
+    poses: Players<Option<TimedPose>>,
+}
+
+impl Default for TeammatePoses {
+    fn default() -> Self {
+        Self {
+            poses: Players::new(None),
+        }
+    }
+}
+
+impl TeammatePoses {
+    /// Keeps the newest pose per player, independent of the order messages arrive in
+    pub fn update(&mut self, player_number: PlayerNumber, time: SystemTime, pose: Pose2<Field>) {
+        let is_newer = self.poses[player_number].is_none_or(|timed_pose| timed_pose.time <= time);
+        if is_newer {
+            self.poses[player_number] = Some(TimedPose { time, pose });
+        }
+    }
+
+    pub fn update_from_message(&mut self, time: SystemTime, message: &HulkMessage) {
+        match message {
+            HulkMessage::Striker(message) => self.update(message.player_number, time, message.pose),
+            HulkMessage::Loser(message) => self.update(message.player_number, time, message.pose),
+            HulkMessage::Pose(message) => self.update(message.player_number, time, message.pose),
+            HulkMessage::VisualReferee(_) => {}
+        }
+    }
+
+    /// Poses which were broadcast at most `maximum_age` before `now`
+    pub fn recent(&self, now: SystemTime, maximum_age: Duration) -> Players<Option<Pose2<Field>>> {
+        self.poses.map(|timed_pose| {
+            timed_pose
+                .filter(|timed_pose| timed_pose.age(now) <= maximum_age)
+                .map(|timed_pose| timed_pose.pose)
+        })
+    }
+
+    /// Ages of the poses returned by [`TeammatePoses::recent`]
+    pub fn recent_ages(&self, now: SystemTime, maximum_age: Duration) -> Players<Option<Duration>> {
+        self.poses.map(|timed_pose| {
+            timed_pose
+                .map(|timed_pose| timed_pose.age(now))
+                .filter(|&age| age <= maximum_age)
+        })
+    }
+}
+
+/// Players with a known pose, others are left out as they cannot be positioned reliably
+pub fn candidates(
+    poses: &Players<Option<Pose2<Field>>>,
+    player_numbers: impl IntoIterator<Item = PlayerNumber>,
+) -> Vec<Candidate> {
+    player_numbers
+        .into_iter()
+        .filter_map(|player_number| {
+            Some(Candidate {
+                player_number,
+                position: poses[player_number]?.position(),
+            })
+        })
+        .collect()
+}
+
+/// Target position of a role that is assignable by the allocation
+pub fn target_position(
+    role: Role,
+    ball_position: Option<Point2<Field>>,
+    parameters: &RoleAllocationParameters,
+) -> Option<Point2<Field>> {
+    let position = match role {
+        Role::DefenderLeft => parameters.defender_left_position,
+        Role::DefenderRight => parameters.defender_right_position,
+        Role::MidfielderLeft => parameters.midfielder_left_position,
+        Role::MidfielderRight => parameters.midfielder_right_position,
+        Role::StrikerSupporter => parameters.striker_supporter_position,
+        _ => return None,
+    };
+    let shift = ball_position.map_or(0.0, |ball| ball.x() * parameters.ball_attraction);
+    Some(point![position.x() + shift, position.y()])
+}
+
+/// Assigns roles to candidates such that the total walking time to the role positions is minimal
+///
+/// A candidate within `occupancy_radius` of a role position occupies the role and keeping it is
+/// cheaper by `hysteresis` seconds to prevent oscillations. Occupancy is derived from the
+/// broadcast poses instead of previous allocations, hence every robot computes the same
+/// allocation from the same broadcast poses regardless of its own history.
+pub fn allocate_roles(
+    candidates: &[Candidate],
+    targets: &[(Role, Point2<Field>)],
+    walking_speed: f32,
+    hysteresis: f32,
+    occupancy_radius: f32,
+) -> Vec<(PlayerNumber, Role)> {
+    if candidates.is_empty() || targets.is_empty() {
+        return Vec::new();
+    }
+    let costs = Array2::from_shape_fn(
+        (candidates.len(), targets.len()),
+        |(candidate_index, target_index)| {
+            let candidate = candidates[candidate_index];
+            let (_, position) = targets[target_index];
+            let distance = distance(candidate.position, position);
+            let mut walking_time = distance / walking_speed;
+            if distance <= occupancy_radius {
+                walking_time -= hysteresis;
+            }
+            // the assignment problem maximizes, hence costs are negated
+            NotNan::new(-walking_time).unwrap_or_default()
+        },
+    );
+
+    AssignmentProblem::from_costs(costs)
+        .solve()
+        .into_iter()
+        .zip(candidates)
+        .filter_map(|(assignment, candidate)| {
+            assignment.map(|assignment| (candidate.player_number, targets[assignment.to].0))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use spl_network_messages::{LoserMessage, PoseMessage};
+    use types::parameters::SplNetworkParameters;
+
+    use super::*;
+
+    /// Messages a team may send during a game according to the SPL rules
+    const MESSAGE_BUDGET: u16 = 1200;
+    const GAME_DURATION: Duration = Duration::from_secs(2 * 10 * 60);
+
+    fn candidate(player_number: PlayerNumber, position: Point2<Field>) -> Candidate {
+        Candidate {
+            player_number,
+            position,
+        }
+    }
+
+    #[test]
+    fn closest_players_are_assigned() {
+        let candidates = [
+            candidate(PlayerNumber::Two, point![-3.0, -1.0]),
+            candidate(PlayerNumber::Three, point![-3.0, 1.0]),
+            candidate(PlayerNumber::Four, point![0.0, 0.0]),
+        ];
+        let targets = [
+            (Role::StrikerSupporter, point![0.5, 0.0]),
+            (Role::DefenderLeft, point![-3.5, 1.0]),
+            (Role::DefenderRight, point![-3.5, -1.0]),
+        ];
+
+        let allocation = allocate_roles(&candidates, &targets, 0.3, 0.0, 0.0);
+
+        assert_eq!(
+            allocation,
+            vec![
+                (PlayerNumber::Two, Role::DefenderRight),
+                (PlayerNumber::Three, Role::DefenderLeft),
+                (PlayerNumber::Four, Role::StrikerSupporter),
+            ]
+        );
+    }
+
+    #[test]
+    fn occupied_roles_are_kept_within_hysteresis() {
+        let candidates = [
+            candidate(PlayerNumber::Two, point![-3.0, 0.7]),
+            candidate(PlayerNumber::Three, point![-3.6, 1.5]),
+        ];
+        let targets = [
+            (Role::DefenderLeft, point![-3.0, 1.0]),
+            (Role::DefenderRight, point![-3.0, -1.0]),
+        ];
+
+        let without_hysteresis = allocate_roles(&candidates, &targets, 0.3, 0.0, 0.5);
+        let with_hysteresis = allocate_roles(&candidates, &targets, 0.3, 2.0, 0.5);
+
+        assert_eq!(
+            without_hysteresis,
+            vec![
+                (PlayerNumber::Two, Role::DefenderRight),
+                (PlayerNumber::Three, Role::DefenderLeft),
+            ]
+        );
+        assert_eq!(
+            with_hysteresis,
+            vec![
+                (PlayerNumber::Two, Role::DefenderLeft),
+                (PlayerNumber::Three, Role::DefenderRight),
+            ]
+        );
+    }
+
+    #[test]
+    fn robots_with_divergent_histories_agree() {
+        let at = |seconds| SystemTime::UNIX_EPOCH + Duration::from_secs(seconds);
+        let pose_message = |player_number, x, y| {
+            HulkMessage::Pose(PoseMessage {
+                player_number,
+                pose: Pose2::from(point![x, y]),
+            })
+        };
+        let history = [
+            (at(0), pose_message(PlayerNumber::Two, -3.0, 0.7)),
+            (at(0), pose_message(PlayerNumber::Three, -3.6, 1.5)),
+            (
+                at(1),
+                HulkMessage::Loser(LoserMessage {
+                    player_number: PlayerNumber::Five,
+                    pose: Pose2::from(point![-3.0, -1.0]),
+                }),
+            ),
+            (at(8), pose_message(PlayerNumber::Four, -1.0, 1.5)),
+            (at(12), pose_message(PlayerNumber::Two, -3.0, -0.6)),
+            (at(13), pose_message(PlayerNumber::Three, -2.5, 1.2)),
+        ];
+        // the first robot received everything, the second one rebooted and only heard the
+        // latest messages, the third one received everything in reverse order
+        let mut histories = [TeammatePoses::default(); 3];
+        for (time, message) in &history {
+            histories[0].update_from_message(*time, message);
+        }
+        for (time, message) in &history[3..] {
+            histories[1].update_from_message(*time, message);
+        }
+        for (time, message) in history.iter().rev() {
+            histories[2].update_from_message(*time, message);
+        }
+        let targets = [
+            (Role::DefenderLeft, point![-3.0, 1.0]),
+            (Role::DefenderRight, point![-3.0, -1.0]),
+            (Role::MidfielderLeft, point![-1.0, 1.8]),
+        ];
+        let players = [
+            PlayerNumber::Two,
+            PlayerNumber::Three,
+            PlayerNumber::Four,
+            PlayerNumber::Five,
+        ];
+
+        let allocations: Vec<_> = histories
+            .iter()
+            .map(|teammate_poses| {
+                let poses = teammate_poses.recent(at(14), Duration::from_secs(10));
+                allocate_roles(&candidates(&poses, players), &targets, 0.3, 2.0, 0.5)
+            })
+            .collect();
+
+        assert_eq!(
+            allocations[0],
+            vec![
+                (PlayerNumber::Two, Role::DefenderRight),
+                (PlayerNumber::Three, Role::DefenderLeft),
+                (PlayerNumber::Four, Role::MidfielderLeft),
+            ]
+        );
+        assert_eq!(allocations[0], allocations[1]);
+        assert_eq!(allocations[0], allocations[2]);
+    }
+
+    #[test]
+    fn pose_messages_fit_into_message_budget() {
+        let parameters: serde_json::Value =
+            serde_json::from_str(include_str!("../../../etc/parameters/default.json")).unwrap();
+        let spl_network: SplNetworkParameters =
+            serde_json::from_value(parameters["spl_network"].clone()).unwrap();
+        let maximum_pose_age: Duration = serde_json::from_value(
+            parameters["teammate_pose_receiver"]["maximum_pose_age"].clone(),
+        )
+        .unwrap();
+        let messages_per_player = |interval: Duration| {
+            (GAME_DURATION.as_secs_f32() / interval.as_secs_f32()).ceil() as u16
+        };
+        // with passing enabled, all players except the striker send their poses
+        let number_of_pose_senders = 6;
+
+        let striker_messages = messages_per_player(spl_network.spl_striker_message_send_interval);
+        let pose_messages =
+            number_of_pose_senders * messages_per_player(spl_network.pose_message_send_interval);
+
+        assert!(maximum_pose_age > spl_network.pose_message_send_interval);
+        assert!(
+            striker_messages + pose_messages
+                <= MESSAGE_BUDGET - spl_network.remaining_amount_of_messages_to_stop_sending,
+            "{striker_messages} striker and {pose_messages} pose messages exceed the budget"
+        );
+    }
+}
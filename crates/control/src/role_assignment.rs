@@ -14,10 +14,10 @@ use context_attribute::context;
 use coordinate_systems::{Field, Ground};
 use framework::{AdditionalOutput, MainOutput, PerceptionInput};
 use hardware::NetworkInterface;
-use linear_algebra::Isometry2;
+use linear_algebra::{Isometry2, Point2, Pose2};
 use spl_network_messages::{
-    GameControllerReturnMessage, GamePhase, HulkMessage, LoserMessage, Penalty, PlayerNumber,
-    StrikerMessage, SubState, Team,
+    GameControllerReturnMessage, GamePhase, HulkMessage, LoserMessage, PassTarget, Penalty,
+    PlayerNumber, PoseMessage, StrikerMessage, SubState, Team,
 };
 use types::{
     ball_position::BallPosition,
@@ -26,14 +26,18 @@ use types::{
     field_dimensions::FieldDimensions,
     filtered_game_controller_state::FilteredGameControllerState,
     initial_pose::InitialPose,
+    kick_decision::KickDecision,
     messages::{IncomingMessage, OutgoingMessage},
-    parameters::SplNetworkParameters,
+    parameters::{RoleAllocationParameters, SplNetworkParameters},
     players::Players,
     primary_state::PrimaryState,
     roles::Role,
 };
 
-use crate::localization::generate_initial_pose;
+use crate::{
+    localization::generate_initial_pose,
+    role_allocation::{allocate_roles, candidates, target_position},
+};
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
 enum SentState {
@@ -49,6 +53,11 @@ pub struct RoleAssignment {
     role: Role,
     last_time_player_was_penalized: Players<Option<SystemTime>>,
     last_sent_state: SentState,
+    last_transmitted_pose_message: Option<SystemTime>,
+    last_transmitted_pose: Option<Pose2<Field>>,
+    last_known_striker: Option<(PlayerNumber, SystemTime)>,
+    last_allocation: Players<Option<Role>>,
+    last_incoming_pass: Option<(SystemTime, Point2<Field>)>,
 }
 
 #[context]
@@ -72,6 +81,8 @@ pub struct CycleContext {
     game_controller_address: Input<Option<SocketAddr>, "game_controller_address?">,
     time_to_reach_kick_position: Input<Option<Duration>, "time_to_reach_kick_position?">,
     team_ball: Input<Option<BallPosition<Field>>, "team_ball?">,
+    teammate_poses: Input<Players<Option<Pose2<Field>>>, "teammate_poses">,
+    kick_decisions: Input<Option<Vec<KickDecision>>, "kick_decisions?">,
 
     field_dimensions: Parameter<FieldDimensions, "field_dimensions">,
     forced_role: Parameter<Option<Role>, "role_assignment.forced_role?">,
@@ -81,8 +92,12 @@ pub struct CycleContext {
         Parameter<Duration, "role_assignment.maximum_trusted_team_ball_age">,
     claim_striker_from_team_ball: Parameter<bool, "role_assignment.claim_striker_from_team_ball">,
     initial_poses: Parameter<Players<InitialPose>, "localization.initial_poses">,
+    maximum_pose_age: Parameter<Duration, "teammate_pose_receiver.maximum_pose_age">,
     optional_roles: Parameter<Vec<Role>, "behavior.optional_roles">,
+    passing_enabled: Parameter<bool, "kick_selector.passing.enabled">,
     player_number: Parameter<PlayerNumber, "player_number">,
+    role_allocation_parameters:
+        Parameter<RoleAllocationParameters, "role_assignment.role_allocation">,
     spl_network_parameters: Parameter<SplNetworkParameters, "spl_network">,
 
     hardware: HardwareInterface,
@@ -91,12 +106,14 @@ pub struct CycleContext {
         AdditionalOutput<Players<Option<SystemTime>>, "last_time_player_penalized">,
     last_sent_state: AdditionalOutput<String, "last_sent_state">,
     last_sent_message: AdditionalOutput<String, "last_sent_message">,
+    role_allocation: AdditionalOutput<Players<Option<Role>>, "role_allocation">,
 }
 
 #[context]
 #[derive(Default)]
 pub struct MainOutputs {
     pub role: MainOutput<Role>,
+    pub incoming_pass: MainOutput<Option<Point2<Field>>>,
 }
 
 impl RoleAssignment {
@@ -119,6 +136,11 @@ impl RoleAssignment {
             role,
             last_time_player_was_penalized: Players::new(None),
             last_sent_state: SentState::Loser,
+            last_transmitted_pose_message: None,
+            last_transmitted_pose: None,
+            last_known_striker: None,
+            last_allocation: Players::new(None),
+            last_incoming_pass: None,
         })
     }
 
@@ -149,6 +171,7 @@ impl RoleAssignment {
 
         let role_from_state_machine =
             self.role_from_state_machine(&context, cycle_start_time, self.role);
+        self.update_from_team_messages(&context);
 
         let mut new_role = [
             context.forced_role.copied(),
@@ -161,6 +184,7 @@ impl RoleAssignment {
             ),
             keep_current_role_if_not_in_playing(primary_state, self.role),
             keep_current_role_during_free_kicks(context.filtered_game_controller_state, self.role),
+            self.role_from_allocation(&context, role_from_state_machine),
             Some(role_from_state_machine),
         ]
         .iter()
@@ -207,16 +231,40 @@ impl RoleAssignment {
                 }
                 _ => {}
             }
+
+            let is_allocated_role =
+                target_position(new_role, None, context.role_allocation_parameters).is_some();
+            let is_pose_required = (context.role_allocation_parameters.enabled
+                && is_allocated_role)
+                || (*context.passing_enabled && new_role != Role::Striker);
+            if is_pose_required && self.is_pose_message_cooldown_elapsed(&context) {
+                self.try_sending_pose_message(&mut context)?;
+            }
         }
+        context
+            .role_allocation
+            .fill_if_subscribed(|| self.last_allocation);
 
         context
             .last_sent_state
             .fill_if_subscribed(|| format!("{:?}", self.last_sent_state));
 
         self.role = new_role;
+        let incoming_pass = self
+            .last_incoming_pass
+            .filter(|(time, _)| {
+                cycle_start_time
+                    .duration_since(*time)
+                    .expect("time ran backwards")
+                    <= context
+                        .spl_network_parameters
+                        .spl_striker_message_receive_timeout
+            })
+            .map(|(_, position)| position);
 
         Ok(MainOutputs {
             role: self.role.into(),
+            incoming_pass: incoming_pass.into(),
         })
     }
 
@@ -336,6 +384,99 @@ impl RoleAssignment {
         new_role
     }
 
+    fn update_from_team_messages(&mut self, context: &CycleContext<'_, impl NetworkInterface>) {
+        for (&time, messages) in &context.network_message.persistent {
+            for message in messages.iter().flatten() {
+                if let IncomingMessage::Spl(HulkMessage::Striker(StrikerMessage {
+                    player_number,
+                    pass_target,
+                    ..
+                })) = message
+                {
+                    self.last_known_striker = Some((*player_number, time));
+                    self.last_incoming_pass = pass_target
+                        .filter(|pass_target| pass_target.receiver == *context.player_number)
+                        .map(|pass_target| (time, pass_target.position));
+                }
+            }
+        }
+    }
+
+    fn role_from_allocation(
+        &mut self,
+        context: &CycleContext<'_, impl NetworkInterface>,
+        role_from_state_machine: Role,
+    ) -> Option<Role> {
+        let parameters = context.role_allocation_parameters;
+        if !parameters.enabled
+            || target_position(role_from_state_machine, None, parameters).is_none()
+        {
+            return None;
+        }
+        let game_controller_state = context.filtered_game_controller_state?;
+        let cycle_start_time = context.cycle_time.start_time;
+        let is_recent = |time: SystemTime, maximum_age: Duration| {
+            cycle_start_time
+                .duration_since(time)
+                .expect("time ran backwards")
+                <= maximum_age
+        };
+
+        let (striker, _) = self.last_known_striker.filter(|(_, time)| {
+            is_recent(
+                *time,
+                context
+                    .spl_network_parameters
+                    .spl_striker_message_receive_timeout,
+            )
+        })?;
+        let penalties = &game_controller_state.penalties;
+        let keeper = penalties.keeper(striker);
+        let available_players = penalties
+            .available_players(striker)
+            .filter(|&player_number| Some(player_number) != keeper);
+        // others only know the pose we broadcast last
+        let mut poses = *context.teammate_poses;
+        poses[*context.player_number] = self.last_transmitted_pose.filter(|_| {
+            self.last_transmitted_pose_message
+                .is_some_and(|time| is_recent(time, *context.maximum_pose_age))
+        });
+        // players in roles that are not allocated stop sending poses and drop out here
+        let candidates = candidates(&poses, available_players);
+
+        let ball_position = context.team_ball.map(|team_ball| team_ball.position);
+        let targets: Vec<_> = context
+            .optional_roles
+            .iter()
+            .filter_map(|&role| Some((role, target_position(role, ball_position, parameters)?)))
+            .take(candidates.len())
+            .collect();
+        let allocation = allocate_roles(
+            &candidates,
+            &targets,
+            parameters.walking_speed,
+            parameters.hysteresis,
+            parameters.occupancy_radius,
+        );
+
+        self.last_allocation = Players::new(None);
+        for (player_number, role) in allocation {
+            self.last_allocation[player_number] = Some(role);
+        }
+        self.last_allocation[*context.player_number]
+    }
+
+    fn is_pose_message_cooldown_elapsed(
+        &self,
+        context: &CycleContext<impl NetworkInterface>,
+    ) -> bool {
+        is_cooldown_elapsed(
+            context.cycle_time.start_time,
+            self.last_transmitted_pose_message,
+            context.spl_network_parameters.pose_message_send_interval,
+        )
+    }
+
     fn is_return_message_cooldown_elapsed(
         &self,
         context: &CycleContext<impl NetworkInterface>,
@@ -443,6 +584,15 @@ impl RoleAssignment {
         let ball_position = own_network_ball
             .or(team_network_ball)
             .ok_or_eyre("we are striker without a ball, this should never happen")?;
+        let pass_target = context
+            .kick_decisions
+            .and_then(|kick_decisions| kick_decisions.first())
+            .and_then(|kick_decision| {
+                Some(PassTarget {
+                    receiver: kick_decision.pass_receiver?,
+                    position: ground_to_field * kick_decision.target,
+                })
+            });
 
         self.last_sent_state = SentState::Striker;
         context
@@ -455,10 +605,48 @@ impl RoleAssignment {
                 pose,
                 ball_position,
                 time_to_reach_kick_position: *context.time_to_reach_kick_position.unwrap(),
+                pass_target,
             })))
             .wrap_err("failed to write StrikerMessage to hardware")
     }
 
+    fn try_sending_pose_message(
+        &mut self,
+        context: &mut CycleContext<impl NetworkInterface>,
+    ) -> Result<()> {
+        if !self.is_striker_silence_period_elapsed(context) {
+            return Ok(());
+        }
+        if context
+            .remaining_amount_of_messages
+            .is_some_and(|remaining_amount_of_messages| {
+                *remaining_amount_of_messages
+                    < context
+                        .spl_network_parameters
+                        .remaining_amount_of_messages_to_stop_sending
+            })
+        {
+            return Ok(());
+        }
+
+        let time = context.cycle_time.start_time;
+        self.last_transmitted_spl_message = Some(time);
+        self.last_transmitted_pose_message = Some(time);
+
+        let pose = ground_to_field_or_initial_pose(context).as_pose();
+        self.last_transmitted_pose = Some(pose);
+        context
+            .last_sent_message
+            .fill_if_subscribed(|| "Pose".to_string());
+        context
+            .hardware
+            .write_to_network(OutgoingMessage::Spl(HulkMessage::Pose(PoseMessage {
+                player_number: *context.player_number,
+                pose,
+            })))
+            .wrap_err("failed to write PoseMessage to hardware")
+    }
+
     fn try_sending_loser_message(
         &mut self,
         context: &mut CycleContext<impl NetworkInterface>,
@@ -813,23 +1001,21 @@ fn pick_role_with_penalties(
     striker_player_number: PlayerNumber,
     optional_roles: &[Role],
 ) -> Role {
-    let mut unassigned_players: VecDeque<_> = penalties
-        .iter()
-        .filter_map(|(player_number, penalty)| {
-            (player_number != striker_player_number && penalty.is_none()).then_some(player_number)
-        })
-        .collect();
+    let keeper = penalties.keeper(striker_player_number);
+    let unassigned_players = penalties
+        .available_players(striker_player_number)
+        .filter(|&player_number| Some(player_number) != keeper);
 
     let mut role_assignment: Players<Option<Role>> = Players::new(None);
     role_assignment[striker_player_number] = Some(Role::Striker);
-    if let Some(keeper) = unassigned_players.pop_front() {
+    if let Some(keeper) = keeper {
         role_assignment[keeper] = Some(match keeper {
             PlayerNumber::One => Role::Keeper,
             _ => Role::ReplacementKeeper,
         })
     }
 
-    for (player_number, &optional_role) in unassigned_players.into_iter().zip(optional_roles) {
+    for (player_number, &optional_role) in unassigned_players.zip(optional_roles) {
         role_assignment[player_number] = Some(optional_role)
     }
 
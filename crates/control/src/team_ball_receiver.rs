@@ -113,7 +113,7 @@ impl TeamBallReceiver {
                 }),
             ),
             HulkMessage::Loser(loser_message) => (loser_message.player_number, None),
-            HulkMessage::VisualReferee(_) => return,
+            HulkMessage::VisualReferee(_) | HulkMessage::Pose(_) => return,
         };
         self.received_balls[player] = ball;
     }
@@ -0,0 +1,70 @@
+use std::time::Duration;
+
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use context_attribute::context;
+use coordinate_systems::Field;
+use framework::{MainOutput, PerceptionInput};
+use linear_algebra::Pose2;
+use spl_network_messages::PlayerNumber;
+use types::{cycle_time::CycleTime, messages::IncomingMessage, players::Players};
+
+use crate::role_allocation::TeammatePoses;
+
+#[derive(Deserialize, Serialize)]
+pub struct TeammatePoseReceiver {
+    teammate_poses: TeammatePoses,
+}
+
+#[context]
+pub struct CreationContext {}
+
+#[context]
+pub struct CycleContext {
+    cycle_time: Input<CycleTime, "cycle_time">,
+    // unfiltered to include the poses of loser messages, which other nodes do not receive
+    network_message: PerceptionInput<IncomingMessage, "SplNetwork", "message">,
+
+    player_number: Parameter<PlayerNumber, "player_number">,
+    maximum_pose_age: Parameter<Duration, "teammate_pose_receiver.maximum_pose_age">,
+}
+
+#[context]
+#[derive(Default)]
+pub struct MainOutputs {
+    pub teammate_poses: MainOutput<Players<Option<Pose2<Field>>>>,
+    pub teammate_pose_ages: MainOutput<Players<Option<Duration>>>,
+}
+
+impl TeammatePoseReceiver {
+    pub fn new(_context: CreationContext) -> Result<Self> {
+        Ok(Self {
+            teammate_poses: TeammatePoses::default(),
+        })
+    }
+
+    pub fn cycle(&mut self, context: CycleContext) -> Result<MainOutputs> {
+        for (&time, messages) in &context.network_message.persistent {
+            for message in messages {
+                if let IncomingMessage::Spl(message) = message {
+                    if message.player_number() != *context.player_number {
+                        self.teammate_poses.update_from_message(time, message);
+                    }
+                }
+            }
+        }
+
+        let now = context.cycle_time.start_time;
+        Ok(MainOutputs {
+            teammate_poses: self
+                .teammate_poses
+                .recent(now, *context.maximum_pose_age)
+                .into(),
+            teammate_pose_ages: self
+                .teammate_poses
+                .recent_ages(now, *context.maximum_pose_age)
+                .into(),
+        })
+    }
+}
@@ -0,0 +1,210 @@
+use std::time::Duration;
+
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use context_attribute::context;
+use filtering::hysteresis::less_than_with_absolute_hysteresis;
+use framework::MainOutput;
+use types::{
+    cycle_time::CycleTime, joints::Joints, parameters::ThermalModelParameters,
+    sensor_data::SensorData, thermal_state::ThermalState,
+};
+
+/// First order thermal model of the joint motors
+///
+/// Each motor heats up with the square of its current and cools down towards the ambient
+/// temperature. The coarse temperature sensors continuously correct the estimate. Assuming the
+/// recent average load persists, the model predicts the temperature at the end of a horizon.
+#[derive(Deserialize, Serialize)]
+pub struct ThermalModel {
+    estimated_temperatures: Option<Joints<f32>>,
+    filtered_squared_currents: Joints<f32>,
+    needs_cooling: bool,
+}
+
+#[context]
+pub struct CreationContext {}
+
+#[context]
+pub struct CycleContext {
+    cycle_time: Input<CycleTime, "cycle_time">,
+    sensor_data: Input<SensorData, "sensor_data">,
+
+    parameters: Parameter<ThermalModelParameters, "thermal_model">,
+}
+
+#[context]
+#[derive(Default)]
+pub struct MainOutputs {
+    pub thermal_state: MainOutput<ThermalState>,
+}
+
+impl ThermalModel {
+    pub fn new(_context: CreationContext) -> Result<Self> {
+        Ok(Self {
+            estimated_temperatures: None,
+            filtered_squared_currents: Joints::default(),
+            needs_cooling: false,
+        })
+    }
+
+    pub fn cycle(&mut self, context: CycleContext) -> Result<MainOutputs> {
+        let parameters = context.parameters;
+        let measured_temperatures = context.sensor_data.temperature_sensors;
+        let squared_currents = square(context.sensor_data.currents);
+        let cycle_duration = context.cycle_time.last_cycle_duration;
+
+        let estimated_temperatures = match self.estimated_temperatures {
+            Some(estimated_temperatures) => {
+                let heated = integrate(
+                    estimated_temperatures,
+                    squared_currents,
+                    cycle_duration,
+                    parameters,
+                );
+                let measurement_gain =
+                    smoothing_factor(cycle_duration, parameters.measurement_time_constant);
+                heated + (measured_temperatures - heated) * measurement_gain
+            }
+            None => measured_temperatures,
+        };
+        self.estimated_temperatures = Some(estimated_temperatures);
+
+        let current_gain = smoothing_factor(cycle_duration, parameters.current_time_constant);
+        self.filtered_squared_currents = self.filtered_squared_currents
+            + (squared_currents - self.filtered_squared_currents) * current_gain;
+
+        let predicted_temperatures = predict(
+            estimated_temperatures,
+            self.filtered_squared_currents,
+            parameters.prediction_horizon,
+            parameters,
+        );
+        let temperature_margins =
+            Joints::fill(parameters.maximum_temperature) - predicted_temperatures;
+
+        let mut thermal_state = ThermalState {
+            estimated_temperatures,
+            predicted_temperatures,
+            temperature_margins,
+            needs_cooling: false,
+        };
+        // the estimate is always published to allow fitting the coefficients
+        self.needs_cooling = parameters.enabled
+            && less_than_with_absolute_hysteresis(
+                self.needs_cooling,
+                thermal_state.minimum_margin(),
+                parameters.cooling_margin
+                    ..=parameters.cooling_margin + parameters.cooling_margin_hysteresis,
+            );
+        thermal_state.needs_cooling = self.needs_cooling;
+
+        Ok(MainOutputs {
+            thermal_state: thermal_state.into(),
+        })
+    }
+}
+
+fn square(currents: Joints<f32>) -> Joints<f32> {
+    let mut squared = currents;
+    for (joint, current) in currents.enumerate() {
+        squared[joint] = current * current;
+    }
+    squared
+}
+
+fn smoothing_factor(cycle_duration: Duration, time_constant: Duration) -> f32 {
+    if time_constant.is_zero() {
+        return 1.0;
+    }
+    (cycle_duration.as_secs_f32() / time_constant.as_secs_f32()).min(1.0)
+}
+
+fn equilibrium_temperatures(
+    squared_currents: Joints<f32>,
+    parameters: &ThermalModelParameters,
+) -> Joints<f32> {
+    Joints::fill(parameters.ambient_temperature)
+        + squared_currents * (parameters.heating_coefficient / parameters.cooling_coefficient)
+}
+
+fn integrate(
+    temperatures: Joints<f32>,
+    squared_currents: Joints<f32>,
+    duration: Duration,
+    parameters: &ThermalModelParameters,
+) -> Joints<f32> {
+    let heating = squared_currents * parameters.heating_coefficient;
+    let cooling = (temperatures - Joints::fill(parameters.ambient_temperature))
+        * parameters.cooling_coefficient;
+    temperatures + (heating - cooling) * duration.as_secs_f32()
+}
+
+/// Closed form solution of the model for a constant load
+fn predict(
+    temperatures: Joints<f32>,
+    squared_currents: Joints<f32>,
+    horizon: Duration,
+    parameters: &ThermalModelParameters,
+) -> Joints<f32> {
+    let equilibrium = equilibrium_temperatures(squared_currents, parameters);
+    let decay = (-parameters.cooling_coefficient * horizon.as_secs_f32()).exp();
+    equilibrium + (temperatures - equilibrium) * decay
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    fn parameters() -> ThermalModelParameters {
+        ThermalModelParameters {
+            ambient_temperature: 30.0,
+            heating_coefficient: 0.02,
+            cooling_coefficient: 0.002,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn loaded_joints_approach_equilibrium() {
+        let parameters = parameters();
+        let squared_currents = Joints::fill(2.0_f32.powi(2));
+        let temperatures = Joints::fill(40.0);
+
+        let predicted = predict(
+            temperatures,
+            squared_currents,
+            Duration::from_secs(100_000),
+            &parameters,
+        );
+
+        assert_relative_eq!(predicted.left_leg.knee_pitch, 70.0, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn prediction_matches_integration() {
+        let parameters = parameters();
+        let squared_currents = Joints::fill(1.5_f32.powi(2));
+        let mut temperatures = Joints::fill(45.0);
+        let step = Duration::from_millis(12);
+        for _ in 0..10_000 {
+            temperatures = integrate(temperatures, squared_currents, step, &parameters);
+        }
+
+        let predicted = predict(
+            Joints::fill(45.0),
+            squared_currents,
+            step * 10_000,
+            &parameters,
+        );
+
+        assert_relative_eq!(
+            predicted.right_leg.ankle_pitch,
+            temperatures.right_leg.ankle_pitch,
+            epsilon = 0.1
+        );
+    }
+}
@@ -6,8 +6,12 @@ use serde::{Deserialize, Serialize};
 use framework::MainOutput;
 use linear_algebra::Vector2;
 use types::{
-    dribble_path_plan::DribblePathPlan, motion_command::OrientationMode,
-    parameters::BehaviorParameters, planned_path::PathSegment, stand_up::RemainingStandUpDuration,
+    dribble_path_plan::DribblePathPlan,
+    motion_command::OrientationMode,
+    parameters::{BehaviorParameters, ThermalModelParameters},
+    planned_path::PathSegment,
+    stand_up::RemainingStandUpDuration,
+    thermal_state::ThermalState,
 };
 
 #[derive(Deserialize, Serialize)]
@@ -17,8 +21,10 @@ use context_attribute::context;
 #[context]
 pub struct CycleContext {
     dribble_path_plan: Input<Option<DribblePathPlan>, "dribble_path_plan?">,
+    thermal_state: Input<ThermalState, "thermal_state">,
 
     configuration: Parameter<BehaviorParameters, "behavior">,
+    thermal_model: Parameter<ThermalModelParameters, "thermal_model">,
 
     stand_up_back_estimated_remaining_duration:
         CyclerState<RemainingStandUpDuration, "stand_up_back_estimated_remaining_duration">,
@@ -83,12 +89,24 @@ impl TimeToReachKickPosition {
             .half_rotation
             .mul_f32(turn_angle / PI);
 
+        // hot robots claim the ball later such that cooler teammates become striker
+        let thermal_penalty = if context.thermal_model.enabled {
+            context.thermal_model.maximum_striker_penalty.mul_f32(
+                1.0 - context
+                    .thermal_state
+                    .leg_headroom(context.thermal_model.stiffness_reduction_margin),
+            )
+        } else {
+            Duration::ZERO
+        };
+
         let time_to_reach_kick_position = [
             Some(walk_duration),
             (*context.stand_up_back_estimated_remaining_duration).into(),
             (*context.stand_up_front_estimated_remaining_duration).into(),
             (*context.stand_up_sitting_estimated_remaining_duration).into(),
             Some(turn_duration),
+            Some(thermal_penalty),
         ]
         .into_iter()
         .flatten()
@@ -2,15 +2,25 @@ use std::{collections::VecDeque, time::SystemTime};
 
 use color_eyre::Result;
 use context_attribute::context;
+use coordinate_systems::{Field, Ground, Head, Robot};
 use framework::{MainOutput, PerceptionInput};
+use linear_algebra::{vector, Isometry2, Isometry3, Orientation2, Point3, Vector2};
+use nalgebra::{Matrix2, Vector2 as NalgebraVector2};
 use serde::{Deserialize, Serialize};
-use types::{cycle_time::CycleTime, filtered_whistle::FilteredWhistle, whistle::Whistle};
+use types::{
+    cycle_time::CycleTime,
+    filtered_whistle::FilteredWhistle,
+    robot_kinematics::RobotKinematics,
+    whistle::{TimeDifferenceOfArrival, Whistle},
+};
 
 #[derive(Deserialize, Serialize)]
 pub struct WhistleFilter {
     detection_buffer: VecDeque<bool>,
     was_detected_last_cycle: bool,
     last_detection: Option<SystemTime>,
+    /// Kept in the field frame such that it stays valid while the robot turns
+    last_bearing: Option<Orientation2<Field>>,
 }
 
 #[context]
@@ -19,9 +29,19 @@ pub struct CreationContext {}
 #[context]
 pub struct CycleContext {
     cycle_time: Input<CycleTime, "cycle_time">,
+    robot_kinematics: Input<RobotKinematics, "robot_kinematics">,
+    robot_to_ground: Input<Option<Isometry3<Robot, Ground>>, "robot_to_ground?">,
+    ground_to_field: CyclerState<Option<Isometry2<Ground, Field>>, "ground_to_field">,
 
     buffer_length: Parameter<usize, "whistle_filter.buffer_length">,
     minimum_detections: Parameter<usize, "whistle_filter.minimum_detections">,
+    microphone_positions: Parameter<Vec<Point3<Head>>, "whistle_filter.microphone_positions">,
+    speed_of_sound: Parameter<f32, "whistle_filter.speed_of_sound">,
+    minimum_coherence: Parameter<f32, "whistle_filter.minimum_coherence">,
+    neighbouring_field_directions:
+        Parameter<Vec<Vector2<Field>>, "whistle_filter.neighbouring_field_directions">,
+    neighbouring_field_rejection_angle:
+        Parameter<f32, "whistle_filter.neighbouring_field_rejection_angle">,
     detected_whistle: PerceptionInput<Whistle, "Audio", "detected_whistle">,
 }
 
@@ -37,20 +57,49 @@ impl WhistleFilter {
             detection_buffer: Default::default(),
             was_detected_last_cycle: false,
             last_detection: None,
+            last_bearing: None,
         })
     }
 
     pub fn cycle(&mut self, context: CycleContext) -> Result<MainOutputs> {
         let cycle_start_time = context.cycle_time.start_time;
+        let head_to_robot = context.robot_kinematics.head.head_to_robot;
 
-        for &is_detected in context
-            .detected_whistle
-            .persistent
-            .values()
-            .flatten()
-            .flat_map(|whistle| &whistle.is_detected)
-        {
-            self.detection_buffer.push_front(is_detected);
+        for whistle in context.detected_whistle.persistent.values().flatten() {
+            let bearing = estimate_bearing(
+                &whistle.time_differences_of_arrival,
+                context.microphone_positions,
+                head_to_robot,
+                *context.speed_of_sound,
+                *context.minimum_coherence,
+            );
+            let bearing = match (bearing, context.robot_to_ground, *context.ground_to_field) {
+                (Some(bearing), Some(robot_to_ground), Some(ground_to_field)) => {
+                    let direction = bearing.as_unit_vector();
+                    let direction_in_ground =
+                        (*robot_to_ground * vector![direction.x(), direction.y(), 0.0]).xy();
+                    Some(Orientation2::from_vector(
+                        ground_to_field * direction_in_ground,
+                    ))
+                }
+                _ => None,
+            };
+            let is_from_neighbouring_field = bearing.is_some_and(|bearing| {
+                context
+                    .neighbouring_field_directions
+                    .iter()
+                    .any(|neighbouring_field_direction| {
+                        bearing.as_unit_vector().angle(neighbouring_field_direction)
+                            < *context.neighbouring_field_rejection_angle
+                    })
+            });
+            if whistle.is_detected.contains(&true) && !is_from_neighbouring_field {
+                self.last_bearing = bearing.or(self.last_bearing);
+            }
+            for &is_detected in &whistle.is_detected {
+                self.detection_buffer
+                    .push_front(is_detected && !is_from_neighbouring_field);
+            }
         }
         self.detection_buffer.truncate(*context.buffer_length);
         let number_of_detections = self
@@ -69,8 +118,88 @@ impl WhistleFilter {
             filtered_whistle: FilteredWhistle {
                 is_detected,
                 last_detection: self.last_detection,
+                bearing: self.last_bearing,
             }
             .into(),
         })
     }
 }
+
+/// Far field estimate of the direction towards a sound source in the horizontal plane
+///
+/// A plane wave from direction `u` reaches microphone `i` at `t0 - p_i · u / c`. Each measured
+/// delay between two microphones therefore constrains `(p_second - p_first) · u = c * delay`,
+/// which is solved in a weighted least squares sense.
+fn estimate_bearing(
+    time_differences_of_arrival: &[TimeDifferenceOfArrival],
+    microphone_positions: &[Point3<Head>],
+    head_to_robot: Isometry3<Head, Robot>,
+    speed_of_sound: f32,
+    minimum_coherence: f32,
+) -> Option<Orientation2<Robot>> {
+    let mut normal_matrix = Matrix2::zeros();
+    let mut normal_vector = NalgebraVector2::zeros();
+    for time_difference_of_arrival in time_differences_of_arrival
+        .iter()
+        .filter(|time_difference| time_difference.coherence >= minimum_coherence)
+    {
+        let first = microphone_positions.get(time_difference_of_arrival.first_channel)?;
+        let second = microphone_positions.get(time_difference_of_arrival.second_channel)?;
+        let baseline = (head_to_robot * (*second - *first)).xy().inner;
+        let weight = time_difference_of_arrival.coherence;
+        normal_matrix += baseline * baseline.transpose() * weight;
+        normal_vector += baseline * (speed_of_sound * time_difference_of_arrival.delay * weight);
+    }
+    let direction = normal_matrix.try_inverse()? * normal_vector;
+    if direction.norm() < f32::EPSILON {
+        return None;
+    }
+    Some(Orientation2::from_vector(Vector2::wrap(direction)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_4;
+
+    use approx::assert_relative_eq;
+    use linear_algebra::{point, Vector3};
+
+    use super::*;
+
+    #[test]
+    fn bearing_of_plane_wave_is_recovered() {
+        let microphone_positions = vec![
+            point![-0.0195, 0.0606, 0.0710],
+            point![-0.0195, -0.0606, 0.0710],
+            point![0.0253, 0.0155, 0.0983],
+            point![0.0253, -0.0155, 0.0983],
+        ];
+        let speed_of_sound = 343.0;
+        let source_angle = 3.0 * FRAC_PI_4;
+        let source_direction = vector![source_angle.cos(), source_angle.sin(), 0.0];
+        let mut time_differences_of_arrival = Vec::new();
+        for first_channel in 0..4 {
+            for second_channel in first_channel + 1..4 {
+                let baseline: Vector3<Head> =
+                    microphone_positions[second_channel] - microphone_positions[first_channel];
+                time_differences_of_arrival.push(TimeDifferenceOfArrival {
+                    first_channel,
+                    second_channel,
+                    delay: baseline.dot(&source_direction) / speed_of_sound,
+                    coherence: 1.0,
+                });
+            }
+        }
+
+        let bearing = estimate_bearing(
+            &time_differences_of_arrival,
+            &microphone_positions,
+            Isometry3::identity(),
+            speed_of_sound,
+            0.5,
+        )
+        .expect("bearing should be estimated");
+
+        assert_relative_eq!(bearing.angle(), source_angle, epsilon = 1e-3);
+    }
+}
@@ -11,11 +11,13 @@ use types::{
     calibration::CalibrationCommand,
     fall_state::FallState,
     filtered_game_controller_state::FilteredGameControllerState,
+    filtered_whistle::FilteredWhistle,
     kick_decision::KickDecision,
     obstacles::Obstacle,
     primary_state::PrimaryState,
     roles::Role,
     rule_obstacles::RuleObstacle,
+    thermal_state::ThermalState,
     world_state::{BallState, RobotState, WorldState},
 };
 
@@ -35,6 +37,7 @@ pub struct CycleContext {
         Input<Option<FilteredGameControllerState>, "filtered_game_controller_state?">,
     ground_to_field: Input<Option<Isometry2<Ground, Field>>, "ground_to_field?">,
     suggested_search_position: Input<Option<Point2<Field>>, "suggested_search_position?">,
+    incoming_pass: Input<Option<Point2<Field>>, "incoming_pass?">,
     kick_decisions: Input<Option<Vec<KickDecision>>, "kick_decisions?">,
     instant_kick_decisions: Input<Option<Vec<KickDecision>>, "instant_kick_decisions?">,
     ground_to_upcoming_support:
@@ -51,6 +54,8 @@ pub struct CycleContext {
     position_of_interest: Input<Point2<Ground>, "position_of_interest">,
     calibration_command: Input<Option<CalibrationCommand>, "calibration_command?">,
     stand_up_count: CyclerState<u32, "stand_up_count">,
+    thermal_state: Input<ThermalState, "thermal_state">,
+    filtered_whistle: Input<FilteredWhistle, "filtered_whistle">,
 }
 
 #[context]
@@ -74,12 +79,14 @@ impl WorldStateComposer {
             player_number: *context.player_number,
             ground_to_upcoming_support: *context.ground_to_upcoming_support,
             stand_up_count: *context.stand_up_count,
+            needs_cooling: context.thermal_state.needs_cooling,
         };
 
         let world_state = WorldState {
             ball: context.ball.copied(),
             rule_ball: context.rule_ball.copied(),
             suggested_search_position: context.suggested_search_position.copied(),
+            incoming_pass: context.incoming_pass.copied(),
             obstacles: context.obstacles.clone(),
             rule_obstacles: context.rule_obstacles.clone(),
             position_of_interest: *context.position_of_interest,
@@ -89,6 +96,7 @@ impl WorldStateComposer {
             filtered_game_controller_state: context.filtered_game_controller_state.cloned(),
             hypothetical_ball_positions: context.hypothetical_ball_position.clone(),
             calibration_command: context.calibration_command.copied(),
+            whistle_bearing: context.filtered_whistle.bearing,
         };
 
         Ok(MainOutputs {
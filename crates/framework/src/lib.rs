@@ -7,6 +7,7 @@ mod panic;
 mod parameters;
 mod perception_databases;
 mod perception_input;
+mod recorded_output_visitor;
 mod recording_index;
 mod recording_trigger;
 
@@ -19,5 +20,6 @@ pub use panic::deserialize_not_implemented;
 pub use parameters::Parameters;
 pub use perception_databases::PerceptionDatabases;
 pub use perception_input::PerceptionInput;
+pub use recorded_output_visitor::RecordedOutputVisitor;
 pub use recording_index::{RecordingFrame, RecordingIndex, Timing};
 pub use recording_trigger::RecordingTrigger;
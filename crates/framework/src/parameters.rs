@@ -8,4 +8,6 @@ pub struct Parameters {
     pub recording_intervals: HashMap<String, usize>,
     pub hardware_parameters: PathBuf,
     pub parameters_directory: PathBuf,
+    #[serde(default)]
+    pub hot_reload_parameters: bool,
 }
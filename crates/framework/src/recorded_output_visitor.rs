@@ -0,0 +1,14 @@
+use serde::Serialize;
+
+/// Receives the main outputs stored in a recording frame
+///
+/// These are the outputs of setup nodes of the recorded cycler and the outputs of other cyclers
+/// as they were read as inputs when the frame was recorded.
+pub trait RecordedOutputVisitor {
+    fn visit<DataType: Serialize>(
+        &mut self,
+        cycler_instance: &str,
+        path: &str,
+        value: &DataType,
+    ) -> color_eyre::Result<()>;
+}
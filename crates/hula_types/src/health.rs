@@ -0,0 +1,141 @@
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use crate::robot_state::JointsArray;
+
+/// Hardware health of one combination of head and body over several sessions
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HealthLog {
+    pub body_id: String,
+    pub head_id: String,
+    pub sessions: Vec<SessionHealth>,
+}
+
+impl HealthLog {
+    pub fn file_name(body_id: &str, head_id: &str) -> String {
+        format!("{body_id}_{head_id}.json")
+    }
+
+    pub fn summary(&self) -> HealthSummary {
+        HealthSummary::from_sessions(&self.sessions)
+    }
+}
+
+/// Hardware health collected during a single run of the HULK
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct SessionHealth {
+    pub started_at: SystemTime,
+    pub updated_at: SystemTime,
+    pub maximum_temperatures: JointsArray,
+    /// Highest joint status reported by LoLA, non-zero values indicate overheating motors
+    pub maximum_status: JointsArray,
+    pub falls: u32,
+    pub charging_cycles: u32,
+    pub minimum_battery_charge: f32,
+    pub maximum_battery_temperature: f32,
+    /// Number of cycles with implausible force sensitive resistor readings
+    pub force_sensitive_resistor_anomalies: u32,
+}
+
+impl SessionHealth {
+    pub fn new(now: SystemTime) -> Self {
+        Self {
+            started_at: now,
+            updated_at: now,
+            maximum_temperatures: JointsArray::default(),
+            maximum_status: JointsArray::default(),
+            falls: 0,
+            charging_cycles: 0,
+            minimum_battery_charge: 1.0,
+            maximum_battery_temperature: 0.0,
+            force_sensitive_resistor_anomalies: 0,
+        }
+    }
+}
+
+/// Hardware health aggregated over any number of sessions
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+pub struct HealthSummary {
+    pub sessions: usize,
+    pub last_session: Option<SystemTime>,
+    pub maximum_temperatures: JointsArray,
+    pub maximum_status: JointsArray,
+    pub falls: u32,
+    pub charging_cycles: u32,
+    pub minimum_battery_charge: Option<f32>,
+    pub force_sensitive_resistor_anomalies: u32,
+}
+
+impl HealthSummary {
+    pub fn from_sessions<'a>(sessions: impl IntoIterator<Item = &'a SessionHealth>) -> Self {
+        sessions
+            .into_iter()
+            .fold(Self::default(), |summary, session| Self {
+                sessions: summary.sessions + 1,
+                last_session: summary.last_session.max(Some(session.updated_at)),
+                maximum_temperatures: summary
+                    .maximum_temperatures
+                    .max(session.maximum_temperatures),
+                maximum_status: summary.maximum_status.max(session.maximum_status),
+                falls: summary.falls + session.falls,
+                charging_cycles: summary.charging_cycles + session.charging_cycles,
+                minimum_battery_charge: Some(
+                    summary
+                        .minimum_battery_charge
+                        .map_or(session.minimum_battery_charge, |charge| {
+                            charge.min(session.minimum_battery_charge)
+                        }),
+                ),
+                force_sensitive_resistor_anomalies: summary.force_sensitive_resistor_anomalies
+                    + session.force_sensitive_resistor_anomalies,
+            })
+    }
+
+    pub fn maximum_temperature(&self) -> f32 {
+        self.maximum_temperatures
+            .into_lola()
+            .into_iter()
+            .fold(0.0, f32::max)
+    }
+
+    pub fn maximum_status(&self) -> f32 {
+        self.maximum_status
+            .into_lola()
+            .into_iter()
+            .fold(0.0, f32::max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::*;
+
+    #[test]
+    fn sessions_are_aggregated() {
+        let mut first = SessionHealth::new(UNIX_EPOCH);
+        first.falls = 2;
+        first.minimum_battery_charge = 0.4;
+        first.maximum_temperatures = JointsArray::from_lola([40.0; 25]);
+        let mut second = SessionHealth::new(UNIX_EPOCH + Duration::from_secs(60));
+        second.falls = 1;
+        second.minimum_battery_charge = 0.7;
+        let mut temperatures = [30.0; 25];
+        temperatures[10] = 65.0;
+        second.maximum_temperatures = JointsArray::from_lola(temperatures);
+
+        let summary = HealthSummary::from_sessions(&[first, second]);
+
+        assert_eq!(summary.sessions, 2);
+        assert_eq!(summary.falls, 3);
+        assert_eq!(summary.minimum_battery_charge, Some(0.4));
+        assert_eq!(
+            summary.last_session,
+            Some(UNIX_EPOCH + Duration::from_secs(60))
+        );
+        assert_eq!(summary.maximum_temperature(), 65.0);
+        assert_eq!(summary.maximum_temperatures.into_lola()[0], 40.0);
+    }
+}
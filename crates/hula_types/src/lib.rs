@@ -1,4 +1,5 @@
 pub mod control_frame;
 pub mod hardware;
+pub mod health;
 pub mod lola;
 pub mod robot_state;
@@ -181,6 +181,45 @@ pub struct JointsArray {
 }
 
 impl JointsArray {
+    pub fn from_lola(values: [f32; 25]) -> Self {
+        Self {
+            head_yaw: values[0],
+            head_pitch: values[1],
+            left_shoulder_pitch: values[2],
+            left_shoulder_roll: values[3],
+            left_elbow_yaw: values[4],
+            left_elbow_roll: values[5],
+            left_wrist_yaw: values[6],
+            left_hip_yaw_pitch: values[7],
+            left_hip_roll: values[8],
+            left_hip_pitch: values[9],
+            left_knee_pitch: values[10],
+            left_ankle_pitch: values[11],
+            left_ankle_roll: values[12],
+            right_hip_roll: values[13],
+            right_hip_pitch: values[14],
+            right_knee_pitch: values[15],
+            right_ankle_pitch: values[16],
+            right_ankle_roll: values[17],
+            right_shoulder_pitch: values[18],
+            right_shoulder_roll: values[19],
+            right_elbow_yaw: values[20],
+            right_elbow_roll: values[21],
+            right_wrist_yaw: values[22],
+            left_hand: values[23],
+            right_hand: values[24],
+        }
+    }
+
+    /// Element-wise maximum of both arrays
+    pub fn max(self, other: Self) -> Self {
+        let mut values = self.into_lola();
+        for (value, other) in values.iter_mut().zip(other.into_lola()) {
+            *value = value.max(other);
+        }
+        Self::from_lola(values)
+    }
+
     pub fn into_lola(self) -> [f32; 25] {
         [
             self.head_yaw,
@@ -1,6 +1,6 @@
 use code_generation::{generate, write_to_file::WriteToFile, ExecutionMode};
 use color_eyre::eyre::{Result, WrapErr};
-use hulk_manifest::collect_hulk_cyclers;
+use hulk_manifest::{collect_hulk_cyclers, manifest_path, MANIFEST_ENVIRONMENT_VARIABLE};
 use source_analyzer::{pretty::to_string_pretty, structs::Structs};
 
 fn main() -> Result<()> {
@@ -9,6 +9,8 @@ fn main() -> Result<()> {
         .cyclers
         .retain(|cycler| cycler.name != "ObjectDetection");
 
+    println!("cargo:rerun-if-env-changed={MANIFEST_ENVIRONMENT_VARIABLE}");
+    println!("cargo:rerun-if-changed={}", manifest_path("..").display());
     for path in cyclers.watch_paths() {
         println!("cargo:rerun-if-changed={}", path.display());
     }
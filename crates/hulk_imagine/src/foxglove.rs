@@ -0,0 +1,791 @@
+//! Conversion of well-known outputs into Foxglove schemas
+//!
+//! Messages are JSON encoded and described by the JSON schemas in `schemas/foxglove`, which
+//! allows generic MCAP viewers to display images, transforms and scene primitives without
+//! knowing about our types.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use clap::ValueEnum;
+use color_eyre::{eyre::WrapErr, Result};
+use coordinate_systems::{Field, Ground, Robot};
+use geometry::{arc::Arc, direction::AngleTo, line_segment::LineSegment};
+use linear_algebra::{Isometry2, Isometry3, Orientation2, Point2};
+use rmp_serde::from_slice;
+use serde::{de::DeserializeOwned, Serialize, Serializer};
+use types::{
+    ball_detection::BallPercept, ball_position::BallPosition, jpeg::JpegImage, line_data::LineData,
+    motion_command::MotionCommand, obstacles::Obstacle, planned_path::PathSegment,
+    robot_kinematics::RobotKinematics, ycbcr422_image::YCbCr422Image,
+};
+
+const BALL_DIAMETER: f64 = 0.1;
+const OBSTACLE_HEIGHT: f64 = 0.6;
+const LINE_THICKNESS: f64 = 0.02;
+const ARC_SAMPLES_PER_RADIAN: f32 = 8.0;
+
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub enum ImageFormat {
+    /// `foxglove.RawImage` with `yuyv` encoding, lossless but large
+    #[default]
+    Raw,
+    /// `foxglove.CompressedImage` with JPEG data
+    Jpeg,
+}
+
+pub struct Schema {
+    pub name: &'static str,
+    pub data: &'static str,
+}
+
+pub const RAW_IMAGE: Schema = Schema {
+    name: "foxglove.RawImage",
+    data: include_str!("../schemas/foxglove/RawImage.json"),
+};
+pub const COMPRESSED_IMAGE: Schema = Schema {
+    name: "foxglove.CompressedImage",
+    data: include_str!("../schemas/foxglove/CompressedImage.json"),
+};
+pub const FRAME_TRANSFORM: Schema = Schema {
+    name: "foxglove.FrameTransform",
+    data: include_str!("../schemas/foxglove/FrameTransform.json"),
+};
+pub const FRAME_TRANSFORMS: Schema = Schema {
+    name: "foxglove.FrameTransforms",
+    data: include_str!("../schemas/foxglove/FrameTransforms.json"),
+};
+pub const SCENE_UPDATE: Schema = Schema {
+    name: "foxglove.SceneUpdate",
+    data: include_str!("../schemas/foxglove/SceneUpdate.json"),
+};
+
+pub struct TypedMessage {
+    pub topic: String,
+    pub schema: &'static Schema,
+    pub data: Vec<u8>,
+}
+
+impl TypedMessage {
+    fn new(topic: String, schema: &'static Schema, message: &impl Serialize) -> Result<Self> {
+        Ok(Self {
+            topic,
+            schema,
+            data: serde_json::to_vec(message).wrap_err("failed to serialize foxglove message")?,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Serialize)]
+struct Time {
+    sec: u32,
+    nsec: u32,
+}
+
+impl From<SystemTime> for Time {
+    fn from(time: SystemTime) -> Self {
+        let duration = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+        Self {
+            sec: duration.as_secs() as u32,
+            nsec: duration.subsec_nanos(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default, Serialize)]
+struct Vector3 {
+    x: f64,
+    y: f64,
+    z: f64,
+}
+
+impl Vector3 {
+    fn new(x: f32, y: f32, z: f32) -> Self {
+        Self {
+            x: x.into(),
+            y: y.into(),
+            z: z.into(),
+        }
+    }
+
+    fn on_ground(point: Point2<Ground>) -> Self {
+        Self::new(point.x(), point.y(), 0.0)
+    }
+}
+
+#[derive(Clone, Copy, Serialize)]
+struct Quaternion {
+    x: f64,
+    y: f64,
+    z: f64,
+    w: f64,
+}
+
+impl Default for Quaternion {
+    fn default() -> Self {
+        Self {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+            w: 1.0,
+        }
+    }
+}
+
+impl Quaternion {
+    fn from_yaw(yaw: f32) -> Self {
+        let half_yaw = f64::from(yaw) / 2.0;
+        Self {
+            z: half_yaw.sin(),
+            w: half_yaw.cos(),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default, Serialize)]
+struct Pose {
+    position: Vector3,
+    orientation: Quaternion,
+}
+
+impl Pose {
+    fn at(position: Vector3) -> Self {
+        Self {
+            position,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Clone, Copy, Serialize)]
+struct Color {
+    r: f64,
+    g: f64,
+    b: f64,
+    a: f64,
+}
+
+impl Color {
+    const WHITE: Self = Self::opaque(1.0, 1.0, 1.0);
+    const ORANGE: Self = Self::opaque(1.0, 0.5, 0.0);
+    const RED: Self = Self::opaque(1.0, 0.0, 0.0);
+    const BLUE: Self = Self::opaque(0.0, 0.4, 1.0);
+
+    const fn opaque(r: f64, g: f64, b: f64) -> Self {
+        Self { r, g, b, a: 1.0 }
+    }
+}
+
+fn serialize_base64<S: Serializer>(data: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&STANDARD.encode(data))
+}
+
+#[derive(Serialize)]
+struct RawImage {
+    timestamp: Time,
+    frame_id: String,
+    width: u32,
+    height: u32,
+    encoding: &'static str,
+    step: u32,
+    #[serde(serialize_with = "serialize_base64")]
+    data: Vec<u8>,
+}
+
+#[derive(Serialize)]
+struct CompressedImage {
+    timestamp: Time,
+    frame_id: String,
+    #[serde(serialize_with = "serialize_base64")]
+    data: Vec<u8>,
+    format: &'static str,
+}
+
+#[derive(Serialize)]
+struct FrameTransform {
+    timestamp: Time,
+    parent_frame_id: String,
+    child_frame_id: String,
+    translation: Vector3,
+    rotation: Quaternion,
+}
+
+#[derive(Serialize)]
+struct FrameTransforms {
+    transforms: Vec<FrameTransform>,
+}
+
+#[derive(Serialize)]
+struct SceneUpdate {
+    entities: Vec<SceneEntity>,
+}
+
+#[derive(Serialize)]
+struct SceneEntity {
+    timestamp: Time,
+    frame_id: String,
+    id: String,
+    lifetime: Time,
+    frame_locked: bool,
+    lines: Vec<LinePrimitive>,
+    spheres: Vec<SpherePrimitive>,
+    cylinders: Vec<CylinderPrimitive>,
+}
+
+impl SceneEntity {
+    /// Entity in the ground frame which replaces the previous entity of the same topic
+    fn on_ground(timestamp: Time, id: String) -> Self {
+        Self {
+            timestamp,
+            frame_id: "ground".to_string(),
+            id,
+            lifetime: Time { sec: 0, nsec: 0 },
+            frame_locked: true,
+            lines: Vec::new(),
+            spheres: Vec::new(),
+            cylinders: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Serialize)]
+#[serde(into = "u8")]
+enum LineType {
+    Strip,
+    List,
+}
+
+impl From<LineType> for u8 {
+    fn from(line_type: LineType) -> Self {
+        match line_type {
+            LineType::Strip => 0,
+            LineType::List => 2,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct LinePrimitive {
+    r#type: LineType,
+    pose: Pose,
+    thickness: f64,
+    scale_invariant: bool,
+    points: Vec<Vector3>,
+    color: Color,
+    colors: Vec<Color>,
+    indices: Vec<u32>,
+}
+
+impl LinePrimitive {
+    fn new(r#type: LineType, points: Vec<Vector3>, color: Color) -> Self {
+        Self {
+            r#type,
+            pose: Pose::default(),
+            thickness: LINE_THICKNESS,
+            scale_invariant: false,
+            points,
+            color,
+            colors: Vec::new(),
+            indices: Vec::new(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct SpherePrimitive {
+    pose: Pose,
+    size: Vector3,
+    color: Color,
+}
+
+#[derive(Serialize)]
+struct CylinderPrimitive {
+    pose: Pose,
+    size: Vector3,
+    bottom_scale: f64,
+    top_scale: f64,
+    color: Color,
+}
+
+/// Converts a MessagePack encoded output into typed messages, if the output is known
+///
+/// `topic` is the full topic of the output, e.g. `VisionTop.main_outputs.image`.
+pub fn to_typed_messages(
+    topic: &str,
+    data: &[u8],
+    time: SystemTime,
+    image_format: ImageFormat,
+) -> Result<Vec<TypedMessage>> {
+    let Some((cycler, path)) = topic.split_once('.') else {
+        return Ok(Vec::new());
+    };
+    let timestamp = Time::from(time);
+    let typed_topic = |name: &str| format!("/{cycler}/{name}");
+
+    let message = match path {
+        "main_outputs.image" => {
+            let image: YCbCr422Image = decode(topic, data)?;
+            let frame_id = camera_frame(cycler);
+            match image_format {
+                ImageFormat::Raw => TypedMessage::new(
+                    typed_topic("image"),
+                    &RAW_IMAGE,
+                    &raw_image(&image, timestamp, frame_id),
+                )?,
+                ImageFormat::Jpeg => {
+                    let jpeg =
+                        JpegImage::try_from(&image).wrap_err("failed to encode image as JPEG")?;
+                    TypedMessage::new(
+                        typed_topic("image/compressed"),
+                        &COMPRESSED_IMAGE,
+                        &CompressedImage {
+                            timestamp,
+                            frame_id,
+                            data: jpeg.data,
+                            format: "jpeg",
+                        },
+                    )?
+                }
+            }
+        }
+        "main_outputs.ground_to_field" => {
+            let Some(ground_to_field): Option<Isometry2<Ground, Field>> = decode(topic, data)?
+            else {
+                return Ok(Vec::new());
+            };
+            let translation = ground_to_field.translation();
+            TypedMessage::new(
+                typed_topic("ground_to_field"),
+                &FRAME_TRANSFORM,
+                &FrameTransform {
+                    timestamp,
+                    parent_frame_id: "field".to_string(),
+                    child_frame_id: "ground".to_string(),
+                    translation: Vector3::new(translation.x(), translation.y(), 0.0),
+                    rotation: Quaternion::from_yaw(ground_to_field.orientation().angle()),
+                },
+            )?
+        }
+        "main_outputs.robot_to_ground" => {
+            let Some(robot_to_ground): Option<Isometry3<Robot, Ground>> = decode(topic, data)?
+            else {
+                return Ok(Vec::new());
+            };
+            TypedMessage::new(
+                typed_topic("robot_to_ground"),
+                &FRAME_TRANSFORM,
+                &frame_transform(timestamp, "ground", "robot", &robot_to_ground),
+            )?
+        }
+        "main_outputs.robot_kinematics" => {
+            let kinematics: RobotKinematics = decode(topic, data)?;
+            TypedMessage::new(
+                typed_topic("robot_kinematics"),
+                &FRAME_TRANSFORMS,
+                &kinematic_chain(timestamp, &kinematics),
+            )?
+        }
+        "main_outputs.line_data" => {
+            let Some(line_data): Option<LineData> = decode(topic, data)? else {
+                return Ok(Vec::new());
+            };
+            let mut entity = SceneEntity::on_ground(timestamp, typed_topic("lines"));
+            entity.lines.push(LinePrimitive::new(
+                LineType::List,
+                line_data
+                    .lines
+                    .iter()
+                    .flat_map(|LineSegment(start, end)| {
+                        [Vector3::on_ground(*start), Vector3::on_ground(*end)]
+                    })
+                    .collect(),
+                Color::WHITE,
+            ));
+            scene_update(typed_topic("lines"), entity)?
+        }
+        "main_outputs.balls" => {
+            let balls: Option<Vec<BallPercept>> = decode(topic, data)?;
+            let mut entity = SceneEntity::on_ground(timestamp, typed_topic("balls"));
+            entity.spheres = balls
+                .unwrap_or_default()
+                .iter()
+                .map(|ball| {
+                    let mean = ball.percept_in_ground.mean;
+                    ball_sphere(Vector3::new(mean.x, mean.y, 0.0), Color::ORANGE)
+                })
+                .collect();
+            scene_update(typed_topic("balls"), entity)?
+        }
+        "main_outputs.ball_position" => {
+            let ball_position: Option<BallPosition<Ground>> = decode(topic, data)?;
+            let mut entity = SceneEntity::on_ground(timestamp, typed_topic("ball_position"));
+            entity.spheres = ball_position
+                .into_iter()
+                .map(|ball| ball_sphere(Vector3::on_ground(ball.position), Color::RED))
+                .collect();
+            scene_update(typed_topic("ball_position"), entity)?
+        }
+        "main_outputs.obstacles" => {
+            let obstacles: Vec<Obstacle> = decode(topic, data)?;
+            let mut entity = SceneEntity::on_ground(timestamp, typed_topic("obstacles"));
+            entity.cylinders = obstacles.iter().map(obstacle_cylinder).collect();
+            scene_update(typed_topic("obstacles"), entity)?
+        }
+        "main_outputs.motion_command" => {
+            let motion_command: MotionCommand = decode(topic, data)?;
+            let mut entity = SceneEntity::on_ground(timestamp, typed_topic("path"));
+            if let MotionCommand::Walk { path, .. } = motion_command {
+                entity.lines.push(LinePrimitive::new(
+                    LineType::Strip,
+                    path_points(&path),
+                    Color::BLUE,
+                ));
+            }
+            scene_update(typed_topic("path"), entity)?
+        }
+        _ => return Ok(Vec::new()),
+    };
+    Ok(vec![message])
+}
+
+fn decode<T: DeserializeOwned>(topic: &str, data: &[u8]) -> Result<T> {
+    from_slice(data).wrap_err_with(|| format!("failed to decode `{topic}`"))
+}
+
+fn camera_frame(cycler: &str) -> String {
+    match cycler {
+        "VisionTop" => "top_camera".to_string(),
+        "VisionBottom" => "bottom_camera".to_string(),
+        _ => cycler.to_lowercase(),
+    }
+}
+
+fn raw_image(image: &YCbCr422Image, timestamp: Time, frame_id: String) -> RawImage {
+    // YCbCr 4:2:2 with Cb before Cr is what Foxglove calls `yuyv`
+    let data = image
+        .buffer()
+        .iter()
+        .flat_map(|pixel| [pixel.y1, pixel.cb, pixel.y2, pixel.cr])
+        .collect();
+    RawImage {
+        timestamp,
+        frame_id,
+        width: image.width(),
+        height: image.height(),
+        encoding: "yuyv",
+        step: image.width() * 2,
+        data,
+    }
+}
+
+fn frame_transform<From, To>(
+    timestamp: Time,
+    parent_frame_id: &str,
+    child_frame_id: &str,
+    child_to_parent: &Isometry3<From, To>,
+) -> FrameTransform {
+    let translation = child_to_parent.inner.translation.vector;
+    let rotation = child_to_parent.inner.rotation;
+    FrameTransform {
+        timestamp,
+        parent_frame_id: parent_frame_id.to_string(),
+        child_frame_id: child_frame_id.to_string(),
+        translation: Vector3::new(translation.x, translation.y, translation.z),
+        rotation: Quaternion {
+            x: rotation.i.into(),
+            y: rotation.j.into(),
+            z: rotation.k.into(),
+            w: rotation.w.into(),
+        },
+    }
+}
+
+/// All links of the kinematic chain as children of the robot frame
+fn kinematic_chain(timestamp: Time, kinematics: &RobotKinematics) -> FrameTransforms {
+    FrameTransforms {
+        transforms: vec![
+            frame_transform(timestamp, "robot", "neck", &kinematics.head.neck_to_robot),
+            frame_transform(timestamp, "robot", "head", &kinematics.head.head_to_robot),
+            frame_transform(
+                timestamp,
+                "robot",
+                "torso",
+                &kinematics.torso.torso_to_robot,
+            ),
+            frame_transform(
+                timestamp,
+                "robot",
+                "left_shoulder",
+                &kinematics.left_arm.shoulder_to_robot,
+            ),
+            frame_transform(
+                timestamp,
+                "robot",
+                "left_upper_arm",
+                &kinematics.left_arm.upper_arm_to_robot,
+            ),
+            frame_transform(
+                timestamp,
+                "robot",
+                "left_elbow",
+                &kinematics.left_arm.elbow_to_robot,
+            ),
+            frame_transform(
+                timestamp,
+                "robot",
+                "left_forearm",
+                &kinematics.left_arm.forearm_to_robot,
+            ),
+            frame_transform(
+                timestamp,
+                "robot",
+                "left_wrist",
+                &kinematics.left_arm.wrist_to_robot,
+            ),
+            frame_transform(
+                timestamp,
+                "robot",
+                "right_shoulder",
+                &kinematics.right_arm.shoulder_to_robot,
+            ),
+            frame_transform(
+                timestamp,
+                "robot",
+                "right_upper_arm",
+                &kinematics.right_arm.upper_arm_to_robot,
+            ),
+            frame_transform(
+                timestamp,
+                "robot",
+                "right_elbow",
+                &kinematics.right_arm.elbow_to_robot,
+            ),
+            frame_transform(
+                timestamp,
+                "robot",
+                "right_forearm",
+                &kinematics.right_arm.forearm_to_robot,
+            ),
+            frame_transform(
+                timestamp,
+                "robot",
+                "right_wrist",
+                &kinematics.right_arm.wrist_to_robot,
+            ),
+            frame_transform(
+                timestamp,
+                "robot",
+                "left_pelvis",
+                &kinematics.left_leg.pelvis_to_robot,
+            ),
+            frame_transform(
+                timestamp,
+                "robot",
+                "left_hip",
+                &kinematics.left_leg.hip_to_robot,
+            ),
+            frame_transform(
+                timestamp,
+                "robot",
+                "left_thigh",
+                &kinematics.left_leg.thigh_to_robot,
+            ),
+            frame_transform(
+                timestamp,
+                "robot",
+                "left_tibia",
+                &kinematics.left_leg.tibia_to_robot,
+            ),
+            frame_transform(
+                timestamp,
+                "robot",
+                "left_ankle",
+                &kinematics.left_leg.ankle_to_robot,
+            ),
+            frame_transform(
+                timestamp,
+                "robot",
+                "left_foot",
+                &kinematics.left_leg.foot_to_robot,
+            ),
+            frame_transform(
+                timestamp,
+                "robot",
+                "left_sole",
+                &kinematics.left_leg.sole_to_robot,
+            ),
+            frame_transform(
+                timestamp,
+                "robot",
+                "right_pelvis",
+                &kinematics.right_leg.pelvis_to_robot,
+            ),
+            frame_transform(
+                timestamp,
+                "robot",
+                "right_hip",
+                &kinematics.right_leg.hip_to_robot,
+            ),
+            frame_transform(
+                timestamp,
+                "robot",
+                "right_thigh",
+                &kinematics.right_leg.thigh_to_robot,
+            ),
+            frame_transform(
+                timestamp,
+                "robot",
+                "right_tibia",
+                &kinematics.right_leg.tibia_to_robot,
+            ),
+            frame_transform(
+                timestamp,
+                "robot",
+                "right_ankle",
+                &kinematics.right_leg.ankle_to_robot,
+            ),
+            frame_transform(
+                timestamp,
+                "robot",
+                "right_foot",
+                &kinematics.right_leg.foot_to_robot,
+            ),
+            frame_transform(
+                timestamp,
+                "robot",
+                "right_sole",
+                &kinematics.right_leg.sole_to_robot,
+            ),
+        ],
+    }
+}
+
+fn scene_update(topic: String, entity: SceneEntity) -> Result<TypedMessage> {
+    TypedMessage::new(
+        topic,
+        &SCENE_UPDATE,
+        &SceneUpdate {
+            entities: vec![entity],
+        },
+    )
+}
+
+fn ball_sphere(position: Vector3, color: Color) -> SpherePrimitive {
+    SpherePrimitive {
+        pose: Pose::at(Vector3 {
+            z: BALL_DIAMETER / 2.0,
+            ..position
+        }),
+        size: Vector3 {
+            x: BALL_DIAMETER,
+            y: BALL_DIAMETER,
+            z: BALL_DIAMETER,
+        },
+        color,
+    }
+}
+
+/// Truncated cone from the radius at foot height to the radius at hip height
+fn obstacle_cylinder(obstacle: &Obstacle) -> CylinderPrimitive {
+    let radius = obstacle
+        .radius_at_foot_height
+        .max(obstacle.radius_at_hip_height)
+        .max(f32::EPSILON);
+    let diameter = f64::from(2.0 * radius);
+    CylinderPrimitive {
+        pose: Pose::at(Vector3 {
+            z: OBSTACLE_HEIGHT / 2.0,
+            ..Vector3::on_ground(obstacle.position)
+        }),
+        size: Vector3 {
+            x: diameter,
+            y: diameter,
+            z: OBSTACLE_HEIGHT,
+        },
+        bottom_scale: (obstacle.radius_at_foot_height / radius).into(),
+        top_scale: (obstacle.radius_at_hip_height / radius).into(),
+        color: Color {
+            a: 0.5,
+            ..Color::RED
+        },
+    }
+}
+
+fn path_points(path: &[PathSegment]) -> Vec<Vector3> {
+    path.iter()
+        .flat_map(|segment| match segment {
+            PathSegment::LineSegment(LineSegment(start, end)) => {
+                vec![Vector3::on_ground(*start), Vector3::on_ground(*end)]
+            }
+            PathSegment::Arc(arc) => arc_points(arc),
+        })
+        .collect()
+}
+
+fn arc_points(arc: &Arc<Ground>) -> Vec<Vector3> {
+    let angle = arc.start.angle_to(arc.end, arc.direction);
+    let number_of_samples = ((angle * ARC_SAMPLES_PER_RADIAN).ceil() as usize).clamp(1, 64);
+    (0..=number_of_samples)
+        .map(|sample| {
+            let angle = arc.start.angle()
+                + arc.direction.angle_sign::<f32>() * angle * sample as f32
+                    / number_of_samples as f32;
+            Vector3::on_ground(arc.circle.point_at_angle(Orientation2::new(angle)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use geometry::{circle::Circle, direction::Direction};
+    use linear_algebra::{point, vector};
+    use serde_json::Value;
+
+    use super::*;
+
+    #[test]
+    fn ground_to_field_becomes_frame_transform() {
+        let ground_to_field = Isometry2::<Ground, Field>::from_parts(vector![1.0, 2.0], FRAC_PI_2);
+        let data = rmp_serde::to_vec_named(&Some(ground_to_field)).unwrap();
+
+        let messages = to_typed_messages(
+            "Control.main_outputs.ground_to_field",
+            &data,
+            UNIX_EPOCH,
+            ImageFormat::Raw,
+        )
+        .unwrap();
+
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].topic, "/Control/ground_to_field");
+        assert_eq!(messages[0].schema.name, "foxglove.FrameTransform");
+        let message: Value = serde_json::from_slice(&messages[0].data).unwrap();
+        assert_eq!(message["parent_frame_id"], "field");
+        assert_eq!(message["translation"]["y"], 2.0);
+        assert!((message["rotation"]["z"].as_f64().unwrap() - 0.5_f64.sqrt()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn counterclockwise_quarter_arc_is_sampled_from_start_to_end() {
+        let arc = Arc::<Ground>::new(
+            Circle {
+                center: point![0.0, 0.0],
+                radius: 1.0,
+            },
+            Orientation2::new(0.0),
+            Orientation2::new(FRAC_PI_2),
+            Direction::Counterclockwise,
+        );
+
+        let points = arc_points(&arc);
+
+        let first = points.first().unwrap();
+        let last = points.last().unwrap();
+        assert!((first.x - 1.0).abs() < 1e-6 && first.y.abs() < 1e-6);
+        assert!(last.x.abs() < 1e-6 && (last.y - 1.0).abs() < 1e-6);
+        assert!(points.iter().all(|point| point.y >= -1e-6));
+    }
+}
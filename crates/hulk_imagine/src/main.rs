@@ -26,11 +26,13 @@ use structs::Parameters;
 use crate::execution::Replayer;
 use crate::{
     extractor_hardware_interface::{ExtractorHardwareInterface, HardwareInterface},
+    foxglove::ImageFormat,
     mcap_converter::McapConverter,
     write_to_mcap::write_to_mcap,
 };
 
 mod extractor_hardware_interface;
+mod foxglove;
 mod mcap_converter;
 mod serializer;
 mod write_to_mcap;
@@ -45,6 +47,9 @@ struct CommandlineArguments {
     #[arg(required = true)]
     output_folder: String,
     parameters_directory: Option<String>,
+    /// Schema of the typed camera image channels
+    #[arg(long, value_enum, default_value_t)]
+    image_format: ImageFormat,
 }
 
 fn main() -> Result<()> {
@@ -146,13 +151,20 @@ fn main() -> Result<()> {
     let vision_top_receiver = replayer.vision_top_receiver();
     let vision_bottom_receiver = replayer.vision_bottom_receiver();
 
-    write_to_mcap(&mut replayer, "Audio", &mut mcap_converter, audio_receiver)
-        .wrap_err("failed to write audio data to mcap")?;
+    write_to_mcap(
+        &mut replayer,
+        "Audio",
+        &mut mcap_converter,
+        audio_receiver,
+        arguments.image_format,
+    )
+    .wrap_err("failed to write audio data to mcap")?;
     write_to_mcap(
         &mut replayer,
         "Control",
         &mut mcap_converter,
         control_receiver,
+        arguments.image_format,
     )
     .wrap_err("failed to write control data to mcap")?;
     write_to_mcap(
@@ -160,6 +172,7 @@ fn main() -> Result<()> {
         "VisionBottom",
         &mut mcap_converter,
         vision_bottom_receiver,
+        arguments.image_format,
     )
     .wrap_err("failed to write vision bottom data to mcap")?;
     write_to_mcap(
@@ -167,6 +180,7 @@ fn main() -> Result<()> {
         "VisionTop",
         &mut mcap_converter,
         vision_top_receiver,
+        arguments.image_format,
     )
     .wrap_err("failed to write vision top data to mcap")?;
     write_to_mcap(
@@ -174,6 +188,7 @@ fn main() -> Result<()> {
         "SplNetwork",
         &mut mcap_converter,
         spl_network_receiver,
+        arguments.image_format,
     )
     .wrap_err("failed to write spl network data to mcap")?;
 
@@ -8,12 +8,17 @@ use mcap::write::Metadata;
 use mcap::{Attachment, McapError, Writer};
 use serde::Serialize;
 
-use crate::serializer::Serializer;
+use crate::{
+    foxglove::{Schema, TypedMessage},
+    serializer::Serializer,
+};
 
 type ChannelId = u16;
+type SchemaId = u16;
 pub struct McapConverter<W: Write + Seek> {
     writer: Writer<W>,
     channel_mapping: BTreeMap<String, ChannelId>,
+    schema_mapping: BTreeMap<&'static str, SchemaId>,
 }
 
 impl<W: Write + Seek> McapConverter<W> {
@@ -21,6 +26,7 @@ impl<W: Write + Seek> McapConverter<W> {
         Ok(Self {
             writer: Writer::new(writer)?,
             channel_mapping: Default::default(),
+            schema_mapping: Default::default(),
         })
     }
 
@@ -33,6 +39,29 @@ impl<W: Write + Seek> McapConverter<W> {
         Ok(channel_id)
     }
 
+    fn create_new_typed_channel(
+        &mut self,
+        topic: String,
+        schema: &'static Schema,
+    ) -> Result<ChannelId, McapError> {
+        let schema_id = match self.schema_mapping.get(schema.name).copied() {
+            Some(schema_id) => schema_id,
+            None => {
+                let schema_id =
+                    self.writer
+                        .add_schema(schema.name, "jsonschema", schema.data.as_bytes())?;
+                self.schema_mapping.insert(schema.name, schema_id);
+                schema_id
+            }
+        };
+        let channel_id = self
+            .writer
+            .add_channel(schema_id, &topic, "json", &Default::default())?;
+        self.channel_mapping.insert(topic, channel_id);
+
+        Ok(channel_id)
+    }
+
     pub fn add_to_mcap(
         &mut self,
         topic: String,
@@ -44,6 +73,29 @@ impl<W: Write + Seek> McapConverter<W> {
             Some(channel_id) => channel_id,
             None => self.create_new_channel(topic)?,
         };
+        self.write_message(channel_id, data, sequence_number, system_time)
+    }
+
+    pub fn add_typed_to_mcap(
+        &mut self,
+        message: TypedMessage,
+        sequence_number: u32,
+        system_time: SystemTime,
+    ) -> Result<(), McapError> {
+        let channel_id = match self.channel_mapping.get(&message.topic).copied() {
+            Some(channel_id) => channel_id,
+            None => self.create_new_typed_channel(message.topic, message.schema)?,
+        };
+        self.write_message(channel_id, &message.data, sequence_number, system_time)
+    }
+
+    fn write_message(
+        &mut self,
+        channel_id: ChannelId,
+        data: &[u8],
+        sequence_number: u32,
+        system_time: SystemTime,
+    ) -> Result<(), McapError> {
         let log_time = system_time_to_nanos(&system_time);
 
         self.writer.write_to_known_channel(
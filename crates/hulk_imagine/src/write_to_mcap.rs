@@ -4,13 +4,15 @@ use color_eyre::{
     eyre::{Context, ContextCompat},
     Result,
 };
-use indicatif::{ProgressIterator, ProgressStyle};
+use indicatif::{ProgressBar, ProgressIterator, ProgressStyle};
 use serde::Serialize;
 
 use buffered_watch::Receiver;
 
 use crate::{
-    execution::Replayer, extractor_hardware_interface::ExtractorHardwareInterface,
+    execution::Replayer,
+    extractor_hardware_interface::ExtractorHardwareInterface,
+    foxglove::{to_typed_messages, ImageFormat},
     mcap_converter::McapConverter,
 };
 
@@ -19,6 +21,7 @@ pub fn write_to_mcap<W, D>(
     cycler_name: &str,
     mcap_converter: &mut McapConverter<W>,
     mut receiver: Receiver<(SystemTime, D)>,
+    image_format: ImageFormat,
 ) -> Result<()>
 where
     W: std::io::Write + std::io::Seek,
@@ -38,11 +41,8 @@ where
         format!("[{{percent:>2}}%] {{wide_bar:.cyan/blue}} {cycler_name}").as_str(),
     )
     .unwrap();
-    for (index, timing) in timings
-        .iter()
-        .enumerate()
-        .progress_with_style(progress_style)
-    {
+    let progress = ProgressBar::new(timings.len() as u64).with_style(progress_style);
+    for (index, timing) in timings.iter().enumerate().progress_with(progress.clone()) {
         let frame = replayer
             .get_recording_indices_mut()
             .get_mut(cycler_name)
@@ -59,14 +59,24 @@ where
 
             let outputs = crate::mcap_converter::database_to_values(&database)?;
 
-            outputs.into_iter().try_for_each(|(topic, data)| {
-                mcap_converter.add_to_mcap(
-                    format!("{cycler_name}.{topic}"),
-                    &data,
-                    index as u32,
-                    timing.timestamp,
-                )
-            })?;
+            for (topic, data) in outputs {
+                let topic = format!("{cycler_name}.{topic}");
+                match to_typed_messages(&topic, &data, timing.timestamp, image_format) {
+                    Ok(messages) => {
+                        for message in messages {
+                            mcap_converter.add_typed_to_mcap(
+                                message,
+                                index as u32,
+                                timing.timestamp,
+                            )?;
+                        }
+                    }
+                    Err(error) => progress.println(format!(
+                        "skipping typed message of `{topic}` in frame {index}: {error:#}"
+                    )),
+                }
+                mcap_converter.add_to_mcap(topic, &data, index as u32, timing.timestamp)?;
+            }
         }
     }
 
@@ -38,6 +38,7 @@ use types::{
 
 use super::{
     camera::Camera,
+    health_log,
     hula_wrapper::HulaWrapper,
     microphones::{self, Microphones},
     speakers::{self, Speakers},
@@ -47,6 +48,7 @@ use super::{
 pub struct Parameters {
     pub camera_top: nao_camera::Parameters,
     pub camera_bottom: nao_camera::Parameters,
+    pub health_log: health_log::Parameters,
     pub microphones: microphones::Parameters,
     pub paths: Paths,
     pub speakers: speakers::Parameters,
@@ -75,7 +77,8 @@ impl HardwareInterface {
             .wrap_err("failed to create tokio runtime")?;
 
         Ok(Self {
-            hula_wrapper: HulaWrapper::new().wrap_err("failed to initialize HULA wrapper")?,
+            hula_wrapper: HulaWrapper::new(parameters.health_log)
+                .wrap_err("failed to initialize HULA wrapper")?,
             microphones: Mutex::new(
                 Microphones::new(parameters.microphones)
                     .wrap_err("failed to initialize microphones")?,
@@ -0,0 +1,203 @@
+use std::{
+    fs::{create_dir_all, rename, File},
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    sync::mpsc::{channel, Sender},
+    thread::{Builder, JoinHandle},
+    time::{Duration, SystemTime},
+};
+
+use color_eyre::{
+    eyre::{eyre, WrapErr},
+    Result,
+};
+use log::warn;
+use nalgebra::Vector2;
+use serde::Deserialize;
+
+use hula_types::{
+    hardware::Ids,
+    health::{HealthLog, SessionHealth},
+};
+use types::sensor_data::ForceSensitiveResistors;
+
+use super::hula::StateStorage;
+
+/// Torso angle in radians above which the robot is considered fallen
+const FALLEN_ANGLE: f32 = 1.0;
+/// Torso angle in radians below which the robot is considered upright again
+const UPRIGHT_ANGLE: f32 = 0.5;
+/// Battery current in amperes above which the robot is considered charging
+const CHARGING_CURRENT: f32 = 0.2;
+/// Battery current in amperes below which the robot is considered discharging again
+const DISCHARGING_CURRENT: f32 = -0.2;
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Parameters {
+    /// Directory outside of the uploaded HULK directory such that the log survives uploads
+    pub directory: PathBuf,
+    pub flush_interval: Duration,
+    pub maximum_sessions: usize,
+    pub maximum_force_sensitive_resistor_reading: f32,
+}
+
+/// Collects hardware health of the current session and keeps it on disk next to earlier ones
+///
+/// Writing happens on a separate thread such that file system latency does not delay the cycle
+/// reading from HULA. The last state is written when the logger is dropped.
+pub struct HealthLogger {
+    parameters: Parameters,
+    log: HealthLog,
+    last_flush: SystemTime,
+    is_fallen: bool,
+    is_charging: bool,
+    sender: Option<Sender<HealthLog>>,
+    writer: Option<JoinHandle<()>>,
+}
+
+impl HealthLogger {
+    pub fn new(parameters: Parameters, ids: &Ids) -> Result<Self> {
+        let path = parameters
+            .directory
+            .join(HealthLog::file_name(&ids.body_id, &ids.head_id));
+        let mut log = match File::open(&path) {
+            Ok(file) => serde_json::from_reader(file)
+                .wrap_err_with(|| format!("failed to parse health log {}", path.display()))?,
+            Err(error) if error.kind() == ErrorKind::NotFound => HealthLog {
+                body_id: ids.body_id.clone(),
+                head_id: ids.head_id.clone(),
+                sessions: Vec::new(),
+            },
+            Err(error) => {
+                return Err(error)
+                    .wrap_err_with(|| format!("failed to open health log {}", path.display()))
+            }
+        };
+        let now = SystemTime::now();
+        log.sessions.push(SessionHealth::new(now));
+        let excess_sessions = log
+            .sessions
+            .len()
+            .saturating_sub(parameters.maximum_sessions);
+        log.sessions.drain(..excess_sessions);
+
+        let (sender, receiver) = channel::<HealthLog>();
+        let directory = parameters.directory.clone();
+        let writer = Builder::new()
+            .name("health_log".to_string())
+            .spawn(move || {
+                for log in receiver {
+                    if let Err(error) = write(&directory, &path, &log) {
+                        warn!("failed to write health log: {error:#}");
+                    }
+                }
+            })
+            .wrap_err("failed to spawn health log writer")?;
+
+        Ok(Self {
+            parameters,
+            log,
+            last_flush: now,
+            is_fallen: false,
+            is_charging: false,
+            sender: Some(sender),
+            writer: Some(writer),
+        })
+    }
+
+    pub fn update(&mut self, state: &StateStorage) -> Result<()> {
+        let now = SystemTime::now();
+        let session = self
+            .log
+            .sessions
+            .last_mut()
+            .expect("current session is created on construction");
+        session.updated_at = now;
+        session.maximum_temperatures = session.maximum_temperatures.max(state.temperature.into());
+        session.maximum_status = session.maximum_status.max(state.status.into());
+
+        let torso_angle = Vector2::from(state.inertial_measurement_unit.angles).amax();
+        if !self.is_fallen && torso_angle > FALLEN_ANGLE {
+            self.is_fallen = true;
+            session.falls += 1;
+        } else if self.is_fallen && torso_angle < UPRIGHT_ANGLE {
+            self.is_fallen = false;
+        }
+
+        // the current fluctuates around zero when a full battery is connected to the charger
+        let battery = state.battery;
+        if !self.is_charging && battery.current > CHARGING_CURRENT {
+            self.is_charging = true;
+            session.charging_cycles += 1;
+        } else if self.is_charging && battery.current < DISCHARGING_CURRENT {
+            self.is_charging = false;
+        }
+        session.minimum_battery_charge = session.minimum_battery_charge.min(battery.charge);
+        session.maximum_battery_temperature =
+            session.maximum_battery_temperature.max(battery.temperature);
+
+        let force_sensitive_resistors =
+            ForceSensitiveResistors::from(state.force_sensitive_resistors);
+        let is_anomalous = [
+            force_sensitive_resistors.left,
+            force_sensitive_resistors.right,
+        ]
+        .into_iter()
+        .flat_map(|foot| {
+            [
+                foot.front_left,
+                foot.front_right,
+                foot.rear_left,
+                foot.rear_right,
+            ]
+        })
+        .any(|reading| {
+            !(0.0..=self.parameters.maximum_force_sensitive_resistor_reading).contains(&reading)
+        });
+        if is_anomalous {
+            session.force_sensitive_resistor_anomalies += 1;
+        }
+
+        if now
+            .duration_since(self.last_flush)
+            .is_ok_and(|elapsed| elapsed >= self.parameters.flush_interval)
+        {
+            self.last_flush = now;
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Hands a snapshot of the log to the writer thread
+    pub fn flush(&self) -> Result<()> {
+        self.sender
+            .as_ref()
+            .expect("sender is only taken on drop")
+            .send(self.log.clone())
+            .map_err(|_| eyre!("health log writer has stopped"))
+    }
+}
+
+impl Drop for HealthLogger {
+    fn drop(&mut self) {
+        if let Err(error) = self.flush() {
+            warn!("failed to flush health log: {error:#}");
+        }
+        // closing the channel lets the writer finish the pending writes and terminate
+        self.sender.take();
+        if let Some(writer) = self.writer.take() {
+            if writer.join().is_err() {
+                warn!("health log writer panicked");
+            }
+        }
+    }
+}
+
+/// Writes the log atomically such that a power loss does not corrupt earlier sessions
+fn write(directory: &Path, path: &Path, log: &HealthLog) -> Result<()> {
+    create_dir_all(directory).wrap_err("failed to create health log directory")?;
+    let temporary_path = path.with_extension("json.tmp");
+    let file = File::create(&temporary_path).wrap_err("failed to create health log")?;
+    serde_json::to_writer(file, log).wrap_err("failed to write health log")?;
+    rename(&temporary_path, path).wrap_err("failed to replace health log")
+}
@@ -267,6 +267,38 @@ impl From<JointsArray> for Joints<f32> {
     }
 }
 
+impl From<JointsArray> for hula_types::robot_state::JointsArray {
+    fn from(joints: JointsArray) -> Self {
+        Self::from_lola([
+            joints.head_yaw,
+            joints.head_pitch,
+            joints.left_shoulder_pitch,
+            joints.left_shoulder_roll,
+            joints.left_elbow_yaw,
+            joints.left_elbow_roll,
+            joints.left_wrist_yaw,
+            joints.left_hip_yaw_pitch,
+            joints.left_hip_roll,
+            joints.left_hip_pitch,
+            joints.left_knee_pitch,
+            joints.left_ankle_pitch,
+            joints.left_ankle_roll,
+            joints.right_hip_roll,
+            joints.right_hip_pitch,
+            joints.right_knee_pitch,
+            joints.right_ankle_pitch,
+            joints.right_ankle_roll,
+            joints.right_shoulder_pitch,
+            joints.right_shoulder_roll,
+            joints.right_elbow_yaw,
+            joints.right_elbow_roll,
+            joints.right_wrist_yaw,
+            joints.left_hand,
+            joints.right_hand,
+        ])
+    }
+}
+
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 #[repr(C)]
 pub struct StateStorage {
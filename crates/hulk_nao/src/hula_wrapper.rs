@@ -5,6 +5,7 @@ use std::{
 };
 
 use color_eyre::{eyre::WrapErr, Result};
+use log::warn;
 use parking_lot::{Mutex, RwLock};
 
 use hula_types::hardware::Ids;
@@ -12,6 +13,7 @@ use types::{joints::Joints, led::Leds, sensor_data::SensorData};
 
 use super::{
     double_buffered_reader::{DoubleBufferedReader, SelectPoller},
+    health_log::{self, HealthLogger},
     hula::{read_from_hula, write_to_hula, ControlStorage, StateStorage},
 };
 
@@ -22,10 +24,11 @@ pub struct HulaWrapper {
     ids: Ids,
     stream: Mutex<UnixStream>,
     hula_reader: Mutex<DoubleBufferedReader<StateStorage, UnixStream, SelectPoller>>,
+    health_logger: Option<Mutex<HealthLogger>>,
 }
 
 impl HulaWrapper {
-    pub fn new() -> Result<Self> {
+    pub fn new(health_log_parameters: health_log::Parameters) -> Result<Self> {
         let stream =
             UnixStream::connect(HULA_SOCKET_PATH).wrap_err("failed to open HULA socket")?;
         stream
@@ -47,11 +50,20 @@ impl HulaWrapper {
                 .wrap_err("failed to convert head ID into UTF-8")?
                 .to_string(),
         };
+        // a broken health log must not keep the robot from playing
+        let health_logger = match HealthLogger::new(health_log_parameters, &ids) {
+            Ok(health_logger) => Some(Mutex::new(health_logger)),
+            Err(error) => {
+                warn!("failed to initialize health log: {error:#}");
+                None
+            }
+        };
         Ok(Self {
             now: RwLock::new(UNIX_EPOCH),
             ids,
             stream: Mutex::new(stream),
             hula_reader: Mutex::new(hula_reader),
+            health_logger,
         })
     }
 
@@ -74,6 +86,12 @@ impl HulaWrapper {
             *now = UNIX_EPOCH + Duration::from_secs_f32(state_storage.received_at);
         }
 
+        if let Some(health_logger) = &self.health_logger {
+            if let Err(error) = health_logger.lock().update(&state_storage) {
+                warn!("failed to update health log: {error:#}");
+            }
+        }
+
         let positions = state_storage.position.into();
         let inertial_measurement_unit = state_storage.inertial_measurement_unit.into();
         let sonar_sensors = state_storage.sonar_sensors.into();
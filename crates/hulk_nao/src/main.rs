@@ -23,6 +23,7 @@ mod audio_parameter_deserializers;
 mod camera;
 mod double_buffered_reader;
 mod hardware_interface;
+mod health_log;
 mod hula;
 mod hula_wrapper;
 mod microphones;
@@ -91,5 +92,6 @@ fn main() -> Result<()> {
         ids,
         keep_running,
         framework_parameters.recording_intervals,
+        framework_parameters.hot_reload_parameters,
     )
 }
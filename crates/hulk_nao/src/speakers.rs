@@ -1,5 +1,7 @@
 use std::{
+    borrow::Cow,
     collections::HashMap,
+    path::Path,
     sync::mpsc::{sync_channel, Receiver, SyncSender, TrySendError},
     thread::{spawn, JoinHandle},
     time::Duration,
@@ -16,7 +18,7 @@ use opusfile_ng::OggOpusFile;
 use serde::Deserialize;
 
 use hula_types::hardware::Paths;
-use types::audio::{Sound, SpeakerRequest};
+use types::audio::{Sound, SpeakerRequest, Word};
 
 use crate::audio_parameter_deserializers::{deserialize_access, deserialize_format};
 
@@ -31,8 +33,17 @@ impl Speakers {
             .wrap_err("failed to initialize playback device")?;
         let sounds =
             Self::load_sounds(paths, parameters.volume).wrap_err("failed to loads sounds")?;
+        let words = Self::load_words(paths, parameters.volume);
+        let pause_between_words = vec![
+            0.0;
+            (parameters.pause_between_words.as_secs_f32() * parameters.sample_rate as f32)
+                as usize
+                * parameters.number_of_channels
+        ];
         let (sender, receiver) = sync_channel(5);
-        let worker = Some(spawn(move || worker(device, sounds, receiver)));
+        let worker = Some(spawn(move || {
+            worker(device, sounds, words, pause_between_words, receiver)
+        }));
         Ok(Self {
             worker_sender: Some(sender),
             worker,
@@ -75,28 +86,28 @@ impl Speakers {
         for sound in all::<Sound>() {
             let file_name = format!("{sound}.ogg");
             let path = paths.sounds.join(file_name);
-            let file = OggOpusFile::open_file(&path)
-                .wrap_err_with(|| format!("failed to open sound file {path:?}"))?;
-            let number_of_samples = file.pcm_total(-1).wrap_err_with(|| {
-                format!("failed to get number of samples of sound file {path:?}")
-            })?;
-            let mut samples = Vec::with_capacity(number_of_samples);
-            let mut buffer = [0.0; 2048];
-            loop {
-                let read_bytes = file
-                    .read_float(&mut buffer, None)
-                    .wrap_err_with(|| format!("failed to read sample of sound file {path:?}"))?;
-                if read_bytes == 0 {
-                    break;
-                }
-                for sample in &mut buffer[..read_bytes] {
-                    *sample *= volume;
+            sounds.insert(sound, load_samples(&path, volume)?);
+        }
+        Ok(sounds)
+    }
+
+    /// Loads the word bank for phrases, missing words are skipped during playback
+    fn load_words(paths: &Paths, volume: f32) -> HashMap<Word, Vec<f32>> {
+        let mut words = HashMap::new();
+        let mut missing_words = Vec::new();
+        for word in all::<Word>() {
+            let path = paths.sounds.join(word.file_name());
+            match load_samples(&path, volume) {
+                Ok(samples) => {
+                    words.insert(word, samples);
                 }
-                samples.extend(&buffer[..read_bytes]);
+                Err(_) => missing_words.push(word.to_string()),
             }
-            sounds.insert(sound, samples);
         }
-        Ok(sounds)
+        if !missing_words.is_empty() {
+            warn!("missing sounds for words {}", missing_words.join(", "));
+        }
+        words
     }
 
     pub fn write_to_speakers(&self, request: SpeakerRequest) {
@@ -127,6 +138,7 @@ pub struct Parameters {
     number_of_channels: usize,
     buffer_time: Duration,
     volume: f32,
+    pause_between_words: Duration,
 
     #[serde(deserialize_with = "deserialize_access")]
     access: Access,
@@ -134,18 +146,68 @@ pub struct Parameters {
     format: Format,
 }
 
-fn worker(device: PCM, sounds: HashMap<Sound, Vec<f32>>, receiver: Receiver<SpeakerRequest>) {
-    while let Ok(SpeakerRequest::PlaySound { sound }) = receiver.recv() {
-        let samples = sounds
-            .get(&sound)
-            .expect("missing sound, recheck Sound::all()");
+fn load_samples(path: &Path, volume: f32) -> Result<Vec<f32>> {
+    let file = OggOpusFile::open_file(path)
+        .wrap_err_with(|| format!("failed to open sound file {path:?}"))?;
+    let number_of_samples = file
+        .pcm_total(-1)
+        .wrap_err_with(|| format!("failed to get number of samples of sound file {path:?}"))?;
+    let mut samples = Vec::with_capacity(number_of_samples);
+    let mut buffer = [0.0; 2048];
+    loop {
+        let read_bytes = file
+            .read_float(&mut buffer, None)
+            .wrap_err_with(|| format!("failed to read sample of sound file {path:?}"))?;
+        if read_bytes == 0 {
+            break;
+        }
+        for sample in &mut buffer[..read_bytes] {
+            *sample *= volume;
+        }
+        samples.extend(&buffer[..read_bytes]);
+    }
+    Ok(samples)
+}
+
+fn worker(
+    device: PCM,
+    sounds: HashMap<Sound, Vec<f32>>,
+    words: HashMap<Word, Vec<f32>>,
+    pause_between_words: Vec<f32>,
+    receiver: Receiver<SpeakerRequest>,
+) {
+    while let Ok(request) = receiver.recv() {
+        let samples = match request {
+            SpeakerRequest::PlaySound { sound } => Cow::Borrowed(
+                sounds
+                    .get(&sound)
+                    .expect("missing sound, recheck Sound::all()"),
+            ),
+            SpeakerRequest::Say { phrase } => {
+                let mut samples: Vec<f32> = Vec::new();
+                for word in &phrase.words {
+                    let Some(word_samples) = words.get(word) else {
+                        warn!("skipping word {word} without sound in \"{phrase}\"");
+                        continue;
+                    };
+                    if !samples.is_empty() {
+                        samples.extend(&pause_between_words);
+                    }
+                    samples.extend(word_samples);
+                }
+                Cow::Owned(samples)
+            }
+        };
+        if samples.is_empty() {
+            continue;
+        }
         let io = device
             .io_f32()
             .expect("f32 device should always be available");
         if let Err(error) = device.prepare() {
             error!("device.prepare(): {error:?}");
         }
-        if let Err(error) = io.writei(samples) {
+        if let Err(error) = io.writei(&samples) {
             error!("device.writei(): {error:?}");
         }
         if let Err(error) = device.drain() {
@@ -0,0 +1,374 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    time::SystemTime,
+};
+
+use color_eyre::{eyre::WrapErr, Result};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::outputs::{Baseline, Sample};
+
+const DEFAULT_TOLERANCE: f64 = 1e-6;
+
+/// Maximum absolute deviation of numbers, optionally overridden per output
+#[derive(Debug)]
+pub struct Tolerances {
+    default: f64,
+    per_output: BTreeMap<String, f64>,
+}
+
+impl Tolerances {
+    /// Parses `<tolerance>` or `<output>=<tolerance>` arguments
+    pub fn parse(arguments: &[String]) -> Result<Self> {
+        let mut tolerances = Self {
+            default: DEFAULT_TOLERANCE,
+            per_output: BTreeMap::new(),
+        };
+        for argument in arguments {
+            let parse = |tolerance: &str| {
+                tolerance
+                    .trim()
+                    .parse::<f64>()
+                    .wrap_err_with(|| format!("failed to parse tolerance `{argument}`"))
+            };
+            match argument.split_once('=') {
+                Some((output, tolerance)) => {
+                    tolerances
+                        .per_output
+                        .insert(output.trim().to_string(), parse(tolerance)?);
+                }
+                None => tolerances.default = parse(argument)?,
+            }
+        }
+        Ok(tolerances)
+    }
+
+    fn for_output(&self, output: &str) -> f64 {
+        self.per_output.get(output).copied().unwrap_or(self.default)
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Report {
+    pub outputs: Vec<OutputReport>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OutputReport {
+    pub output: String,
+    pub tolerance: f64,
+    pub cycles: usize,
+    pub divergent_cycles: usize,
+    pub missing_cycles: usize,
+    pub maximum_deviation: f64,
+    pub first_divergence: Option<Divergence>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Divergence {
+    pub cycle: usize,
+    pub seconds_since_start: f32,
+    pub timestamp: SystemTime,
+    /// Path of the first differing value inside the output, empty if the whole output differs
+    pub path: String,
+    pub baseline: Option<Value>,
+    pub replayed: Option<Value>,
+}
+
+impl Report {
+    pub fn new(baseline: &Baseline, replayed: &Baseline, tolerances: &Tolerances) -> Self {
+        let outputs = replayed
+            .outputs
+            .iter()
+            .map(|(output, replayed_samples)| {
+                let baseline_samples = baseline
+                    .outputs
+                    .get(output)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                compare_samples(
+                    output,
+                    baseline_samples,
+                    replayed_samples,
+                    tolerances.for_output(output),
+                )
+            })
+            .collect();
+        Self { outputs }
+    }
+
+    pub fn has_divergence(&self) -> bool {
+        self.outputs
+            .iter()
+            .any(|output| output.divergent_cycles > 0 || output.missing_cycles > 0)
+    }
+
+    pub fn print(&self) {
+        println!(
+            "{:<50} {:>8} {:>10} {:>8} {:>14}",
+            "output", "cycles", "divergent", "missing", "max deviation"
+        );
+        for output in &self.outputs {
+            println!(
+                "{:<50} {:>8} {:>10} {:>8} {:>14.3e}",
+                output.output,
+                output.cycles,
+                output.divergent_cycles,
+                output.missing_cycles,
+                output.maximum_deviation,
+            );
+        }
+        for output in &self.outputs {
+            let Some(divergence) = &output.first_divergence else {
+                continue;
+            };
+            let format_value = |value: &Option<Value>| {
+                value
+                    .as_ref()
+                    .map_or("<missing>".to_string(), Value::to_string)
+            };
+            println!();
+            println!(
+                "{} first diverges in cycle {} ({:.3}s after start)",
+                output.output, divergence.cycle, divergence.seconds_since_start
+            );
+            if !divergence.path.is_empty() {
+                println!("  at:       {}", divergence.path);
+            }
+            println!("  baseline: {}", format_value(&divergence.baseline));
+            println!("  replayed: {}", format_value(&divergence.replayed));
+        }
+    }
+}
+
+fn compare_samples(
+    output: &str,
+    baseline: &[Sample],
+    replayed: &[Sample],
+    tolerance: f64,
+) -> OutputReport {
+    let baseline: BTreeMap<_, _> = baseline
+        .iter()
+        .map(|sample| (sample.timestamp, &sample.value))
+        .collect();
+    let replayed: BTreeMap<_, _> = replayed
+        .iter()
+        .map(|sample| (sample.timestamp, &sample.value))
+        .collect();
+    let timestamps: BTreeSet<_> = baseline.keys().chain(replayed.keys()).copied().collect();
+    let start = timestamps.first().copied();
+
+    let mut report = OutputReport {
+        output: output.to_string(),
+        tolerance,
+        cycles: timestamps.len(),
+        divergent_cycles: 0,
+        missing_cycles: 0,
+        maximum_deviation: 0.0,
+        first_divergence: None,
+    };
+    for (cycle, timestamp) in timestamps.into_iter().enumerate() {
+        let baseline_value = baseline.get(&timestamp).copied();
+        let replayed_value = replayed.get(&timestamp).copied();
+        let mismatch = match (baseline_value, replayed_value) {
+            (Some(baseline_value), Some(replayed_value)) => {
+                let comparison = compare_values(baseline_value, replayed_value, tolerance);
+                report.maximum_deviation = report.maximum_deviation.max(comparison.deviation);
+                let Some(mismatch) = comparison.first_mismatch else {
+                    continue;
+                };
+                report.divergent_cycles += 1;
+                mismatch
+            }
+            _ => {
+                report.missing_cycles += 1;
+                Mismatch {
+                    path: String::new(),
+                    baseline: baseline_value.cloned(),
+                    replayed: replayed_value.cloned(),
+                }
+            }
+        };
+        if report.first_divergence.is_none() {
+            let seconds_since_start = start
+                .and_then(|start| timestamp.duration_since(start).ok())
+                .unwrap_or_default()
+                .as_secs_f32();
+            report.first_divergence = Some(Divergence {
+                cycle,
+                seconds_since_start,
+                timestamp,
+                path: mismatch.path,
+                baseline: mismatch.baseline,
+                replayed: mismatch.replayed,
+            });
+        }
+    }
+    report
+}
+
+#[derive(Debug)]
+struct Mismatch {
+    path: String,
+    baseline: Option<Value>,
+    replayed: Option<Value>,
+}
+
+#[derive(Debug, Default)]
+struct Comparison {
+    /// Maximum absolute deviation of all numbers present in both values
+    deviation: f64,
+    first_mismatch: Option<Mismatch>,
+}
+
+impl Comparison {
+    fn merge(&mut self, other: Self) {
+        self.deviation = self.deviation.max(other.deviation);
+        if self.first_mismatch.is_none() {
+            self.first_mismatch = other.first_mismatch;
+        }
+    }
+}
+
+fn compare_values(baseline: &Value, replayed: &Value, tolerance: f64) -> Comparison {
+    compare_values_at("", baseline, replayed, tolerance)
+}
+
+fn compare_values_at(path: &str, baseline: &Value, replayed: &Value, tolerance: f64) -> Comparison {
+    let mismatch = || Mismatch {
+        path: path.to_string(),
+        baseline: Some(baseline.clone()),
+        replayed: Some(replayed.clone()),
+    };
+    match (baseline, replayed) {
+        (Value::Number(baseline_number), Value::Number(replayed_number)) => {
+            let (Some(baseline_number), Some(replayed_number)) =
+                (baseline_number.as_f64(), replayed_number.as_f64())
+            else {
+                return Comparison::default();
+            };
+            let deviation = (baseline_number - replayed_number).abs();
+            Comparison {
+                deviation,
+                first_mismatch: (deviation > tolerance).then(mismatch),
+            }
+        }
+        (Value::Array(baseline_items), Value::Array(replayed_items)) => {
+            let mut comparison = Comparison::default();
+            for (index, (baseline_item, replayed_item)) in
+                baseline_items.iter().zip(replayed_items).enumerate()
+            {
+                comparison.merge(compare_values_at(
+                    &format!("{path}[{index}]"),
+                    baseline_item,
+                    replayed_item,
+                    tolerance,
+                ));
+            }
+            if baseline_items.len() != replayed_items.len() && comparison.first_mismatch.is_none() {
+                comparison.first_mismatch = Some(mismatch());
+            }
+            comparison
+        }
+        (Value::Object(baseline_fields), Value::Object(replayed_fields)) => {
+            let mut comparison = Comparison::default();
+            let keys: BTreeSet<_> = baseline_fields
+                .keys()
+                .chain(replayed_fields.keys())
+                .collect();
+            for key in keys {
+                let field_path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                match (baseline_fields.get(key), replayed_fields.get(key)) {
+                    (Some(baseline_field), Some(replayed_field)) => comparison.merge(
+                        compare_values_at(&field_path, baseline_field, replayed_field, tolerance),
+                    ),
+                    (baseline_field, replayed_field) => comparison.merge(Comparison {
+                        deviation: 0.0,
+                        first_mismatch: Some(Mismatch {
+                            path: field_path,
+                            baseline: baseline_field.cloned(),
+                            replayed: replayed_field.cloned(),
+                        }),
+                    }),
+                }
+            }
+            comparison
+        }
+        _ => Comparison {
+            deviation: 0.0,
+            first_mismatch: (baseline != replayed).then(mismatch),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn numbers_within_tolerance_are_equal_and_first_mismatch_has_path() {
+        let baseline = json!({ "position": [1.0, 2.0], "kind": "Robot" });
+
+        let within_tolerance = json!({ "position": [1.0005, 2.0], "kind": "Robot" });
+        let comparison = compare_values(&baseline, &within_tolerance, 1e-3);
+        assert!(comparison.first_mismatch.is_none());
+        assert!((comparison.deviation - 0.0005).abs() < 1e-9);
+
+        let diverged = json!({ "position": [1.0, 2.5], "kind": "Goal" });
+        let mismatch = compare_values(&baseline, &diverged, 1e-3)
+            .first_mismatch
+            .unwrap();
+        assert_eq!(mismatch.path, "kind");
+        let mismatch = compare_values(&baseline["position"], &diverged["position"], 1e-3)
+            .first_mismatch
+            .unwrap();
+        assert_eq!(mismatch.path, "[1]");
+    }
+
+    #[test]
+    fn report_counts_divergent_and_missing_cycles() {
+        let sample = |milliseconds, value| Sample {
+            timestamp: UNIX_EPOCH + Duration::from_millis(milliseconds),
+            value,
+        };
+        let baseline = Baseline {
+            outputs: BTreeMap::from([(
+                "Control.main_outputs.value".to_string(),
+                vec![
+                    sample(0, json!(1.0)),
+                    sample(12, json!(2.0)),
+                    sample(24, json!(3.0)),
+                ],
+            )]),
+        };
+        let replayed = Baseline {
+            outputs: BTreeMap::from([(
+                "Control.main_outputs.value".to_string(),
+                vec![sample(0, json!(1.0)), sample(12, json!(2.5))],
+            )]),
+        };
+        let tolerances =
+            Tolerances::parse(&["Control.main_outputs.value=0.1".to_string()]).unwrap();
+
+        let report = Report::new(&baseline, &replayed, &tolerances);
+
+        assert!(report.has_divergence());
+        let output = &report.outputs[0];
+        assert_eq!(output.cycles, 3);
+        assert_eq!(output.divergent_cycles, 1);
+        assert_eq!(output.missing_cycles, 1);
+        assert_eq!(output.maximum_deviation, 0.5);
+        let divergence = output.first_divergence.as_ref().unwrap();
+        assert_eq!(divergence.cycle, 1);
+        assert!((divergence.seconds_since_start - 0.012).abs() < 1e-6);
+    }
+}
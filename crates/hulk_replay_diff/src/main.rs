@@ -0,0 +1,144 @@
+#![recursion_limit = "256"]
+
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use clap::Parser;
+use color_eyre::{
+    eyre::{bail, ContextCompat, Result, WrapErr},
+    install,
+};
+use serde_json::{from_reader, to_writer, to_writer_pretty};
+
+use hula_types::hardware::Ids;
+
+use crate::{
+    diff::{Report, Tolerances},
+    outputs::{replay_outputs, Baseline},
+    recorded::recorded_outputs,
+    replay_hardware_interface::{HardwareInterface, ReplayHardwareInterface},
+};
+
+mod diff;
+mod outputs;
+mod recorded;
+mod replay_hardware_interface;
+
+include!(concat!(env!("OUT_DIR"), "/generated_code.rs"));
+
+/// Replay a recording headless and compare outputs against a baseline run or the recording
+///
+/// Node states are restored from the recording before every cycle, so a divergence in one cycle
+/// does not propagate to later cycles.
+#[derive(Parser, Debug)]
+#[clap(name = "replay_diff")]
+struct Arguments {
+    /// Replay directory of a single robot, e.g. `logs/10.1.24.42/12345678`
+    replay_path: PathBuf,
+    /// Outputs to compare, e.g. `Control.main_outputs.ground_to_field`
+    #[arg(long = "output", short = 'o', required = true)]
+    outputs: Vec<String>,
+    /// Directory to read parameters from, defaults to the replay path
+    #[arg(long)]
+    parameters_directory: Option<PathBuf>,
+    /// Write the replayed outputs as baseline instead of comparing
+    #[arg(long, conflicts_with_all = ["baseline", "recorded"])]
+    write_baseline: Option<PathBuf>,
+    /// Baseline to compare the replayed outputs against
+    #[arg(long, required_unless_present_any = ["write_baseline", "recorded"])]
+    baseline: Option<PathBuf>,
+    /// Compare the replayed outputs against their values stored in the recording
+    ///
+    /// Recordings only contain outputs of setup nodes and outputs read by other cyclers, e.g.
+    /// `Control.main_outputs.camera_matrices` which the vision cyclers read.
+    #[arg(long, conflicts_with = "baseline")]
+    recorded: bool,
+    /// Maximum absolute deviation of numbers, either for all outputs or per output, e.g.
+    /// `1e-4` or `Control.main_outputs.ground_to_field=0.01`
+    #[arg(long)]
+    tolerance: Vec<String>,
+    /// Output the report as json
+    #[arg(long, short = 'j')]
+    json: bool,
+}
+
+fn main() -> Result<()> {
+    install()?;
+    let arguments = Arguments::parse();
+    let tolerances = Tolerances::parse(&arguments.tolerance)?;
+
+    let ids = Ids {
+        body_id: "replayer".into(),
+        head_id: "replayer".into(),
+    };
+    let parameters_directory = arguments
+        .parameters_directory
+        .clone()
+        .unwrap_or(arguments.replay_path.clone());
+    let mut replayer = Replayer::new(
+        Arc::new(ReplayHardwareInterface),
+        parameters_directory,
+        ids,
+        arguments.replay_path.clone(),
+    )
+    .wrap_err("failed to create replayer")?;
+
+    let recorded = arguments
+        .recorded
+        .then(|| recorded_outputs(&mut replayer, &arguments.outputs))
+        .transpose()?;
+    let mut replayed = replay_outputs(&mut replayer, &arguments.outputs)?;
+
+    if let Some(path) = &arguments.write_baseline {
+        write_baseline(path, &replayed)?;
+        println!(
+            "wrote {} outputs to {}",
+            replayed.outputs.len(),
+            path.display()
+        );
+        return Ok(());
+    }
+
+    let (baseline, reference) = match recorded {
+        Some(recorded) => {
+            replayed.retain_cycles_of(&recorded);
+            (recorded, "the recording".to_string())
+        }
+        None => {
+            let baseline_path = arguments.baseline.as_ref().wrap_err("expected baseline")?;
+            (
+                read_baseline(baseline_path)?,
+                baseline_path.display().to_string(),
+            )
+        }
+    };
+    let report = Report::new(&baseline, &replayed, &tolerances);
+    if arguments.json {
+        to_writer(std::io::stdout(), &report).wrap_err("failed to serialize report")?;
+        println!();
+    } else {
+        report.print();
+    }
+    if report.has_divergence() {
+        bail!("replayed outputs diverge from {reference}");
+    }
+    Ok(())
+}
+
+fn write_baseline(path: &Path, baseline: &Baseline) -> Result<()> {
+    let file =
+        File::create(path).wrap_err_with(|| format!("failed to create {}", path.display()))?;
+    to_writer_pretty(BufWriter::new(file), baseline)
+        .wrap_err_with(|| format!("failed to write {}", path.display()))
+}
+
+fn read_baseline(path: &Path) -> Result<Baseline> {
+    let file = File::open(path).wrap_err_with(|| format!("failed to open {}", path.display()))?;
+    let baseline: Baseline = from_reader(BufReader::new(file))
+        .wrap_err_with(|| format!("failed to parse {}", path.display()))?;
+    Ok(baseline)
+}
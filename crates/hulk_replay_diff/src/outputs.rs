@@ -0,0 +1,173 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    time::SystemTime,
+};
+
+use color_eyre::{
+    eyre::{bail, ContextCompat, WrapErr},
+    Result,
+};
+use indicatif::{ProgressIterator, ProgressStyle};
+use path_serde::PathSerialize;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use buffered_watch::Receiver;
+
+use crate::{execution::Replayer, replay_hardware_interface::ReplayHardwareInterface};
+
+/// Replayed or recorded values of outputs, keyed by `<cycler>.<path>`
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct Baseline {
+    pub outputs: BTreeMap<String, Vec<Sample>>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Sample {
+    pub timestamp: SystemTime,
+    pub value: Value,
+}
+
+impl Baseline {
+    /// Drops samples of cycles `other` has no sample of the same output for
+    pub fn retain_cycles_of(&mut self, other: &Baseline) {
+        for (output, samples) in &mut self.outputs {
+            let Some(other_samples) = other.outputs.get(output) else {
+                continue;
+            };
+            let timestamps: BTreeSet<_> = other_samples
+                .iter()
+                .map(|sample| sample.timestamp)
+                .collect();
+            samples.retain(|sample| timestamps.contains(&sample.timestamp));
+        }
+    }
+}
+
+pub fn replay_outputs(
+    replayer: &mut Replayer<ReplayHardwareInterface>,
+    outputs: &[String],
+) -> Result<Baseline> {
+    let mut paths_by_cycler: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for output in outputs {
+        let (cycler, path) = output.split_once('.').wrap_err_with(|| {
+            format!("expected output `{output}` in the form `<cycler>.<path>`")
+        })?;
+        paths_by_cycler.entry(cycler).or_default().push(path);
+    }
+
+    let mut baseline = Baseline::default();
+    for (cycler, paths) in paths_by_cycler {
+        let additional_outputs = paths
+            .iter()
+            .filter(|path| path.starts_with("additional_outputs."))
+            .map(|path| path.to_string());
+        let samples = match cycler {
+            "Audio" => {
+                replayer
+                    .audio_subscriptions_sender
+                    .borrow_mut()
+                    .extend(additional_outputs);
+                let receiver = replayer.audio_receiver();
+                replay_cycler(replayer, cycler, receiver, &paths)
+            }
+            "Control" => {
+                replayer
+                    .control_subscriptions_sender
+                    .borrow_mut()
+                    .extend(additional_outputs);
+                let receiver = replayer.control_receiver();
+                replay_cycler(replayer, cycler, receiver, &paths)
+            }
+            "SplNetwork" => {
+                replayer
+                    .spl_network_subscriptions_sender
+                    .borrow_mut()
+                    .extend(additional_outputs);
+                let receiver = replayer.spl_network_receiver();
+                replay_cycler(replayer, cycler, receiver, &paths)
+            }
+            "VisionTop" => {
+                replayer
+                    .vision_top_subscriptions_sender
+                    .borrow_mut()
+                    .extend(additional_outputs);
+                let receiver = replayer.vision_top_receiver();
+                replay_cycler(replayer, cycler, receiver, &paths)
+            }
+            "VisionBottom" => {
+                replayer
+                    .vision_bottom_subscriptions_sender
+                    .borrow_mut()
+                    .extend(additional_outputs);
+                let receiver = replayer.vision_bottom_receiver();
+                replay_cycler(replayer, cycler, receiver, &paths)
+            }
+            _ => bail!("unknown cycler `{cycler}`"),
+        }
+        .wrap_err_with(|| format!("failed to replay {cycler}"))?;
+        baseline.outputs.extend(samples);
+    }
+
+    Ok(baseline)
+}
+
+fn replay_cycler<D>(
+    replayer: &mut Replayer<ReplayHardwareInterface>,
+    cycler_name: &str,
+    mut receiver: Receiver<(SystemTime, D)>,
+    paths: &[&str],
+) -> Result<BTreeMap<String, Vec<Sample>>>
+where
+    D: PathSerialize,
+{
+    let unknown_indices_error_message =
+        format!("could not find recording indices for `{cycler_name}`");
+
+    let timings: Vec<_> = replayer
+        .get_recording_indices()
+        .get(cycler_name)
+        .wrap_err_with(|| unknown_indices_error_message.clone())?
+        .iter()
+        .collect();
+
+    let progress_style = ProgressStyle::with_template(
+        format!("[{{percent:>2}}%] {{wide_bar:.cyan/blue}} {cycler_name}").as_str(),
+    )
+    .unwrap();
+    let mut samples: BTreeMap<String, Vec<Sample>> = paths
+        .iter()
+        .map(|path| (format!("{cycler_name}.{path}"), Vec::new()))
+        .collect();
+    for timing in timings.iter().progress_with_style(progress_style) {
+        let frame = replayer
+            .get_recording_indices_mut()
+            .get_mut(cycler_name)
+            .wrap_err_with(|| unknown_indices_error_message.clone())?
+            .find_latest_frame_up_to(timing.timestamp)
+            .wrap_err("failed to find latest frame")?;
+
+        let Some(frame) = frame else {
+            continue;
+        };
+        replayer
+            .replay(cycler_name, frame.timing.timestamp, &frame.data)
+            .wrap_err("failed to replay frame")?;
+
+        let (_, database) = &*receiver.borrow_and_mark_as_seen();
+        for path in paths {
+            let value = database
+                .serialize_path(path, serde_json::value::Serializer)
+                .wrap_err_with(|| format!("failed to serialize `{cycler_name}.{path}`"))?;
+            samples
+                .get_mut(&format!("{cycler_name}.{path}"))
+                .expect("every path should have samples")
+                .push(Sample {
+                    timestamp: frame.timing.timestamp,
+                    value,
+                });
+        }
+    }
+
+    Ok(samples)
+}
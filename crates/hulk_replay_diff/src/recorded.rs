@@ -0,0 +1,142 @@
+use std::{collections::BTreeMap, time::SystemTime};
+
+use color_eyre::{
+    eyre::{bail, ContextCompat, WrapErr},
+    Result,
+};
+use framework::{RecordedOutputVisitor, Timing};
+use indicatif::{ProgressIterator, ProgressStyle};
+use serde::Serialize;
+use serde_json::{to_value, Value};
+
+use crate::{
+    execution::Replayer,
+    outputs::{Baseline, Sample},
+    replay_hardware_interface::ReplayHardwareInterface,
+};
+
+/// Reads the values of outputs stored in the recording, keyed by the cycle that produced them
+///
+/// Recordings contain the outputs of setup nodes and the outputs other cyclers read as inputs.
+/// Such an input is assigned to the latest cycle of its cycler that finished before the frame
+/// reading it was recorded.
+pub fn recorded_outputs(
+    replayer: &mut Replayer<ReplayHardwareInterface>,
+    outputs: &[String],
+) -> Result<Baseline> {
+    let timings: BTreeMap<String, Vec<Timing>> = replayer
+        .get_recording_indices()
+        .into_iter()
+        .map(|(cycler_instance, index)| (cycler_instance, index.iter().collect()))
+        .collect();
+    let mut visitor = RecordedOutputs {
+        outputs,
+        timings: &timings,
+        frame: None,
+        samples: BTreeMap::new(),
+    };
+
+    for (cycler_instance, frame_timings) in &timings {
+        let progress_style = ProgressStyle::with_template(
+            format!("[{{percent:>2}}%] {{wide_bar:.cyan/blue}} recorded {cycler_instance}")
+                .as_str(),
+        )
+        .unwrap();
+        for timing in frame_timings.iter().progress_with_style(progress_style) {
+            let frame = replayer
+                .get_recording_indices_mut()
+                .get_mut(cycler_instance)
+                .wrap_err_with(|| {
+                    format!("could not find recording indices for `{cycler_instance}`")
+                })?
+                .find_latest_frame_up_to(timing.timestamp)
+                .wrap_err("failed to find latest frame")?
+                .wrap_err("expected frame at its own timestamp")?;
+            visitor.frame = Some((cycler_instance.as_str(), frame.timing.timestamp));
+            replayer
+                .visit_recorded_outputs(cycler_instance, &frame.data, &mut visitor)
+                .wrap_err("failed to read recorded outputs")?;
+        }
+    }
+
+    let mut recorded = Baseline::default();
+    for output in outputs {
+        let Some(samples) = visitor.samples.remove(output) else {
+            bail!("recording does not contain `{output}`");
+        };
+        recorded.outputs.insert(
+            output.clone(),
+            samples
+                .into_iter()
+                .map(|(timestamp, value)| Sample { timestamp, value })
+                .collect(),
+        );
+    }
+    Ok(recorded)
+}
+
+struct RecordedOutputs<'a> {
+    outputs: &'a [String],
+    timings: &'a BTreeMap<String, Vec<Timing>>,
+    frame: Option<(&'a str, SystemTime)>,
+    samples: BTreeMap<String, BTreeMap<SystemTime, Value>>,
+}
+
+impl RecordedOutputs<'_> {
+    fn producing_cycle(&self, cycler_instance: &str) -> Option<SystemTime> {
+        let (frame_cycler_instance, frame_timestamp) = self.frame?;
+        if cycler_instance == frame_cycler_instance {
+            return Some(frame_timestamp);
+        }
+        self.timings
+            .get(cycler_instance)?
+            .iter()
+            .rev()
+            .find(|timing| timing.timestamp + timing.duration <= frame_timestamp)
+            .map(|timing| timing.timestamp)
+    }
+}
+
+impl RecordedOutputVisitor for RecordedOutputs<'_> {
+    fn visit<DataType: Serialize>(
+        &mut self,
+        cycler_instance: &str,
+        path: &str,
+        value: &DataType,
+    ) -> Result<()> {
+        let recorded_output = format!("{cycler_instance}.{path}");
+        let requested_outputs: Vec<_> = self
+            .outputs
+            .iter()
+            .filter_map(|output| {
+                let remainder = output.strip_prefix(&recorded_output)?;
+                if remainder.is_empty() {
+                    Some((output, String::new()))
+                } else {
+                    let pointer = remainder.strip_prefix('.')?.replace('.', "/");
+                    Some((output, format!("/{pointer}")))
+                }
+            })
+            .collect();
+        if requested_outputs.is_empty() {
+            return Ok(());
+        }
+        let Some(timestamp) = self.producing_cycle(cycler_instance) else {
+            return Ok(());
+        };
+
+        let value =
+            to_value(value).wrap_err_with(|| format!("failed to serialize `{recorded_output}`"))?;
+        for (output, pointer) in requested_outputs {
+            let Some(value) = value.pointer(&pointer) else {
+                continue;
+            };
+            self.samples
+                .entry(output.clone())
+                .or_default()
+                .entry(timestamp)
+                .or_insert_with(|| value.clone());
+        }
+        Ok(())
+    }
+}
@@ -75,5 +75,6 @@ fn main() -> Result<()> {
         ids,
         keep_running,
         framework_parameters.recording_intervals,
+        framework_parameters.hot_reload_parameters,
     )
 }
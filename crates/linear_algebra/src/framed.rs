@@ -1,9 +1,11 @@
 use approx::{AbsDiffEq, RelativeEq};
 use num_traits::Num;
-use path_serde::{deserialize, serialize, PathDeserialize, PathIntrospect, PathSerialize};
+use path_serde::{
+    deserialize, schema::FieldSchema, serialize, PathDeserialize, PathIntrospect, PathSerialize,
+};
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashSet,
+    collections::{BTreeMap, HashSet},
     hash::{Hash, Hasher},
     iter::Sum,
     marker::PhantomData,
@@ -298,6 +300,10 @@ where
     fn extend_with_fields(fields: &mut HashSet<String>, prefix: &str) {
         Inner::extend_with_fields(fields, prefix)
     }
+
+    fn extend_with_schema(schema: &mut BTreeMap<String, FieldSchema>, prefix: &str) {
+        Inner::extend_with_schema(schema, prefix)
+    }
 }
 
 impl<Frame, Inner> RelativeEq for Framed<Frame, Inner>
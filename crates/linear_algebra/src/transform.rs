@@ -1,7 +1,13 @@
-use std::{collections::HashSet, marker::PhantomData, ops::Mul};
+use std::{
+    collections::{BTreeMap, HashSet},
+    marker::PhantomData,
+    ops::Mul,
+};
 
 use approx::{AbsDiffEq, RelativeEq};
-use path_serde::{deserialize, serialize, PathDeserialize, PathIntrospect, PathSerialize};
+use path_serde::{
+    deserialize, schema::FieldSchema, serialize, PathDeserialize, PathIntrospect, PathSerialize,
+};
 use serde::{Deserialize, Serialize};
 
 use crate::framed::Framed;
@@ -210,4 +216,8 @@ where
     fn extend_with_fields(fields: &mut HashSet<String>, prefix: &str) {
         Inner::extend_with_fields(fields, prefix)
     }
+
+    fn extend_with_schema(schema: &mut BTreeMap<String, FieldSchema>, prefix: &str) {
+        Inner::extend_with_schema(schema, prefix)
+    }
 }
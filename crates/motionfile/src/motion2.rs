@@ -0,0 +1,222 @@
+use std::{fs::File, path::Path, time::Duration};
+
+use color_eyre::eyre::{bail, eyre, Result, WrapErr};
+use serde::Deserialize;
+use serde_json::from_reader;
+use types::joints::{arm::ArmJoints, head::HeadJoints, leg::LegJoints, Joints};
+
+use crate::{KeyFrame, MotionFile, MotionFileFrame};
+
+const NUMBER_OF_JOINTS: usize = 26;
+
+/// Keyframe motion in the `.motion2` format of the HULKs C++ framework
+#[derive(Clone, Debug, Deserialize)]
+pub struct Motion2 {
+    pub header: Motion2Header,
+    #[serde(default)]
+    pub position: Vec<Motion2Position>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Motion2Header {
+    /// Joint index for every entry of the parameters, in the NAO joint order from head yaw to
+    /// right hand
+    pub joints: Vec<usize>,
+    /// Duration of the whole motion in milliseconds
+    pub time: f32,
+    pub title: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Motion2Position {
+    pub parameters: Vec<f32>,
+    /// Relative duration to reach this position, all times are scaled to the header time
+    pub time: f32,
+}
+
+impl MotionFile<Joints<f32>> {
+    pub fn from_motion2_path(motion_file_path: impl AsRef<Path>) -> Result<Self> {
+        let file = File::open(&motion_file_path).wrap_err_with(|| {
+            format!("failed to open motion file {:?}", motion_file_path.as_ref())
+        })?;
+        let motion2: Motion2 = from_reader(file).wrap_err_with(|| {
+            format!(
+                "failed to parse motion file {:?}",
+                motion_file_path.as_ref()
+            )
+        })?;
+        Self::try_from(motion2).wrap_err_with(|| {
+            format!(
+                "failed to convert motion file {:?}",
+                motion_file_path.as_ref()
+            )
+        })
+    }
+}
+
+impl TryFrom<Motion2> for MotionFile<Joints<f32>> {
+    type Error = color_eyre::Report;
+
+    /// The first position becomes the initial positions, all following positions are keyframes
+    /// of a single unconditioned frame. A single position is held for the header time.
+    fn try_from(motion2: Motion2) -> Result<Self> {
+        let Some((initial_position, positions)) = motion2.position.split_first() else {
+            bail!("motion contains no positions");
+        };
+        let to_joints = |position: &Motion2Position| {
+            joints_from_parameters(&motion2.header.joints, &position.parameters)
+        };
+        let initial_positions = to_joints(initial_position)?;
+        let keyframes = if positions.is_empty() {
+            vec![KeyFrame {
+                duration: Duration::from_secs_f32(motion2.header.time / 1000.0),
+                positions: initial_positions,
+            }]
+        } else {
+            let total_time: f32 = motion2.position.iter().map(|position| position.time).sum();
+            if total_time <= 0.0 {
+                bail!("motion has no duration");
+            }
+            positions
+                .iter()
+                .map(|position| {
+                    Ok(KeyFrame {
+                        duration: Duration::from_secs_f32(
+                            position.time / total_time * motion2.header.time / 1000.0,
+                        ),
+                        positions: to_joints(position)?,
+                    })
+                })
+                .collect::<Result<_>>()?
+        };
+        Ok(Self {
+            interpolation_mode: Default::default(),
+            initial_positions,
+            motion: vec![MotionFileFrame {
+                name: motion2.header.title,
+                entry_condition: None,
+                interrupt_conditions: Vec::new(),
+                keyframes,
+                exit_condition: None,
+            }],
+        })
+    }
+}
+
+fn joints_from_parameters(joint_indices: &[usize], parameters: &[f32]) -> Result<Joints<f32>> {
+    if joint_indices.len() != NUMBER_OF_JOINTS || parameters.len() != NUMBER_OF_JOINTS {
+        bail!(
+            "expected {NUMBER_OF_JOINTS} joints but got {} joint indices and {} parameters",
+            joint_indices.len(),
+            parameters.len()
+        );
+    }
+    let mut positions = [None; NUMBER_OF_JOINTS];
+    for (&joint_index, &parameter) in joint_indices.iter().zip(parameters) {
+        let position = positions
+            .get_mut(joint_index)
+            .ok_or_else(|| eyre!("invalid joint index {joint_index}"))?;
+        *position = Some(parameter);
+    }
+    let positions = positions
+        .into_iter()
+        .enumerate()
+        .map(|(joint_index, position)| {
+            position.ok_or_else(|| eyre!("missing position for joint index {joint_index}"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(Joints {
+        head: HeadJoints {
+            yaw: positions[0],
+            pitch: positions[1],
+        },
+        left_arm: ArmJoints {
+            shoulder_pitch: positions[2],
+            shoulder_roll: positions[3],
+            elbow_yaw: positions[4],
+            elbow_roll: positions[5],
+            wrist_yaw: positions[6],
+            hand: positions[7],
+        },
+        left_leg: LegJoints {
+            hip_yaw_pitch: positions[8],
+            hip_roll: positions[9],
+            hip_pitch: positions[10],
+            knee_pitch: positions[11],
+            ankle_pitch: positions[12],
+            ankle_roll: positions[13],
+        },
+        right_leg: LegJoints {
+            hip_yaw_pitch: positions[14],
+            hip_roll: positions[15],
+            hip_pitch: positions[16],
+            knee_pitch: positions[17],
+            ankle_pitch: positions[18],
+            ankle_roll: positions[19],
+        },
+        right_arm: ArmJoints {
+            shoulder_pitch: positions[20],
+            shoulder_roll: positions[21],
+            elbow_yaw: positions[22],
+            elbow_roll: positions[23],
+            wrist_yaw: positions[24],
+            hand: positions[25],
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn positions_are_mapped_to_joints_and_scaled_to_header_time() {
+        // parameters are stored in the order arms, then legs
+        let joint_indices: Vec<usize> = (0..8).chain(20..26).chain(8..20).collect();
+        let parameters: Vec<f32> = joint_indices.iter().map(|&index| index as f32).collect();
+        let motion2: Motion2 = serde_json::from_value(json!({
+            "header": { "joints": joint_indices, "time": 3000, "title": "test" },
+            "position": [
+                { "parameters": parameters, "time": 100 },
+                { "parameters": parameters, "time": 100 },
+                { "parameters": parameters, "time": 400 },
+            ],
+        }))
+        .unwrap();
+
+        let motion_file = MotionFile::try_from(motion2).unwrap();
+
+        let initial_positions = motion_file.initial_positions;
+        assert_eq!(initial_positions.head.pitch, 1.0);
+        assert_eq!(initial_positions.left_leg.hip_yaw_pitch, 8.0);
+        assert_eq!(initial_positions.right_arm.shoulder_pitch, 20.0);
+        assert_eq!(initial_positions.right_arm.hand, 25.0);
+        let keyframes = &motion_file.motion[0].keyframes;
+        assert_eq!(keyframes.len(), 2);
+        assert!((keyframes[0].duration.as_secs_f32() - 0.5).abs() < 1e-5);
+        assert!((keyframes[1].duration.as_secs_f32() - 2.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn single_position_is_held_for_header_time() {
+        let joint_indices: Vec<usize> = (0..26).collect();
+        let parameters: Vec<f32> = joint_indices.iter().map(|&index| index as f32).collect();
+        let motion2: Motion2 = serde_json::from_value(json!({
+            "header": { "joints": joint_indices, "time": 1500, "title": "hold" },
+            "position": [
+                { "parameters": parameters, "time": 0 },
+            ],
+        }))
+        .unwrap();
+
+        let motion_file = MotionFile::try_from(motion2).unwrap();
+
+        let keyframes = &motion_file.motion[0].keyframes;
+        assert_eq!(keyframes.len(), 1);
+        assert_eq!(keyframes[0].positions, motion_file.initial_positions);
+        assert!((keyframes[0].duration.as_secs_f32() - 1.5).abs() < 1e-5);
+    }
+}
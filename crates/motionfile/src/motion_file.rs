@@ -3,7 +3,7 @@ use std::{fs::File, path::Path, time::Duration};
 
 use color_eyre::eyre::{Result, WrapErr};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use serde_json::from_reader;
+use serde_json::{from_reader, to_writer_pretty};
 use splines::{Interpolate, Interpolation};
 
 use crate::condition::{ContinuousConditionType, DiscreteConditionType};
@@ -33,6 +33,26 @@ where
     }
 }
 
+impl<T> MotionFile<T>
+where
+    T: Serialize,
+{
+    pub fn to_path(&self, motion_file_path: impl AsRef<Path>) -> Result<()> {
+        let file = File::create(&motion_file_path).wrap_err_with(|| {
+            format!(
+                "failed to create motion file {:?}",
+                motion_file_path.as_ref()
+            )
+        })?;
+        to_writer_pretty(file, self).wrap_err_with(|| {
+            format!(
+                "failed to write motion file {:?}",
+                motion_file_path.as_ref()
+            )
+        })
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct MotionFileFrame<T> {
     pub name: Option<String>,
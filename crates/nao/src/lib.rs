@@ -277,6 +277,27 @@ impl Nao {
         String::from_utf8(output.stdout).wrap_err("failed to decode UTF-8")
     }
 
+    pub async fn retrieve_health_logs(&self, directory: &Path) -> Result<String> {
+        let output = self
+            .ssh_to_nao()?
+            .arg("find")
+            .arg(directory)
+            .arg("-maxdepth 1 -name '*.json' -exec cat {} +")
+            .output()
+            .await
+            .wrap_err("failed to execute find command")?;
+
+        if !output.status.success() {
+            bail!(
+                "find ssh command exited with {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        String::from_utf8(output.stdout).wrap_err("failed to decode UTF-8")
+    }
+
     pub async fn power_off(&self) -> Result<()> {
         let status = self
             .ssh_to_nao()?
@@ -57,54 +57,26 @@ pub fn deserialize<Parameters>(
 where
     Parameters: DeserializeOwned,
 {
-    let default_file_path = parameters_root_path.as_ref().join("default.json");
-    let mut parameters =
-        read_from_file(default_file_path).map_err(DirectoryError::DefaultParametersNotGet)?;
-
-    let location_directory = parameters_root_path
-        .as_ref()
-        .join(location_directory_from_head_id(&hardware_ids.head_id));
-
-    let location_default_file_path = location_directory.join("default.json");
-    if location_default_file_path.exists() {
-        let location_default_parameters = read_from_file(location_default_file_path)
-            .map_err(DirectoryError::DefaultParametersOfLocationNotGet)?;
-        merge_json(&mut parameters, &location_default_parameters);
-    }
-
-    let body_file_path = parameters_root_path
-        .as_ref()
-        .join(format!("body.{}.json", &hardware_ids.body_id));
-    if body_file_path.exists() {
-        let body_parameters =
-            read_from_file(body_file_path).map_err(DirectoryError::BodyParametersNotGet)?;
-        merge_json(&mut parameters, &body_parameters);
-    }
-
-    let head_file_path = parameters_root_path
-        .as_ref()
-        .join(format!("head.{}.json", &hardware_ids.head_id));
-    if head_file_path.exists() {
-        let head_parameters =
-            read_from_file(head_file_path).map_err(DirectoryError::HeadParametersNotGet)?;
-        merge_json(&mut parameters, &head_parameters);
-    }
-
-    let location_body_file_path =
-        location_directory.join(format!("body.{}.json", &hardware_ids.body_id));
-    if location_body_file_path.exists() {
-        let location_body_parameters = read_from_file(location_body_file_path)
-            .map_err(DirectoryError::BodyParametersOfLocationNotGet)?;
-        merge_json(&mut parameters, &location_body_parameters);
-    }
+    deserialize_in_location(
+        parameters_root_path,
+        location_directory_from_head_id(&hardware_ids.head_id),
+        hardware_ids,
+        allow_superfluous_fields,
+    )
+}
 
-    let location_head_file_path =
-        location_directory.join(format!("head.{}.json", &hardware_ids.head_id));
-    if location_head_file_path.exists() {
-        let location_head_parameters = read_from_file(location_head_file_path)
-            .map_err(DirectoryError::HeadParametersOfLocationNotGet)?;
-        merge_json(&mut parameters, &location_head_parameters);
-    }
+/// Like [`deserialize`] but with the location directory given instead of derived from the head id
+pub fn deserialize_in_location<Parameters>(
+    parameters_root_path: impl AsRef<Path>,
+    location_directory: &str,
+    hardware_ids: &Ids,
+    allow_superfluous_fields: bool,
+) -> Result<Parameters, DirectoryError>
+where
+    Parameters: DeserializeOwned,
+{
+    let parameters =
+        merge_layers_in_location(parameters_root_path, location_directory, hardware_ids)?;
 
     let mut superfluous_fields = BTreeSet::<String>::new();
     let parsed = serde_ignored::deserialize(parameters, |path| {
@@ -119,6 +91,99 @@ where
     parsed
 }
 
+/// Merges the parameter files of all layers into the effective parameters without parsing them
+pub fn merge_layers(
+    parameters_root_path: impl AsRef<Path>,
+    hardware_ids: &Ids,
+) -> Result<Value, DirectoryError> {
+    merge_layers_in_location(
+        parameters_root_path,
+        location_directory_from_head_id(&hardware_ids.head_id),
+        hardware_ids,
+    )
+}
+
+fn merge_layers_in_location(
+    parameters_root_path: impl AsRef<Path>,
+    location_directory: &str,
+    hardware_ids: &Ids,
+) -> Result<Value, DirectoryError> {
+    let mut parameters = Value::Object(Default::default());
+    for layer in Layer::ALL {
+        let file_path =
+            layer.file_path_in_location(&parameters_root_path, location_directory, hardware_ids);
+        if layer != Layer::Default && !file_path.exists() {
+            continue;
+        }
+        let layer_parameters = read_from_file(file_path).map_err(|error| layer.error(error))?;
+        merge_json(&mut parameters, &layer_parameters);
+    }
+    Ok(parameters)
+}
+
+/// Parameter files in the order they are merged, later layers override earlier ones
+#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Layer {
+    Default,
+    LocationDefault,
+    Body,
+    Head,
+    LocationBody,
+    LocationHead,
+}
+
+impl Layer {
+    pub const ALL: [Self; 6] = [
+        Self::Default,
+        Self::LocationDefault,
+        Self::Body,
+        Self::Head,
+        Self::LocationBody,
+        Self::LocationHead,
+    ];
+
+    pub fn file_path(self, parameters_root_path: impl AsRef<Path>, hardware_ids: &Ids) -> PathBuf {
+        self.file_path_in_location(
+            parameters_root_path,
+            location_directory_from_head_id(&hardware_ids.head_id),
+            hardware_ids,
+        )
+    }
+
+    fn file_path_in_location(
+        self,
+        parameters_root_path: impl AsRef<Path>,
+        location_directory: &str,
+        hardware_ids: &Ids,
+    ) -> PathBuf {
+        let scope = match self {
+            Layer::Default => Scope::default_location(),
+            Layer::LocationDefault => Scope::current_location(),
+            Layer::Body => Scope::default_body(),
+            Layer::Head => Scope::default_head(),
+            Layer::LocationBody => Scope::current_body(),
+            Layer::LocationHead => Scope::current_head(),
+        };
+        file_path_from_scope_in_location(
+            scope,
+            parameters_root_path,
+            location_directory,
+            hardware_ids,
+        )
+    }
+
+    pub(crate) fn error(self, error: SerializationError) -> DirectoryError {
+        match self {
+            Layer::Default => DirectoryError::DefaultParametersNotGet(error),
+            Layer::LocationDefault => DirectoryError::DefaultParametersOfLocationNotGet(error),
+            Layer::Body => DirectoryError::BodyParametersNotGet(error),
+            Layer::Head => DirectoryError::HeadParametersNotGet(error),
+            Layer::LocationBody => DirectoryError::BodyParametersOfLocationNotGet(error),
+            Layer::LocationHead => DirectoryError::HeadParametersOfLocationNotGet(error),
+        }
+    }
+}
+
 pub fn serialize<Parameters>(
     parameters: &Parameters,
     scope: Scope,
@@ -178,6 +243,13 @@ impl Scope {
         }
     }
 
+    pub fn default_body() -> Self {
+        Self {
+            location: Location::All,
+            id: Id::Body,
+        }
+    }
+
     pub fn current_location() -> Self {
         Self {
             location: Location::Current,
@@ -217,12 +289,24 @@ fn file_path_from_scope(
     scope: Scope,
     parameters_root_path: impl AsRef<Path>,
     hardware_ids: &Ids,
+) -> PathBuf {
+    file_path_from_scope_in_location(
+        scope,
+        parameters_root_path,
+        location_directory_from_head_id(&hardware_ids.head_id),
+        hardware_ids,
+    )
+}
+
+fn file_path_from_scope_in_location(
+    scope: Scope,
+    parameters_root_path: impl AsRef<Path>,
+    location_directory: &str,
+    hardware_ids: &Ids,
 ) -> PathBuf {
     let directory = match scope.location {
         Location::All => parameters_root_path.as_ref().to_path_buf(),
-        Location::Current => parameters_root_path
-            .as_ref()
-            .join(location_directory_from_head_id(&hardware_ids.head_id)),
+        Location::Current => parameters_root_path.as_ref().join(location_directory),
     };
     match scope.id {
         Id::All => directory.join("default.json"),
@@ -243,7 +327,7 @@ fn location_directory_from_head_id(head_id: &str) -> &'static str {
     }
 }
 
-fn read_from_file(file_path: impl AsRef<Path>) -> Result<Value, SerializationError> {
+pub(crate) fn read_from_file(file_path: impl AsRef<Path>) -> Result<Value, SerializationError> {
     let file_contents =
         read_to_string(&file_path).map_err(|source| SerializationError::FileNotRead {
             source,
@@ -35,6 +35,18 @@ pub fn prune_equal_branches(own: &mut Value, other: &Value) {
     }
 }
 
+/// Removes objects left empty, e.g. by [`prune_equal_branches`], except for the root
+pub fn prune_empty_objects(value: &mut Value) {
+    if let Value::Object(object) = value {
+        for nested_value in object.values_mut() {
+            prune_empty_objects(nested_value);
+        }
+        object.retain(|_, nested_value| {
+            !matches!(nested_value, Value::Object(nested_object) if nested_object.is_empty())
+        });
+    }
+}
+
 pub fn clone_nested_value(value: &Value, path: &str) -> Option<Value> {
     if path.is_empty() {
         return Some(value.clone());
@@ -102,6 +114,15 @@ mod tests {
         assert_eq!(own, json!({"a":{"b":{"c":42}}}));
     }
 
+    #[test]
+    fn empty_objects_are_removed_recursively() {
+        let mut value = json!({"a":{"b":{"c":{}},"d":[{}]},"e":{},"f":42});
+
+        prune_empty_objects(&mut value);
+
+        assert_eq!(value, json!({"a":{"d":[{}]},"f":42}));
+    }
+
     #[test]
     fn branches_matching_the_path_are_retained_others_are_removed() {
         let value = json!({"a":{"b":{"c":42},"d":{"e":1337}}});
@@ -1,2 +1,4 @@
 pub mod directory;
 pub mod json;
+pub mod provenance;
+pub mod watcher;
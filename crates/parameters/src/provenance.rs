@@ -0,0 +1,200 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use hula_types::hardware::Ids;
+
+use crate::directory::{merge_layers, read_from_file, DirectoryError, Layer};
+
+/// Value of a parameter path as set by a single parameter file
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct LayerValue {
+    pub layer: Layer,
+    pub file: PathBuf,
+    pub value: Value,
+}
+
+/// All layers setting a parameter path, in merge order, and the resulting effective value
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Provenance {
+    pub path: String,
+    pub chain: Vec<LayerValue>,
+    pub effective: Option<Value>,
+}
+
+impl Provenance {
+    /// The last layer setting the path, i.e. the one which is effective for leaf values
+    pub fn origin(&self) -> Option<&LayerValue> {
+        self.chain.last()
+    }
+}
+
+pub fn explain(
+    parameters_root_path: impl AsRef<Path>,
+    hardware_ids: &Ids,
+    path: &str,
+) -> Result<Provenance, DirectoryError> {
+    let mut chain = Vec::new();
+    for layer in Layer::ALL {
+        let file = layer.file_path(&parameters_root_path, hardware_ids);
+        if layer != Layer::Default && !file.exists() {
+            continue;
+        }
+        let parameters = read_from_file(&file).map_err(|error| layer.error(error))?;
+        if let Some(value) = value_at_path(&parameters, path) {
+            chain.push(LayerValue {
+                layer,
+                file,
+                value: value.clone(),
+            });
+        }
+    }
+    let parameters = merge_layers(parameters_root_path, hardware_ids)?;
+    Ok(Provenance {
+        path: path.to_string(),
+        chain,
+        effective: value_at_path(&parameters, path).cloned(),
+    })
+}
+
+pub fn value_at_path<'value>(value: &'value Value, path: &str) -> Option<&'value Value> {
+    if path.is_empty() {
+        return Some(value);
+    }
+    path.split('.').try_fold(value, |value, key| match value {
+        Value::Object(object) => object.get(key),
+        Value::Array(array) => array.get(key.parse::<usize>().ok()?),
+        _ => None,
+    })
+}
+
+/// Flattens a value into its leaves, keyed by their dotted paths
+pub fn leaves(value: &Value) -> BTreeMap<String, Value> {
+    let mut leaves = BTreeMap::new();
+    extend_with_leaves(&mut leaves, "", value);
+    leaves
+}
+
+fn extend_with_leaves(leaves: &mut BTreeMap<String, Value>, path: &str, value: &Value) {
+    let child_path = |key: &str| {
+        if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{path}.{key}")
+        }
+    };
+    match value {
+        Value::Object(object) if !object.is_empty() => {
+            for (key, child) in object {
+                extend_with_leaves(leaves, &child_path(key), child);
+            }
+        }
+        _ => {
+            leaves.insert(path.to_string(), value.clone());
+        }
+    }
+}
+
+/// Leaves whose effective values differ between the given parameter sets
+pub fn diff<Label>(
+    parameters: &[(Label, Value)],
+) -> BTreeMap<String, Vec<(&Label, Option<Value>)>> {
+    let leaves_per_label: Vec<_> = parameters
+        .iter()
+        .map(|(label, parameters)| (label, leaves(parameters)))
+        .collect();
+    let mut paths: Vec<_> = leaves_per_label
+        .iter()
+        .flat_map(|(_, leaves)| leaves.keys().cloned())
+        .collect();
+    paths.sort();
+    paths.dedup();
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let values: Vec<_> = leaves_per_label
+                .iter()
+                .map(|(label, leaves)| (*label, leaves.get(&path).cloned()))
+                .collect();
+            let first = &values.first()?.1;
+            values
+                .iter()
+                .any(|(_, value)| value != first)
+                .then_some((path, values))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{create_dir, write};
+
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn chain_lists_every_layer_setting_the_path() {
+        let directory = tempfile::tempdir().unwrap();
+        let root = directory.path();
+        create_dir(root.join("nao_location")).unwrap();
+        write(
+            root.join("default.json"),
+            json!({ "walk": { "height": 0.23, "speed": 0.3 } }).to_string(),
+        )
+        .unwrap();
+        write(
+            root.join("nao_location/default.json"),
+            json!({ "walk": { "height": 0.22 } }).to_string(),
+        )
+        .unwrap();
+        write(
+            root.join("body.B1.json"),
+            json!({ "walk": { "height": 0.21 } }).to_string(),
+        )
+        .unwrap();
+        let ids = Ids {
+            body_id: "B1".to_string(),
+            head_id: "H1".to_string(),
+        };
+
+        let provenance = explain(root, &ids, "walk.height").unwrap();
+        let speed = explain(root, &ids, "walk.speed").unwrap();
+
+        let layers: Vec<_> = provenance
+            .chain
+            .iter()
+            .map(|layer_value| (layer_value.layer, layer_value.value.clone()))
+            .collect();
+        assert_eq!(
+            layers,
+            [
+                (Layer::Default, json!(0.23)),
+                (Layer::LocationDefault, json!(0.22)),
+                (Layer::Body, json!(0.21)),
+            ]
+        );
+        assert_eq!(provenance.effective, Some(json!(0.21)));
+        assert_eq!(speed.origin().unwrap().layer, Layer::Default);
+    }
+
+    #[test]
+    fn diff_contains_only_differing_leaves() {
+        let parameters = [
+            ("21", json!({ "a": { "b": 1, "c": 2 }, "d": [1, 2] })),
+            ("22", json!({ "a": { "b": 1, "c": 3 }, "d": [1, 2] })),
+        ];
+
+        let differences = diff(&parameters);
+
+        assert_eq!(differences.keys().collect::<Vec<_>>(), [&"a.c".to_string()]);
+        assert_eq!(
+            differences["a.c"],
+            [(&"21", Some(json!(2))), (&"22", Some(json!(3)))]
+        );
+    }
+}
@@ -0,0 +1,41 @@
+use std::{
+    fs::metadata,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use hula_types::hardware::Ids;
+
+use crate::directory::Layer;
+
+/// Detects changes of the parameter files merged for a robot by polling their modification times
+pub struct LayerWatcher {
+    files: Vec<PathBuf>,
+    modification_times: Vec<Option<SystemTime>>,
+}
+
+impl LayerWatcher {
+    pub fn new(parameters_root_path: impl AsRef<Path>, hardware_ids: &Ids) -> Self {
+        let files: Vec<_> = Layer::ALL
+            .into_iter()
+            .map(|layer| layer.file_path(&parameters_root_path, hardware_ids))
+            .collect();
+        let modification_times = files.iter().map(modification_time).collect();
+        Self {
+            files,
+            modification_times,
+        }
+    }
+
+    /// Whether a file was modified, created, or removed since the last call
+    pub fn has_changed(&mut self) -> bool {
+        let modification_times: Vec<_> = self.files.iter().map(modification_time).collect();
+        let has_changed = modification_times != self.modification_times;
+        self.modification_times = modification_times;
+        has_changed
+    }
+}
+
+fn modification_time(path: impl AsRef<Path>) -> Option<SystemTime> {
+    metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
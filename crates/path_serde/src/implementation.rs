@@ -1,5 +1,5 @@
 use std::{
-    collections::HashSet,
+    collections::{BTreeMap, HashSet},
     ops::{Deref, DerefMut, Range, RangeInclusive},
     sync::Arc,
     time::Duration,
@@ -12,7 +12,9 @@ use nalgebra::{
 use num_traits::real::Real;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::{deserialize, serialize, PathDeserialize, PathIntrospect, PathSerialize};
+use crate::{
+    deserialize, schema::FieldSchema, serialize, PathDeserialize, PathIntrospect, PathSerialize,
+};
 
 impl<T> PathSerialize for Box<T>
 where
@@ -53,6 +55,10 @@ where
     fn extend_with_fields(fields: &mut HashSet<String>, prefix: &str) {
         T::extend_with_fields(fields, prefix)
     }
+
+    fn extend_with_schema(schema: &mut BTreeMap<String, FieldSchema>, prefix: &str) {
+        T::extend_with_schema(schema, prefix)
+    }
 }
 
 impl<T> PathSerialize for Arc<T>
@@ -78,6 +84,10 @@ where
     fn extend_with_fields(fields: &mut HashSet<String>, prefix: &str) {
         T::extend_with_fields(fields, prefix)
     }
+
+    fn extend_with_schema(schema: &mut BTreeMap<String, FieldSchema>, prefix: &str) {
+        T::extend_with_schema(schema, prefix)
+    }
 }
 
 impl<T> PathSerialize for Option<T>
@@ -129,6 +139,10 @@ where
     fn extend_with_fields(fields: &mut HashSet<String>, prefix: &str) {
         T::extend_with_fields(fields, prefix)
     }
+
+    fn extend_with_schema(schema: &mut BTreeMap<String, FieldSchema>, prefix: &str) {
+        T::extend_with_schema(schema, prefix)
+    }
 }
 
 impl<T> PathSerialize for Range<T>
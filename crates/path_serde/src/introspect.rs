@@ -1,4 +1,6 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
+
+use crate::schema::FieldSchema;
 
 pub trait PathIntrospect {
     fn get_fields() -> HashSet<String> {
@@ -8,4 +10,12 @@ pub trait PathIntrospect {
     }
 
     fn extend_with_fields(fields: &mut HashSet<String>, prefix: &str);
+
+    fn get_schema() -> BTreeMap<String, FieldSchema> {
+        let mut schema = BTreeMap::default();
+        Self::extend_with_schema(&mut schema, "");
+        schema
+    }
+
+    fn extend_with_schema(_schema: &mut BTreeMap<String, FieldSchema>, _prefix: &str) {}
 }
@@ -7,4 +7,5 @@ pub mod deserialize;
 mod implementation;
 pub mod introspect;
 mod not_supported;
+pub mod schema;
 pub mod serialize;
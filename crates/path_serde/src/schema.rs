@@ -0,0 +1,291 @@
+use std::{collections::BTreeMap, fmt};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Unit, description and bounds declared via `#[path_serde(...)]` attributes of a field
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct FieldSchema {
+    pub unit: Option<String>,
+    pub description: Option<String>,
+    pub minimum: Option<f64>,
+    pub maximum: Option<f64>,
+    /// Name of a sibling field the value must not fall below
+    pub minimum_field: Option<String>,
+    /// Whether the `end` of the range must not fall below its `start`
+    pub ordered: bool,
+}
+
+impl FieldSchema {
+    pub fn is_bounded(&self) -> bool {
+        self.minimum.is_some()
+            || self.maximum.is_some()
+            || self.minimum_field.is_some()
+            || self.ordered
+    }
+
+    pub fn contains(&self, value: f64) -> bool {
+        self.minimum.is_none_or(|minimum| value >= minimum)
+            && self.maximum.is_none_or(|maximum| value <= maximum)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Violation {
+    pub path: String,
+    pub value: f64,
+    pub schema: FieldSchema,
+    /// Path and value of the sibling field the value falls below
+    pub minimum_field: Option<(String, f64)>,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let unit = self.schema.unit.as_deref().unwrap_or_default();
+        if let Some((minimum_path, minimum)) = &self.minimum_field {
+            return write!(
+                formatter,
+                "{} = {}{unit} is below {minimum_path} = {minimum}{unit}",
+                self.path, self.value
+            );
+        }
+        write!(
+            formatter,
+            "{} = {}{unit} is out of range [",
+            self.path, self.value
+        )?;
+        if let Some(minimum) = self.schema.minimum {
+            write!(formatter, "{minimum}{unit}")?;
+        }
+        write!(formatter, ", ")?;
+        if let Some(maximum) = self.schema.maximum {
+            write!(formatter, "{maximum}{unit}")?;
+        }
+        write!(formatter, "]")
+    }
+}
+
+/// Checks all bounded fields of the schema against the serialized value
+///
+/// Bounds apply to every number below a field, e.g. both ends of a range or all components of a
+/// vector. Durations are compared in seconds. Paths missing in the value, e.g. `None` options,
+/// are skipped. An empty path refers to the value itself.
+///
+/// A `minimum_field` is resolved next to the field. Ordered ranges are checked like an `end` with
+/// `start` as its minimum field.
+pub fn validate(schema: &BTreeMap<String, FieldSchema>, value: &Value) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    for (path, field_schema) in schema {
+        if !field_schema.is_bounded() {
+            continue;
+        }
+        let Some(field_value) = lookup(value, path) else {
+            continue;
+        };
+        if field_schema.minimum.is_some() || field_schema.maximum.is_some() {
+            check(path, field_schema, field_value, &mut violations);
+        }
+        if let Some(minimum_field) = &field_schema.minimum_field {
+            check_minimum_field(
+                path,
+                field_schema,
+                field_value,
+                minimum_field,
+                value,
+                &mut violations,
+            );
+        }
+        if field_schema.ordered {
+            let end_path = if path.is_empty() {
+                "end".to_string()
+            } else {
+                format!("{path}.end")
+            };
+            if let Some(end) = lookup(value, &end_path) {
+                check_minimum_field(
+                    &end_path,
+                    field_schema,
+                    end,
+                    "start",
+                    value,
+                    &mut violations,
+                );
+            }
+        }
+    }
+    violations
+}
+
+fn check_minimum_field(
+    path: &str,
+    schema: &FieldSchema,
+    field_value: &Value,
+    minimum_field: &str,
+    value: &Value,
+    violations: &mut Vec<Violation>,
+) {
+    let minimum_path = match path.rsplit_once('.') {
+        Some((parent, _)) => format!("{parent}.{minimum_field}"),
+        None if !path.is_empty() => minimum_field.to_string(),
+        None => return,
+    };
+    let (Some(field_value), Some(minimum)) = (
+        as_number(field_value),
+        lookup(value, &minimum_path).and_then(as_number),
+    ) else {
+        return;
+    };
+    if field_value < minimum {
+        violations.push(Violation {
+            path: path.to_string(),
+            value: field_value,
+            schema: schema.clone(),
+            minimum_field: Some((minimum_path, minimum)),
+        });
+    }
+}
+
+/// Numbers and durations in seconds
+fn as_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::Object(fields) if fields.len() == 2 => {
+            match (fields.get("secs"), fields.get("nanos")) {
+                (Some(Value::Number(seconds)), Some(Value::Number(nanoseconds))) => seconds
+                    .as_f64()
+                    .zip(nanoseconds.as_f64())
+                    .map(|(seconds, nanoseconds)| seconds + nanoseconds * 1e-9),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn lookup<'value>(value: &'value Value, path: &str) -> Option<&'value Value> {
+    if path.is_empty() {
+        return Some(value);
+    }
+    path.split('.')
+        .try_fold(value, |value, segment| match value {
+            Value::Object(fields) => fields.get(segment),
+            Value::Array(items) => items.get(segment.parse::<usize>().ok()?),
+            _ => None,
+        })
+}
+
+fn check(path: &str, schema: &FieldSchema, value: &Value, violations: &mut Vec<Violation>) {
+    if let Some(number) = as_number(value) {
+        if !schema.contains(number) {
+            violations.push(Violation {
+                path: path.to_string(),
+                value: number,
+                schema: schema.clone(),
+                minimum_field: None,
+            });
+        }
+        return;
+    }
+    match value {
+        Value::Object(fields) => {
+            for (key, field) in fields {
+                check(&format!("{path}.{key}"), schema, field, violations);
+            }
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                check(&format!("{path}.{index}"), schema, item, violations);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn reports_numbers_ranges_and_durations_out_of_bounds() {
+        let bounded = |minimum, maximum| FieldSchema {
+            minimum: Some(minimum),
+            maximum: Some(maximum),
+            ..Default::default()
+        };
+        let schema = BTreeMap::from([
+            ("step.height".to_string(), bounded(0.0, 0.1)),
+            ("step.duration".to_string(), bounded(0.1, 1.0)),
+            ("search.range".to_string(), bounded(-1.0, 1.0)),
+            ("search.missing".to_string(), bounded(-1.0, 1.0)),
+        ]);
+        let value = json!({
+            "step": { "height": 0.05, "duration": { "secs": 1, "nanos": 500000000 } },
+            "search": { "range": { "start": -2.0, "end": 0.5 }, "missing": null },
+        });
+
+        let violations: Vec<_> = validate(&schema, &value)
+            .into_iter()
+            .map(|violation| (violation.path, violation.value))
+            .collect();
+
+        assert_eq!(
+            violations,
+            [
+                ("search.range.start".to_string(), -2.0),
+                ("step.duration".to_string(), 1.5)
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_fields_below_their_minimum_field_and_unordered_ranges() {
+        let at_least = |field: &str| FieldSchema {
+            minimum_field: Some(field.to_string()),
+            ..Default::default()
+        };
+        let schema = BTreeMap::from([
+            (
+                "walk.max_step_duration".to_string(),
+                at_least("min_step_duration"),
+            ),
+            ("walk.max_speed".to_string(), at_least("min_speed")),
+            (
+                "search.range".to_string(),
+                FieldSchema {
+                    ordered: true,
+                    ..Default::default()
+                },
+            ),
+        ]);
+        let value = json!({
+            "walk": {
+                "min_step_duration": { "secs": 0, "nanos": 300000000 },
+                "max_step_duration": { "secs": 0, "nanos": 200000000 },
+                "min_speed": 0.1,
+                "max_speed": 0.3,
+            },
+            "search": { "range": { "start": 1.0, "end": 0.5 } },
+        });
+
+        let violations: Vec<_> = validate(&schema, &value)
+            .into_iter()
+            .map(|violation| (violation.path, violation.minimum_field.unwrap().0))
+            .collect();
+
+        assert_eq!(
+            violations,
+            [
+                (
+                    "search.range.end".to_string(),
+                    "search.range.start".to_string()
+                ),
+                (
+                    "walk.max_step_duration".to_string(),
+                    "walk.min_step_duration".to_string()
+                ),
+            ]
+        );
+    }
+}
@@ -1,8 +1,8 @@
 use proc_macro2::TokenStream;
 use quote::ToTokens;
 use syn::{
-    parenthesized, parse::Parse as _, parse_quote, Data, DataStruct, DeriveInput, Ident, Index,
-    Result, Token, Type, WherePredicate,
+    parenthesized, parse::Parse as _, parse_quote, Data, DataStruct, DeriveInput, Expr, ExprLit,
+    Ident, Index, Lit, LitStr, Meta, Result, Token, Type, WherePredicate,
 };
 
 use crate::bound::infer_predicates;
@@ -138,18 +138,58 @@ pub struct Field {
     pub skip_deserialize: bool,
     pub skip_introspect: bool,
     pub is_leaf: bool,
+    pub schema: FieldSchema,
     pub identifier: Identifier,
     pub ty: Type,
 }
 
+/// Unit, description, bounds and ordering of a field, the description defaults to the doc comment
+#[derive(Debug, Default)]
+pub struct FieldSchema {
+    pub unit: Option<LitStr>,
+    pub description: Option<String>,
+    pub minimum: Option<Expr>,
+    pub maximum: Option<Expr>,
+    pub minimum_field: Option<LitStr>,
+    pub ordered: bool,
+}
+
+impl FieldSchema {
+    pub fn is_empty(&self) -> bool {
+        self.unit.is_none()
+            && self.description.is_none()
+            && self.minimum.is_none()
+            && self.maximum.is_none()
+            && self.minimum_field.is_none()
+            && !self.ordered
+    }
+}
+
 impl Field {
     fn try_from_ast(index: usize, field: &syn::Field) -> Result<Self> {
         let mut skip_serialize = false;
         let mut skip_deserialize = false;
         let mut skip_introspect = false;
         let mut is_leaf = false;
+        let mut schema = FieldSchema::default();
+        let mut documentation = Vec::new();
 
         for attribute in &field.attrs {
+            if attribute.path().is_ident("doc") {
+                if let Meta::NameValue(name_value) = &attribute.meta {
+                    if let Expr::Lit(ExprLit {
+                        lit: Lit::Str(line),
+                        ..
+                    }) = &name_value.value
+                    {
+                        let line = line.value();
+                        if !line.trim().is_empty() {
+                            documentation.push(line.trim().to_string());
+                        }
+                    }
+                }
+                continue;
+            }
             if !attribute.path().is_ident("path_serde") {
                 continue;
             }
@@ -166,6 +206,18 @@ impl Field {
                     skip_introspect = true;
                 } else if meta.path.is_ident("leaf") {
                     is_leaf = true;
+                } else if meta.path.is_ident("unit") {
+                    schema.unit = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("description") {
+                    schema.description = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("minimum") {
+                    schema.minimum = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("maximum") {
+                    schema.maximum = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("minimum_field") {
+                    schema.minimum_field = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("ordered") {
+                    schema.ordered = true;
                 } else {
                     return Err(meta.error("unknown attribute"));
                 }
@@ -178,12 +230,16 @@ impl Field {
             None => Identifier::Index(Index::from(index)),
         };
         let ty = field.ty.clone();
+        if schema.description.is_none() && !documentation.is_empty() {
+            schema.description = Some(documentation.join(" "));
+        }
 
         Ok(Field {
             skip_serialize,
             skip_deserialize,
             skip_introspect,
             is_leaf,
+            schema,
             identifier,
             ty,
         })
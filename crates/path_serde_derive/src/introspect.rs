@@ -2,7 +2,10 @@ use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{DeriveInput, Result};
 
-use crate::{bound::ExtendGenerics, container::Container};
+use crate::{
+    bound::ExtendGenerics,
+    container::{Container, FieldSchema},
+};
 
 pub fn derive_path_introspect(mut input: DeriveInput) -> Result<TokenStream> {
     let container = Container::try_from_ast(&input)?;
@@ -16,12 +19,20 @@ pub fn derive_path_introspect(mut input: DeriveInput) -> Result<TokenStream> {
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
     let extend_with_fields = generate_extend_with_fields(&container);
+    let extend_with_schema = generate_extend_with_schema(&container);
 
     Ok(quote! {
         impl #impl_generics path_serde::PathIntrospect for #name #ty_generics #where_clause {
             fn extend_with_fields(fields: &mut std::collections::HashSet<String>, prefix: &str)  {
                 #(#extend_with_fields)*
             }
+
+            fn extend_with_schema(
+                schema: &mut std::collections::BTreeMap<String, path_serde::schema::FieldSchema>,
+                prefix: &str,
+            ) {
+                #(#extend_with_schema)*
+            }
         }
     })
 }
@@ -58,3 +69,70 @@ fn generate_extend_with_fields(container: &Container) -> Vec<TokenStream> {
         }))
         .collect()
 }
+
+fn generate_extend_with_schema(container: &Container) -> Vec<TokenStream> {
+    let described_fields = container
+        .fields
+        .iter()
+        .filter(|field| !field.skip_introspect && !field.schema.is_empty());
+    let children = container
+        .fields
+        .iter()
+        .filter(|field| !field.skip_introspect && !field.is_leaf);
+
+    described_fields
+        .map(|field| {
+            let field_name = &field.identifier.to_field_name();
+            let FieldSchema {
+                unit,
+                description,
+                minimum,
+                maximum,
+                minimum_field,
+                ordered,
+            } = &field.schema;
+            let unit = option_to_tokens(unit.as_ref().map(|unit| quote! { #unit.to_string() }));
+            let description = option_to_tokens(
+                description
+                    .as_ref()
+                    .map(|description| quote! { #description.to_string() }),
+            );
+            let minimum =
+                option_to_tokens(minimum.as_ref().map(|minimum| quote! { f64::from(#minimum) }));
+            let maximum =
+                option_to_tokens(maximum.as_ref().map(|maximum| quote! { f64::from(#maximum) }));
+            let minimum_field = option_to_tokens(
+                minimum_field
+                    .as_ref()
+                    .map(|minimum_field| quote! { #minimum_field.to_string() }),
+            );
+            quote! {
+                schema.insert(
+                    format!("{prefix}{}", #field_name),
+                    path_serde::schema::FieldSchema {
+                        unit: #unit,
+                        description: #description,
+                        minimum: #minimum,
+                        maximum: #maximum,
+                        minimum_field: #minimum_field,
+                        ordered: #ordered,
+                    },
+                );
+            }
+        })
+        .chain(children.map(|field| {
+            let field_name = &field.identifier.to_field_name();
+            let ty = &field.ty;
+            quote! {
+                <#ty as path_serde::PathIntrospect>::extend_with_schema(schema, &format!("{prefix}{}.", #field_name));
+            }
+        }))
+        .collect()
+}
+
+fn option_to_tokens(value: Option<TokenStream>) -> TokenStream {
+    match value {
+        Some(value) => quote! { Some(#value) },
+        None => quote! { None },
+    }
+}
@@ -2,6 +2,7 @@ use coordinate_systems::{Camera, Ground, Head, Pixel, Robot};
 use linear_algebra::{IntoFramed, Isometry3, Rotation3, Vector2};
 use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
 use serde::{Deserialize, Serialize};
+use types::lens_distortion::LensDistortion;
 
 use crate::{
     camera_projection::{CameraProjection, InverseCameraProjection},
@@ -74,8 +75,15 @@ impl CameraMatrix {
         }
     }
 
+    pub fn with_lens_distortion(mut self, lens_distortion: LensDistortion) -> Self {
+        self.intrinsics.distortion = lens_distortion;
+        self.compute_memoized();
+        self
+    }
+
     pub fn compute_memoized(&mut self) {
         self.ground_to_camera = self.head_to_camera * self.robot_to_head * self.ground_to_robot;
+        self.horizon = Horizon::from_parameters(self.ground_to_camera, &self.intrinsics);
         self.ground_to_pixel = CameraProjection::new(self.ground_to_camera, self.intrinsics);
         self.pixel_to_ground =
             CameraProjection::new(self.ground_to_camera, self.intrinsics).inverse(0.0);
@@ -57,6 +57,7 @@ impl<From> CameraProjection<From> {
 )]
 pub struct InverseCameraProjection<To> {
     back_project: Transform<Pixel, To, nalgebra::Matrix3<f32>>,
+    intrinsic: Intrinsic,
     z: f32,
 }
 
@@ -76,15 +77,18 @@ impl<To> InverseCameraProjection<To> {
 
         Self {
             back_project: Transform::wrap(inverse),
+            intrinsic: forward.intrinsic,
             z,
         }
     }
 
     pub fn back_project_unchecked(&self, point: Point2<Pixel>) -> Point3<To> {
+        let point = self.intrinsic.undistort(point);
         let point_to = self.back_project.inner * point.inner.to_homogeneous();
         point![point_to.x / point_to.z, point_to.y / point_to.z, self.z]
     }
 
+    /// Back projection of undistorted pixels
     pub fn as_matrix(&self) -> nalgebra::Matrix3<f32> {
         self.back_project.inner
     }
@@ -94,6 +98,7 @@ impl<To> InverseCameraProjection<To> {
 mod tests {
     use super::*;
     use coordinate_systems::Ground;
+    use types::lens_distortion::LensDistortion;
 
     #[test]
     fn invertable() {
@@ -103,4 +108,35 @@ mod tests {
         );
         camera_projection.inverse(0.0);
     }
+
+    #[test]
+    fn back_projection_inverts_distorted_projection() {
+        let intrinsic = Intrinsic::new(nalgebra::vector![600.0, 600.0], point![320.0, 240.0])
+            .with_distortion(LensDistortion::Fisheye {
+                k1: 0.05,
+                k2: -0.01,
+                k3: 0.0,
+                k4: 0.0,
+            });
+        // camera 0.5m above the ground origin looking along the x axis of the ground
+        let ground_to_camera_rotation =
+            nalgebra::Rotation3::from_matrix_unchecked(nalgebra::matrix![
+                0.0, -1.0, 0.0;
+                0.0, 0.0, -1.0;
+                1.0, 0.0, 0.0;
+            ]);
+        let camera_projection = CameraProjection::<Ground>::new(
+            Isometry3::wrap(nalgebra::Isometry3::from_parts(
+                nalgebra::Translation3::new(0.0, 0.5, 0.0),
+                nalgebra::UnitQuaternion::from_rotation_matrix(&ground_to_camera_rotation),
+            )),
+            intrinsic,
+        );
+        let point = point![2.0, 0.7, 0.0];
+
+        let pixel = camera_projection.project(point);
+        let back_projected = camera_projection.inverse(0.0).back_project_unchecked(pixel);
+
+        approx::assert_relative_eq!(back_projected, point, epsilon = 1e-3);
+    }
 }
@@ -57,7 +57,8 @@ impl Horizon {
         let vanishing_point = ground_to_camera * ground_front;
         let vanishing_point_image = intrinsics.transform(vanishing_point);
 
-        Some(Vector2::wrap(vanishing_point_image.xy().inner).as_point())
+        // with lens distortion the horizon is curved, it is linearized at the distorted vanishing point
+        Some(intrinsics.distort(Vector2::wrap(vanishing_point_image.xy().inner).as_point()))
     }
 
     fn find_horizon_normal(
@@ -2,6 +2,7 @@ use coordinate_systems::{Camera, NormalizedDeviceCoordinates, Pixel};
 use linear_algebra::{point, vector, Point2, Vector2, Vector3};
 use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
 use serde::{Deserialize, Serialize};
+use types::lens_distortion::LensDistortion;
 
 #[derive(
     Clone,
@@ -17,6 +18,8 @@ use serde::{Deserialize, Serialize};
 pub struct Intrinsic {
     pub focals: nalgebra::Vector2<f32>,
     pub optical_center: Point2<Pixel>,
+    #[serde(default)]
+    pub distortion: LensDistortion,
 }
 
 impl Default for Intrinsic {
@@ -24,6 +27,7 @@ impl Default for Intrinsic {
         Self {
             focals: nalgebra::vector![1.0, 1.0],
             optical_center: point![0.0, 0.0],
+            distortion: LensDistortion::None,
         }
     }
 }
@@ -33,9 +37,14 @@ impl Intrinsic {
         Self {
             focals: focal_length,
             optical_center,
+            distortion: LensDistortion::None,
         }
     }
 
+    pub fn with_distortion(self, distortion: LensDistortion) -> Self {
+        Self { distortion, ..self }
+    }
+
     pub fn as_matrix(&self) -> nalgebra::Matrix3x4<f32> {
         nalgebra::matrix![
             self.focals.x, 0.0, self.optical_center.x(), 0.0;
@@ -44,6 +53,7 @@ impl Intrinsic {
         ]
     }
 
+    /// Pinhole projection in homogeneous coordinates, lens distortion is not applied
     pub fn transform(&self, ray: Vector3<Camera>) -> Vector3<NormalizedDeviceCoordinates> {
         let (x, y, z) = (ray.x(), ray.y(), ray.z());
 
@@ -55,15 +65,60 @@ impl Intrinsic {
     }
 
     pub fn project(&self, ray: Vector3<Camera>) -> Point2<Pixel> {
-        let projected = self.transform(ray);
-        point![projected.x() / projected.z(), projected.y() / projected.z()]
+        let normalized = nalgebra::point![ray.x() / ray.z(), ray.y() / ray.z()];
+        self.normalized_to_pixel(self.distortion.distort(normalized))
     }
 
     pub fn bearing(&self, pixel: Point2<Pixel>) -> Vector3<Camera> {
-        let x = (pixel.x() - self.optical_center.x()) / self.focals.x;
-        let y = (pixel.y() - self.optical_center.y()) / self.focals.y;
+        let normalized = self.distortion.undistort(self.pixel_to_normalized(pixel));
+
+        vector![normalized.x, normalized.y, 1.0]
+    }
+
+    /// Maps a pixel of an ideal pinhole camera to the pixel observed through the lens
+    pub fn distort(&self, pixel: Point2<Pixel>) -> Point2<Pixel> {
+        if self.distortion == LensDistortion::None {
+            return pixel;
+        }
+        self.normalized_to_pixel(self.distortion.distort(self.pixel_to_normalized(pixel)))
+    }
 
-        vector![x, y, 1.0]
+    /// Maps a pixel observed through the lens to the pixel of an ideal pinhole camera
+    pub fn undistort(&self, pixel: Point2<Pixel>) -> Point2<Pixel> {
+        if self.distortion == LensDistortion::None {
+            return pixel;
+        }
+        self.normalized_to_pixel(self.distortion.undistort(self.pixel_to_normalized(pixel)))
+    }
+
+    /// Derivative of the undistorted pixel with respect to the observed pixel
+    pub fn undistortion_jacobian(&self, pixel: Point2<Pixel>) -> nalgebra::Matrix2<f32> {
+        if self.distortion == LensDistortion::None {
+            return nalgebra::Matrix2::identity();
+        }
+        const STEP: f32 = 0.5;
+        let horizontal = vector![STEP, 0.0];
+        let vertical = vector![0.0, STEP];
+        let derivative_x = (self.undistort(pixel + horizontal)
+            - self.undistort(pixel - horizontal))
+            / (2.0 * STEP);
+        let derivative_y =
+            (self.undistort(pixel + vertical) - self.undistort(pixel - vertical)) / (2.0 * STEP);
+        nalgebra::Matrix2::from_columns(&[derivative_x.inner, derivative_y.inner])
+    }
+
+    fn pixel_to_normalized(&self, pixel: Point2<Pixel>) -> nalgebra::Point2<f32> {
+        nalgebra::point![
+            (pixel.x() - self.optical_center.x()) / self.focals.x,
+            (pixel.y() - self.optical_center.y()) / self.focals.y
+        ]
+    }
+
+    fn normalized_to_pixel(&self, normalized: nalgebra::Point2<f32>) -> Point2<Pixel> {
+        point![
+            self.focals.x * normalized.x + self.optical_center.x(),
+            self.focals.y * normalized.y + self.optical_center.y()
+        ]
     }
 
     pub fn calculate_field_of_view(
@@ -98,4 +153,48 @@ mod tests {
         let bearing = intrinsic.bearing(pixel);
         assert_eq!(bearing, vector![0.0, 0.0, 1.0]);
     }
+
+    #[test]
+    fn distorted_projection_is_inverted_by_bearing() {
+        let intrinsic = Intrinsic::new(nalgebra::vector![600.0, 600.0], point![320.0, 240.0])
+            .with_distortion(LensDistortion::RadialTangential {
+                k1: -0.1,
+                k2: 0.02,
+                k3: 0.0,
+                p1: 0.001,
+                p2: 0.0,
+            });
+        let ray = vector![0.4, -0.3, 1.0];
+
+        let pixel = intrinsic.project(ray);
+        let pinhole_pixel = Intrinsic::new(intrinsic.focals, intrinsic.optical_center).project(ray);
+
+        assert!((pixel - pinhole_pixel).norm() > 1.0);
+        approx::assert_relative_eq!(intrinsic.undistort(pixel), pinhole_pixel, epsilon = 1e-2);
+        approx::assert_relative_eq!(intrinsic.bearing(pixel), ray, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn undistortion_jacobian_linearizes_undistortion() {
+        let intrinsic = Intrinsic::new(nalgebra::vector![600.0, 600.0], point![320.0, 240.0])
+            .with_distortion(LensDistortion::RadialTangential {
+                k1: -0.1,
+                k2: 0.02,
+                k3: 0.0,
+                p1: 0.001,
+                p2: 0.0,
+            });
+        let pixel = point![550.0, 80.0];
+        let offset = vector![2.0, -1.0];
+
+        let jacobian = intrinsic.undistortion_jacobian(pixel);
+        let linearized = intrinsic.undistort(pixel).inner.coords + jacobian * offset.inner;
+
+        assert!((jacobian - nalgebra::Matrix2::identity()).norm() > 1e-3);
+        approx::assert_relative_eq!(
+            intrinsic.undistort(pixel + offset).inner.coords,
+            linearized,
+            epsilon = 1e-2
+        );
+    }
 }
@@ -152,10 +152,8 @@ impl Projection for CameraMatrix {
         ground_coordinates: Point2<Ground>,
         noise: Vector2<Pixel>,
     ) -> Result<Matrix2<f32>, Error> {
-        let gamma = self
-            .ground_to_pixel
-            .transform(point![ground_coordinates.x(), ground_coordinates.y(), 0.0])
-            .z();
+        let ground_point = point![ground_coordinates.x(), ground_coordinates.y(), 0.0];
+        let gamma = self.ground_to_pixel.transform(ground_point).z();
         let inverse = self.pixel_to_ground.as_matrix();
 
         let x = ground_coordinates.x();
@@ -167,7 +165,14 @@ impl Projection for CameraMatrix {
                 inverse.m21 - inverse.m31 * y, inverse.m22 - inverse.m32 * y;
             ];
 
-        Ok(noise_projection * Matrix2::from_diagonal(&noise.inner) * noise_projection.transpose())
+        // the noise is given for observed pixels, the projection acts on undistorted pixels
+        let undistortion = self
+            .intrinsics
+            .undistortion_jacobian(self.ground_to_pixel.project(ground_point));
+        let undistorted_noise =
+            undistortion * Matrix2::from_diagonal(&noise.inner) * undistortion.transpose();
+
+        Ok(noise_projection * undistorted_noise * noise_projection.transpose())
     }
 
     fn bearing(&self, pixel_coordinates: Point2<Pixel>) -> Vector3<Camera> {
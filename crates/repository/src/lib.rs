@@ -22,6 +22,7 @@ pub mod sdk;
 pub mod symlink;
 pub mod team;
 pub mod upload;
+pub mod words;
 
 /// The HULK repository.
 pub struct Repository {
@@ -0,0 +1,38 @@
+use color_eyre::{
+    eyre::{bail, Context},
+    Result,
+};
+use tokio::process::Command;
+
+use crate::Repository;
+
+impl Repository {
+    /// Renders the words for phrases into `etc/sounds/words` with the Google text to speech API
+    ///
+    /// Without an `api_key`, the text to speech tool reads it from
+    /// `tools/TextToSpeech/.api_key`.
+    pub async fn generate_words(
+        &self,
+        voice_name: &str,
+        api_key: Option<&str>,
+        force: bool,
+    ) -> Result<()> {
+        let tool_directory = self.root.join("tools/TextToSpeech");
+        let mut command = Command::new(tool_directory.join("generate_words.py"));
+        command
+            .current_dir(&tool_directory)
+            .arg("--voice_name")
+            .arg(voice_name);
+        if let Some(api_key) = api_key {
+            command.arg("--api_key").arg(api_key);
+        }
+        if force {
+            command.arg("--force");
+        }
+        let status = command.status().await.wrap_err("failed to spawn command")?;
+        if !status.success() {
+            bail!("generate_words.py failed with {status}");
+        }
+        Ok(())
+    }
+}
@@ -113,7 +113,7 @@ impl Cycler {
             .collect::<Result<Vec<_>, _>>()?;
 
         let mut cycler = Self {
-            name: cycler_manifest.name.to_string(),
+            name: cycler_manifest.name,
             kind: cycler_manifest.kind,
             instances,
             setup_nodes,
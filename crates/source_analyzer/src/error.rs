@@ -21,6 +21,18 @@ pub enum Error {
     InvalidModulePath,
     #[error("`{node}` requires output `{output}`, but it is never produced")]
     MissingOutput { node: String, output: String },
+    #[error("failed to parse manifest")]
+    ManifestParse(#[source] toml::de::Error),
+    #[error("invalid manifest at `{path}`")]
+    Manifest { source: Box<Error>, path: PathBuf },
+    #[error("cycler `{cycler}` is declared more than once")]
+    DuplicateCycler { cycler: String },
+    #[error("cycler `{cycler}` has no instances")]
+    MissingInstances { cycler: String },
+    #[error("cycler `{cycler}` declares instance `{instance}` more than once")]
+    DuplicateInstance { cycler: String, instance: String },
+    #[error("cycler `{cycler}` declares node `{node}` more than once")]
+    DuplicateNode { cycler: String, node: String },
     #[error("failed to sort nodes, circular dependencies detected: {}", .0.first().map(|cycle| cycle.join(", ")).unwrap_or("failed to determine loop".to_string()))]
     CircularDependency(Vec<Vec<String>>),
 }
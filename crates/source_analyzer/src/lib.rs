@@ -1,6 +1,7 @@
 pub mod contexts;
 pub mod cyclers;
 pub mod error;
+pub mod lint;
 pub mod manifest;
 pub mod node;
 pub mod path;
@@ -0,0 +1,563 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::PathBuf,
+};
+
+use quote::ToTokens;
+use serde_json::Value;
+use syn::{
+    visit::{self, Visit},
+    visit_mut::{self, VisitMut},
+    Expr, ExprCall, ExprField, ExprMethodCall, Ident, Member, Type,
+};
+
+use crate::{
+    contexts::Field,
+    cyclers::{Cycler, CyclerName, Cyclers},
+    error::Error,
+    node::{parse_rust_file, Node, NodeName},
+    path::Path,
+};
+
+/// Main outputs which are consumed by the generated framework code instead of nodes
+const FRAMEWORK_CONSUMED_OUTPUTS: &[&str] = &["cycle_time"];
+
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Finding {
+    pub severity: Severity,
+    pub cycler: CyclerName,
+    pub node: NodeName,
+    pub file_path: PathBuf,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl Finding {
+    fn new(
+        severity: Severity,
+        cycler: &Cycler,
+        node: &Node,
+        name: &Ident,
+        message: String,
+    ) -> Self {
+        let location = name.span().start();
+        Self {
+            severity,
+            cycler: cycler.name.clone(),
+            node: node.name.clone(),
+            file_path: node.file_path.clone(),
+            line: location.line,
+            column: location.column + 1,
+            message,
+        }
+    }
+}
+
+/// Statically checks the dataflow between all nodes of the given cyclers
+///
+/// Reports main outputs that are never consumed, additional outputs that are never filled,
+/// parameters missing in the default parameters, and paths declared with conflicting types.
+pub fn lint(cyclers: &Cyclers, default_parameters: &Value) -> Result<Vec<Finding>, Error> {
+    let mut findings = Vec::new();
+    findings.extend(unused_main_outputs(cyclers));
+    findings.extend(unfilled_additional_outputs(cyclers)?);
+    findings.extend(missing_parameters(cyclers, default_parameters));
+    findings.extend(mismatching_types(cyclers));
+    findings.sort_by(|left, right| {
+        (&left.file_path, left.line, left.column).cmp(&(&right.file_path, right.line, right.column))
+    });
+    Ok(findings)
+}
+
+fn unused_main_outputs(cyclers: &Cyclers) -> Vec<Finding> {
+    let consumed: BTreeSet<(&str, &str)> = cyclers
+        .cyclers
+        .iter()
+        .flat_map(|cycler| {
+            cycler.iter_nodes().flat_map(move |node| {
+                node.contexts.cycle_context.iter().filter_map(move |field| {
+                    let (cycler_instance, path) = match field {
+                        Field::HistoricInput { path, .. } => (None, path),
+                        Field::Input {
+                            cycler_instance,
+                            path,
+                            ..
+                        }
+                        | Field::RequiredInput {
+                            cycler_instance,
+                            path,
+                            ..
+                        } => (cycler_instance.as_deref(), path),
+                        Field::PerceptionInput {
+                            cycler_instance,
+                            path,
+                            ..
+                        } => (Some(cycler_instance.as_str()), path),
+                        _ => return None,
+                    };
+                    let producing_cycler = match cycler_instance {
+                        Some(instance) => cyclers
+                            .cyclers
+                            .iter()
+                            .find(|cycler| cycler.instances.iter().any(|name| name == instance))?,
+                        None => cycler,
+                    };
+                    let output = path.segments.first()?;
+                    Some((producing_cycler.name.as_str(), output.name.as_str()))
+                })
+            })
+        })
+        .collect();
+
+    cyclers
+        .cyclers
+        .iter()
+        .flat_map(|cycler| {
+            let consumed = &consumed;
+            cycler.iter_nodes().flat_map(move |node| {
+                node.contexts
+                    .main_outputs
+                    .iter()
+                    .filter_map(move |field| match field {
+                        Field::MainOutput { name, .. } => {
+                            let output = name.to_string();
+                            if consumed.contains(&(cycler.name.as_str(), output.as_str()))
+                                || FRAMEWORK_CONSUMED_OUTPUTS.contains(&output.as_str())
+                            {
+                                return None;
+                            }
+                            Some(Finding::new(
+                                Severity::Warning,
+                                cycler,
+                                node,
+                                name,
+                                format!("main output `{output}` is never consumed by any node"),
+                            ))
+                        }
+                        _ => None,
+                    })
+            })
+        })
+        .collect()
+}
+
+fn unfilled_additional_outputs(cyclers: &Cyclers) -> Result<Vec<Finding>, Error> {
+    let mut findings = Vec::new();
+    let mut visited_files = BTreeSet::new();
+    for cycler in &cyclers.cyclers {
+        for node in cycler.iter_nodes() {
+            if !visited_files.insert(&node.file_path) {
+                continue;
+            }
+            let file = parse_rust_file(&node.file_path)?;
+            let mut filled = FilledOutputs::default();
+            filled.visit_file(&file);
+            for field in &node.contexts.cycle_context {
+                let Field::AdditionalOutput { name, path, .. } = field else {
+                    continue;
+                };
+                if !filled.names.contains(&name.to_string()) {
+                    findings.push(Finding::new(
+                        Severity::Warning,
+                        cycler,
+                        node,
+                        name,
+                        format!(
+                            "additional output `{name}` at `{}` is never filled",
+                            path_to_string(path)
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+    Ok(findings)
+}
+
+/// Methods of `AdditionalOutput` which write data into it
+const FILLING_METHODS: &[&str] = &["fill_if_subscribed", "mutate_if_subscribed"];
+
+/// Collects the names of fields which are filled via `AdditionalOutput` methods
+///
+/// Fields passed as arguments to other functions, e.g. `&mut context.path_obstacles_output`,
+/// count as filled since the callee is not analyzed.
+#[derive(Default)]
+struct FilledOutputs {
+    names: BTreeSet<String>,
+}
+
+impl<'ast> Visit<'ast> for FilledOutputs {
+    fn visit_expr_method_call(&mut self, call: &'ast ExprMethodCall) {
+        if FILLING_METHODS.contains(&call.method.to_string().as_str()) {
+            if let Some(name) = field_name(&call.receiver) {
+                self.names.insert(name);
+            }
+        }
+        self.names.extend(call.args.iter().filter_map(field_name));
+        visit::visit_expr_method_call(self, call);
+    }
+
+    fn visit_expr_call(&mut self, call: &'ast ExprCall) {
+        self.names.extend(call.args.iter().filter_map(field_name));
+        visit::visit_expr_call(self, call);
+    }
+}
+
+/// Name of the accessed field, looking through references and parentheses
+fn field_name(expression: &Expr) -> Option<String> {
+    match expression {
+        Expr::Field(ExprField {
+            member: Member::Named(name),
+            ..
+        }) => Some(name.to_string()),
+        Expr::Reference(reference) => field_name(&reference.expr),
+        Expr::Paren(parenthesized) => field_name(&parenthesized.expr),
+        _ => None,
+    }
+}
+
+fn missing_parameters(cyclers: &Cyclers, default_parameters: &Value) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    for cycler in &cyclers.cyclers {
+        for node in cycler.iter_nodes() {
+            for field in node
+                .contexts
+                .creation_context
+                .iter()
+                .chain(&node.contexts.cycle_context)
+            {
+                let Field::Parameter { name, path, .. } = field else {
+                    continue;
+                };
+                for path in path.expand_variables(&cycler.instances) {
+                    if !parameter_exists(&path, default_parameters) {
+                        findings.push(Finding::new(
+                            Severity::Error,
+                            cycler,
+                            node,
+                            name,
+                            format!(
+                                "parameter `{}` is missing in the default parameters",
+                                path_to_string(&path)
+                            ),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+    findings
+}
+
+fn parameter_exists(path: &Path, parameters: &Value) -> bool {
+    let mut value = parameters;
+    for segment in &path.segments {
+        match value.get(&segment.name) {
+            Some(Value::Null) if segment.is_optional => return true,
+            Some(child) => value = child,
+            None => return false,
+        }
+    }
+    true
+}
+
+fn mismatching_types(cyclers: &Cyclers) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    let mut parameter_types: BTreeMap<String, (String, &NodeName)> = BTreeMap::new();
+    for cycler in &cyclers.cyclers {
+        let main_output_types: BTreeMap<String, String> = cycler
+            .iter_nodes()
+            .flat_map(|node| &node.contexts.main_outputs)
+            .filter_map(|field| match field {
+                Field::MainOutput { data_type, name } => {
+                    Some((name.to_string(), type_to_string(data_type)))
+                }
+                _ => None,
+            })
+            .collect();
+        let mut additional_output_types: BTreeMap<String, (String, &NodeName)> = BTreeMap::new();
+
+        for node in cycler.iter_nodes() {
+            for field in node
+                .contexts
+                .creation_context
+                .iter()
+                .chain(&node.contexts.cycle_context)
+            {
+                match field {
+                    Field::HistoricInput {
+                        data_type,
+                        name,
+                        path,
+                    }
+                    | Field::Input {
+                        cycler_instance: None,
+                        data_type,
+                        name,
+                        path,
+                    }
+                    | Field::RequiredInput {
+                        cycler_instance: None,
+                        data_type,
+                        name,
+                        path,
+                    } if path.segments.len() == 1 => {
+                        let output = &path.segments[0].name;
+                        let data_type = type_to_string(data_type);
+                        match main_output_types.get(output) {
+                            Some(output_type) if *output_type != data_type => {
+                                findings.push(Finding::new(
+                                    Severity::Error,
+                                    cycler,
+                                    node,
+                                    name,
+                                    format!("`{output}` is consumed as `{data_type}` but produced as `{output_type}`"),
+                                ));
+                            }
+                            _ => {}
+                        }
+                    }
+                    Field::AdditionalOutput {
+                        data_type,
+                        name,
+                        path,
+                    } => {
+                        let path = path_to_string(path);
+                        let data_type = type_to_string(data_type);
+                        match additional_output_types.get(&path) {
+                            Some((other_type, other_node)) if *other_type != data_type => {
+                                findings.push(Finding::new(
+                                    Severity::Error,
+                                    cycler,
+                                    node,
+                                    name,
+                                    format!("additional output `{path}` is declared as `{data_type}` but as `{other_type}` in `{other_node}`"),
+                                ));
+                            }
+                            Some(_) => {}
+                            None => {
+                                additional_output_types.insert(path, (data_type, &node.name));
+                            }
+                        }
+                    }
+                    Field::Parameter {
+                        data_type,
+                        name,
+                        path,
+                    } => {
+                        let path = path_to_string(path);
+                        let data_type = type_to_string(data_type);
+                        match parameter_types.get(&path) {
+                            Some((other_type, other_node)) if *other_type != data_type => {
+                                findings.push(Finding::new(
+                                    Severity::Error,
+                                    cycler,
+                                    node,
+                                    name,
+                                    format!("parameter `{path}` is declared as `{data_type}` but as `{other_type}` in `{other_node}`"),
+                                ));
+                            }
+                            Some(_) => {}
+                            None => {
+                                parameter_types.insert(path, (data_type, &node.name));
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    findings
+}
+
+fn path_to_string(path: &Path) -> String {
+    path.segments
+        .iter()
+        .map(|segment| {
+            let prefix = if segment.is_variable { "$" } else { "" };
+            let suffix = if segment.is_optional { "?" } else { "" };
+            format!("{prefix}{}{suffix}", segment.name)
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Type as compact string with every path reduced to its last segment
+///
+/// Nodes name the same type with different qualification, e.g. `Point2<Ground>` and
+/// `linear_algebra::Point2<Ground>`, which must not be reported as mismatch.
+fn type_to_string(data_type: &Type) -> String {
+    let mut data_type = data_type.clone();
+    LastSegments.visit_type_mut(&mut data_type);
+    data_type.to_token_stream().to_string().replace(' ', "")
+}
+
+struct LastSegments;
+
+impl VisitMut for LastSegments {
+    fn visit_path_mut(&mut self, path: &mut syn::Path) {
+        visit_mut::visit_path_mut(self, path);
+        let last_segment = path.segments.pop().map(|pair| pair.into_value());
+        path.leading_colon = None;
+        path.segments.clear();
+        path.segments.extend(last_segment);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{contexts::Contexts, cyclers::CyclerKind};
+
+    use super::*;
+
+    fn node(name: &str, source: &str) -> Node {
+        let file: syn::File = syn::parse_str(source).unwrap();
+        Node {
+            name: name.to_string(),
+            module: syn::parse_str(&format!("control::{name}")).unwrap(),
+            file_path: PathBuf::from(format!("control/src/{name}.rs")),
+            contexts: Contexts::try_from_file(&file).unwrap(),
+        }
+    }
+
+    fn control_cycler(cycle_nodes: Vec<Node>) -> Cyclers {
+        Cyclers {
+            cyclers: vec![Cycler {
+                name: "Control".to_string(),
+                kind: CyclerKind::RealTime,
+                instances: vec!["Control".to_string()],
+                setup_nodes: vec![],
+                cycle_nodes,
+                execution_time_warning_threshold: None,
+            }],
+        }
+    }
+
+    fn ball_filter() -> Node {
+        node(
+            "ball_filter",
+            "
+            use linear_algebra::Point2;
+
+            #[context]
+            pub struct CreationContext {}
+            #[context]
+            pub struct CycleContext {}
+            #[context]
+            pub struct MainOutputs {
+                pub ball_position: MainOutput<Option<Point2<Ground>>>,
+                pub ball_velocity: MainOutput<f32>,
+                pub cycle_time: MainOutput<CycleTime>,
+            }
+            ",
+        )
+    }
+
+    fn behavior(ball_position_type: &str) -> Node {
+        node(
+            "behavior",
+            &format!(
+                "
+                #[context]
+                pub struct CreationContext {{}}
+                #[context]
+                pub struct CycleContext {{
+                    ball_position: Input<{ball_position_type}, \"ball_position\">,
+                }}
+                #[context]
+                pub struct MainOutputs {{}}
+                "
+            ),
+        )
+    }
+
+    #[test]
+    fn existing_parameters_are_found() {
+        let parameters = json!({
+            "walking_engine": {
+                "step_duration": 0.25,
+                "gyro_balancing": null,
+            },
+        });
+
+        let existing = Path::try_new("walking_engine.step_duration", true).unwrap();
+        let optional = Path::try_new("walking_engine.gyro_balancing?.factor", true).unwrap();
+        let missing = Path::try_new("walking_engine.foot_leveling", true).unwrap();
+
+        assert!(parameter_exists(&existing, &parameters));
+        assert!(parameter_exists(&optional, &parameters));
+        assert!(!parameter_exists(&missing, &parameters));
+    }
+
+    #[test]
+    fn only_filling_calls_and_forwarded_fields_count_as_filled() {
+        let file: syn::File = syn::parse_str(
+            "
+            fn cycle(context: CycleContext) {
+                context.ball_candidates.fill_if_subscribed(|| 42);
+                context.path_obstacles.mutate_if_subscribed(|data| data.clear());
+                plan(&mut context.planned_path);
+                let mentioned_only = context.obstacles.is_subscribed();
+                let shadowed = 42;
+            }
+            ",
+        )
+        .unwrap();
+        let mut filled = FilledOutputs::default();
+        filled.visit_file(&file);
+
+        assert_eq!(
+            filled.names,
+            BTreeSet::from([
+                "ball_candidates".to_string(),
+                "path_obstacles".to_string(),
+                "planned_path".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn only_unconsumed_main_outputs_are_reported() {
+        let cyclers = control_cycler(vec![ball_filter(), behavior("Option<Point2<Ground>>")]);
+
+        let findings = unused_main_outputs(&cyclers);
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Warning);
+        assert_eq!(findings[0].node, "ball_filter");
+        assert!(findings[0].message.contains("`ball_velocity`"));
+    }
+
+    #[test]
+    fn differently_qualified_paths_of_the_same_type_match() {
+        let cyclers = control_cycler(vec![
+            ball_filter(),
+            behavior("Option<linear_algebra::Point2<coordinate_systems::Ground>>"),
+        ]);
+
+        assert!(mismatching_types(&cyclers).is_empty());
+    }
+
+    #[test]
+    fn inputs_consumed_with_other_type_are_reported() {
+        let cyclers = control_cycler(vec![ball_filter(), behavior("Point2<Ground>")]);
+
+        let findings = mismatching_types(&cyclers);
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Error);
+        assert_eq!(findings[0].node, "behavior");
+        assert!(findings[0].message.contains("`ball_position`"));
+    }
+}
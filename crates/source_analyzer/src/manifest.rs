@@ -1,20 +1,191 @@
-use std::time::Duration;
+use std::{collections::BTreeSet, fs::read_to_string, path::Path, time::Duration};
 
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
 
-use crate::cyclers::CyclerKind;
+use crate::{cyclers::CyclerKind, error::Error};
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct FrameworkManifest {
+    #[serde(rename = "cycler")]
     pub cyclers: Vec<CyclerManifest>,
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct CyclerManifest {
-    pub name: &'static str,
+    pub name: String,
     pub kind: CyclerKind,
-    pub instances: Vec<&'static str>,
-    pub setup_nodes: Vec<&'static str>,
-    pub nodes: Vec<&'static str>,
+    pub instances: Vec<String>,
+    pub setup_nodes: Vec<String>,
+    pub nodes: Vec<String>,
+    #[serde(default, deserialize_with = "deserialize_optional_seconds")]
     pub execution_time_warning_threshold: Option<Duration>,
 }
+
+impl FrameworkManifest {
+    pub fn try_from_file(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let buffer = read_to_string(path).map_err(|source| Error::Io {
+            source,
+            path: path.to_path_buf(),
+        })?;
+        Self::try_from_toml(&buffer).map_err(|source| Error::Manifest {
+            source: Box::new(source),
+            path: path.to_path_buf(),
+        })
+    }
+
+    pub fn try_from_toml(buffer: &str) -> Result<Self, Error> {
+        let manifest: Self = toml::from_str(buffer).map_err(Error::ManifestParse)?;
+        manifest.validate()?;
+        Ok(manifest)
+    }
+
+    pub fn validate(&self) -> Result<(), Error> {
+        let mut cycler_names = BTreeSet::new();
+        for cycler in &self.cyclers {
+            if !cycler_names.insert(&cycler.name) {
+                return Err(Error::DuplicateCycler {
+                    cycler: cycler.name.clone(),
+                });
+            }
+            cycler.validate()?;
+        }
+        Ok(())
+    }
+}
+
+impl CyclerManifest {
+    fn validate(&self) -> Result<(), Error> {
+        if self.instances.is_empty() {
+            return Err(Error::MissingInstances {
+                cycler: self.name.clone(),
+            });
+        }
+        let mut instances = BTreeSet::new();
+        if let Some(instance) = self
+            .instances
+            .iter()
+            .find(|instance| !instances.insert(*instance))
+        {
+            return Err(Error::DuplicateInstance {
+                cycler: self.name.clone(),
+                instance: instance.clone(),
+            });
+        }
+        let mut nodes = BTreeSet::new();
+        if let Some(node) = self
+            .setup_nodes
+            .iter()
+            .chain(&self.nodes)
+            .find(|node| !nodes.insert(*node))
+        {
+            return Err(Error::DuplicateNode {
+                cycler: self.name.clone(),
+                node: node.clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
+fn deserialize_optional_seconds<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<f32>::deserialize(deserializer)?
+        .map(|seconds| Duration::try_from_secs_f32(seconds).map_err(serde::de::Error::custom))
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_is_parsed_from_toml() {
+        let manifest = FrameworkManifest::try_from_toml(
+            r#"
+            [[cycler]]
+            name = "Control"
+            kind = "RealTime"
+            instances = [""]
+            setup_nodes = ["control::sensor_data_receiver"]
+            nodes = ["control::odometry", "control::localization"]
+            execution_time_warning_threshold = 0.012
+
+            [[cycler]]
+            name = "Audio"
+            kind = "Perception"
+            instances = [""]
+            setup_nodes = ["audio::microphone_recorder"]
+            nodes = []
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(manifest.cyclers.len(), 2);
+        assert_eq!(manifest.cyclers[0].kind, CyclerKind::RealTime);
+        assert_eq!(
+            manifest.cyclers[0].execution_time_warning_threshold,
+            Some(Duration::from_secs_f32(0.012))
+        );
+        assert_eq!(manifest.cyclers[1].execution_time_warning_threshold, None);
+    }
+
+    #[test]
+    fn duplicate_nodes_are_rejected() {
+        let result = FrameworkManifest::try_from_toml(
+            r#"
+            [[cycler]]
+            name = "Control"
+            kind = "RealTime"
+            instances = [""]
+            setup_nodes = ["control::sensor_data_receiver"]
+            nodes = ["control::odometry", "control::odometry"]
+            "#,
+        );
+
+        assert!(matches!(result, Err(Error::DuplicateNode { .. })));
+    }
+
+    #[test]
+    fn duplicate_cyclers_are_rejected() {
+        let result = FrameworkManifest::try_from_toml(
+            r#"
+            [[cycler]]
+            name = "Vision"
+            kind = "Perception"
+            instances = ["Top"]
+            setup_nodes = []
+            nodes = []
+
+            [[cycler]]
+            name = "Vision"
+            kind = "Perception"
+            instances = ["Bottom"]
+            setup_nodes = []
+            nodes = []
+            "#,
+        );
+
+        assert!(matches!(result, Err(Error::DuplicateCycler { .. })));
+    }
+
+    #[test]
+    fn cyclers_without_instances_are_rejected() {
+        let result = FrameworkManifest::try_from_toml(
+            r#"
+            [[cycler]]
+            name = "Audio"
+            kind = "Perception"
+            instances = []
+            setup_nodes = ["audio::microphone_recorder"]
+            nodes = []
+            "#,
+        );
+
+        assert!(matches!(result, Err(Error::MissingInstances { .. })));
+    }
+}
@@ -3,6 +3,7 @@ use std::fmt::{self, Write};
 use crate::{
     contexts::{Contexts, Field},
     cyclers::{Cycler, Cyclers},
+    lint::{Finding, Severity},
     node::Node,
 };
 
@@ -102,3 +103,33 @@ impl ToWriterPretty for Field {
         }
     }
 }
+
+impl ToWriterPretty for Finding {
+    fn to_writer_pretty(&self, writer: &mut impl Write) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        let message = &self.message;
+        writeln!(writer, "{severity}: {message}")?;
+        let file_path = self.file_path.display();
+        let line = self.line;
+        let column = self.column;
+        let cycler = &self.cycler;
+        let node = &self.node;
+        write!(
+            writer,
+            "  --> {file_path}:{line}:{column} ({node} in {cycler})"
+        )
+    }
+}
+
+impl ToWriterPretty for Vec<Finding> {
+    fn to_writer_pretty(&self, writer: &mut impl Write) -> fmt::Result {
+        for finding in self {
+            finding.to_writer_pretty(writer)?;
+            writeln!(writer)?;
+        }
+        Ok(())
+    }
+}
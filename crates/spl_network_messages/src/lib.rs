@@ -23,6 +23,7 @@ pub enum HulkMessage {
     Striker(StrikerMessage),
     Loser(LoserMessage),
     VisualReferee(VisualRefereeMessage),
+    Pose(PoseMessage),
 }
 
 impl Default for HulkMessage {
@@ -31,12 +32,30 @@ impl Default for HulkMessage {
     }
 }
 
+impl HulkMessage {
+    pub fn player_number(&self) -> PlayerNumber {
+        match self {
+            HulkMessage::Striker(message) => message.player_number,
+            HulkMessage::Loser(message) => message.player_number,
+            HulkMessage::VisualReferee(message) => message.player_number,
+            HulkMessage::Pose(message) => message.player_number,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
 pub struct StrikerMessage {
     pub player_number: PlayerNumber,
     pub pose: Pose2<Field>,
     pub ball_position: BallPosition<Field>,
     pub time_to_reach_kick_position: Duration,
+    pub pass_target: Option<PassTarget>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct PassTarget {
+    pub receiver: PlayerNumber,
+    pub position: Point2<Field>,
 }
 
 #[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
@@ -45,6 +64,12 @@ pub struct LoserMessage {
     pub pose: Pose2<Field>,
 }
 
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+pub struct PoseMessage {
+    pub player_number: PlayerNumber,
+    pub pose: Pose2<Field>,
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 pub struct VisualRefereeMessage {
     pub player_number: PlayerNumber,
@@ -129,6 +154,10 @@ mod tests {
                 age: Duration::MAX,
             },
             time_to_reach_kick_position: Duration::MAX,
+            pass_target: Some(PassTarget {
+                receiver: PlayerNumber::Five,
+                position: Point::origin(),
+            }),
         });
         assert!(bincode::serialize(&test_message).unwrap().len() <= 128)
     }
@@ -150,4 +179,13 @@ mod tests {
         });
         assert!(bincode::serialize(&test_message).unwrap().len() <= 128)
     }
+
+    #[test]
+    fn hulk_pose_message_size() {
+        let test_message = HulkMessage::Pose(PoseMessage {
+            player_number: PlayerNumber::Three,
+            pose: Pose2::default(),
+        });
+        assert!(bincode::serialize(&test_message).unwrap().len() <= 128)
+    }
 }
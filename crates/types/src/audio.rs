@@ -1,10 +1,16 @@
-use std::fmt::{self, Display, Formatter};
+use std::{
+    fmt::{self, Display, Formatter},
+    path::PathBuf,
+    str::FromStr,
+};
 
-use enum_iterator::Sequence;
+use color_eyre::{eyre::eyre, Report};
+use enum_iterator::{all, Sequence};
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Clone, Debug)]
 pub enum SpeakerRequest {
     PlaySound { sound: Sound },
+    Say { phrase: Phrase },
 }
 
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, Sequence)]
@@ -69,3 +75,232 @@ impl Display for Sound {
         write!(formatter, "{self:?}")
     }
 }
+
+/// Single words from which phrases are composed at runtime
+///
+/// Each word is rendered once into `etc/sounds/words/<Word>.ogg` by
+/// `tools/TextToSpeech/generate_words.py`.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, Sequence)]
+pub enum Word {
+    Zero,
+    One,
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Ten,
+    Eleven,
+    Twelve,
+    Thirteen,
+    Fourteen,
+    Fifteen,
+    Sixteen,
+    Seventeen,
+    Eighteen,
+    Nineteen,
+    Twenty,
+    Thirty,
+    Forty,
+    Fifty,
+    Sixty,
+    Seventy,
+    Eighty,
+    Ninety,
+    Hundred,
+    Ankle,
+    Arm,
+    Ball,
+    Battery,
+    Bottom,
+    Camera,
+    Charging,
+    Connected,
+    Degrees,
+    Disconnected,
+    Elbow,
+    Error,
+    Fallen,
+    Head,
+    High,
+    Hip,
+    Hot,
+    Joint,
+    Knee,
+    Left,
+    Low,
+    Network,
+    Percent,
+    Player,
+    Right,
+    Shoulder,
+    Stuck,
+    Temperature,
+    Top,
+    Warning,
+    Wifi,
+}
+
+impl Word {
+    const ONES: [Word; 20] = [
+        Word::Zero,
+        Word::One,
+        Word::Two,
+        Word::Three,
+        Word::Four,
+        Word::Five,
+        Word::Six,
+        Word::Seven,
+        Word::Eight,
+        Word::Nine,
+        Word::Ten,
+        Word::Eleven,
+        Word::Twelve,
+        Word::Thirteen,
+        Word::Fourteen,
+        Word::Fifteen,
+        Word::Sixteen,
+        Word::Seventeen,
+        Word::Eighteen,
+        Word::Nineteen,
+    ];
+    const TENS: [Word; 8] = [
+        Word::Twenty,
+        Word::Thirty,
+        Word::Forty,
+        Word::Fifty,
+        Word::Sixty,
+        Word::Seventy,
+        Word::Eighty,
+        Word::Ninety,
+    ];
+
+    /// The word as it is spoken and written in text phrases
+    pub fn text(self) -> String {
+        format!("{self:?}").to_lowercase()
+    }
+
+    /// Sound file of the word relative to the sounds directory
+    pub fn file_name(self) -> PathBuf {
+        PathBuf::from("words").join(format!("{self}.ogg"))
+    }
+}
+
+impl Display for Word {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{self:?}")
+    }
+}
+
+/// A sequence of words played back to back, e.g. "player 21 battery low"
+///
+/// Phrases are built either with [`Phrase::word`] and [`Phrase::number`] or parsed from text.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Phrase {
+    pub words: Vec<Word>,
+}
+
+impl Phrase {
+    pub fn word(mut self, word: Word) -> Self {
+        self.words.push(word);
+        self
+    }
+
+    /// Appends the spoken form of a number, numbers from 1000 on are spoken digit by digit
+    pub fn number(mut self, number: u32) -> Self {
+        let number = number as usize;
+        match number {
+            0..=19 => self.words.push(Word::ONES[number]),
+            20..=99 => {
+                self.words.push(Word::TENS[number / 10 - 2]);
+                if number % 10 != 0 {
+                    self.words.push(Word::ONES[number % 10]);
+                }
+            }
+            100..=999 => {
+                self.words.push(Word::ONES[number / 100]);
+                self.words.push(Word::Hundred);
+                if number % 100 != 0 {
+                    return self.number((number % 100) as u32);
+                }
+            }
+            _ => {
+                for digit in number.to_string().bytes() {
+                    self.words.push(Word::ONES[(digit - b'0') as usize]);
+                }
+            }
+        }
+        self
+    }
+}
+
+impl Display for Phrase {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        let text: Vec<_> = self.words.iter().map(|word| word.text()).collect();
+        write!(formatter, "{}", text.join(" "))
+    }
+}
+
+impl FromStr for Phrase {
+    type Err = Report;
+
+    /// Parses whitespace separated words and numbers, e.g. "player 21 battery low"
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        text.split_whitespace()
+            .try_fold(Phrase::default(), |phrase, token| {
+                if let Ok(number) = token.parse() {
+                    return Ok(phrase.number(number));
+                }
+                let token = token.to_lowercase();
+                let word = all::<Word>()
+                    .find(|word| word.text() == token)
+                    .ok_or_else(|| eyre!("no sound for word `{token}`"))?;
+                Ok(phrase.word(word))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_are_spoken_as_words() {
+        let spoken = |number| Phrase::default().number(number).to_string();
+
+        assert_eq!(spoken(7), "seven");
+        assert_eq!(spoken(40), "forty");
+        assert_eq!(spoken(36), "thirty six");
+        assert_eq!(spoken(100), "one hundred");
+        assert_eq!(spoken(215), "two hundred fifteen");
+        assert_eq!(spoken(2024), "two zero two four");
+    }
+
+    #[test]
+    fn phrase_is_played_as_sequence_of_word_files() {
+        let phrase: Phrase = "Player 21 battery low".parse().unwrap();
+
+        let files: Vec<_> = phrase.words.iter().map(|word| word.file_name()).collect();
+
+        assert_eq!(
+            files,
+            [
+                "words/Player.ogg",
+                "words/Twenty.ogg",
+                "words/One.ogg",
+                "words/Battery.ogg",
+                "words/Low.ogg",
+            ]
+            .map(PathBuf::from)
+        );
+    }
+
+    #[test]
+    fn unknown_words_are_rejected() {
+        assert!("player 21 battery low".parse::<Phrase>().is_ok());
+        assert!("player 21 battery empty".parse::<Phrase>().is_err());
+    }
+}
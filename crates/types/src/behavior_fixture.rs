@@ -0,0 +1,85 @@
+use coordinate_systems::Field;
+use linear_algebra::Point2;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    behavior_state::BehaviorState,
+    cycle_time::CycleTime,
+    dribble_path_plan::DribblePathPlan,
+    field_dimensions::FieldDimensions,
+    initial_pose::InitialPose,
+    kick_decision::DecisionParameters,
+    motion_command::{MotionCommand, WalkSpeed},
+    parameters::{BehaviorParameters, InWalkKicksParameters, KeeperMotionParameters},
+    players::Players,
+    world_state::WorldState,
+};
+
+/// Inputs and parameters of a single behavior cycle together with its expected motion command
+///
+/// Fixtures are extracted from simulator runs or recordings and replayed by the behavior tests.
+/// Parameters equal to `etc/parameters/default.json` are not stored but taken from it on replay.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BehaviorFixture {
+    pub inputs: BehaviorInputs,
+    /// State of the behavior node at the start of the recorded cycle
+    pub state: BehaviorState,
+    /// Behavior parameters differing from the defaults, nested like the parameter files
+    #[serde(default)]
+    pub parameter_overrides: Value,
+    /// Output of the recorded cycle, replaced by running the tests with `UPDATE_BEHAVIOR_FIXTURES=1`
+    pub expected_motion_command: MotionCommand,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BehaviorInputs {
+    pub has_ground_contact: bool,
+    pub world_state: WorldState,
+    pub dribble_path_plan: Option<DribblePathPlan>,
+    pub cycle_time: CycleTime,
+    pub is_localization_converged: bool,
+    pub expected_referee_position: Option<Point2<Field>>,
+    pub last_motion_command: MotionCommand,
+}
+
+/// Parameters read by the behavior node, laid out like the parameter files
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BehaviorFixtureParameters {
+    pub behavior: BehaviorParameters,
+    pub kick_selector: DecisionParameters,
+    pub in_walk_kicks: InWalkKicksParameters,
+    pub field_dimensions: FieldDimensions,
+    pub pose_detection: BehaviorPoseDetection,
+    pub keeper_motion: KeeperMotionParameters,
+    pub calibration_controller: BehaviorCalibrationController,
+    pub localization: BehaviorLocalization,
+    pub walk_speed: BehaviorWalkSpeeds,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct BehaviorPoseDetection {
+    pub enable: bool,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct BehaviorCalibrationController {
+    pub use_stand_head_unstiff_calibration: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BehaviorLocalization {
+    pub initial_poses: Players<InitialPose>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct BehaviorWalkSpeeds {
+    pub defend: WalkSpeed,
+    pub dribble: WalkSpeed,
+    pub intercept_ball: WalkSpeed,
+    pub lost_ball: WalkSpeed,
+    pub search: WalkSpeed,
+    pub support: WalkSpeed,
+    pub walk_to_kickoff: WalkSpeed,
+    pub walk_to_penalty_kick: WalkSpeed,
+}
@@ -0,0 +1,47 @@
+use std::time::SystemTime;
+
+use coordinate_systems::Field;
+use linear_algebra::{point, Point2};
+use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::roles::Role;
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Deserialize,
+    Eq,
+    PartialEq,
+    Serialize,
+    PathSerialize,
+    PathDeserialize,
+    PathIntrospect,
+)]
+pub enum DefendMode {
+    Aggressive,
+    Passive,
+}
+
+/// Internal state the behavior carries from one cycle to the next
+#[derive(
+    Clone, Copy, Debug, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect,
+)]
+pub struct BehaviorState {
+    pub last_known_ball_position: Point2<Field>,
+    pub active_since: Option<SystemTime>,
+    pub previous_role: Role,
+    pub last_defender_mode: DefendMode,
+}
+
+impl Default for BehaviorState {
+    fn default() -> Self {
+        Self {
+            last_known_ball_position: point![0.0, 0.0],
+            active_since: None,
+            previous_role: Role::Searcher,
+            last_defender_mode: DefendMode::Passive,
+        }
+    }
+}
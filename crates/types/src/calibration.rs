@@ -6,12 +6,33 @@ use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
 
 use crate::{camera_position::CameraPosition, cycle_time::CycleTime};
 
+/// Body pose to capture in, the torso tilt and knee bend of the poses differ
+#[derive(
+    Default,
+    Debug,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    Serialize,
+    Deserialize,
+    PathSerialize,
+    PathDeserialize,
+    PathIntrospect,
+)]
+pub enum CalibrationPose {
+    #[default]
+    Stand,
+    Initial,
+}
+
 #[derive(
     Copy, Clone, Debug, Serialize, Deserialize, PathSerialize, PathDeserialize, PathIntrospect,
 )]
 pub struct CalibrationCommand {
     pub target: Point2<Ground>,
     pub camera: CameraPosition,
+    pub pose: CalibrationPose,
     pub dispatch_time: CycleTime,
     pub capture: bool,
 }
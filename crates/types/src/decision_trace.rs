@@ -0,0 +1,98 @@
+use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::{action::Action, roles::Role};
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Deserialize,
+    Eq,
+    PartialEq,
+    Serialize,
+    PathSerialize,
+    PathDeserialize,
+    PathIntrospect,
+)]
+pub enum ActionOutcome {
+    /// The action returned a motion command and was executed
+    Chosen,
+    /// The action was evaluated but its preconditions were not met
+    PreconditionFailed { precondition: Precondition },
+    /// An action with higher priority was chosen before this one was evaluated
+    NotEvaluated,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Deserialize,
+    Eq,
+    PartialEq,
+    Serialize,
+    PathSerialize,
+    PathDeserialize,
+    PathIntrospect,
+)]
+pub enum Precondition {
+    /// The robot is not localized on the field
+    GroundToField,
+    /// No ball is perceived
+    Ball,
+    /// The kick selector did not provide kick decisions
+    KickDecisions,
+    /// No teammate announced a pass to this robot
+    IncomingPass,
+    /// The game phase or set play excludes the action, e.g. a penalty shootout
+    GamePhase,
+    /// No game controller state was received yet
+    GameControllerState,
+    /// The world state does not call for the action, e.g. due to the primary or fall state
+    Situation,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Deserialize,
+    Eq,
+    PartialEq,
+    Serialize,
+    PathSerialize,
+    PathDeserialize,
+    PathIntrospect,
+)]
+pub struct ActionCandidate {
+    pub action: Action,
+    pub outcome: ActionOutcome,
+}
+
+/// All actions considered by the behavior in one cycle, sorted by priority
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    Deserialize,
+    Eq,
+    PartialEq,
+    Serialize,
+    PathSerialize,
+    PathDeserialize,
+    PathIntrospect,
+)]
+pub struct DecisionTrace {
+    pub role: Role,
+    pub candidates: Vec<ActionCandidate>,
+}
+
+impl DecisionTrace {
+    pub fn chosen_action(&self) -> Option<Action> {
+        self.candidates
+            .iter()
+            .find(|candidate| candidate.outcome == ActionOutcome::Chosen)
+            .map(|candidate| candidate.action)
+    }
+}
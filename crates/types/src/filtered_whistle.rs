@@ -1,5 +1,7 @@
 use std::time::SystemTime;
 
+use coordinate_systems::Field;
+use linear_algebra::Orientation2;
 use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
 use serde::{Deserialize, Serialize};
 
@@ -10,4 +12,6 @@ pub struct FilteredWhistle {
     pub is_detected: bool,
     #[path_serde(leaf)]
     pub last_detection: Option<SystemTime>,
+    /// Direction towards the most recently detected whistle
+    pub bearing: Option<Orientation2<Field>>,
 }
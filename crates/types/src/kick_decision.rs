@@ -1,7 +1,10 @@
+use std::time::Duration;
+
 use coordinate_systems::Ground;
 use linear_algebra::{Point2, Pose2};
 use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
 use serde::{Deserialize, Serialize};
+use spl_network_messages::PlayerNumber;
 
 use crate::{motion_command::KickVariant, support_foot::Side};
 
@@ -33,6 +36,7 @@ pub struct KickDecision {
     pub kicking_side: Side,
     pub kick_pose: Pose2<Ground>,
     pub strength: f32,
+    pub pass_receiver: Option<PlayerNumber>,
 }
 
 #[derive(
@@ -58,4 +62,33 @@ pub struct DecisionParameters {
     pub angle_distance_weight: f32,
     pub closer_to_goal_threshold: f32,
     pub goal_accuracy_margin: f32,
+
+    pub passing: PassingParameters,
+}
+
+#[derive(
+    Debug, Default, Clone, Serialize, Deserialize, PathSerialize, PathDeserialize, PathIntrospect,
+)]
+pub struct PassingParameters {
+    pub enabled: bool,
+    pub kick_strength: f32,
+    /// Average speed of a passed ball in m/s
+    pub ball_speed: f32,
+    pub receiver_walking_speed: f32,
+    /// Speed in rad/s at which the receiver turns towards the ball
+    pub receiver_turning_speed: f32,
+    pub opponent_walking_speed: f32,
+    /// Distance in front of the receiver the ball is passed to
+    pub lead_distance: f32,
+    pub minimum_pass_distance: f32,
+    pub maximum_pass_distance: f32,
+    pub minimum_progress: f32,
+    /// Obstacles this close to the receiver are assumed to be the receiver itself
+    pub receiver_obstacle_radius: f32,
+    pub maximum_interception_risk: f32,
+    pub receiver_time_weight: f32,
+    /// Receivers whose last broadcast pose is older are not passed to
+    pub maximum_receiver_pose_age: Duration,
+    /// Score added per second of receiver pose age since the receiver may have moved meanwhile
+    pub receiver_pose_age_weight: f32,
 }
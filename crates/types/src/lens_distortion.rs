@@ -0,0 +1,153 @@
+use nalgebra::{point, Point2};
+use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
+use serde::{Deserialize, Serialize};
+
+const UNDISTORTION_ITERATIONS: usize = 20;
+
+/// Lens distortion acting on normalized image coordinates, i.e. `(x / z, y / z)` of a ray in
+/// camera coordinates, with coefficients as used by OpenCV
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Deserialize,
+    PartialEq,
+    Serialize,
+    PathSerialize,
+    PathDeserialize,
+    PathIntrospect,
+)]
+pub enum LensDistortion {
+    /// Ideal pinhole camera
+    #[default]
+    None,
+    /// Brown-Conrady model with radial coefficients `k1`..`k3` and tangential `p1`, `p2`
+    RadialTangential {
+        k1: f32,
+        k2: f32,
+        k3: f32,
+        p1: f32,
+        p2: f32,
+    },
+    /// Kannala-Brandt model with coefficients `k1`..`k4` on the angle of incidence
+    Fisheye { k1: f32, k2: f32, k3: f32, k4: f32 },
+}
+
+impl LensDistortion {
+    pub fn distort(&self, undistorted: Point2<f32>) -> Point2<f32> {
+        match *self {
+            LensDistortion::None => undistorted,
+            LensDistortion::RadialTangential { k1, k2, k3, p1, p2 } => {
+                let (x, y) = (undistorted.x, undistorted.y);
+                let r2 = x * x + y * y;
+                let radial = 1.0 + r2 * (k1 + r2 * (k2 + r2 * k3));
+                point![
+                    x * radial + 2.0 * p1 * x * y + p2 * (r2 + 2.0 * x * x),
+                    y * radial + p1 * (r2 + 2.0 * y * y) + 2.0 * p2 * x * y
+                ]
+            }
+            LensDistortion::Fisheye { k1, k2, k3, k4 } => {
+                let r = undistorted.coords.norm();
+                if r <= f32::EPSILON {
+                    return undistorted;
+                }
+                let theta = r.atan();
+                let theta2 = theta * theta;
+                let theta_distorted =
+                    theta * (1.0 + theta2 * (k1 + theta2 * (k2 + theta2 * (k3 + theta2 * k4))));
+                undistorted * (theta_distorted / r)
+            }
+        }
+    }
+
+    /// Inverse of [`Self::distort`], solved iteratively
+    pub fn undistort(&self, distorted: Point2<f32>) -> Point2<f32> {
+        match *self {
+            LensDistortion::None => distorted,
+            LensDistortion::RadialTangential { k1, k2, k3, p1, p2 } => {
+                let mut undistorted = distorted;
+                for _ in 0..UNDISTORTION_ITERATIONS {
+                    let (x, y) = (undistorted.x, undistorted.y);
+                    let r2 = x * x + y * y;
+                    let radial = 1.0 + r2 * (k1 + r2 * (k2 + r2 * k3));
+                    let tangential_x = 2.0 * p1 * x * y + p2 * (r2 + 2.0 * x * x);
+                    let tangential_y = p1 * (r2 + 2.0 * y * y) + 2.0 * p2 * x * y;
+                    undistorted = point![
+                        (distorted.x - tangential_x) / radial,
+                        (distorted.y - tangential_y) / radial
+                    ];
+                }
+                undistorted
+            }
+            LensDistortion::Fisheye { k1, k2, k3, k4 } => {
+                let theta_distorted = distorted.coords.norm();
+                if theta_distorted <= f32::EPSILON {
+                    return distorted;
+                }
+                // Newton's method on theta_d = theta * (1 + k1 theta^2 + ... + k4 theta^8)
+                let mut theta = theta_distorted;
+                for _ in 0..UNDISTORTION_ITERATIONS {
+                    let theta2 = theta * theta;
+                    let error = theta
+                        * (1.0 + theta2 * (k1 + theta2 * (k2 + theta2 * (k3 + theta2 * k4))))
+                        - theta_distorted;
+                    let derivative = 1.0
+                        + theta2
+                            * (3.0 * k1
+                                + theta2 * (5.0 * k2 + theta2 * (7.0 * k3 + theta2 * 9.0 * k4)));
+                    theta -= error / derivative;
+                }
+                distorted * (theta.tan() / theta_distorted)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    #[test]
+    fn undistort_inverts_distort() {
+        let distortions = [
+            LensDistortion::RadialTangential {
+                k1: -0.12,
+                k2: 0.05,
+                k3: -0.01,
+                p1: 0.001,
+                p2: -0.002,
+            },
+            LensDistortion::Fisheye {
+                k1: 0.02,
+                k2: -0.01,
+                k3: 0.005,
+                k4: -0.001,
+            },
+        ];
+        for distortion in distortions {
+            for undistorted in [point![0.0, 0.0], point![0.3, -0.2], point![-0.5, 0.4]] {
+                let distorted = distortion.distort(undistorted);
+                assert_relative_eq!(distortion.undistort(distorted), undistorted, epsilon = 1e-5);
+            }
+        }
+    }
+
+    #[test]
+    fn barrel_distortion_moves_points_towards_center() {
+        let distortion = LensDistortion::RadialTangential {
+            k1: -0.2,
+            k2: 0.0,
+            k3: 0.0,
+            p1: 0.0,
+            p2: 0.0,
+        };
+        let undistorted = point![0.5, 0.0];
+
+        let distorted = distortion.distort(undistorted);
+
+        assert_relative_eq!(distorted, point![0.5 * (1.0 - 0.2 * 0.25), 0.0]);
+    }
+}
@@ -3,6 +3,8 @@ pub mod action;
 pub mod audio;
 pub mod ball_detection;
 pub mod ball_position;
+pub mod behavior_fixture;
+pub mod behavior_state;
 pub mod bounding_box;
 pub mod buttons;
 pub mod calibration;
@@ -10,6 +12,7 @@ pub mod camera_position;
 pub mod color;
 pub mod condition_input;
 pub mod cycle_time;
+pub mod decision_trace;
 pub mod detected_feet;
 pub mod dribble_path_plan;
 pub mod fall_state;
@@ -34,6 +37,7 @@ pub mod jpeg;
 pub mod kick_decision;
 pub mod last_filtered_game_controller_state_change;
 pub mod led;
+pub mod lens_distortion;
 pub mod limb;
 pub mod line_data;
 pub mod localization;
@@ -46,6 +50,7 @@ pub mod multivariate_normal_distribution;
 pub mod obstacle_avoiding_arms;
 pub mod obstacle_filter;
 pub mod obstacles;
+pub mod odometry;
 pub mod parameters;
 pub mod path_obstacles;
 pub mod penalty_shot_direction;
@@ -69,6 +74,7 @@ pub mod sonar_values;
 pub mod stand_up;
 pub mod step;
 pub mod support_foot;
+pub mod thermal_state;
 pub mod walk_command;
 pub mod whistle;
 pub mod world_state;
@@ -1,5 +1,6 @@
 use std::time::SystemTime;
 
+use nalgebra::Vector2;
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -9,6 +10,7 @@ use crate::{
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Hypothesis {
     pub state: MultivariateNormalDistribution<2>,
+    pub velocity: Vector2<f32>,
     pub measurement_count: usize,
     pub last_update: SystemTime,
     pub obstacle_kind: ObstacleKind,
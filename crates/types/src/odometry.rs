@@ -0,0 +1,27 @@
+use nalgebra::Matrix3;
+use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
+use serde::{Deserialize, Serialize};
+
+/// Uncertainty of the odometry step of a single cycle, in the robot frame of the last cycle
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Deserialize,
+    Serialize,
+    PathSerialize,
+    PathDeserialize,
+    PathIntrospect,
+)]
+pub struct OdometryUncertainty {
+    /// Covariance of the x, y and yaw offsets
+    pub covariance: Matrix3<f32>,
+    /// Confidence in [0, 1] that the support foot has firm ground contact
+    pub contact_confidence: f32,
+    /// Difference between the IMU and the kinematic yaw offset
+    pub yaw_residual: f32,
+    /// Whether the support foot has weak contact or turns differently than the IMU, sliding
+    /// without turning is not detected
+    pub is_slipping: bool,
+}
@@ -1,13 +1,14 @@
 use std::ops::{Index, Range};
 use std::{path::PathBuf, time::Duration};
 
-use coordinate_systems::{Field, Ground, NormalizedPixel, Pixel};
+use coordinate_systems::{Field, Ground, NormalizedPixel, Pixel, Robot};
 use linear_algebra::{Point2, Vector2};
 use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
 use serde::{Deserialize, Serialize};
 
 use crate::{
     joints::head::HeadJoints,
+    lens_distortion::LensDistortion,
     motion_command::{KickVariant, MotionCommand},
     roles::Role,
 };
@@ -16,12 +17,42 @@ use crate::{
     Clone, Debug, Default, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect,
 )]
 pub struct WhistleDetectionParameters {
+    pub detector: WhistleDetector,
+    #[path_serde(unit = "Hz", minimum = 0.0, ordered)]
     pub detection_band: Range<f32>,
     pub background_noise_scaling: f32,
     pub whistle_scaling: f32,
     pub number_of_chunks: usize,
 }
 
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Deserialize,
+    Serialize,
+    PathSerialize,
+    PathDeserialize,
+    PathIntrospect,
+    PartialEq,
+    Eq,
+)]
+pub enum WhistleDetector {
+    #[default]
+    Spectral,
+    Learned,
+}
+
+#[derive(
+    Clone, Debug, Default, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect,
+)]
+pub struct WhistleClassifierParameters {
+    pub neural_network: PathBuf,
+    #[path_serde(minimum = 0.0, maximum = 1.0)]
+    pub detection_threshold: f32,
+}
+
 #[derive(
     Clone, Debug, Default, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect,
 )]
@@ -134,8 +165,11 @@ pub struct InWalkKickInfoParameters {
     pub position: nalgebra::Point2<f32>,
     pub position_offset: nalgebra::Vector2<f32>,
     pub orientation: f32,
+    #[path_serde(ordered)]
     pub reached_x: Range<f32>,
+    #[path_serde(ordered)]
     pub reached_y: Range<f32>,
+    #[path_serde(ordered)]
     pub reached_turn: Range<f32>,
     pub shot_distance: f32,
     pub enabled: bool,
@@ -218,15 +252,113 @@ pub struct InterceptBallParameters {
     Clone, Debug, Default, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect,
 )]
 pub struct PathPlanningParameters {
+    #[path_serde(unit = "m/s", minimum = 0.0)]
     pub arc_walking_speed: f32,
+    #[path_serde(unit = "m", minimum = 0.0)]
     pub ball_obstacle_radius: f32,
     pub field_border_weight: f32,
+    #[path_serde(unit = "m/s", minimum = 0.0)]
     pub line_walking_speed: f32,
     pub rotation_penalty_factor: f32,
+    #[path_serde(unit = "m", minimum = 0.0)]
     pub minimum_robot_radius_at_foot_height: f32,
+    #[path_serde(unit = "m", minimum = 0.0)]
     pub robot_radius_at_foot_height: f32,
+    #[path_serde(unit = "m", minimum = 0.0)]
     pub robot_radius_at_hip_height: f32,
+    #[path_serde(unit = "s", minimum = 0.0)]
     pub half_rotation: Duration,
+    pub predict_obstacle_motion: bool,
+    #[path_serde(unit = "s", minimum = 0.0)]
+    pub obstacle_prediction_horizon: Duration,
+    /// Upper bound of the radius added to an obstacle by its predicted motion
+    #[path_serde(unit = "m", minimum = 0.0)]
+    pub maximum_obstacle_inflation: f32,
+}
+
+#[derive(
+    Clone, Debug, Default, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect,
+)]
+pub struct RoleAllocationParameters {
+    pub enabled: bool,
+    pub walking_speed: f32,
+    /// Walking time in seconds which a player may lose by keeping an occupied role
+    pub hysteresis: f32,
+    /// Distance to a role position within which a player occupies the role
+    pub occupancy_radius: f32,
+    pub ball_attraction: f32,
+    pub defender_left_position: Point2<Field>,
+    pub defender_right_position: Point2<Field>,
+    pub midfielder_left_position: Point2<Field>,
+    pub midfielder_right_position: Point2<Field>,
+    pub striker_supporter_position: Point2<Field>,
+}
+
+#[derive(
+    Clone, Debug, Default, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect,
+)]
+pub struct ThermalModelParameters {
+    /// Whether the estimate reduces stiffness, currents and striker claims. The coefficients are
+    /// not yet fitted to measured joint temperatures.
+    pub enabled: bool,
+    #[path_serde(unit = "°C")]
+    pub ambient_temperature: f32,
+    /// Temperature increase in K/s per squared ampere
+    pub heating_coefficient: f32,
+    /// Rate in 1/s at which the temperature difference to the ambient decays
+    pub cooling_coefficient: f32,
+    #[path_serde(unit = "s", minimum = 0.0)]
+    pub measurement_time_constant: Duration,
+    #[path_serde(unit = "s", minimum = 0.0)]
+    pub current_time_constant: Duration,
+    #[path_serde(unit = "s", minimum = 0.0)]
+    pub prediction_horizon: Duration,
+    /// Temperature at which the motors reduce their stiffness on their own
+    #[path_serde(unit = "°C")]
+    pub maximum_temperature: f32,
+    #[path_serde(unit = "K", minimum = 0.0)]
+    pub cooling_margin: f32,
+    pub cooling_margin_hysteresis: f32,
+    /// Leg margin below which the walking stiffness is reduced
+    pub stiffness_reduction_margin: f32,
+    #[path_serde(minimum = 0.0, maximum = 1.0)]
+    pub minimum_stiffness_factor: f32,
+    #[path_serde(minimum = 0.0, maximum = 1.0)]
+    pub hot_allowed_current_factor: f32,
+    /// Added to the time to reach the kick position at zero leg margin
+    pub maximum_striker_penalty: Duration,
+}
+
+#[derive(
+    Clone, Debug, Default, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect,
+)]
+pub struct OdometryFusionParameters {
+    /// Whether the kinematic yaw is fused with the IMU yaw, otherwise the IMU yaw is used alone
+    pub enabled: bool,
+    /// Summed support sole pressure at which the support foot is considered in full contact
+    #[path_serde(unit = "kg", minimum = 0.0)]
+    pub full_contact_pressure: f32,
+    /// Contact confidence below which the support foot is considered slipping
+    #[path_serde(minimum = 0.0, maximum = 1.0)]
+    pub minimum_contact_confidence: f32,
+    /// Standard deviation of the kinematic yaw offset per cycle at full contact
+    #[path_serde(unit = "rad", minimum = 0.0)]
+    pub kinematic_yaw_noise: f32,
+    /// Standard deviation of the IMU yaw offset per cycle
+    #[path_serde(unit = "rad", minimum = 0.0)]
+    pub imu_yaw_noise: f32,
+    /// IMU and kinematic yaw offsets differing by more than this indicate a slipping foot
+    #[path_serde(unit = "rad", minimum = 0.0)]
+    pub slip_yaw_threshold: f32,
+    /// Standard deviation of the translation offset per cycle
+    #[path_serde(unit = "m", minimum = 0.0)]
+    pub translation_noise: Vector2<Robot>,
+    /// Standard deviation of the translation offset per meter travelled
+    #[path_serde(minimum = 0.0)]
+    pub relative_translation_noise: f32,
+    /// Factor applied to the translation variance while slipping
+    #[path_serde(minimum = 1.0)]
+    pub slip_noise_factor: f32,
 }
 
 #[derive(
@@ -298,6 +430,7 @@ pub struct SplNetworkParameters {
     pub silence_interval_between_messages: Duration,
     pub spl_striker_message_receive_timeout: Duration,
     pub spl_striker_message_send_interval: Duration,
+    pub pose_message_send_interval: Duration,
 }
 
 #[derive(
@@ -407,6 +540,8 @@ pub struct ObstacleFilterParameters {
     pub robot_obstacle_radius_at_foot_height: f32,
     pub unknown_obstacle_radius: f32,
     pub goal_post_obstacle_radius: f32,
+    pub velocity_smoothing_factor: f32,
+    pub maximum_velocity: f32,
 }
 
 #[derive(
@@ -416,6 +551,7 @@ pub struct CameraMatrixParameters {
     pub camera_pitch: f32,
     pub focal_lengths: nalgebra::Vector2<f32>,
     pub cc_optical_center: nalgebra::Point2<f32>,
+    pub lens_distortion: LensDistortion,
 }
 
 #[derive(
@@ -108,6 +108,23 @@ impl From<TeamState> for Players<Option<Penalty>> {
     }
 }
 
+impl Players<Option<Penalty>> {
+    /// Players without penalty except the striker, in the order roles are assigned to them
+    pub fn available_players(
+        &self,
+        striker: PlayerNumber,
+    ) -> impl Iterator<Item = PlayerNumber> + '_ {
+        self.iter().filter_map(move |(player_number, penalty)| {
+            (player_number != striker && penalty.is_none()).then_some(player_number)
+        })
+    }
+
+    /// The keeper is the first available player besides the striker
+    pub fn keeper(&self, striker: PlayerNumber) -> Option<PlayerNumber> {
+        self.available_players(striker).next()
+    }
+}
+
 #[derive(Clone, Copy)]
 pub struct PlayersIterator<'a, T> {
     data: &'a Players<T>,
@@ -205,6 +222,8 @@ impl<T> Players<T> {
 
 #[cfg(test)]
 mod test {
+    use std::time::Duration;
+
     use super::*;
 
     #[test]
@@ -269,4 +288,35 @@ mod test {
         assert_eq!(iterator.next_back(), None);
         assert_eq!(iterator.len(), 0);
     }
+
+    #[test]
+    fn keeper_is_first_available_player_besides_striker() {
+        let penalties = Players {
+            one: Some(Penalty::PlayerPushing {
+                remaining: Duration::from_secs(30),
+            }),
+            ..Players::new(None)
+        };
+
+        assert_eq!(
+            penalties.keeper(PlayerNumber::Two),
+            Some(PlayerNumber::Three)
+        );
+        assert_eq!(
+            penalties.keeper(PlayerNumber::Four),
+            Some(PlayerNumber::Two)
+        );
+        assert_eq!(
+            penalties
+                .available_players(PlayerNumber::Two)
+                .collect::<Vec<_>>(),
+            vec![
+                PlayerNumber::Three,
+                PlayerNumber::Four,
+                PlayerNumber::Five,
+                PlayerNumber::Six,
+                PlayerNumber::Seven,
+            ]
+        );
+    }
 }
@@ -0,0 +1,48 @@
+use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::joints::Joints;
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Deserialize,
+    Serialize,
+    PathSerialize,
+    PathDeserialize,
+    PathIntrospect,
+)]
+pub struct ThermalState {
+    pub estimated_temperatures: Joints<f32>,
+    /// Temperatures expected at the end of the prediction horizon if the load stays the same
+    pub predicted_temperatures: Joints<f32>,
+    /// Distance of the predicted temperatures to the maximum temperature
+    pub temperature_margins: Joints<f32>,
+    pub needs_cooling: bool,
+}
+
+impl ThermalState {
+    pub fn minimum_margin(&self) -> f32 {
+        self.temperature_margins
+            .into_iter()
+            .fold(f32::INFINITY, f32::min)
+    }
+
+    pub fn minimum_leg_margin(&self) -> f32 {
+        self.temperature_margins
+            .left_leg
+            .into_iter()
+            .chain(self.temperature_margins.right_leg)
+            .fold(f32::INFINITY, f32::min)
+    }
+
+    /// Fraction of the load which the legs can take before reaching the given margin
+    ///
+    /// Is 1.0 as long as the legs are at least `margin` away from the maximum temperature and
+    /// approaches 0.0 towards the maximum temperature.
+    pub fn leg_headroom(&self, margin: f32) -> f32 {
+        (self.minimum_leg_margin() / margin).clamp(0.0, 1.0)
+    }
+}
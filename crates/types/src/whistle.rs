@@ -6,6 +6,28 @@ use serde::{Deserialize, Serialize};
 )]
 pub struct Whistle {
     pub is_detected: Vec<bool>,
+    /// Delays between all pairs of microphones, only estimated if a whistle was detected
+    pub time_differences_of_arrival: Vec<TimeDifferenceOfArrival>,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Deserialize,
+    Serialize,
+    PathSerialize,
+    PathDeserialize,
+    PathIntrospect,
+)]
+pub struct TimeDifferenceOfArrival {
+    pub first_channel: usize,
+    pub second_channel: usize,
+    /// Arrival time at the first minus arrival time at the second microphone in seconds
+    pub delay: f32,
+    /// Peak of the phase transform weighted cross correlation, 1.0 for a perfectly coherent source
+    pub coherence: f32,
 }
 
 #[derive(
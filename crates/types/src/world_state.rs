@@ -3,7 +3,7 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use serde::{Deserialize, Serialize};
 
 use coordinate_systems::{Field, Ground, UpcomingSupport};
-use linear_algebra::{Isometry2, Point2, Vector2};
+use linear_algebra::{Isometry2, Orientation2, Point2, Vector2};
 use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
 use spl_network_messages::PlayerNumber;
 
@@ -25,10 +25,12 @@ pub struct WorldState {
     pub rule_obstacles: Vec<RuleObstacle>,
     pub position_of_interest: Point2<Ground>,
     pub suggested_search_position: Option<Point2<Field>>,
+    pub incoming_pass: Option<Point2<Field>>,
     pub kick_decisions: Option<Vec<KickDecision>>,
     pub instant_kick_decisions: Option<Vec<KickDecision>>,
     pub robot: RobotState,
     pub calibration_command: Option<CalibrationCommand>,
+    pub whistle_bearing: Option<Orientation2<Field>>,
 }
 
 #[derive(
@@ -105,4 +107,5 @@ pub struct RobotState {
     pub player_number: PlayerNumber,
     pub ground_to_upcoming_support: Isometry2<Ground, UpcomingSupport>,
     pub stand_up_count: u32,
+    pub needs_cooling: bool,
 }
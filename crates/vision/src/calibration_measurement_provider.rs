@@ -128,6 +128,9 @@ fn get_fake_measurement(
                 connecting_line: project_line_to_camera(matrix, connecting_line)?,
                 goal_box_line: project_line_to_camera(matrix, goal_box_line)?,
             },
+            // filled in by the calibration controller from the sensor data at image time
+            joint_positions: Default::default(),
+            support_side: Default::default(),
         })
     } else {
         Err(eyre!("don't have a measurement for you!"))
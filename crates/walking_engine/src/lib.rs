@@ -5,6 +5,7 @@ use linear_algebra::{Isometry3, Orientation3, Point2, Point3};
 use mode::{standing::Standing, Mode};
 use parameters::Parameters;
 use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
+use preview_control::PreviewController;
 use serde::{Deserialize, Serialize};
 use types::{
     cycle_time::CycleTime, joints::body::BodyJoints, motion_command::KickVariant,
@@ -22,6 +23,7 @@ pub mod kick_state;
 pub mod kick_steps;
 pub mod mode;
 pub mod parameters;
+pub mod preview_control;
 mod step_plan;
 pub mod step_state;
 mod stiffness;
@@ -47,6 +49,7 @@ pub struct Context<'a> {
     pub measured_joints: BodyJoints,
     pub robot_to_walk: Isometry3<Robot, Walk>,
     pub obstacle_avoiding_arms: &'a ArmCommands,
+    pub preview_controller: &'a PreviewController,
 }
 
 pub trait WalkTransition {
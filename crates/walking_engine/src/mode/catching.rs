@@ -116,6 +116,7 @@ impl Catching {
         Self {
             step: StepState {
                 plan,
+                preview_control: None,
                 ..last_step_state
             },
         }
@@ -75,6 +75,7 @@ impl Standing {
             gyro_balancing: Default::default(),
             foot_leveling: Default::default(),
             last_engine_feet: feet,
+            preview_control: None,
         };
         zero_step_state
             .compute_joints(context, feet)
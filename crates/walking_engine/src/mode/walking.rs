@@ -12,8 +12,8 @@ use types::{
 };
 
 use crate::{
-    kick_state::KickState, step_plan::StepPlan, step_state::StepState, stiffness::Stiffness as _,
-    Context,
+    kick_state::KickState, parameters::Backend, preview_control::PreviewControl,
+    step_plan::StepPlan, step_state::StepState, stiffness::Stiffness as _, Context,
 };
 
 #[derive(
@@ -111,7 +111,7 @@ impl WalkTransition for Walking {
         let current_step = self.step;
 
         if current_step.is_support_switched(context) {
-            return Mode::Walking(Walking::new(
+            return self.followed_by(Walking::new(
                 context,
                 requested_step,
                 current_step.plan.support_side.opposite(),
@@ -132,7 +132,7 @@ impl WalkTransition for Walking {
         }
 
         if current_step.is_timeouted(context.parameters) {
-            return Mode::Walking(Walking::new(
+            return self.followed_by(Walking::new(
                 context,
                 Step::ZERO,
                 current_step.plan.support_side.opposite(),
@@ -156,7 +156,7 @@ impl WalkTransition for Walking {
             let next_support_side = current_step.plan.support_side.opposite();
             // TODO: all kicks require a pre-step
             if next_support_side != kicking_side {
-                return Mode::Walking(Walking::new(
+                return self.followed_by(Walking::new(
                     context,
                     Step::ZERO,
                     next_support_side,
@@ -184,7 +184,7 @@ impl WalkTransition for Walking {
         }
 
         if current_step.is_timeouted(context.parameters) {
-            return Mode::Walking(Walking::new(
+            return self.followed_by(Walking::new(
                 context,
                 Step::ZERO,
                 current_step.plan.support_side.opposite(),
@@ -207,5 +207,28 @@ impl Walking {
 
     pub fn tick(&mut self, context: &Context) {
         self.step.tick(context);
+        if context.parameters.backend == Backend::PreviewControl {
+            let plan = self.step.plan;
+            self.step
+                .preview_control
+                .get_or_insert_with(|| PreviewControl::new(&plan))
+                .tick(
+                    context,
+                    &plan,
+                    self.step.time_since_start.as_secs_f32(),
+                    self.requested_step,
+                );
+        } else {
+            self.step.preview_control = None;
+        }
+    }
+
+    /// Continues the center of mass of the preview control with the following walking step
+    fn followed_by(self, mut next: Walking) -> Mode {
+        next.step.preview_control = self
+            .step
+            .preview_control
+            .map(|preview_control| preview_control.carried_to(&self.step.plan, &next.step.plan));
+        Mode::Walking(next)
     }
 }
@@ -15,6 +15,7 @@ use types::{
 )]
 pub struct Parameters {
     pub anatomic_constraints: AnatomicConstraintsParameters,
+    pub backend: Backend,
     pub base: Base,
     pub catching_steps: CatchingStepsParameters,
     pub gyro_balancing: GyroBalancingParameters,
@@ -26,20 +27,46 @@ pub struct Parameters {
     pub max_base_inside_turn: f32,
     pub max_forward_acceleration: f32,
     pub max_inside_turn_increase: f32,
+    #[path_serde(unit = "m/s", minimum = 0.0)]
     pub max_foot_speed: f32,
     pub max_rotation_speed: f32,
+    #[path_serde(unit = "s", minimum = 0.0, minimum_field = "min_step_duration")]
     pub max_step_duration: Duration,
     pub max_support_foot_lift_speed: f32,
     pub max_turn_acceleration: f32,
+    #[path_serde(unit = "s", minimum = 0.0)]
     pub min_step_duration: Duration,
     pub sole_pressure_threshold: f32,
     pub min_sole_pressure: f32,
+    pub preview_control: PreviewControlParameters,
     pub step_midpoint: Step,
     pub stiffness_loss_compensation: StiffnessLossCompensation,
     pub stiffnesses: Stiffnesses,
     pub swinging_arms: SwingingArmsParameters,
 }
 
+/// Generator of the torso motion relative to the feet while walking
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Deserialize,
+    Serialize,
+    PathSerialize,
+    PathDeserialize,
+    PathIntrospect,
+    PartialEq,
+    Eq,
+)]
+pub enum Backend {
+    /// Interpolates the feet linearly, based on the rUNSWift walk by Bernhard Hengst
+    #[default]
+    Hengst,
+    /// Plans the center of mass by linear inverted pendulum preview control
+    PreviewControl,
+}
+
 #[derive(
     Clone, Debug, Default, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect,
 )]
@@ -52,19 +79,53 @@ pub struct AnatomicConstraintsParameters {
     Clone, Debug, Default, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect,
 )]
 pub struct Base {
+    #[path_serde(unit = "m", minimum = 0.0, maximum = 0.05)]
     pub foot_lift_apex: f32,
     pub foot_lift_apex_increase: Step,
     pub foot_offset_left: Vector3<Walk>,
     pub foot_offset_right: Vector3<Walk>,
+    #[path_serde(unit = "s", minimum = 0.1, maximum = 1.0)]
     pub step_duration: Duration,
     pub step_duration_increase: Step,
+    #[path_serde(minimum = 0.0, maximum = 1.0)]
     pub step_midpoint: f32,
     pub torso_offset: f32,
     pub torso_tilt_base: f32,
     pub torso_tilt: Step,
+    #[path_serde(unit = "m", minimum = 0.15, maximum = 0.3)]
     pub walk_height: f32,
 }
 
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    Deserialize,
+    Serialize,
+    PathSerialize,
+    PathDeserialize,
+    PathIntrospect,
+    PartialEq,
+)]
+pub struct PreviewControlParameters {
+    /// Height of the linear inverted pendulum
+    #[path_serde(unit = "m", minimum = 0.1, maximum = 0.4)]
+    pub center_of_mass_height: f32,
+    /// Period the controller is discretized with, the measured cycle time is split into it
+    #[path_serde(unit = "s", minimum = 0.001, maximum = 0.1)]
+    pub sample_period: Duration,
+    /// How far the zero moment point reference is looked ahead
+    #[path_serde(unit = "s", minimum = 0.0, maximum = 3.0)]
+    pub preview_duration: Duration,
+    #[path_serde(minimum = 0.0)]
+    pub tracking_weight: f32,
+    #[path_serde(minimum = 0.0)]
+    pub jerk_weight: f32,
+    /// Shift of the zero moment point reference from the sole center towards the other foot
+    #[path_serde(unit = "m", minimum = 0.0, maximum = 0.05)]
+    pub zero_moment_point_inward_offset: f32,
+}
+
 #[derive(
     Clone, Debug, Default, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect,
 )]
@@ -77,8 +138,11 @@ pub struct StartingStepParameters {
     Clone, Debug, Default, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect,
 )]
 pub struct Stiffnesses {
+    #[path_serde(minimum = 0.0, maximum = 1.0)]
     pub arm_stiffness: f32,
+    #[path_serde(minimum = 0.0, maximum = 1.0)]
     pub leg_stiffness_walk: f32,
+    #[path_serde(minimum = 0.0, maximum = 1.0)]
     pub leg_stiffness_stand: f32,
 }
 
@@ -0,0 +1,563 @@
+use coordinate_systems::Walk;
+use linear_algebra::{point, vector, Orientation2, Point2, Pose3, Vector2};
+use nalgebra::{Matrix3, Rotation2, RowVector3, Vector3};
+use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
+use serde::{Deserialize, Serialize};
+use types::{step::Step, support_foot::Side};
+
+use crate::{
+    anatomic_constraints::AnatomicConstraints, feet::Feet, parameters::PreviewControlParameters,
+    step_plan::StepPlan, Context,
+};
+
+const GRAVITY: f32 = 9.81;
+const MAXIMUM_RICCATI_ITERATIONS: usize = 1000;
+const RICCATI_TOLERANCE: f32 = 1e-6;
+
+/// # Preview Control
+/// Plans the center of mass as a linear inverted pendulum (cart-table model) following a zero
+/// moment point reference along the upcoming footsteps. The controller is the one described by
+/// Kajita et al. in "Biped Walking Pattern Generation by using Preview Control of Zero-Moment
+/// Point" (ICRA 2003).
+///
+/// The state holds position, velocity and acceleration of the center of mass per axis, relative
+/// to the current support sole over ground. It advances in whole sample periods of the measured
+/// cycle time, the remainder is carried to the next cycle.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Serialize,
+    Deserialize,
+    PathSerialize,
+    PathDeserialize,
+    PathIntrospect,
+)]
+pub struct PreviewControl {
+    pub x: Vector3<f32>,
+    pub y: Vector3<f32>,
+    /// Cycle time not yet integrated because it is shorter than a sample period
+    pub pending_duration: f32,
+}
+
+impl PreviewControl {
+    /// Starts at rest above the torso position at the beginning of the step
+    pub fn new(plan: &StepPlan) -> Self {
+        let center_of_mass = -plan.start_feet.support_sole.position().xy().coords();
+        Self {
+            x: Vector3::new(center_of_mass.x(), 0.0, 0.0),
+            y: Vector3::new(center_of_mass.y(), 0.0, 0.0),
+            pending_duration: 0.0,
+        }
+    }
+
+    /// Moves the state to the support sole of the next step
+    ///
+    /// The position follows from the measured start feet of the next step. Velocity and
+    /// acceleration are kept but rotated from the walk frame at the end of the last step into the
+    /// one of the next step, the new support sole relates both as it landed as the last swing sole.
+    pub fn carried_to(self, last_plan: &StepPlan, plan: &StepPlan) -> Self {
+        let center_of_mass = -plan.start_feet.support_sole.position().xy().coords();
+        let turn = yaw(&plan.start_feet.support_sole) - yaw(&last_plan.end_feet.swing_sole);
+        let rotation = Rotation2::new(turn);
+        let velocity = rotation * nalgebra::vector![self.x.y, self.y.y];
+        let acceleration = rotation * nalgebra::vector![self.x.z, self.y.z];
+        Self {
+            x: Vector3::new(center_of_mass.x(), velocity.x, acceleration.x),
+            y: Vector3::new(center_of_mass.y(), velocity.y, acceleration.y),
+            pending_duration: self.pending_duration,
+        }
+    }
+
+    pub fn center_of_mass(&self) -> Point2<Walk> {
+        point![self.x.x, self.y.x]
+    }
+
+    pub fn tick(&mut self, context: &Context, plan: &StepPlan, elapsed: f32, requested_step: Step) {
+        let controller = context.preview_controller;
+        let period = controller.parameters.sample_period.as_secs_f32();
+        self.pending_duration += context.cycle_time.last_cycle_duration.as_secs_f32();
+        let number_of_substeps = (self.pending_duration / period).floor() as usize;
+        let number_of_samples = controller.gains.preview.len();
+        let start = elapsed - self.pending_duration;
+        let footsteps = plan_footsteps(
+            context,
+            plan,
+            requested_step,
+            start + (number_of_substeps + number_of_samples) as f32 * period,
+        );
+
+        for substep in 0..number_of_substeps {
+            let time = start + substep as f32 * period;
+            let reference: Vec<_> = (1..=number_of_samples)
+                .map(|sample| zero_moment_point_at(&footsteps, time + sample as f32 * period))
+                .collect();
+            self.x = controller.step(self.x, reference.iter().map(|point| point.x()));
+            self.y = controller.step(self.y, reference.iter().map(|point| point.y()));
+        }
+        self.pending_duration -= number_of_substeps as f32 * period;
+    }
+}
+
+/// Cart-table model and gains of the preview controller, derived only when the parameters change
+#[derive(Clone, Debug)]
+pub struct PreviewController {
+    parameters: PreviewControlParameters,
+    model: CartTable,
+    gains: Gains,
+}
+
+impl PreviewController {
+    pub fn new(parameters: &PreviewControlParameters) -> Self {
+        let number_of_samples = (parameters.preview_duration.as_secs_f32()
+            / parameters.sample_period.as_secs_f32())
+        .ceil() as usize;
+        let model = CartTable::new(parameters);
+        let gains = model.gains(parameters, number_of_samples);
+        Self {
+            parameters: parameters.clone(),
+            model,
+            gains,
+        }
+    }
+
+    pub fn update(&mut self, parameters: &PreviewControlParameters) {
+        if *parameters != self.parameters {
+            *self = Self::new(parameters);
+        }
+    }
+
+    fn step(&self, state: Vector3<f32>, reference: impl Iterator<Item = f32>) -> Vector3<f32> {
+        self.model.step(&self.gains, state, reference)
+    }
+}
+
+/// Upcoming footsteps with the time relative to the current step start until which they support
+struct Footstep {
+    end_time: f32,
+    zero_moment_point: Point2<Walk>,
+}
+
+/// The current step followed by repetitions of the requested step on alternating sides
+///
+/// Each step is requested in its own walk frame, its landing and the inward offset are rotated by
+/// the turn accumulated up to that step.
+fn plan_footsteps(
+    context: &Context,
+    plan: &StepPlan,
+    requested_step: Step,
+    horizon: f32,
+) -> Vec<Footstep> {
+    let parameters = context.parameters;
+    let inward_offset = parameters.preview_control.zero_moment_point_inward_offset;
+    let step_duration = plan.step_duration.as_secs_f32();
+
+    let mut support_side = plan.support_side;
+    let mut support_sole = Point2::origin();
+    let mut support_orientation = Orientation2::<Walk>::new(yaw(&plan.end_feet.support_sole));
+    let mut end_time = step_duration;
+    let mut feet = plan.end_feet;
+    let mut footsteps = vec![Footstep {
+        end_time,
+        zero_moment_point: support_sole
+            + support_orientation.as_transform() * inward(inward_offset, support_side),
+    }];
+    while end_time < horizon {
+        let walk_orientation =
+            Orientation2::<Walk>::new(support_orientation.angle() - yaw(&feet.support_sole));
+        support_side = support_side.opposite();
+        support_sole += walk_orientation.as_transform() * swing_landing(&feet);
+        support_orientation = Orientation2::new(walk_orientation.angle() + yaw(&feet.swing_sole));
+        end_time += step_duration;
+        footsteps.push(Footstep {
+            end_time,
+            zero_moment_point: support_sole
+                + support_orientation.as_transform() * inward(inward_offset, support_side),
+        });
+        let step = requested_step.clamp_to_anatomic_constraints(
+            support_side,
+            parameters.max_base_inside_turn,
+            parameters.max_inside_turn_increase,
+        );
+        feet = Feet::end_from_request(parameters, step, support_side);
+    }
+    footsteps
+}
+
+fn swing_landing(feet: &Feet) -> Vector2<Walk> {
+    (feet.swing_sole.position() - feet.support_sole.position()).xy()
+}
+
+fn yaw(sole: &Pose3<Walk>) -> f32 {
+    let (_, _, yaw) = sole.orientation().euler_angles();
+    yaw
+}
+
+/// Shifts the zero moment point from the sole center towards the other foot
+fn inward(offset: f32, support_side: Side) -> Vector2<Walk> {
+    match support_side {
+        Side::Left => vector![0.0, -offset],
+        Side::Right => vector![0.0, offset],
+    }
+}
+
+fn zero_moment_point_at(footsteps: &[Footstep], time: f32) -> Point2<Walk> {
+    footsteps
+        .iter()
+        .find(|footstep| time < footstep.end_time)
+        .or(footsteps.last())
+        .map_or(Point2::origin(), |footstep| footstep.zero_moment_point)
+}
+
+/// Discrete cart-table model with the jerk of the center of mass as input
+#[derive(Clone, Debug)]
+struct CartTable {
+    state_transition: Matrix3<f32>,
+    input: Vector3<f32>,
+    output: RowVector3<f32>,
+}
+
+#[derive(Clone, Debug)]
+struct Gains {
+    state: RowVector3<f32>,
+    preview: Vec<f32>,
+}
+
+impl CartTable {
+    fn new(parameters: &PreviewControlParameters) -> Self {
+        let period = parameters.sample_period.as_secs_f32();
+        Self {
+            state_transition: Matrix3::new(
+                1.0,
+                period,
+                period.powi(2) / 2.0,
+                0.0,
+                1.0,
+                period,
+                0.0,
+                0.0,
+                1.0,
+            ),
+            input: Vector3::new(period.powi(3) / 6.0, period.powi(2) / 2.0, period),
+            output: RowVector3::new(1.0, 0.0, -parameters.center_of_mass_height / GRAVITY),
+        }
+    }
+
+    /// Solves the discrete algebraic Riccati equation by iteration and derives the preview gains
+    fn gains(&self, parameters: &PreviewControlParameters, number_of_samples: usize) -> Gains {
+        let a = self.state_transition;
+        let b = self.input;
+        let c = self.output;
+        let q = c.transpose() * c * parameters.tracking_weight;
+        let r = parameters.jerk_weight;
+
+        let mut p = q;
+        for _ in 0..MAXIMUM_RICCATI_ITERATIONS {
+            let gain = (b.transpose() * p * a) / (r + (b.transpose() * p * b).x);
+            let next = a.transpose() * p * a - a.transpose() * p * b * gain + q;
+            let converged = (next - p).abs().max() < RICCATI_TOLERANCE * p.abs().max();
+            p = next;
+            if converged {
+                break;
+            }
+        }
+        let normalization = 1.0 / (r + (b.transpose() * p * b).x);
+        let state = (b.transpose() * p * a) * normalization;
+        let closed_loop = a - b * state;
+
+        let mut propagation = c.transpose() * parameters.tracking_weight;
+        let preview = (0..number_of_samples)
+            .map(|_| {
+                let gain = (b.transpose() * propagation).x * normalization;
+                propagation = closed_loop.transpose() * propagation;
+                gain
+            })
+            .collect();
+        Gains { state, preview }
+    }
+
+    fn step(
+        &self,
+        gains: &Gains,
+        state: Vector3<f32>,
+        reference: impl Iterator<Item = f32>,
+    ) -> Vector3<f32> {
+        let preview: f32 = gains
+            .preview
+            .iter()
+            .zip(reference)
+            .map(|(gain, zero_moment_point)| gain * zero_moment_point)
+            .sum();
+        let jerk = -(gains.state * state).x + preview;
+        self.state_transition * state + self.input * jerk
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        iter::repeat,
+        time::{Duration, UNIX_EPOCH},
+    };
+
+    use approx::assert_relative_eq;
+    use linear_algebra::{Isometry3, Orientation3, Point3};
+    use types::cycle_time::CycleTime;
+
+    use crate::parameters::{Base, Parameters};
+
+    use super::*;
+
+    const CYCLE_DURATION: Duration = Duration::from_millis(12);
+
+    fn parameters() -> PreviewControlParameters {
+        PreviewControlParameters {
+            center_of_mass_height: 0.23,
+            sample_period: Duration::from_millis(12),
+            preview_duration: Duration::from_secs(1),
+            tracking_weight: 1.0,
+            jerk_weight: 1e-6,
+            zero_moment_point_inward_offset: 0.01,
+        }
+    }
+
+    #[test]
+    fn preview_gains_sum_to_position_gain() {
+        let controller = PreviewController::new(&parameters());
+
+        let preview_gains: f32 = controller.gains.preview.iter().sum();
+
+        assert_relative_eq!(preview_gains, controller.gains.state.x, max_relative = 0.01);
+    }
+
+    #[test]
+    fn center_of_mass_converges_to_constant_zero_moment_point() {
+        let controller = PreviewController::new(&parameters());
+
+        let mut state = Vector3::zeros();
+        for _ in 0..500 {
+            state = controller.step(state, repeat(0.05));
+        }
+
+        assert_relative_eq!(state.x, 0.05, epsilon = 1e-3);
+        assert_relative_eq!(state.y, 0.0, epsilon = 1e-4);
+        assert_relative_eq!(state.z, 0.0, epsilon = 1e-4);
+        assert_relative_eq!((controller.model.output * state).x, 0.05, epsilon = 1e-3);
+    }
+
+    fn walking_parameters() -> Parameters {
+        Parameters {
+            base: Base {
+                foot_offset_left: vector![0.0, 0.05, 0.0],
+                foot_offset_right: vector![0.0, -0.05, 0.0],
+                step_duration: Duration::from_millis(250),
+                ..Default::default()
+            },
+            max_base_inside_turn: 1.0,
+            preview_control: parameters(),
+            ..Default::default()
+        }
+    }
+
+    fn with_context(parameters: &Parameters, test: impl FnOnce(&Context)) {
+        let preview_controller = PreviewController::new(&parameters.preview_control);
+        let context = Context {
+            parameters,
+            max_step_size: &Step::ZERO,
+            kick_steps: &Default::default(),
+            cycle_time: &CycleTime {
+                start_time: UNIX_EPOCH,
+                last_cycle_duration: CYCLE_DURATION,
+            },
+            center_of_mass: &Point3::origin(),
+            zero_moment_point: &Point2::origin(),
+            consecutive_cycles_zero_moment_point_outside_support_polygon: &0,
+            force_sensitive_resistors: &Default::default(),
+            robot_orientation: &Orientation3::from_euler_angles(0.0, 0.0, 0.0),
+            robot_to_ground: None,
+            gyro: nalgebra::Vector3::zeros(),
+            last_actuated_joints: Default::default(),
+            measured_joints: Default::default(),
+            robot_to_walk: Isometry3::identity(),
+            obstacle_avoiding_arms: &Default::default(),
+            preview_controller: &preview_controller,
+        };
+        test(&context);
+    }
+
+    fn plan(parameters: &Parameters, start_feet: Feet, step: Step, support_side: Side) -> StepPlan {
+        StepPlan {
+            step_duration: parameters.base.step_duration,
+            start_feet,
+            end_feet: Feet::end_from_request(parameters, step, support_side),
+            support_side,
+            foot_lift_apex: 0.0,
+            midpoint: 0.5,
+        }
+    }
+
+    fn standing_feet(parameters: &Parameters, support_side: Side) -> Feet {
+        Feet::end_from_request(parameters, Step::ZERO, support_side)
+    }
+
+    #[test]
+    fn footsteps_follow_the_turn_of_previous_steps() {
+        let parameters = walking_parameters();
+        let turn = 0.4;
+        let step = Step {
+            forward: 0.0,
+            left: 0.0,
+            turn,
+        };
+        let plan = plan(
+            &parameters,
+            standing_feet(&parameters, Side::Left),
+            step,
+            Side::Left,
+        );
+
+        with_context(&parameters, |context| {
+            let footsteps = plan_footsteps(context, &plan, step, 1.5);
+
+            assert_eq!(footsteps.len(), 6);
+            let landings: Vec<_> = footsteps
+                .windows(2)
+                .map(|pair| {
+                    let inward_offsets = [&pair[0], &pair[1]].map(|footstep| {
+                        let index = (footstep.end_time / 0.25).round() as i32 - 1;
+                        let sole_orientation = (2 * index - 1) as f32 * turn / 2.0;
+                        let side = if index % 2 == 0 {
+                            Side::Left
+                        } else {
+                            Side::Right
+                        };
+                        Orientation2::<Walk>::new(sole_orientation).as_transform::<Walk>()
+                            * inward(0.01, side)
+                    });
+                    (pair[1].zero_moment_point - inward_offsets[1])
+                        - (pair[0].zero_moment_point - inward_offsets[0])
+                })
+                .collect();
+            assert_relative_eq!(landings[0], vector![0.0, -0.1], epsilon = 1e-6);
+            for pair in landings.windows(2) {
+                let expected = -(Orientation2::<Walk>::new(turn).as_transform::<Walk>() * pair[0]);
+                assert_relative_eq!(pair[1], expected, epsilon = 1e-5);
+            }
+        });
+    }
+
+    #[test]
+    fn carried_state_keeps_velocity_over_ground_in_rotated_walk_frame() {
+        let parameters = walking_parameters();
+        let step = Step {
+            forward: 0.04,
+            left: 0.0,
+            turn: 0.3,
+        };
+        let last_plan = plan(
+            &parameters,
+            standing_feet(&parameters, Side::Left),
+            step,
+            Side::Left,
+        );
+        let frame_turn = 0.1;
+        let walk_rotation =
+            Isometry3::<Walk, Walk>::from(Orientation3::from_euler_angles(0.0, 0.0, -frame_turn));
+        let switched = last_plan.end_feet.switch();
+        let start_feet = Feet {
+            support_sole: walk_rotation * switched.support_sole,
+            swing_sole: walk_rotation * switched.swing_sole,
+        };
+        let next_plan = plan(&parameters, start_feet, step, Side::Right);
+        let state = PreviewControl {
+            x: Vector3::new(0.01, 0.2, 0.5),
+            y: Vector3::new(-0.05, -0.1, 0.3),
+            pending_duration: 0.004,
+        };
+
+        let carried = state.carried_to(&last_plan, &next_plan);
+
+        let rotation = Rotation2::new(-frame_turn);
+        let velocity = rotation * nalgebra::vector![0.2, -0.1];
+        let acceleration = rotation * nalgebra::vector![0.5, 0.3];
+        let center_of_mass = -start_feet.support_sole.position().xy().coords();
+        assert_relative_eq!(
+            carried.center_of_mass().coords(),
+            center_of_mass,
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(carried.x.y, velocity.x, epsilon = 1e-6);
+        assert_relative_eq!(carried.y.y, velocity.y, epsilon = 1e-6);
+        assert_relative_eq!(carried.x.z, acceleration.x, epsilon = 1e-6);
+        assert_relative_eq!(carried.y.z, acceleration.y, epsilon = 1e-6);
+        assert_relative_eq!(carried.pending_duration, 0.004);
+    }
+
+    #[test]
+    fn center_of_mass_is_continuous_over_ground_across_support_switch() {
+        let parameters = walking_parameters();
+        let step = Step {
+            forward: 0.04,
+            left: 0.0,
+            turn: 0.3,
+        };
+        let last_plan = plan(
+            &parameters,
+            standing_feet(&parameters, Side::Left),
+            step,
+            Side::Left,
+        );
+
+        with_context(&parameters, |context| {
+            let mut state = PreviewControl::new(&last_plan);
+            let mut elapsed = 0.0;
+            let mut last_center_of_mass = state.center_of_mass();
+            while elapsed < last_plan.step_duration.as_secs_f32() {
+                elapsed += CYCLE_DURATION.as_secs_f32();
+                state.tick(context, &last_plan, elapsed, step);
+                let center_of_mass = state.center_of_mass();
+                assert!((center_of_mass - last_center_of_mass).norm() < 0.01);
+                last_center_of_mass = center_of_mass;
+            }
+
+            // the next step starts from the feet placed around the planned center of mass
+            let center_of_mass = state.center_of_mass().coords();
+            let swing_landing = swing_landing(&last_plan.end_feet);
+            let start_feet = Feet {
+                support_sole: Pose3::from_parts(
+                    (swing_landing - center_of_mass).as_point().extend(0.0),
+                    last_plan.end_feet.swing_sole.orientation(),
+                ),
+                swing_sole: Pose3::from_parts(
+                    (-center_of_mass).as_point().extend(0.0),
+                    last_plan.end_feet.support_sole.orientation(),
+                ),
+            };
+            let next_plan = plan(&parameters, start_feet, step, Side::Right);
+            let velocity_before = nalgebra::vector![state.x.y, state.y.y];
+            let mut state = state.carried_to(&last_plan, &next_plan);
+
+            assert_relative_eq!(
+                state.center_of_mass().coords() + swing_landing,
+                center_of_mass,
+                epsilon = 1e-6
+            );
+            assert_relative_eq!(
+                nalgebra::vector![state.x.y, state.y.y],
+                velocity_before,
+                epsilon = 1e-6
+            );
+
+            let mut last_center_of_mass = state.center_of_mass();
+            let mut elapsed = 0.0;
+            while elapsed < next_plan.step_duration.as_secs_f32() {
+                elapsed += CYCLE_DURATION.as_secs_f32();
+                state.tick(context, &next_plan, elapsed, step);
+                let center_of_mass = state.center_of_mass();
+                assert!((center_of_mass - last_center_of_mass).norm() < 0.01);
+                last_center_of_mass = center_of_mass;
+            }
+        });
+    }
+}
@@ -2,7 +2,7 @@ use std::{f32::consts::FRAC_PI_2, time::Duration};
 
 use coordinate_systems::{LeftSole, RightSole, Walk};
 use kinematics::inverse::leg_angles;
-use linear_algebra::{point, Isometry3, Orientation3, Point3, Pose3, Rotation3};
+use linear_algebra::{point, Isometry3, Orientation3, Point3, Pose3, Rotation3, Vector2};
 use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
 use serde::{Deserialize, Serialize};
 use splines::Interpolate;
@@ -22,6 +22,7 @@ use super::{
     feet::Feet,
     foot_leveling::{FootLeveling, FootLevelingExt},
     gyro_balancing::{GyroBalancing, GyroBalancingExt},
+    preview_control::PreviewControl,
     step_plan::StepPlan,
 };
 
@@ -34,6 +35,7 @@ pub struct StepState {
     pub gyro_balancing: GyroBalancing,
     pub foot_leveling: FootLeveling,
     pub last_engine_feet: Feet,
+    pub preview_control: Option<PreviewControl>,
 }
 
 impl StepState {
@@ -44,6 +46,7 @@ impl StepState {
             gyro_balancing: Default::default(),
             foot_leveling: Default::default(),
             last_engine_feet: plan.start_feet,
+            preview_control: None,
         }
     }
 
@@ -158,9 +161,16 @@ impl StepState {
         let dt = context.cycle_time.last_cycle_duration.as_secs_f32();
         let max_movement = parameters.max_foot_speed * dt;
 
-        let support_position = self.support_sole_position(parameters);
+        let (support_position, swing_position) = match self.preview_control {
+            Some(preview_control) => {
+                self.sole_positions_around(parameters, preview_control.center_of_mass().coords())
+            }
+            None => (
+                self.support_sole_position(parameters),
+                self.swing_sole_position(),
+            ),
+        };
         let support_turn = self.support_orientation(parameters);
-        let swing_position = self.swing_sole_position();
         let swing_turn = self.swing_orientation(parameters);
 
         let limited_support_position = clamp_xy_movement(
@@ -197,6 +207,36 @@ impl StepState {
         point![offsets.x(), offsets.y(), lift]
     }
 
+    /// Places the soles relative to a planned center of mass given over the support sole
+    fn sole_positions_around(
+        &self,
+        parameters: &Parameters,
+        center_of_mass: Vector2<Walk>,
+    ) -> (Point3<Walk>, Point3<Walk>) {
+        let parabolic_time = parabolic_step(self.normalized_time_since_start());
+        let start_feet = self.plan.start_feet;
+        let end_feet = self.plan.end_feet;
+        let swing_start =
+            (start_feet.swing_sole.position() - start_feet.support_sole.position()).xy();
+        let swing_end = (end_feet.swing_sole.position() - end_feet.support_sole.position()).xy();
+        let swing_offsets = swing_start + (swing_end - swing_start) * parabolic_time;
+
+        let support_offsets = -center_of_mass;
+        let swing_offsets = swing_offsets - center_of_mass;
+        (
+            point![
+                support_offsets.x(),
+                support_offsets.y(),
+                self.support_sole_lift_at(parameters)
+            ],
+            point![
+                swing_offsets.x(),
+                swing_offsets.y(),
+                self.swing_sole_lift_at()
+            ],
+        )
+    }
+
     fn support_sole_lift_at(&self, parameters: &Parameters) -> f32 {
         let start_lift = self.plan.start_feet.support_sole.position().z();
         let end_lift = self.plan.end_feet.support_sole.position().z();
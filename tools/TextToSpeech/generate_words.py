@@ -0,0 +1,29 @@
+#!/usr/bin/env python3
+from pathlib import Path
+import argparse, re, subprocess, sys
+
+repository_root = Path(__file__).resolve().parents[2]
+audio_types = repository_root / 'crates/types/src/audio.rs'
+words_directory = repository_root / 'etc/sounds/words'
+
+parser = argparse.ArgumentParser(description='Generate the word bank for phrases from types::audio::Word')
+parser.add_argument('-a', '--api_key', help='Google api key')
+parser.add_argument('-v', '--voice_name', default='en-US-Neural2-F', help='The full name of the voice')
+parser.add_argument('-f', '--force', action='store_true', help='Regenerate words which already exist')
+args = parser.parse_args()
+
+# The word bank is defined by the variants of `pub enum Word` in the types crate
+source = audio_types.read_text()
+enum_body = re.search(r'pub enum Word \{(.*?)\}', source, re.DOTALL).group(1)
+words = re.findall(r'^\s*(\w+),', enum_body, re.MULTILINE)
+
+words_directory.mkdir(parents=True, exist_ok=True)
+for word in words:
+    output = words_directory / '{}.ogg'.format(word)
+    if output.exists() and not args.force:
+        continue
+    print('Generating {}'.format(output.relative_to(repository_root)))
+    command = [sys.executable, str(Path(__file__).parent / 'tts.py'), '-v', args.voice_name, '-o', str(output), word.lower()]
+    if args.api_key is not None:
+        command += ['-a', args.api_key]
+    subprocess.run(command, check=True)
@@ -0,0 +1,165 @@
+#!/usr/bin/env python3
+"""Train the network of the whistle classifier in crates/audio/src/whistle_classifier.rs
+
+The recordings and labels have the same format as for the whistle evaluation tool, see
+docs/tooling/whistle_evaluation.md. The features have to match `log_mel_spectrogram()` of the
+whistle classifier exactly, change both together.
+"""
+from pathlib import Path
+import argparse, json, random
+
+import numpy as np
+import soundfile
+import tensorflow as tf
+
+repository_root = Path(__file__).resolve().parents[3]
+
+# Constants of crates/audio/src/whistle_detection.rs and crates/audio/src/whistle_classifier.rs
+AUDIO_SAMPLE_RATE = 44100
+NUMBER_OF_AUDIO_SAMPLES = 2048
+FRAME_LENGTH = 512
+HOP_LENGTH = 256
+NUMBER_OF_FRAMES = (NUMBER_OF_AUDIO_SAMPLES - FRAME_LENGTH) // HOP_LENGTH + 1
+NUMBER_OF_FREQUENCY_BINS = FRAME_LENGTH // 2 + 1
+NUMBER_OF_MEL_BANDS = 40
+MINIMUM_FREQUENCY = 500.0
+MAXIMUM_FREQUENCY = 10000.0
+LOG_OFFSET = 1e-6
+
+
+def hertz_to_mel(frequency):
+    return 2595.0 * np.log10(1.0 + frequency / 700.0)
+
+
+def mel_to_hertz(mel):
+    return 700.0 * (10.0 ** (mel / 2595.0) - 1.0)
+
+
+def mel_filter_bank():
+    minimum_mel = hertz_to_mel(MINIMUM_FREQUENCY)
+    maximum_mel = hertz_to_mel(MAXIMUM_FREQUENCY)
+    corner_frequencies = mel_to_hertz(
+        np.linspace(minimum_mel, maximum_mel, NUMBER_OF_MEL_BANDS + 2))
+    frequencies = np.arange(NUMBER_OF_FREQUENCY_BINS) * AUDIO_SAMPLE_RATE / FRAME_LENGTH
+    lower = corner_frequencies[:-2, np.newaxis]
+    center = corner_frequencies[1:-1, np.newaxis]
+    upper = corner_frequencies[2:, np.newaxis]
+    rising = (frequencies - lower) / (center - lower)
+    falling = (upper - frequencies) / (upper - center)
+    return np.maximum(np.minimum(rising, falling), 0.0)
+
+
+FILTER_BANK = mel_filter_bank()
+WINDOW = np.sin(np.pi * np.arange(FRAME_LENGTH) / FRAME_LENGTH) ** 2
+
+
+def log_mel_spectrogram(buffer):
+    """Log mel band energies of overlapping Hann windowed frames, ordered frame by frame"""
+    frames = np.stack([
+        buffer[start:start + FRAME_LENGTH]
+        for start in range(0, NUMBER_OF_AUDIO_SAMPLES - FRAME_LENGTH + 1, HOP_LENGTH)
+    ])
+    power_spectrum = np.abs(np.fft.rfft(frames * WINDOW)) ** 2 / FRAME_LENGTH
+    return np.log(power_spectrum @ FILTER_BANK.T + LOG_OFFSET).reshape(-1)
+
+
+def load_samples(recording, buffer_stride):
+    """Features and labels of all buffers of all channels of a recording
+
+    A buffer is a whistle if at least half of it lies within a labeled whistle. Buffers which
+    overlap a whistle less than that are ambiguous and skipped.
+    """
+    audio, sample_rate = soundfile.read(recording, dtype='float32', always_2d=True)
+    if sample_rate != AUDIO_SAMPLE_RATE:
+        raise ValueError(f'{recording} has a sample rate of {sample_rate}, expected {AUDIO_SAMPLE_RATE}')
+    whistles = json.loads(recording.with_suffix('.json').read_text())
+    is_whistle = np.zeros(len(audio), dtype=bool)
+    for whistle in whistles:
+        is_whistle[int(whistle['start'] * sample_rate):int(whistle['end'] * sample_rate)] = True
+
+    features = []
+    labels = []
+    for start in range(0, len(audio) - NUMBER_OF_AUDIO_SAMPLES + 1, buffer_stride):
+        whistle_fraction = is_whistle[start:start + NUMBER_OF_AUDIO_SAMPLES].mean()
+        if 0.0 < whistle_fraction < 0.5:
+            continue
+        for channel in audio[start:start + NUMBER_OF_AUDIO_SAMPLES].T:
+            features.append(log_mel_spectrogram(channel))
+            labels.append(float(whistle_fraction >= 0.5))
+    return features, labels
+
+
+def load_dataset(recordings, buffer_stride):
+    features = []
+    labels = []
+    for recording in recordings:
+        recording_features, recording_labels = load_samples(recording, buffer_stride)
+        features += recording_features
+        labels += recording_labels
+    return np.array(features, dtype=np.float32), np.array(labels, dtype=np.float32)
+
+
+def build_model():
+    inputs = tf.keras.Input(shape=(NUMBER_OF_FRAMES * NUMBER_OF_MEL_BANDS,))
+    x = tf.keras.layers.Reshape((NUMBER_OF_FRAMES, NUMBER_OF_MEL_BANDS, 1))(inputs)
+    x = tf.keras.layers.BatchNormalization()(x)
+    x = tf.keras.layers.Conv2D(8, (3, 5), padding='same', activation='relu')(x)
+    x = tf.keras.layers.MaxPooling2D((1, 2))(x)
+    x = tf.keras.layers.Conv2D(16, (3, 5), padding='same', activation='relu')(x)
+    x = tf.keras.layers.MaxPooling2D((1, 2))(x)
+    x = tf.keras.layers.Flatten()(x)
+    x = tf.keras.layers.Dense(32, activation='relu')(x)
+    outputs = tf.keras.layers.Dense(1, activation='sigmoid')(x)
+    return tf.keras.Model(inputs, outputs)
+
+
+def main():
+    parser = argparse.ArgumentParser(description='Train the network of the whistle classifier')
+    parser.add_argument('recordings', nargs='+', type=Path, help='WAV or FLAC recordings with a <name>.json label file next to them')
+    parser.add_argument('-o', '--output', type=Path, default=repository_root / 'etc/neural_networks/whistle_classifier.hdf5')
+    parser.add_argument('--buffer_stride', type=int, default=NUMBER_OF_AUDIO_SAMPLES // 2, help='Samples between the starts of consecutive buffers')
+    parser.add_argument('--validation_fraction', type=float, default=0.2, help='Fraction of the recordings used for validation')
+    parser.add_argument('--epochs', type=int, default=30)
+    parser.add_argument('--detection_threshold', type=float, default=0.8, help='Threshold for the reported validation metrics, see whistle_classifier.detection_threshold')
+    args = parser.parse_args()
+
+    # split by recording since buffers of the same recording are strongly correlated
+    recordings = sorted(args.recordings)
+    random.Random(0).shuffle(recordings)
+    number_of_validation_recordings = round(len(recordings) * args.validation_fraction)
+    validation_recordings = recordings[:number_of_validation_recordings]
+    training_recordings = recordings[number_of_validation_recordings:]
+
+    training_features, training_labels = load_dataset(training_recordings, args.buffer_stride)
+    print(f'{len(training_labels)} training buffers, {int(training_labels.sum())} with whistle')
+    positive_weight = (len(training_labels) - training_labels.sum()) / max(training_labels.sum(), 1.0)
+
+    model = build_model()
+    model.compile(
+        optimizer='adam',
+        loss='binary_crossentropy',
+        metrics=[
+            tf.keras.metrics.Precision(thresholds=args.detection_threshold),
+            tf.keras.metrics.Recall(thresholds=args.detection_threshold),
+        ],
+    )
+    validation_data = None
+    if validation_recordings:
+        validation_data = load_dataset(validation_recordings, NUMBER_OF_AUDIO_SAMPLES)
+    model.fit(
+        training_features,
+        training_labels,
+        epochs=args.epochs,
+        batch_size=256,
+        shuffle=True,
+        class_weight={0: 1.0, 1: positive_weight},
+        validation_data=validation_data,
+    )
+
+    args.output.parent.mkdir(parents=True, exist_ok=True)
+    tf.keras.models.save_model(model, str(args.output), save_format='h5')
+    print(f'Saved {args.output}')
+
+
+if __name__ == '__main__':
+    main()
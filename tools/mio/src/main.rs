@@ -8,6 +8,7 @@ use bevy_panorbit_camera::{PanOrbitCamera, PanOrbitCameraPlugin};
 use color_eyre::eyre::Result;
 use field::FieldPlugin;
 use fps::FpsPlugin;
+use motion_editor::MotionEditorPlugin;
 use nao::NaoPlugin;
 use parameters::Parameters;
 use shadow_toggle::ShadowTogglePlugin;
@@ -17,6 +18,7 @@ mod async_runtime;
 mod ball;
 mod field;
 mod fps;
+mod motion_editor;
 mod nao;
 mod parameters;
 mod ring;
@@ -35,6 +37,7 @@ fn main() -> Result<()> {
         .add_plugins(BallPlugin)
         .add_plugins(FieldPlugin)
         .add_plugins(NaoPlugin)
+        .add_plugins(MotionEditorPlugin)
         .add_plugins(ShadowTogglePlugin)
         .add_plugins(AsyncRuntimePlugin)
         .add_systems(Startup, setup_camera)
@@ -0,0 +1,571 @@
+use std::{
+    f32::consts::PI,
+    path::{absolute, Path, PathBuf},
+    time::Duration,
+};
+
+use bevy::prelude::*;
+use bevy_egui::egui::{
+    CollapsingHeader, Color32, DragValue, Grid, ScrollArea, Slider, TextEdit, Ui,
+};
+use color_eyre::{eyre::WrapErr, Result};
+use egui_plot::{Legend, Line, Plot, PlotPoints, Points, VLine};
+use motionfile::{KeyFrame, MotionFile, MotionFileFrame, MotionInterpolator, TimedSpline};
+use repository::Repository;
+use serde::{de::DeserializeOwned, Serialize};
+use splines::Interpolation;
+use types::joints::{Joints, JointsName};
+
+use crate::nao::{apply_joint_positions, joint_links, spawn_robot_model, RobotSpecification};
+
+/// Height of the robot frame above the ground while previewing a standing motion
+const PREVIEW_ROBOT_HEIGHT: f32 = 0.33;
+const NUMBER_OF_CURVE_SAMPLES: usize = 200;
+
+pub struct MotionEditorPlugin;
+
+impl Plugin for MotionEditorPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(MotionEditor::default())
+            .add_systems(Update, spawn_preview)
+            .add_systems(Update, advance_playback)
+            .add_systems(Update, update_preview.after(advance_playback));
+    }
+}
+
+#[derive(Component)]
+struct MotionPreview {
+    joints: Joints<Entity>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Selection {
+    frame: usize,
+    keyframe: usize,
+}
+
+/// Conditions of a frame as editable JSON, applied to the frame whenever they parse
+#[derive(Default)]
+struct ConditionTexts {
+    entry_condition: String,
+    interrupt_conditions: String,
+    exit_condition: String,
+}
+
+impl ConditionTexts {
+    fn new(frame: &MotionFileFrame<Joints<f32>>) -> Self {
+        Self {
+            entry_condition: to_json(&frame.entry_condition),
+            interrupt_conditions: to_json(&frame.interrupt_conditions),
+            exit_condition: to_json(&frame.exit_condition),
+        }
+    }
+}
+
+enum Edit {
+    AddKeyframe { frame: usize },
+    DuplicateKeyframe(Selection),
+    RemoveKeyframe(Selection),
+    AddFrame,
+    RemoveFrame { frame: usize },
+}
+
+#[derive(Resource, Default)]
+pub struct MotionEditor {
+    path: String,
+    motion: MotionFile<Joints<f32>>,
+    condition_texts: Vec<ConditionTexts>,
+    selection: Option<Selection>,
+    time: Duration,
+    playing: bool,
+    plotted_joints: Vec<JointsName>,
+    status: Option<Result<String, String>>,
+}
+
+impl MotionEditor {
+    fn open(&mut self) -> Result<()> {
+        let path = resolve(&self.path);
+        let motion = if path
+            .extension()
+            .is_some_and(|extension| extension == "motion2")
+        {
+            MotionFile::from_motion2_path(&path)?
+        } else {
+            MotionFile::from_path(&path)?
+        };
+        self.condition_texts = motion.motion.iter().map(ConditionTexts::new).collect();
+        self.motion = motion;
+        self.selection = None;
+        self.time = Duration::ZERO;
+        self.playing = false;
+        Ok(())
+    }
+
+    /// Saves as JSON motion file, imported `.motion2` files are saved next to the original
+    fn save(&mut self) -> Result<String> {
+        let mut path = resolve(&self.path);
+        if path
+            .extension()
+            .is_some_and(|extension| extension == "motion2")
+        {
+            path.set_extension("json");
+        }
+        MotionInterpolator::try_from(self.motion.clone()).wrap_err_with(|| {
+            format!("not saving {}, it cannot be interpolated", path.display())
+        })?;
+        self.motion.to_path(&path)?;
+        self.path = path.display().to_string();
+        Ok(format!("saved {}", path.display()))
+    }
+
+    fn total_duration(&self) -> Duration {
+        self.motion
+            .motion
+            .iter()
+            .flat_map(|frame| &frame.keyframes)
+            .map(|keyframe| keyframe.duration)
+            .sum()
+    }
+
+    /// Time at which each keyframe is reached, in the order of the motion
+    fn keyframe_times(&self) -> Vec<(Selection, Duration)> {
+        let mut time = Duration::ZERO;
+        let mut keyframe_times = Vec::new();
+        for (frame_index, frame) in self.motion.motion.iter().enumerate() {
+            for (keyframe_index, keyframe) in frame.keyframes.iter().enumerate() {
+                time += keyframe.duration;
+                keyframe_times.push((
+                    Selection {
+                        frame: frame_index,
+                        keyframe: keyframe_index,
+                    },
+                    time,
+                ));
+            }
+        }
+        keyframe_times
+    }
+
+    /// Positions at the given time, interpolating each frame separately as the robot does
+    fn positions_at(&self, time: Duration) -> Option<Joints<f32>> {
+        let mut start_positions = self.motion.initial_positions;
+        let mut frame_start = Duration::ZERO;
+        for frame in &self.motion.motion {
+            let Some(last_keyframe) = frame.keyframes.last() else {
+                continue;
+            };
+            let spline = TimedSpline::try_new_with_start(
+                start_positions,
+                frame.keyframes.clone(),
+                self.motion.interpolation_mode,
+            )
+            .ok()?;
+            if time < frame_start + spline.total_duration() {
+                return Some(spline.value_at(time - frame_start));
+            }
+            frame_start += spline.total_duration();
+            start_positions = last_keyframe.positions;
+        }
+        Some(start_positions)
+    }
+
+    fn is_valid(&self, selection: Selection) -> bool {
+        self.motion
+            .motion
+            .get(selection.frame)
+            .is_some_and(|frame| selection.keyframe < frame.keyframes.len())
+    }
+
+    pub fn ui(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.add(
+                TextEdit::singleline(&mut self.path)
+                    .hint_text("etc/motions/stand_up_back.json or .motion2"),
+            );
+            if ui.button("Open").clicked() {
+                self.status = Some(
+                    self.open()
+                        .map(|()| format!("opened {}", self.path))
+                        .map_err(|error| format!("{error:#}")),
+                );
+            }
+            if ui.button("Save").clicked() {
+                self.status = Some(self.save().map_err(|error| format!("{error:#}")));
+            }
+        });
+        match &self.status {
+            Some(Ok(message)) => {
+                ui.label(message);
+            }
+            Some(Err(error)) => {
+                ui.colored_label(Color32::RED, error);
+            }
+            None => {}
+        }
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            ui.label("Interpolation");
+            for (name, mode) in [
+                ("Linear", Interpolation::Linear),
+                ("Cosine", Interpolation::Cosine),
+                ("Catmull-Rom", Interpolation::CatmullRom),
+            ] {
+                let is_selected = matches!(
+                    (self.motion.interpolation_mode, mode),
+                    (Interpolation::Linear, Interpolation::Linear)
+                        | (Interpolation::Cosine, Interpolation::Cosine)
+                        | (Interpolation::CatmullRom, Interpolation::CatmullRom)
+                );
+                if ui.selectable_label(is_selected, name).clicked() {
+                    self.motion.interpolation_mode = mode;
+                }
+            }
+        });
+        self.timeline_ui(ui);
+        ui.separator();
+
+        ScrollArea::vertical().show(ui, |ui| {
+            self.frames_ui(ui);
+            ui.separator();
+            self.joints_ui(ui);
+            ui.separator();
+            self.curves_ui(ui);
+        });
+    }
+
+    fn timeline_ui(&mut self, ui: &mut Ui) {
+        let total_duration = self.total_duration();
+        ui.horizontal(|ui| {
+            if ui.button(if self.playing { "⏸" } else { "▶" }).clicked() {
+                self.playing = !self.playing;
+            }
+            let mut seconds = self.time.as_secs_f32();
+            if ui
+                .add(Slider::new(&mut seconds, 0.0..=total_duration.as_secs_f32()).suffix(" s"))
+                .changed()
+            {
+                self.time = Duration::from_secs_f32(seconds);
+                self.playing = false;
+            }
+        });
+    }
+
+    fn frames_ui(&mut self, ui: &mut Ui) {
+        let keyframe_times = self.keyframe_times();
+        let mut edits = Vec::new();
+        for (frame_index, frame) in self.motion.motion.iter_mut().enumerate() {
+            let title = frame
+                .name
+                .clone()
+                .unwrap_or_else(|| format!("Frame {frame_index}"));
+            CollapsingHeader::new(title)
+                .id_salt(frame_index)
+                .default_open(true)
+                .show(ui, |ui| {
+                    let mut name = frame.name.clone().unwrap_or_default();
+                    ui.horizontal(|ui| {
+                        ui.label("Name");
+                        if ui.text_edit_singleline(&mut name).changed() {
+                            frame.name = (!name.is_empty()).then_some(name);
+                        }
+                    });
+                    let texts = &mut self.condition_texts[frame_index];
+                    condition_ui(
+                        ui,
+                        "Entry condition",
+                        &mut texts.entry_condition,
+                        &mut frame.entry_condition,
+                    );
+                    condition_ui(
+                        ui,
+                        "Interrupt conditions",
+                        &mut texts.interrupt_conditions,
+                        &mut frame.interrupt_conditions,
+                    );
+                    condition_ui(
+                        ui,
+                        "Exit condition",
+                        &mut texts.exit_condition,
+                        &mut frame.exit_condition,
+                    );
+
+                    for (keyframe_index, keyframe) in frame.keyframes.iter_mut().enumerate() {
+                        let selection = Selection {
+                            frame: frame_index,
+                            keyframe: keyframe_index,
+                        };
+                        ui.horizontal(|ui| {
+                            let is_selected = self.selection == Some(selection);
+                            if ui
+                                .selectable_label(is_selected, format!("Keyframe {keyframe_index}"))
+                                .clicked()
+                            {
+                                self.selection = Some(selection);
+                                self.playing = false;
+                                if let Some((_, time)) = keyframe_times
+                                    .iter()
+                                    .find(|(keyframe, _)| *keyframe == selection)
+                                {
+                                    self.time = *time;
+                                }
+                            }
+                            let mut seconds = keyframe.duration.as_secs_f32();
+                            if ui
+                                .add(
+                                    DragValue::new(&mut seconds)
+                                        .speed(0.01)
+                                        .range(0.0..=10.0)
+                                        .suffix(" s"),
+                                )
+                                .changed()
+                            {
+                                keyframe.duration = Duration::from_secs_f32(seconds);
+                            }
+                            if ui.button("Duplicate").clicked() {
+                                edits.push(Edit::DuplicateKeyframe(selection));
+                            }
+                            if ui.button("Remove").clicked() {
+                                edits.push(Edit::RemoveKeyframe(selection));
+                            }
+                        });
+                    }
+                    ui.horizontal(|ui| {
+                        if ui.button("Add keyframe").clicked() {
+                            edits.push(Edit::AddKeyframe { frame: frame_index });
+                        }
+                        if ui.button("Remove frame").clicked() {
+                            edits.push(Edit::RemoveFrame { frame: frame_index });
+                        }
+                    });
+                });
+        }
+        if ui.button("Add frame").clicked() {
+            edits.push(Edit::AddFrame);
+        }
+        for edit in edits {
+            self.apply(edit);
+        }
+    }
+
+    fn apply(&mut self, edit: Edit) {
+        let current_positions = self
+            .positions_at(self.time)
+            .unwrap_or(self.motion.initial_positions);
+        match edit {
+            Edit::AddKeyframe { frame } => {
+                let keyframes = &mut self.motion.motion[frame].keyframes;
+                keyframes.push(KeyFrame {
+                    duration: Duration::from_secs_f32(0.5),
+                    positions: current_positions,
+                });
+                self.selection = Some(Selection {
+                    frame,
+                    keyframe: keyframes.len() - 1,
+                });
+            }
+            Edit::DuplicateKeyframe(selection) => {
+                let keyframes = &mut self.motion.motion[selection.frame].keyframes;
+                let keyframe = keyframes[selection.keyframe].clone();
+                keyframes.insert(selection.keyframe + 1, keyframe);
+            }
+            Edit::RemoveKeyframe(selection) => {
+                let keyframes = &mut self.motion.motion[selection.frame].keyframes;
+                keyframes.remove(selection.keyframe);
+                // frames without keyframes cannot be interpolated
+                if keyframes.is_empty() {
+                    self.motion.motion.remove(selection.frame);
+                    self.condition_texts.remove(selection.frame);
+                }
+                self.selection = None;
+            }
+            Edit::AddFrame => {
+                let frame = MotionFileFrame {
+                    name: None,
+                    entry_condition: None,
+                    interrupt_conditions: Vec::new(),
+                    keyframes: vec![KeyFrame {
+                        duration: Duration::from_secs_f32(0.5),
+                        positions: current_positions,
+                    }],
+                    exit_condition: None,
+                };
+                self.condition_texts.push(ConditionTexts::new(&frame));
+                self.motion.motion.push(frame);
+            }
+            Edit::RemoveFrame { frame } => {
+                self.motion.motion.remove(frame);
+                self.condition_texts.remove(frame);
+                self.selection = None;
+            }
+        }
+    }
+
+    fn joints_ui(&mut self, ui: &mut Ui) {
+        let mut plotted_joints = std::mem::take(&mut self.plotted_joints);
+        let selection = self.selection.filter(|&selection| self.is_valid(selection));
+        let (title, positions) = match selection {
+            Some(selection) => (
+                format!("Frame {} keyframe {}", selection.frame, selection.keyframe),
+                &mut self.motion.motion[selection.frame].keyframes[selection.keyframe].positions,
+            ),
+            None => (
+                "Initial positions".to_string(),
+                &mut self.motion.initial_positions,
+            ),
+        };
+        let mut changed = false;
+        CollapsingHeader::new(title)
+            .id_salt("joints")
+            .default_open(true)
+            .show(ui, |ui| {
+                Grid::new("joints").num_columns(3).show(ui, |ui| {
+                    for (name, _) in positions.enumerate() {
+                        ui.label(format!("{name:?}"));
+                        changed |= ui
+                            .add(Slider::new(&mut positions[name], -PI..=PI).suffix(" rad"))
+                            .changed();
+                        let mut is_plotted = plotted_joints.contains(&name);
+                        if ui.checkbox(&mut is_plotted, "plot").changed() {
+                            if is_plotted {
+                                plotted_joints.push(name);
+                            } else {
+                                plotted_joints.retain(|joint| *joint != name);
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+            });
+        self.plotted_joints = plotted_joints;
+        if changed {
+            self.playing = false;
+        }
+    }
+
+    fn curves_ui(&self, ui: &mut Ui) {
+        let total_duration = self.total_duration().as_secs_f32();
+        let keyframe_times = self.keyframe_times();
+        Plot::new("joint curves")
+            .height(250.0)
+            .legend(Legend::default())
+            .show(ui, |plot_ui| {
+                for &joint in &self.plotted_joints {
+                    let curve: PlotPoints = (0..=NUMBER_OF_CURVE_SAMPLES)
+                        .filter_map(|index| {
+                            let seconds =
+                                total_duration * index as f32 / NUMBER_OF_CURVE_SAMPLES as f32;
+                            let positions = self.positions_at(Duration::from_secs_f32(seconds))?;
+                            Some([seconds as f64, positions[joint] as f64])
+                        })
+                        .collect();
+                    plot_ui.line(Line::new(curve).name(format!("{joint:?}")));
+                    let keyframes: PlotPoints =
+                        std::iter::once([0.0, self.motion.initial_positions[joint] as f64])
+                            .chain(keyframe_times.iter().map(|(selection, time)| {
+                                let keyframe = &self.motion.motion[selection.frame].keyframes
+                                    [selection.keyframe];
+                                [time.as_secs_f64(), keyframe.positions[joint] as f64]
+                            }))
+                            .collect();
+                    plot_ui.points(
+                        Points::new(keyframes)
+                            .radius(3.0)
+                            .name(format!("{joint:?}")),
+                    );
+                }
+                plot_ui.vline(VLine::new(self.time.as_secs_f64()).color(Color32::GRAY));
+            });
+    }
+}
+
+fn condition_ui<T>(ui: &mut Ui, label: &str, text: &mut String, condition: &mut T)
+where
+    T: Serialize + DeserializeOwned,
+{
+    ui.label(label);
+    if ui
+        .add(
+            TextEdit::multiline(text)
+                .code_editor()
+                .desired_rows(1)
+                .desired_width(f32::INFINITY),
+        )
+        .changed()
+    {
+        if let Ok(parsed) = serde_json::from_str(text) {
+            *condition = parsed;
+        }
+    }
+    if let Err(error) = serde_json::from_str::<T>(text) {
+        ui.colored_label(Color32::RED, error.to_string());
+    }
+}
+
+fn to_json(value: &impl Serialize) -> String {
+    serde_json::to_string(value).expect("conditions should always be serializable")
+}
+
+/// Relative paths are interpreted relative to the repository root
+fn resolve(path: &str) -> PathBuf {
+    let path = Path::new(path);
+    if path.is_absolute() {
+        return path.to_path_buf();
+    }
+    match absolute(".")
+        .ok()
+        .and_then(|current_directory| Repository::find_root(current_directory).ok())
+    {
+        Some(repository) => repository.root.join(path),
+        None => path.to_path_buf(),
+    }
+}
+
+fn spawn_preview(
+    mut commands: Commands,
+    robot_specification: Option<Res<RobotSpecification>>,
+    mut is_spawned: Local<bool>,
+) {
+    let Some(robot_specification) = robot_specification else {
+        return;
+    };
+    if *is_spawned {
+        return;
+    }
+    let (robot, links) = spawn_robot_model(&mut commands, &robot_specification, "motion preview");
+    commands.entity(robot).insert((
+        Transform::from_xyz(0.0, 0.0, PREVIEW_ROBOT_HEIGHT),
+        MotionPreview {
+            joints: joint_links(&links),
+        },
+    ));
+    *is_spawned = true;
+}
+
+fn advance_playback(time: Res<Time>, mut editor: ResMut<MotionEditor>) {
+    if !editor.playing {
+        return;
+    }
+    let total_duration = editor.total_duration();
+    editor.time += time.delta();
+    if editor.time > total_duration {
+        editor.time = Duration::ZERO;
+    }
+}
+
+fn update_preview(
+    editor: Res<MotionEditor>,
+    mut previews: Query<(&MotionPreview, &mut Visibility)>,
+    mut transforms: Query<&mut Transform>,
+) {
+    for (preview, mut visibility) in previews.iter_mut() {
+        if editor.motion.motion.is_empty() {
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+        *visibility = Visibility::Visible;
+        if let Some(positions) = editor.positions_at(editor.time) {
+            apply_joint_positions(positions, preview.joints, &mut transforms);
+        }
+    }
+}
@@ -224,44 +224,7 @@ impl Nao {
             Subscription::new(&client, "VisionBottom.main_outputs.line_data.lines").await;
         let lines_in_ground_top =
             Subscription::new(&client, "VisionTop.main_outputs.line_data.lines").await;
-        let joints = Joints {
-            head: HeadJoints {
-                yaw: *links.get("HeadYaw_link").unwrap(),
-                pitch: *links.get("HeadPitch_link").unwrap(),
-            },
-            left_arm: ArmJoints {
-                shoulder_pitch: *links.get("LShoulderPitch_link").unwrap(),
-                shoulder_roll: *links.get("LShoulderRoll_link").unwrap(),
-                elbow_yaw: *links.get("LElbowYaw_link").unwrap(),
-                elbow_roll: *links.get("LElbowRoll_link").unwrap(),
-                wrist_yaw: *links.get("LWristYaw_link").unwrap(),
-                hand: Entity::PLACEHOLDER,
-            },
-            right_arm: ArmJoints {
-                shoulder_pitch: *links.get("RShoulderPitch_link").unwrap(),
-                shoulder_roll: *links.get("RShoulderRoll_link").unwrap(),
-                elbow_yaw: *links.get("RElbowYaw_link").unwrap(),
-                elbow_roll: *links.get("RElbowRoll_link").unwrap(),
-                wrist_yaw: *links.get("RWristYaw_link").unwrap(),
-                hand: Entity::PLACEHOLDER,
-            },
-            left_leg: LegJoints {
-                hip_yaw_pitch: *links.get("LHipYawPitch_link").unwrap(),
-                hip_roll: *links.get("LHipRoll_link").unwrap(),
-                hip_pitch: *links.get("LHipPitch_link").unwrap(),
-                knee_pitch: *links.get("LKneePitch_link").unwrap(),
-                ankle_pitch: *links.get("LAnklePitch_link").unwrap(),
-                ankle_roll: *links.get("LAnkleRoll_link").unwrap(),
-            },
-            right_leg: LegJoints {
-                hip_yaw_pitch: *links.get("RHipYawPitch_link").unwrap(),
-                hip_roll: *links.get("RHipRoll_link").unwrap(),
-                hip_pitch: *links.get("RHipPitch_link").unwrap(),
-                knee_pitch: *links.get("RKneePitch_link").unwrap(),
-                ankle_pitch: *links.get("RAnklePitch_link").unwrap(),
-                ankle_roll: *links.get("RAnkleRoll_link").unwrap(),
-            },
-        };
+        let joints = joint_links(links);
         Self {
             client,
             address: String::new(),
@@ -278,6 +241,48 @@ impl Nao {
     }
 }
 
+/// Entities of the links which are rotated by each joint, hands are not modeled
+pub fn joint_links(links: &HashMap<String, Entity>) -> Joints<Entity> {
+    Joints {
+        head: HeadJoints {
+            yaw: *links.get("HeadYaw_link").unwrap(),
+            pitch: *links.get("HeadPitch_link").unwrap(),
+        },
+        left_arm: ArmJoints {
+            shoulder_pitch: *links.get("LShoulderPitch_link").unwrap(),
+            shoulder_roll: *links.get("LShoulderRoll_link").unwrap(),
+            elbow_yaw: *links.get("LElbowYaw_link").unwrap(),
+            elbow_roll: *links.get("LElbowRoll_link").unwrap(),
+            wrist_yaw: *links.get("LWristYaw_link").unwrap(),
+            hand: Entity::PLACEHOLDER,
+        },
+        right_arm: ArmJoints {
+            shoulder_pitch: *links.get("RShoulderPitch_link").unwrap(),
+            shoulder_roll: *links.get("RShoulderRoll_link").unwrap(),
+            elbow_yaw: *links.get("RElbowYaw_link").unwrap(),
+            elbow_roll: *links.get("RElbowRoll_link").unwrap(),
+            wrist_yaw: *links.get("RWristYaw_link").unwrap(),
+            hand: Entity::PLACEHOLDER,
+        },
+        left_leg: LegJoints {
+            hip_yaw_pitch: *links.get("LHipYawPitch_link").unwrap(),
+            hip_roll: *links.get("LHipRoll_link").unwrap(),
+            hip_pitch: *links.get("LHipPitch_link").unwrap(),
+            knee_pitch: *links.get("LKneePitch_link").unwrap(),
+            ankle_pitch: *links.get("LAnklePitch_link").unwrap(),
+            ankle_roll: *links.get("LAnkleRoll_link").unwrap(),
+        },
+        right_leg: LegJoints {
+            hip_yaw_pitch: *links.get("RHipYawPitch_link").unwrap(),
+            hip_roll: *links.get("RHipRoll_link").unwrap(),
+            hip_pitch: *links.get("RHipPitch_link").unwrap(),
+            knee_pitch: *links.get("RKneePitch_link").unwrap(),
+            ankle_pitch: *links.get("RAnklePitch_link").unwrap(),
+            ankle_roll: *links.get("RAnkleRoll_link").unwrap(),
+        },
+    }
+}
+
 fn handle_communication(mut naos: Query<(&Nao, &mut Visibility)>, runtime: Res<AsyncRuntime>) {
     for (nao, mut visibility) in naos.iter_mut() {
         let status = runtime.runtime.block_on(nao.client.status());
@@ -307,47 +312,64 @@ pub fn spawn_robot(
                 MeshMaterial3d(ball_assets.material.clone()),
             ))
             .id();
-        let mut links = HashMap::new();
+        let (robot, links) = spawn_robot_model(&mut commands, &robot_specification, "robot");
         commands
-            .spawn((Name::new("robot"), Visibility::Hidden, Transform::default()))
-            .with_children(|builder| {
-                for link in robot_specification.links.values() {
-                    let id = builder
-                        .spawn((
-                            Name::new(link.name.clone()),
-                            Transform::default(),
-                            Visibility::default(),
-                        ))
-                        .with_children(|builder| {
-                            for visual in link.visuals.iter() {
-                                builder.spawn((
-                                    Mesh3d(visual.mesh.clone()),
-                                    MeshMaterial3d(visual.material.clone()),
-                                    visual.origin,
-                                ));
-                            }
-                        })
-                        .id();
-                    links.insert(link.name.clone(), id);
-                }
-            })
+            .entity(robot)
             .insert(runtime.runtime.block_on(Nao::new(&links, ball)));
-        for joint in robot_specification.joints.values() {
-            let parent = links.get(&joint.parent).unwrap();
-            let child = links.get(&joint.child).unwrap();
-            let joint_id = commands
-                .spawn((
-                    Name::new(joint.name.clone()),
-                    joint.origin,
-                    Visibility::default(),
-                ))
-                .add_child(*child)
-                .id();
-            commands.entity(*parent).add_child(joint_id);
-        }
     }
 }
 
+/// Spawns the links and joints of the robot below a hidden root entity
+pub fn spawn_robot_model(
+    commands: &mut Commands,
+    robot_specification: &RobotSpecification,
+    name: &str,
+) -> (Entity, HashMap<String, Entity>) {
+    let mut links = HashMap::new();
+    let robot = commands
+        .spawn((
+            Name::new(name.to_string()),
+            Visibility::Hidden,
+            Transform::default(),
+        ))
+        .with_children(|builder| {
+            for link in robot_specification.links.values() {
+                let id = builder
+                    .spawn((
+                        Name::new(link.name.clone()),
+                        Transform::default(),
+                        Visibility::default(),
+                    ))
+                    .with_children(|builder| {
+                        for visual in link.visuals.iter() {
+                            builder.spawn((
+                                Mesh3d(visual.mesh.clone()),
+                                MeshMaterial3d(visual.material.clone()),
+                                visual.origin,
+                            ));
+                        }
+                    })
+                    .id();
+                links.insert(link.name.clone(), id);
+            }
+        })
+        .id();
+    for joint in robot_specification.joints.values() {
+        let parent = links.get(&joint.parent).unwrap();
+        let child = links.get(&joint.child).unwrap();
+        let joint_id = commands
+            .spawn((
+                Name::new(joint.name.clone()),
+                joint.origin,
+                Visibility::default(),
+            ))
+            .add_child(*child)
+            .id();
+        commands.entity(*parent).add_child(joint_id);
+    }
+    (robot, links)
+}
+
 const FALLBACK_ROBOT_HEIGHT: f32 = 0.5;
 
 fn update_robot_transform(mut naos: Query<(&mut Nao, &mut Transform)>) {
@@ -391,93 +413,23 @@ fn update_robot_transform(mut naos: Query<(&mut Nao, &mut Transform)>) {
 fn update_joints(mut robots: Query<&mut Nao>, mut transforms: Query<&mut Transform>) {
     for mut robot in robots.iter_mut() {
         robot.sensor_data.update();
-        let joints = robot.sensor_data.value.unwrap_or_default();
-        let mut head_yaw = transforms.get_mut(robot.joints.head.yaw).unwrap();
-        head_yaw.rotation = Quat::from_rotation_z(joints.head.yaw);
-        let mut head_pitch = transforms.get_mut(robot.joints.head.pitch).unwrap();
-        head_pitch.rotation = Quat::from_rotation_z(joints.head.pitch);
-
-        let mut left_shoulder_pitch = transforms
-            .get_mut(robot.joints.left_arm.shoulder_pitch)
-            .unwrap();
-        left_shoulder_pitch.rotation = Quat::from_rotation_z(joints.left_arm.shoulder_pitch);
-        let mut left_shoulder_roll = transforms
-            .get_mut(robot.joints.left_arm.shoulder_roll)
-            .unwrap();
-        left_shoulder_roll.rotation = Quat::from_rotation_z(joints.left_arm.shoulder_roll);
-        let mut left_elbow_yaw = transforms.get_mut(robot.joints.left_arm.elbow_yaw).unwrap();
-        left_elbow_yaw.rotation = Quat::from_rotation_z(joints.left_arm.elbow_yaw);
-        let mut left_elbow_roll = transforms
-            .get_mut(robot.joints.left_arm.elbow_roll)
-            .unwrap();
-        left_elbow_roll.rotation = Quat::from_rotation_z(joints.left_arm.elbow_roll);
-        let mut left_wrist_yaw = transforms.get_mut(robot.joints.left_arm.wrist_yaw).unwrap();
-        left_wrist_yaw.rotation = Quat::from_rotation_z(joints.left_arm.wrist_yaw);
-
-        let mut right_shoulder_pitch = transforms
-            .get_mut(robot.joints.right_arm.shoulder_pitch)
-            .unwrap();
-        right_shoulder_pitch.rotation = Quat::from_rotation_z(joints.right_arm.shoulder_pitch);
-        let mut right_shoulder_roll = transforms
-            .get_mut(robot.joints.right_arm.shoulder_roll)
-            .unwrap();
-        right_shoulder_roll.rotation = Quat::from_rotation_z(joints.right_arm.shoulder_roll);
-        let mut right_elbow_yaw = transforms
-            .get_mut(robot.joints.right_arm.elbow_yaw)
-            .unwrap();
-        right_elbow_yaw.rotation = Quat::from_rotation_z(joints.right_arm.elbow_yaw);
-        let mut right_elbow_roll = transforms
-            .get_mut(robot.joints.right_arm.elbow_roll)
-            .unwrap();
-        right_elbow_roll.rotation = Quat::from_rotation_z(joints.right_arm.elbow_roll);
-        let mut right_wrist_yaw = transforms
-            .get_mut(robot.joints.right_arm.wrist_yaw)
-            .unwrap();
-        right_wrist_yaw.rotation = Quat::from_rotation_z(joints.right_arm.wrist_yaw);
-
-        let mut left_hip_yaw_pitch = transforms
-            .get_mut(robot.joints.left_leg.hip_yaw_pitch)
-            .unwrap();
-        left_hip_yaw_pitch.rotation = Quat::from_rotation_z(joints.left_leg.hip_yaw_pitch);
-        let mut left_hip_roll = transforms.get_mut(robot.joints.left_leg.hip_roll).unwrap();
-        left_hip_roll.rotation = Quat::from_rotation_z(joints.left_leg.hip_roll);
-        let mut left_hip_pitch = transforms.get_mut(robot.joints.left_leg.hip_pitch).unwrap();
-        left_hip_pitch.rotation = Quat::from_rotation_z(joints.left_leg.hip_pitch);
-        let mut left_knee_pitch = transforms
-            .get_mut(robot.joints.left_leg.knee_pitch)
-            .unwrap();
-        left_knee_pitch.rotation = Quat::from_rotation_z(joints.left_leg.knee_pitch);
-        let mut left_ankle_pitch = transforms
-            .get_mut(robot.joints.left_leg.ankle_pitch)
-            .unwrap();
-        left_ankle_pitch.rotation = Quat::from_rotation_z(joints.left_leg.ankle_pitch);
-        let mut left_ankle_roll = transforms
-            .get_mut(robot.joints.left_leg.ankle_roll)
-            .unwrap();
-        left_ankle_roll.rotation = Quat::from_rotation_z(joints.left_leg.ankle_roll);
+        let positions = robot.sensor_data.value.unwrap_or_default();
+        apply_joint_positions(positions, robot.joints, &mut transforms);
+    }
+}
 
-        let mut right_hip_yaw_pitch = transforms
-            .get_mut(robot.joints.right_leg.hip_yaw_pitch)
-            .unwrap();
-        right_hip_yaw_pitch.rotation = Quat::from_rotation_z(joints.right_leg.hip_yaw_pitch);
-        let mut right_hip_roll = transforms.get_mut(robot.joints.right_leg.hip_roll).unwrap();
-        right_hip_roll.rotation = Quat::from_rotation_z(joints.right_leg.hip_roll);
-        let mut right_hip_pitch = transforms
-            .get_mut(robot.joints.right_leg.hip_pitch)
-            .unwrap();
-        right_hip_pitch.rotation = Quat::from_rotation_z(joints.right_leg.hip_pitch);
-        let mut right_knee_pitch = transforms
-            .get_mut(robot.joints.right_leg.knee_pitch)
-            .unwrap();
-        right_knee_pitch.rotation = Quat::from_rotation_z(joints.right_leg.knee_pitch);
-        let mut right_ankle_pitch = transforms
-            .get_mut(robot.joints.right_leg.ankle_pitch)
-            .unwrap();
-        right_ankle_pitch.rotation = Quat::from_rotation_z(joints.right_leg.ankle_pitch);
-        let mut right_ankle_roll = transforms
-            .get_mut(robot.joints.right_leg.ankle_roll)
-            .unwrap();
-        right_ankle_roll.rotation = Quat::from_rotation_z(joints.right_leg.ankle_roll);
+pub fn apply_joint_positions(
+    positions: Joints<f32>,
+    joints: Joints<Entity>,
+    transforms: &mut Query<&mut Transform>,
+) {
+    for (position, joint) in positions.into_iter().zip(joints) {
+        if joint == Entity::PLACEHOLDER {
+            continue;
+        }
+        if let Ok(mut transform) = transforms.get_mut(joint) {
+            transform.rotation = Quat::from_rotation_z(position);
+        }
     }
 }
 
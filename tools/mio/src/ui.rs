@@ -8,6 +8,7 @@ use egui_dock::{egui::Context, DockArea, DockState, NodeIndex, Style};
 
 use crate::{
     async_runtime::AsyncRuntime,
+    motion_editor::MotionEditor,
     nao::{Nao, SpawnRobot},
     shadow_toggle::ShadowState,
     MainCamera,
@@ -35,6 +36,7 @@ impl Plugin for UiPlugin {
 enum Tab {
     World,
     Connections,
+    MotionEditor,
 }
 
 struct TabViewer<'a> {
@@ -96,6 +98,11 @@ impl egui_dock::TabViewer for TabViewer<'_> {
                     ui.checkbox(&mut shadow_state.enabled, "Shadows");
                 }
             }
+            Tab::MotionEditor => {
+                if let Some(mut motion_editor) = self.world.get_resource_mut::<MotionEditor>() {
+                    motion_editor.ui(ui);
+                }
+            }
         }
     }
 
@@ -108,7 +115,7 @@ impl egui_dock::TabViewer for TabViewer<'_> {
     }
 
     fn closeable(&mut self, tab: &mut Self::Tab) -> bool {
-        !matches!(tab, Tab::World | Tab::Connections)
+        !matches!(tab, Tab::World | Tab::Connections | Tab::MotionEditor)
     }
 }
 
@@ -122,7 +129,11 @@ impl UiState {
     fn new() -> Self {
         let mut state = DockState::new(vec![Tab::World]);
         let tree = state.main_surface_mut();
-        tree.split_right(NodeIndex::root(), 0.8, vec![Tab::Connections]);
+        tree.split_right(
+            NodeIndex::root(),
+            0.7,
+            vec![Tab::Connections, Tab::MotionEditor],
+        );
 
         Self {
             viewport: Rect::NOTHING,
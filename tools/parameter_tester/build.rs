@@ -1,11 +1,16 @@
 use color_eyre::eyre::{Result, WrapErr};
 
 use code_generation::{structs::generate_structs, write_to_file::WriteToFile};
-use hulk_manifest::collect_hulk_cyclers;
+use hulk_manifest::{collect_hulk_cyclers, manifest_path, MANIFEST_ENVIRONMENT_VARIABLE};
 use source_analyzer::{pretty::to_string_pretty, structs::Structs};
 
 fn main() -> Result<()> {
     let cyclers = collect_hulk_cyclers("../../crates/")?;
+    println!("cargo:rerun-if-env-changed={MANIFEST_ENVIRONMENT_VARIABLE}");
+    println!(
+        "cargo:rerun-if-changed={}",
+        manifest_path("../../crates/").display()
+    );
     for path in cyclers.watch_paths() {
         println!("cargo:rerun-if-changed={}", path.display());
     }
@@ -1,18 +1,20 @@
 use std::{
     env::{args, set_current_dir},
-    fs::File,
-    path::PathBuf,
+    fs::{read_dir, File},
+    iter::once,
+    path::{Path, PathBuf},
 };
 
 use color_eyre::{
-    eyre::{ContextCompat, Ok, WrapErr},
+    eyre::{bail, ContextCompat, Ok, WrapErr},
     Result,
 };
-use serde_json::from_reader;
+use serde_json::{from_reader, to_value};
 
 use framework::Parameters as FrameworkParameters;
 use hula_types::hardware::Ids;
-use parameters::directory::deserialize;
+use parameters::directory::deserialize_in_location;
+use path_serde::{schema::validate, PathIntrospect};
 use repository::Repository;
 
 fn main() -> Result<()> {
@@ -29,16 +31,85 @@ fn main() -> Result<()> {
     let framework_parameters: FrameworkParameters =
         from_reader(file).wrap_err("failed to parse framework parameters")?;
 
-    let ids = Ids {
+    let schema = structs::Parameters::get_schema();
+    let mut amount_of_violations = 0;
+    let parameters_directory = &framework_parameters.parameters_directory;
+    let default_ids = Ids {
         body_id: String::new(),
         head_id: String::new(),
     };
-    let _robotics_parameters: structs::Parameters =
-        deserialize(framework_parameters.parameters_directory, &ids, false)?;
+    let root_ids = hardware_ids(parameters_directory)?;
+    for location in locations(parameters_directory)? {
+        let location_ids = hardware_ids(&parameters_directory.join(&location))?;
+        for ids in once(&default_ids).chain(&root_ids).chain(&location_ids) {
+            let robotics_parameters: structs::Parameters =
+                deserialize_in_location(parameters_directory, &location, ids, false)
+                    .wrap_err_with(|| {
+                        format!("failed to deserialize parameters of {location} for {ids:?}")
+                    })?;
+            let value =
+                to_value(&robotics_parameters).wrap_err("failed to serialize parameters")?;
+            for violation in validate(&schema, &value) {
+                eprintln!(
+                    "{location}, head {:?}, body {:?}: {violation}",
+                    ids.head_id, ids.body_id
+                );
+                amount_of_violations += 1;
+            }
+        }
+    }
+    if amount_of_violations > 0 {
+        bail!("{amount_of_violations} parameters out of range");
+    }
 
     Ok(())
 }
 
+/// Location directories, symlinks like `nao_location` only alias one of them
+fn locations(parameters_directory: &Path) -> Result<Vec<String>> {
+    let mut locations = Vec::new();
+    for entry in read_dir(parameters_directory).wrap_err("failed to read parameters directory")? {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        let location = entry
+            .file_name()
+            .into_string()
+            .ok()
+            .wrap_err("location directory name is not valid unicode")?;
+        locations.push(location);
+    }
+    locations.sort();
+    Ok(locations)
+}
+
+/// Ids of every head and body with a parameter file in the directory
+fn hardware_ids(parameters_directory: &Path) -> Result<Vec<Ids>> {
+    let mut ids = Vec::new();
+    for entry in read_dir(parameters_directory).wrap_err("failed to read parameters directory")? {
+        let file_name = entry?.file_name();
+        let file_name = file_name
+            .to_str()
+            .wrap_err("parameter file name is not valid unicode")?;
+        let Some(id) = file_name.strip_suffix(".json") else {
+            continue;
+        };
+        if let Some(head_id) = id.strip_prefix("head.") {
+            ids.push(Ids {
+                body_id: String::new(),
+                head_id: head_id.to_string(),
+            });
+        } else if let Some(body_id) = id.strip_prefix("body.") {
+            ids.push(Ids {
+                body_id: body_id.to_string(),
+                head_id: String::new(),
+            });
+        }
+    }
+    Ok(ids)
+}
+
 mod structs {
     include!(concat!(env!("OUT_DIR"), "/generated_code.rs"));
 }
@@ -1,4 +1,7 @@
-use std::path::{Path, PathBuf};
+use std::{
+    fs::read_to_string,
+    path::{Path, PathBuf},
+};
 
 use bat::{PagingMode, PrettyPrinter};
 use clap::Subcommand;
@@ -7,8 +10,14 @@ use color_eyre::{
     Result,
 };
 
+use hulk_manifest::{collect_cyclers_from_manifest, manifest_path};
 use repository::Repository;
-use source_analyzer::{contexts::Contexts, node::parse_rust_file, pretty::to_string_pretty};
+use source_analyzer::{
+    contexts::Contexts,
+    lint::{lint, Severity},
+    node::parse_rust_file,
+    pretty::to_string_pretty,
+};
 
 fn find_latest_file(path_pattern: impl AsRef<Path>) -> Result<PathBuf> {
     let matching_paths: Vec<_> = glob::glob(
@@ -48,6 +57,18 @@ pub enum Arguments {
         /// File name to dump (may contain wildcard characters usable by glob())
         file_name: String,
     },
+    DumpCyclers {
+        /// File path to a cycler manifest (defaults to $HULK_MANIFEST or the default manifest)
+        manifest_path: Option<PathBuf>,
+    },
+    /// Statically check the dataflow between nodes, e.g. for unused outputs or missing parameters
+    Lint {
+        /// File path to a cycler manifest (defaults to $HULK_MANIFEST or the default manifest)
+        manifest_path: Option<PathBuf>,
+        /// Fail if any warnings are reported, not only errors
+        #[arg(long)]
+        deny_warnings: bool,
+    },
 }
 
 pub async fn analyze(arguments: Arguments, repository: Result<Repository>) -> Result<()> {
@@ -76,6 +97,48 @@ pub async fn analyze(arguments: Arguments, repository: Result<Repository>) -> Re
                 .print()
                 .wrap_err("failed to print file")?;
         }
+        Arguments::DumpCyclers {
+            manifest_path: path,
+        } => {
+            let repository = repository?;
+            let root = repository.root.join("crates");
+            let path = path.unwrap_or_else(|| manifest_path(&root));
+            let cyclers = collect_cyclers_from_manifest(&path, root)
+                .wrap_err_with(|| format!("failed to collect cyclers from {}", path.display()))?;
+            let string = to_string_pretty(&cyclers)?;
+            print!("{string}");
+        }
+        Arguments::Lint {
+            manifest_path: path,
+            deny_warnings,
+        } => {
+            let repository = repository?;
+            let root = repository.root.join("crates");
+            let path = path.unwrap_or_else(|| manifest_path(&root));
+            let cyclers = collect_cyclers_from_manifest(&path, root)
+                .wrap_err_with(|| format!("failed to collect cyclers from {}", path.display()))?;
+            let default_parameters_path = repository.root.join("etc/parameters/default.json");
+            let default_parameters =
+                read_to_string(&default_parameters_path).wrap_err_with(|| {
+                    format!("failed to read {}", default_parameters_path.display())
+                })?;
+            let default_parameters = serde_json::from_str(&default_parameters)
+                .wrap_err("failed to parse default parameters")?;
+
+            let findings =
+                lint(&cyclers, &default_parameters).wrap_err("failed to lint cyclers")?;
+            print!("{}", to_string_pretty(&findings)?);
+
+            let errors = findings
+                .iter()
+                .filter(|finding| finding.severity == Severity::Error)
+                .count();
+            let warnings = findings.len() - errors;
+            println!("{errors} error(s), {warnings} warning(s)");
+            if errors > 0 || (deny_warnings && warnings > 0) {
+                return Err(eyre!("dataflow lint failed"));
+            }
+        }
     }
 
     Ok(())
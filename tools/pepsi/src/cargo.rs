@@ -39,6 +39,7 @@ lazy_static! {
         HashMap::from([
             ("imagine", "crates/hulk_imagine"),
             ("nao", "crates/hulk_nao"),
+            ("replay_diff", "crates/hulk_replay_diff"),
             ("replayer", "crates/hulk_replayer"),
             ("webots", "crates/hulk_webots"),
             ("aliveness", "services/aliveness"),
@@ -0,0 +1,391 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    env::current_exe,
+    net::IpAddr,
+    num::ParseIntError,
+    process::Command,
+    time::{Duration, Instant},
+};
+
+use clap::Args;
+use color_eyre::{
+    eyre::{bail, WrapErr},
+    Result,
+};
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Cell, Paragraph, Row, Table, TableState},
+    DefaultTerminal, Frame,
+};
+use tokio::{sync::watch, task::spawn_blocking, time::sleep};
+
+use aliveness::{
+    query_aliveness,
+    service_manager::{ServiceState, SystemServices},
+    AlivenessState,
+};
+use repository::{team::Nao, Repository};
+
+const BATTERY_CHARGE_WARN: f32 = 0.3;
+const TEMPERATURE_WARN_THRESHOLD: f32 = 60.0;
+const TEMPERATURE_ERROR_THRESHOLD: f32 = 75.0;
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+const LOG_LINES: usize = 5;
+
+#[derive(Args)]
+pub struct Arguments {
+    /// Interval in ms between two aliveness queries
+    #[arg(long, short = 'i', value_parser = parse_duration, default_value = "1000")]
+    interval: Duration,
+    /// Timeout in ms for waiting for responses
+    #[arg(long, short = 't', value_parser = parse_duration, default_value = "200")]
+    timeout: Duration,
+    /// Time in ms after which a NAO without response is shown as offline
+    #[arg(long, value_parser = parse_duration, default_value = "5000")]
+    offline_after: Duration,
+}
+
+fn parse_duration(arg: &str) -> Result<Duration, ParseIntError> {
+    let milliseconds = arg.parse()?;
+    Ok(Duration::from_millis(milliseconds))
+}
+
+struct Response {
+    ip: IpAddr,
+    received_at: Instant,
+    state: AlivenessState,
+}
+
+type Responses = BTreeMap<u8, Response>;
+
+/// Everything shown by the dashboard
+///
+/// Errors are collected in the log pane because printing them would corrupt the terminal.
+#[derive(Default)]
+struct State {
+    responses: Responses,
+    log: VecDeque<String>,
+}
+
+impl State {
+    fn log(&mut self, message: String) {
+        if self.log.len() == LOG_LINES {
+            self.log.pop_front();
+        }
+        self.log.push_back(message);
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Severity {
+    Ok,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    fn style(self) -> Style {
+        match self {
+            Severity::Ok => Style::new(),
+            Severity::Warning => Style::new().fg(Color::Yellow),
+            Severity::Error => Style::new().fg(Color::Red),
+        }
+    }
+}
+
+pub async fn dashboard(arguments: Arguments, repository: &Repository) -> Result<()> {
+    let team = repository
+        .read_team_configuration()
+        .await
+        .wrap_err("failed to get team configuration")?;
+    let mut state = State::default();
+    let expected_os_version = repository
+        .read_os_version()
+        .await
+        .map_err(|error| state.log(format!("failed to read OS version: {error:#}")))
+        .ok();
+
+    let (sender, receiver) = watch::channel(state);
+    let query_task = tokio::spawn(async move {
+        loop {
+            match query_aliveness(arguments.timeout, None).await {
+                Ok(states) => sender.send_modify(|state| {
+                    let received_at = Instant::now();
+                    for (ip, aliveness_state) in states {
+                        state.responses.insert(
+                            number_from_ip(ip),
+                            Response {
+                                ip,
+                                received_at,
+                                state: aliveness_state,
+                            },
+                        );
+                    }
+                }),
+                Err(error) => sender.send_modify(|state| {
+                    state.log(format!("failed to query aliveness: {error:#}"))
+                }),
+            }
+            sleep(arguments.interval).await;
+        }
+    });
+
+    let result = spawn_blocking(move || {
+        let terminal = ratatui::init();
+        let result = run(
+            terminal,
+            &team.naos,
+            expected_os_version.as_deref(),
+            receiver,
+            arguments.offline_after,
+        );
+        ratatui::restore();
+        result
+    })
+    .await
+    .wrap_err("failed to join dashboard")?;
+    query_task.abort();
+    result
+}
+
+fn number_from_ip(ip: IpAddr) -> u8 {
+    match ip {
+        IpAddr::V4(ip) => ip.octets()[3],
+        IpAddr::V6(ip) => ip.octets()[15],
+    }
+}
+
+fn run(
+    mut terminal: DefaultTerminal,
+    naos: &[Nao],
+    expected_os_version: Option<&str>,
+    receiver: watch::Receiver<State>,
+    offline_after: Duration,
+) -> Result<()> {
+    let mut table_state = TableState::default().with_selected(Some(0));
+    let mut status = String::from("↑/↓ select   r reboot   u upload   s shell   q quit");
+    loop {
+        {
+            let state = receiver.borrow();
+            terminal
+                .draw(|frame| {
+                    draw(
+                        frame,
+                        naos,
+                        &state,
+                        expected_os_version,
+                        offline_after,
+                        &mut table_state,
+                        &status,
+                    )
+                })
+                .wrap_err("failed to draw dashboard")?;
+        }
+
+        if !event::poll(POLL_INTERVAL).wrap_err("failed to poll terminal events")? {
+            continue;
+        }
+        let Event::Key(key) = event::read().wrap_err("failed to read terminal event")? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        let selected_nao = table_state.selected().and_then(|index| naos.get(index));
+        let subcommand = match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+            KeyCode::Down | KeyCode::Char('j') => {
+                table_state.select_next();
+                continue;
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                table_state.select_previous();
+                continue;
+            }
+            KeyCode::Char('r') => "reboot",
+            KeyCode::Char('u') => "upload",
+            KeyCode::Char('s') => "shell",
+            _ => continue,
+        };
+        let Some(nao) = selected_nao else {
+            continue;
+        };
+        let address = receiver
+            .borrow()
+            .responses
+            .get(&nao.number)
+            .map_or(nao.number.to_string(), |response| response.ip.to_string());
+
+        ratatui::restore();
+        let result = run_pepsi(subcommand, &address);
+        terminal = ratatui::init();
+        status = match result {
+            Ok(()) => format!("{subcommand} {address} finished"),
+            Err(error) => format!("{subcommand} {address} failed: {error:#}"),
+        };
+    }
+}
+
+/// Runs another pepsi subcommand in the foreground while the dashboard is suspended
+fn run_pepsi(subcommand: &str, address: &str) -> Result<()> {
+    let pepsi = current_exe().wrap_err("failed to get pepsi executable")?;
+    let status = Command::new(pepsi)
+        .arg(subcommand)
+        .arg(address)
+        .status()
+        .wrap_err_with(|| format!("failed to execute pepsi {subcommand}"))?;
+    if !status.success() {
+        bail!("pepsi {subcommand} exited with {status}");
+    }
+    Ok(())
+}
+
+fn draw(
+    frame: &mut Frame,
+    naos: &[Nao],
+    state: &State,
+    expected_os_version: Option<&str>,
+    offline_after: Duration,
+    table_state: &mut TableState,
+    status: &str,
+) {
+    let [table_area, log_area, status_area] = Layout::vertical([
+        Constraint::Min(0),
+        Constraint::Length(LOG_LINES as u16 + 2),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    let header = Row::new([
+        "NAO", "Network", "Battery", "Hottest", "HAL", "LoLA", "HuLA", "HULK", "OS",
+    ])
+    .style(Style::new().add_modifier(Modifier::BOLD));
+    let rows = naos.iter().map(|nao| {
+        let name = format!("{} {}", nao.number, nao.hostname);
+        match state
+            .responses
+            .get(&nao.number)
+            .filter(|response| response.received_at.elapsed() < offline_after)
+        {
+            Some(response) => row(name, &response.state, expected_os_version),
+            None => {
+                Row::new([Cell::from(name), Cell::from("offline")]).style(Severity::Error.style())
+            }
+        }
+    });
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(14),
+            Constraint::Length(16),
+            Constraint::Length(9),
+            Constraint::Length(8),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Min(8),
+        ],
+    )
+    .header(header)
+    .block(Block::bordered().title("Team Dashboard"))
+    .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+
+    frame.render_stateful_widget(table, table_area, table_state);
+    frame.render_widget(
+        Paragraph::new(
+            state
+                .log
+                .iter()
+                .map(|message| Line::from(message.as_str()))
+                .collect::<Vec<_>>(),
+        )
+        .style(Severity::Error.style())
+        .block(Block::bordered().title("Log")),
+        log_area,
+    );
+    frame.render_widget(Paragraph::new(Line::from(status)), status_area);
+}
+
+fn row<'a>(name: String, state: &AlivenessState, expected_os_version: Option<&str>) -> Row<'a> {
+    let mut cells = vec![(name, Severity::Ok)];
+
+    cells.push((
+        state.network.clone().unwrap_or_else(|| "None".to_string()),
+        Severity::Ok,
+    ));
+
+    cells.push(match state.battery {
+        Some(battery) => {
+            let charging = if battery.current.is_sign_negative() {
+                ""
+            } else {
+                "+"
+            };
+            let severity = if battery.charge < BATTERY_CHARGE_WARN {
+                Severity::Warning
+            } else {
+                Severity::Ok
+            };
+            (
+                format!("{:.0}%{charging}", battery.charge * 100.0),
+                severity,
+            )
+        }
+        None => ("?".to_string(), Severity::Warning),
+    });
+
+    cells.push(match state.temperature {
+        Some(temperatures) => {
+            let hottest = temperatures.into_lola().into_iter().fold(0.0, f32::max);
+            let severity = if hottest > TEMPERATURE_ERROR_THRESHOLD {
+                Severity::Error
+            } else if hottest > TEMPERATURE_WARN_THRESHOLD {
+                Severity::Warning
+            } else {
+                Severity::Ok
+            };
+            (format!("{hottest:.0}°C"), severity)
+        }
+        None => ("?".to_string(), Severity::Warning),
+    });
+
+    let SystemServices {
+        hal,
+        hula,
+        hulk,
+        lola,
+    } = state.system_services;
+    for service in [hal, lola, hula, hulk] {
+        let severity = match service {
+            ServiceState::Active => Severity::Ok,
+            ServiceState::Activating | ServiceState::Reloading => Severity::Warning,
+            _ => Severity::Error,
+        };
+        cells.push((service.to_string(), severity));
+    }
+
+    let os_severity = match expected_os_version {
+        Some(expected) if expected != state.hulks_os_version => Severity::Error,
+        _ => Severity::Ok,
+    };
+    cells.push((state.hulks_os_version.clone(), os_severity));
+
+    let row_severity = cells
+        .iter()
+        .map(|(_, severity)| *severity)
+        .max()
+        .unwrap_or(Severity::Ok);
+    Row::new(
+        cells
+            .into_iter()
+            .map(|(text, severity)| Cell::from(text).style(severity.style())),
+    )
+    .style(match row_severity {
+        Severity::Ok => Style::new(),
+        severity => severity.style().add_modifier(Modifier::BOLD),
+    })
+}
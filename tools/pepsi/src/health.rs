@@ -0,0 +1,208 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use clap::Args;
+use color_eyre::{
+    eyre::{bail, WrapErr},
+    owo_colors::OwoColorize,
+    Result,
+};
+use futures_util::{stream::FuturesUnordered, StreamExt};
+use serde::Serialize;
+use serde_json::{from_str, Deserializer, Value};
+use tokio::fs::read_to_string;
+use tracing::{error, warn};
+
+use argument_parsers::{number_to_ip, Connection, NaoAddress};
+use hula_types::health::{HealthLog, HealthSummary};
+use nao::Nao;
+use repository::Repository;
+
+const TEMPERATURE_WARN_THRESHOLD: f32 = 70.0;
+const FALLS_WARN_THRESHOLD: u32 = 50;
+const BATTERY_CHARGE_WARN: f32 = 0.1;
+
+#[derive(Args)]
+pub struct Arguments {
+    /// Collect the health logs of all NAOs of the team
+    #[arg(long)]
+    all: bool,
+    /// Only aggregate the most recent sessions of each robot
+    #[arg(long)]
+    sessions: Option<usize>,
+    /// Output aggregated health information as json
+    #[arg(long, short = 'j')]
+    json: bool,
+    /// The NAOs to collect health logs from e.g. 20w or 10.1.24.22
+    #[arg(required_unless_present = "all", conflicts_with = "all")]
+    naos: Vec<NaoAddress>,
+}
+
+#[derive(Serialize)]
+struct RobotHealth {
+    body_id: String,
+    head_id: String,
+    summary: HealthSummary,
+    maintenance_reasons: Vec<String>,
+}
+
+pub async fn health(arguments: Arguments, repository: &Repository) -> Result<()> {
+    let directory = health_log_directory(repository).await?;
+    let naos = if arguments.all {
+        let team = repository
+            .read_team_configuration()
+            .await
+            .wrap_err("failed to get team configuration")?;
+        team.naos
+            .iter()
+            .map(|nao| async move {
+                let host = number_to_ip(nao.number, Connection::Wired)?;
+                match Nao::try_new_with_ping(host).await {
+                    Ok(nao) => Ok(nao),
+                    Err(_) => {
+                        let host = number_to_ip(nao.number, Connection::Wireless)?;
+                        Nao::try_new_with_ping(host).await
+                    }
+                }
+            })
+            .collect::<FuturesUnordered<_>>()
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .filter_map(|nao| nao.map_err(|error| error!("{error:#}")).ok())
+            .collect()
+    } else {
+        arguments
+            .naos
+            .iter()
+            .map(|nao_address| Nao::new(nao_address.ip))
+            .collect::<Vec<_>>()
+    };
+    if naos.is_empty() {
+        bail!("no NAO is reachable");
+    }
+
+    let directory = &directory;
+    let logs = naos
+        .iter()
+        .map(|nao| async move {
+            let output = nao
+                .retrieve_health_logs(directory)
+                .await
+                .wrap_err_with(|| format!("failed to retrieve health logs from {nao}"))?;
+            if output.trim().is_empty() {
+                warn!("{nao} has not recorded any health logs yet");
+            }
+            Deserializer::from_str(&output)
+                .into_iter::<HealthLog>()
+                .collect::<Result<Vec<_>, _>>()
+                .wrap_err_with(|| format!("failed to parse health logs from {nao}"))
+        })
+        .collect::<FuturesUnordered<_>>()
+        .collect::<Vec<_>>()
+        .await;
+
+    let mut sessions_per_robot = BTreeMap::<_, Vec<_>>::new();
+    for log in logs
+        .into_iter()
+        .filter_map(|logs| logs.map_err(|error| error!("{error:#}")).ok())
+        .flatten()
+    {
+        sessions_per_robot
+            .entry((log.body_id, log.head_id))
+            .or_default()
+            .extend(log.sessions);
+    }
+
+    let robots: Vec<_> = sessions_per_robot
+        .into_iter()
+        .map(|((body_id, head_id), mut sessions)| {
+            sessions.sort_by_key(|session| session.started_at);
+            let skipped_sessions = arguments
+                .sessions
+                .map_or(0, |limit| sessions.len().saturating_sub(limit));
+            let summary = HealthSummary::from_sessions(&sessions[skipped_sessions..]);
+            RobotHealth {
+                body_id,
+                head_id,
+                maintenance_reasons: maintenance_reasons(&summary),
+                summary,
+            }
+        })
+        .collect();
+
+    if arguments.json {
+        let json = serde_json::to_string(&robots).wrap_err("failed to serialize health")?;
+        println!("{json}");
+    } else {
+        print_summary(&robots);
+    }
+    Ok(())
+}
+
+async fn health_log_directory(repository: &Repository) -> Result<PathBuf> {
+    let hardware_parameters_path = repository.root.join("etc/parameters/hardware.json");
+    let hardware_parameters = read_to_string(&hardware_parameters_path)
+        .await
+        .wrap_err_with(|| format!("failed to read {}", hardware_parameters_path.display()))?;
+    let hardware_parameters: Value =
+        from_str(&hardware_parameters).wrap_err("failed to parse hardware parameters")?;
+    let Some(directory) = hardware_parameters
+        .pointer("/health_log/directory")
+        .and_then(Value::as_str)
+    else {
+        bail!("hardware parameters do not contain health_log.directory");
+    };
+    Ok(PathBuf::from(directory))
+}
+
+fn maintenance_reasons(summary: &HealthSummary) -> Vec<String> {
+    let mut reasons = Vec::new();
+    let maximum_temperature = summary.maximum_temperature();
+    if maximum_temperature > TEMPERATURE_WARN_THRESHOLD {
+        reasons.push(format!("joints reached {maximum_temperature}°C"));
+    }
+    let maximum_status = summary.maximum_status();
+    if maximum_status > 0.0 {
+        reasons.push(format!("joint status reached {maximum_status}"));
+    }
+    if summary.falls > FALLS_WARN_THRESHOLD {
+        reasons.push(format!("{} falls", summary.falls));
+    }
+    if summary
+        .minimum_battery_charge
+        .is_some_and(|charge| charge < BATTERY_CHARGE_WARN)
+    {
+        reasons.push("battery was drained".to_string());
+    }
+    if summary.force_sensitive_resistor_anomalies > 0 {
+        reasons.push(format!(
+            "{} cycles with implausible FSR readings",
+            summary.force_sensitive_resistor_anomalies
+        ));
+    }
+    reasons
+}
+
+fn print_summary(robots: &[RobotHealth]) {
+    for robot in robots {
+        let summary = &robot.summary;
+        println!(
+            "{} {}",
+            robot.body_id.bold(),
+            format!("(head {})", robot.head_id).dimmed()
+        );
+        println!(
+            "  {} sessions, {} falls, {} charging cycles, maximum temperature {}°C",
+            summary.sessions,
+            summary.falls,
+            summary.charging_cycles,
+            summary.maximum_temperature(),
+        );
+        if robot.maintenance_reasons.is_empty() {
+            println!("  {}", "✔".green());
+        }
+        for reason in &robot.maintenance_reasons {
+            println!("  {} {reason}", "✗".red());
+        }
+    }
+}
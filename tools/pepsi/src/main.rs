@@ -13,11 +13,14 @@ use analyze::analyze;
 use cargo::{build, cargo, check, clippy, install, run, test};
 use communication::communication;
 use completions::completions;
+use dashboard::dashboard;
 use game_branch::game_branch;
 use gammaray::gammaray;
+use health::health;
 use hulk::hulk;
 use location::location;
 use logs::logs;
+use parameters::parameters;
 use ping::ping;
 use player_number::player_number;
 use post_game::post_game;
@@ -30,19 +33,23 @@ use shell::shell;
 use tracing::warn;
 use upload::upload;
 use wifi::wifi;
+use words::words;
 
 mod aliveness;
 mod analyze;
 mod cargo;
 mod communication;
 mod completions;
+mod dashboard;
 mod deploy_config;
 mod game_branch;
 mod gammaray;
 mod git;
+mod health;
 mod hulk;
 mod location;
 mod logs;
+mod parameters;
 mod ping;
 mod player_number;
 mod post_game;
@@ -55,6 +62,7 @@ mod sdk;
 mod shell;
 mod upload;
 mod wifi;
+mod words;
 
 #[derive(Parser)]
 #[clap(version, name = "pepsi")]
@@ -86,10 +94,14 @@ enum Command {
     Communication(communication::Arguments),
     /// Generate shell completion files
     Completions(completions::Arguments),
+    /// Show a live dashboard of all NAOs of the team
+    Dashboard(dashboard::Arguments),
     /// Create a game branch from the deploy.toml in the repository root
     Gamebranch(game_branch::Arguments),
     /// Flash a HULKs-OS image to NAOs
     Gammaray(gammaray::Arguments),
+    /// Collect and aggregate hardware health logs from NAOs
+    Health(health::Arguments),
     /// Control the HULK service
     Hulk(hulk::Arguments),
     /// Install a Rust binary
@@ -100,6 +112,9 @@ enum Command {
     /// Interact with logs on NAOs
     #[command(subcommand)]
     Logs(logs::Arguments),
+    /// Inspect the parameter files
+    #[command(subcommand)]
+    Parameters(parameters::Arguments),
     /// Change player numbers of NAOs in local parameters
     Playernumber(player_number::Arguments),
     /// Ping NAOs
@@ -130,6 +145,8 @@ enum Command {
     /// Control WiFi on NAOs
     #[command(subcommand, name = "wifi", alias = "wlan", alias = "wireless")]
     WiFi(wifi::Arguments),
+    /// Generate missing words of the phrase word bank with the Google text to speech API
+    Words(words::Arguments),
 }
 
 #[tokio::main]
@@ -186,12 +203,18 @@ async fn main() -> Result<()> {
         Command::Completions(arguments) => completions(arguments, Arguments::command())
             .await
             .wrap_err("failed to execute completion command")?,
+        Command::Dashboard(arguments) => dashboard(arguments, &repository?)
+            .await
+            .wrap_err("failed to execute dashboard command")?,
         Command::Gamebranch(arguments) => game_branch(arguments, &repository?)
             .await
             .wrap_err("failed to execute gamebranch command")?,
         Command::Gammaray(arguments) => gammaray(arguments, &repository?)
             .await
             .wrap_err("failed to execute gammaray command")?,
+        Command::Health(arguments) => health(arguments, &repository?)
+            .await
+            .wrap_err("failed to execute health command")?,
         Command::Hulk(arguments) => hulk(arguments)
             .await
             .wrap_err("failed to execute hulk command")?,
@@ -204,6 +227,9 @@ async fn main() -> Result<()> {
         Command::Logs(arguments) => logs(arguments)
             .await
             .wrap_err("failed to execute logs command")?,
+        Command::Parameters(arguments) => parameters(arguments, &repository?)
+            .await
+            .wrap_err("failed to execute parameters command")?,
         Command::Ping(arguments) => ping(arguments).await,
         Command::Playernumber(arguments) => player_number(arguments, &repository?)
             .await
@@ -241,6 +267,9 @@ async fn main() -> Result<()> {
         Command::WiFi(arguments) => wifi(arguments)
             .await
             .wrap_err("failed to execute wifi command")?,
+        Command::Words(arguments) => words(arguments, &repository?)
+            .await
+            .wrap_err("failed to execute words command")?,
     }
 
     Ok(())
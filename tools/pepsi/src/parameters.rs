@@ -0,0 +1,121 @@
+use clap::Subcommand;
+use color_eyre::{
+    eyre::{eyre, WrapErr},
+    Result,
+};
+use serde_json::Value;
+
+use argument_parsers::NaoNumber;
+use hula_types::hardware::Ids;
+use parameters::{
+    directory::merge_layers,
+    provenance::{diff, explain, value_at_path},
+};
+use repository::Repository;
+
+#[derive(Subcommand)]
+pub enum Arguments {
+    /// Show which parameter files set a path and the resulting effective value
+    Explain {
+        /// The parameter path, e.g. walking_engine.base.step_duration
+        path: String,
+        /// The NAO whose head and body files are considered, only default files if not specified
+        #[arg(long)]
+        nao: Option<NaoNumber>,
+    },
+    /// Show effective parameters which differ between NAOs
+    Diff {
+        /// Only compare parameters below this path
+        #[arg(long, default_value = "")]
+        path: String,
+        /// The NAOs to compare, all NAOs of the team if not specified
+        naos: Option<Vec<NaoNumber>>,
+    },
+}
+
+pub async fn parameters(arguments: Arguments, repository: &Repository) -> Result<()> {
+    let parameters_root = repository.root.join("etc/parameters");
+    match arguments {
+        Arguments::Explain { path, nao } => {
+            let ids = match nao {
+                Some(nao) => hardware_ids(repository, nao).await?,
+                None => Ids {
+                    body_id: String::new(),
+                    head_id: String::new(),
+                },
+            };
+            let provenance = explain(&parameters_root, &ids, &path)
+                .wrap_err_with(|| format!("failed to explain {path}"))?;
+            if provenance.chain.is_empty() {
+                println!("{path} is not set by any parameter file");
+            }
+            for layer_value in &provenance.chain {
+                let file = layer_value
+                    .file
+                    .strip_prefix(&repository.root)
+                    .unwrap_or(&layer_value.file);
+                println!(
+                    "{:<16} {}: {}",
+                    format!("{:?}", layer_value.layer),
+                    file.display(),
+                    layer_value.value
+                );
+            }
+            if let Some(effective) = &provenance.effective {
+                println!("{:<16} {effective}", "Effective");
+            }
+        }
+        Arguments::Diff { path, naos } => {
+            let naos = match naos {
+                Some(naos) => naos,
+                None => repository
+                    .read_team_configuration()
+                    .await
+                    .wrap_err("failed to get team configuration")?
+                    .naos
+                    .iter()
+                    .map(|nao| NaoNumber { number: nao.number })
+                    .collect(),
+            };
+            let mut effective_parameters = Vec::new();
+            for nao in naos {
+                let ids = hardware_ids(repository, nao).await?;
+                let parameters = merge_layers(&parameters_root, &ids)
+                    .wrap_err_with(|| format!("failed to merge parameters of NAO {nao}"))?;
+                let parameters = value_at_path(&parameters, &path)
+                    .cloned()
+                    .unwrap_or(Value::Null);
+                effective_parameters.push((nao.to_string(), parameters));
+            }
+            for (leaf, values) in diff(&effective_parameters) {
+                let leaf = [path.as_str(), leaf.as_str()]
+                    .into_iter()
+                    .filter(|part| !part.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(".");
+                println!("{leaf}");
+                for (nao, value) in values {
+                    let value = value.map_or("<missing>".to_string(), |value| value.to_string());
+                    println!("  {nao:>4}: {value}");
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn hardware_ids(repository: &Repository, nao: NaoNumber) -> Result<Ids> {
+    let team = repository
+        .read_team_configuration()
+        .await
+        .wrap_err("failed to get team configuration")?;
+    let nao = team
+        .naos
+        .into_iter()
+        .find(|team_nao| team_nao.number == nao.number)
+        .ok_or_else(|| eyre!("NAO with Hardware ID {nao} does not exist"))?;
+    Ok(Ids {
+        body_id: nao.body_id,
+        head_id: nao.head_id,
+    })
+}
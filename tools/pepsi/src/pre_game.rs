@@ -32,7 +32,7 @@ pub struct Arguments {
 
 #[derive(Args)]
 pub struct PreGameArguments {
-    /// Skip running the parameter tester
+    /// Skip running the parameter tester, which also checks parameter ranges
     #[arg(long)]
     pub skip_parameter_check: bool,
     /// Do not build before uploading
@@ -0,0 +1,27 @@
+use clap::Args;
+use color_eyre::{eyre::WrapErr, Result};
+use repository::Repository;
+
+#[derive(Args)]
+pub struct Arguments {
+    /// The full name of the voice
+    #[arg(long, default_value = "en-US-Neural2-F")]
+    pub voice_name: String,
+    /// Google API key, read from tools/TextToSpeech/.api_key if not given
+    #[arg(long)]
+    pub api_key: Option<String>,
+    /// Regenerate words which already exist
+    #[arg(long)]
+    pub force: bool,
+}
+
+pub async fn words(arguments: Arguments, repository: &Repository) -> Result<()> {
+    repository
+        .generate_words(
+            &arguments.voice_name,
+            arguments.api_key.as_deref(),
+            arguments.force,
+        )
+        .await
+        .wrap_err("failed to generate words")
+}
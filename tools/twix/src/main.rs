@@ -34,10 +34,10 @@ use log::{error, warn};
 use nao::Nao;
 use panel::Panel;
 use panels::{
-    BallCandidatePanel, BehaviorSimulatorPanel, CameraCalibrationExportPanel, EnumPlotPanel,
-    ImageColorSelectPanel, ImagePanel, ImageSegmentsPanel, LookAtPanel, ManualCalibrationPanel,
-    MapPanel, ParameterPanel, PlotPanel, RemotePanel, SemiAutomaticCameraCalibrationPanel,
-    TextPanel, VisionTunerPanel,
+    BallCandidatePanel, BehaviorSimulatorPanel, BehaviorTracePanel, CameraCalibrationExportPanel,
+    EnumPlotPanel, FieldMeasurementPanel, ImageColorSelectPanel, ImagePanel, ImageSegmentsPanel,
+    LookAtPanel, ManualCalibrationPanel, MapPanel, ParameterPanel, PlotPanel, RemotePanel,
+    SemiAutomaticCameraCalibrationPanel, TextPanel, VisionTunerPanel,
 };
 use reachable_naos::ReachableNaos;
 use repository::{inspect_version::check_for_update, Repository};
@@ -136,8 +136,10 @@ fn main() -> Result<(), eframe::Error> {
 impl_selectable_panel!(
     BallCandidatePanel,
     BehaviorSimulatorPanel,
+    BehaviorTracePanel,
     CameraCalibrationExportPanel,
     EnumPlotPanel,
+    FieldMeasurementPanel,
     ImageColorSelectPanel,
     ImagePanel,
     ImageSegmentsPanel,
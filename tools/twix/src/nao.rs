@@ -1,4 +1,8 @@
-use std::time::{Duration, SystemTime};
+use std::{
+    fs::read_to_string,
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
 
 use bincode::deserialize;
 use color_eyre::{
@@ -17,7 +21,11 @@ use communication::{
     messages::{Path, TextOrBinary},
 };
 use hula_types::hardware::Ids;
-use parameters::{directory::Scope, json::nest_value_at_path};
+use parameters::{
+    directory::{Layer, Scope},
+    json::nest_value_at_path,
+    provenance::{explain, Provenance},
+};
 use repository::Repository;
 
 use crate::{
@@ -194,6 +202,47 @@ impl Nao {
         });
         Ok(())
     }
+
+    /// Parameter directory of the location the connected robot runs with
+    pub fn location_directory(&self) -> Result<PathBuf> {
+        let parameters_root = self
+            .repository
+            .as_ref()
+            .ok_or_eyre("repository not available, cannot locate parameters")?
+            .root
+            .join("etc/parameters/");
+        let (_, ids) = self.blocking_read::<Ids>("hardware_ids")?;
+        let location_parameters = Layer::LocationDefault.file_path(parameters_root, &ids);
+        location_parameters
+            .parent()
+            .map(|directory| directory.to_path_buf())
+            .ok_or_eyre("location parameters have no parent directory")
+    }
+
+    /// Parameters of the repository all robots share unless a more specific file overrides them
+    pub fn default_parameters(&self) -> Result<Value> {
+        let path = self
+            .repository
+            .as_ref()
+            .ok_or_eyre("repository not available, cannot read default parameters")?
+            .root
+            .join("etc/parameters/default.json");
+        let content =
+            read_to_string(&path).wrap_err_with(|| format!("failed to read {}", path.display()))?;
+        serde_json::from_str(&content)
+            .wrap_err_with(|| format!("failed to parse {}", path.display()))
+    }
+
+    /// Parameter files of the repository setting the path for the given robot
+    pub fn explain_parameter(&self, path: &str, ids: &Ids) -> Result<Provenance> {
+        let parameters_root = self
+            .repository
+            .as_ref()
+            .ok_or_eyre("repository not available, cannot explain parameters")?
+            .root
+            .join("etc/parameters/");
+        explain(parameters_root, ids, path).wrap_err("failed to explain parameter")
+    }
 }
 
 async fn store_parameters(
@@ -2,13 +2,13 @@ use std::sync::Arc;
 
 use eframe::egui::{Response, Ui, Widget};
 use log::error;
-use nalgebra::Vector3;
+use nalgebra::{Vector2, Vector3};
 use serde_json::Value;
 
-use calibration::corrections::Corrections;
+use calibration::corrections::{correct_radial_distortion, Corrections};
 use communication::messages::TextOrBinary;
 use parameters::directory::Scope;
-use types::primary_state::PrimaryState;
+use types::{joints::Joints, lens_distortion::LensDistortion, primary_state::PrimaryState};
 
 use crate::{log_error::LogError, nao::Nao, panel::Panel, value_buffer::BufferHandle};
 
@@ -18,14 +18,24 @@ pub const BOTTOM_CAMERA_EXTRINSICS_PATH: &str =
     "camera_matrix_parameters.calibration.correction_in_camera_bottom";
 pub const ROBOT_BODY_ROTATION_PATH: &str =
     "camera_matrix_parameters.calibration.correction_in_robot";
+pub const HEAD_CALIBRATION_OFFSETS_PATH: &str = "joint_calibration_offsets.head";
+pub const LEFT_LEG_CALIBRATION_OFFSETS_PATH: &str = "joint_calibration_offsets.left_leg";
+pub const RIGHT_LEG_CALIBRATION_OFFSETS_PATH: &str = "joint_calibration_offsets.right_leg";
+pub const TOP_CAMERA_LENS_DISTORTION_PATH: &str =
+    "camera_matrix_parameters.vision_top.lens_distortion";
+pub const BOTTOM_CAMERA_LENS_DISTORTION_PATH: &str =
+    "camera_matrix_parameters.vision_bottom.lens_distortion";
 
 pub struct CameraCalibrationExportPanel {
     nao: Arc<Nao>,
     top_camera: BufferHandle<Vector3<f32>>,
     bottom_camera: BufferHandle<Vector3<f32>>,
     body_rotations: BufferHandle<Vector3<f32>>,
+    top_lens_distortion: BufferHandle<LensDistortion>,
+    bottom_lens_distortion: BufferHandle<LensDistortion>,
     calibration_corrections: BufferHandle<Value>,
     calibration_measurements: BufferHandle<Value>,
+    calibrated_joint_offsets: BufferHandle<Joints<f32>>,
     primary_state: BufferHandle<PrimaryState>,
 }
 
@@ -37,10 +47,16 @@ impl Panel for CameraCalibrationExportPanel {
         let bottom_camera =
             nao.subscribe_value(format!("parameters.{BOTTOM_CAMERA_EXTRINSICS_PATH}"));
         let body_rotations = nao.subscribe_value(format!("parameters.{ROBOT_BODY_ROTATION_PATH}"));
+        let top_lens_distortion =
+            nao.subscribe_value(format!("parameters.{TOP_CAMERA_LENS_DISTORTION_PATH}"));
+        let bottom_lens_distortion =
+            nao.subscribe_value(format!("parameters.{BOTTOM_CAMERA_LENS_DISTORTION_PATH}"));
         let calibration_corrections =
             nao.subscribe_json("Control.additional_outputs.last_corrections");
         let calibration_measurements =
             nao.subscribe_json("Control.additional_outputs.last_measurements");
+        let calibrated_joint_offsets =
+            nao.subscribe_value("Control.additional_outputs.calibrated_joint_calibration_offsets");
         let primary_state = nao.subscribe_value("Control.main_outputs.primary_state");
 
         Self {
@@ -48,8 +64,11 @@ impl Panel for CameraCalibrationExportPanel {
             top_camera,
             bottom_camera,
             body_rotations,
+            top_lens_distortion,
+            bottom_lens_distortion,
             calibration_corrections,
             calibration_measurements,
+            calibrated_joint_offsets,
             primary_state,
         }
     }
@@ -72,6 +91,13 @@ impl Widget for &mut CameraCalibrationExportPanel {
 
                 draw_group(ui, "Top", top_angles, &self.nao, TOP_CAMERA_EXTRINSICS_PATH);
                 draw_angles_from_buffer(ui, &self.top_camera);
+                draw_lens_distortion(
+                    ui,
+                    value.radial_distortion_top,
+                    &self.top_lens_distortion,
+                    &self.nao,
+                    TOP_CAMERA_LENS_DISTORTION_PATH,
+                );
                 ui.separator();
 
                 draw_group(
@@ -82,11 +108,28 @@ impl Widget for &mut CameraCalibrationExportPanel {
                     BOTTOM_CAMERA_EXTRINSICS_PATH,
                 );
                 draw_angles_from_buffer(ui, &self.bottom_camera);
+                draw_lens_distortion(
+                    ui,
+                    value.radial_distortion_bottom,
+                    &self.bottom_lens_distortion,
+                    &self.nao,
+                    BOTTOM_CAMERA_LENS_DISTORTION_PATH,
+                );
                 ui.separator();
 
                 draw_group(ui, "Body", body_angles, &self.nao, ROBOT_BODY_ROTATION_PATH);
                 draw_angles_from_buffer(ui, &self.body_rotations);
 
+                if let Some(joint_offsets) = self
+                    .calibrated_joint_offsets
+                    .get_last_value()
+                    .ok()
+                    .flatten()
+                {
+                    ui.separator();
+                    draw_joint_offsets(ui, joint_offsets, &self.nao);
+                }
+
                 if let Some(measurements_value) = self
                     .calibration_measurements
                     .get_last_value()
@@ -153,6 +196,80 @@ fn draw_group(ui: &mut Ui, label: &str, rotations_radians: (f32, f32, f32), nao:
     draw_angles(ui, &rotations_degrees, "Calibrated");
 }
 
+/// The radial correction applies to the current lens distortion, which the measurements were
+/// captured with
+fn draw_lens_distortion(
+    ui: &mut Ui,
+    radial_correction: Vector2<f32>,
+    current: &BufferHandle<LensDistortion>,
+    nao: &Nao,
+    path: &str,
+) {
+    if radial_correction == Vector2::zeros() {
+        return;
+    }
+    let Some(current) = current.get_last_value().ok().flatten() else {
+        return;
+    };
+    let calibrated = correct_radial_distortion(current, radial_correction);
+    ui.horizontal(|ui| {
+        ui.label("Lens distortion");
+        if ui.button("Save to repo").clicked() {
+            serialize_and_call(calibrated, |value| {
+                nao.store_parameters(path, value, Scope::default_head())
+                    .log_err();
+            });
+        }
+        if ui.button("Set in Nao").clicked() {
+            serialize_and_call(calibrated, |value| {
+                nao.write(format!("parameters.{path}"), TextOrBinary::Text(value));
+            });
+        }
+    });
+    ui.label(format!("Calibrated: {calibrated:?}"));
+    ui.label(format!("Current: {current:?}"));
+}
+
+/// Head offsets belong to the head, leg offsets to the body of the robot
+fn draw_joint_offsets(ui: &mut Ui, joint_offsets: Joints<f32>, nao: &Nao) {
+    ui.horizontal(|ui| {
+        ui.label("Joint offsets");
+        if ui.button("Save to repo").clicked() {
+            serialize_and_call(joint_offsets.head, |value| {
+                nao.store_parameters(HEAD_CALIBRATION_OFFSETS_PATH, value, Scope::default_head())
+                    .log_err();
+            });
+            for (path, leg) in [
+                (LEFT_LEG_CALIBRATION_OFFSETS_PATH, joint_offsets.left_leg),
+                (RIGHT_LEG_CALIBRATION_OFFSETS_PATH, joint_offsets.right_leg),
+            ] {
+                serialize_and_call(leg, |value| {
+                    nao.store_parameters(path, value, Scope::default_body())
+                        .log_err();
+                });
+            }
+        }
+        if ui.button("Set in Nao").clicked() {
+            serialize_and_call(joint_offsets, |value| {
+                nao.write(
+                    "parameters.joint_calibration_offsets",
+                    TextOrBinary::Text(value),
+                );
+            });
+        }
+    });
+    let offsets_degrees = [
+        ("head yaw", joint_offsets.head.yaw),
+        ("head pitch", joint_offsets.head.pitch),
+        ("left knee pitch", joint_offsets.left_leg.knee_pitch),
+        ("left ankle pitch", joint_offsets.left_leg.ankle_pitch),
+        ("right knee pitch", joint_offsets.right_leg.knee_pitch),
+        ("right ankle pitch", joint_offsets.right_leg.ankle_pitch),
+    ]
+    .map(|(joint, radians)| format!("{joint}: {:.2}°", radians.to_degrees()));
+    ui.label(format!("Calibrated: {}", offsets_degrees.join(", ")));
+}
+
 fn draw_angles_from_buffer(ui: &mut Ui, current_values: &BufferHandle<Vector3<f32>>) {
     if let Some(value) = current_values.get_last_value().ok().flatten() {
         draw_angles(ui, &[value.x, value.y, value.z], "Current");
@@ -0,0 +1,209 @@
+use std::{fs::File, sync::Arc, time::Duration};
+
+use color_eyre::{
+    eyre::{bail, WrapErr},
+    Result,
+};
+use eframe::egui::{Color32, Grid, Response, ScrollArea, TextEdit, Ui, Widget};
+use serde_json::{json, Value};
+
+use parameters::json::{prune_empty_objects, prune_equal_branches};
+use types::{
+    behavior_fixture::{BehaviorFixture, BehaviorFixtureParameters, BehaviorInputs},
+    behavior_state::BehaviorState,
+    decision_trace::{ActionOutcome, DecisionTrace},
+    motion_command::MotionCommand,
+};
+
+use crate::{
+    nao::Nao,
+    panel::Panel,
+    value_buffer::{BufferHandle, Datum},
+};
+
+const HISTORY: Duration = Duration::from_secs(30);
+const DEFAULT_FIXTURE_PATH: &str = "crates/control/fixtures/behavior/new.json";
+
+pub struct BehaviorTracePanel {
+    nao: Arc<Nao>,
+    decision_trace: BufferHandle<Option<DecisionTrace>>,
+    motion_command: BufferHandle<MotionCommand>,
+    behavior_state: BufferHandle<Option<BehaviorState>>,
+    fixture_path: String,
+    fixture_status: Option<Result<(), String>>,
+}
+
+impl Panel for BehaviorTracePanel {
+    const NAME: &'static str = "Behavior Trace";
+
+    fn new(nao: Arc<Nao>, value: Option<&Value>) -> Self {
+        let decision_trace =
+            nao.subscribe_buffered_value("Control.additional_outputs.decision_trace", HISTORY);
+        let motion_command =
+            nao.subscribe_buffered_value("Control.main_outputs.motion_command", HISTORY);
+        let behavior_state = nao.subscribe_value("Control.additional_outputs.behavior_state");
+        let fixture_path = value
+            .and_then(|value| value.get("fixture_path")?.as_str())
+            .unwrap_or(DEFAULT_FIXTURE_PATH)
+            .to_string();
+        Self {
+            nao,
+            decision_trace,
+            motion_command,
+            behavior_state,
+            fixture_path,
+            fixture_status: None,
+        }
+    }
+
+    fn save(&self) -> Value {
+        json!({
+            "fixture_path": self.fixture_path,
+        })
+    }
+}
+
+impl Widget for &mut BehaviorTracePanel {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let traces: Vec<_> = match self.decision_trace.get() {
+            Ok(traces) => traces
+                .into_iter()
+                .filter_map(|datum| {
+                    Some(Datum {
+                        timestamp: datum.timestamp,
+                        value: datum.value?,
+                    })
+                })
+                .collect(),
+            Err(error) => return ui.colored_label(Color32::RED, format!("Error: {error:#}")),
+        };
+        let Some(latest) = traces.last() else {
+            return ui.label("no decision trace received");
+        };
+
+        ui.vertical(|ui| {
+            ui.horizontal(|ui| {
+                ui.add(TextEdit::singleline(&mut self.fixture_path).hint_text("fixture path"));
+                if ui.button("Save fixture").clicked() {
+                    self.fixture_status =
+                        Some(self.save_fixture().map_err(|error| format!("{error:#}")));
+                }
+                match &self.fixture_status {
+                    Some(Ok(())) => {
+                        ui.label("saved");
+                    }
+                    Some(Err(error)) => {
+                        ui.colored_label(Color32::RED, error);
+                    }
+                    None => {}
+                }
+            });
+            ui.label(format!("Role: {:?}", latest.value.role));
+            Grid::new("candidates").striped(true).show(ui, |ui| {
+                for (priority, candidate) in latest.value.candidates.iter().enumerate() {
+                    let color = match candidate.outcome {
+                        ActionOutcome::Chosen => Color32::GREEN,
+                        ActionOutcome::PreconditionFailed { .. } => Color32::RED,
+                        ActionOutcome::NotEvaluated => Color32::GRAY,
+                    };
+                    ui.label(format!("{priority}"));
+                    ui.colored_label(color, format!("{:?}", candidate.action));
+                    ui.colored_label(color, format!("{:?}", candidate.outcome));
+                    ui.end_row();
+                }
+            });
+
+            ui.separator();
+            ui.label("Timeline");
+            ScrollArea::vertical().show(ui, |ui| {
+                Grid::new("timeline").striped(true).show(ui, |ui| {
+                    for (start, end) in changes(&traces).into_iter().rev() {
+                        let age = latest
+                            .timestamp
+                            .duration_since(start.timestamp)
+                            .unwrap_or_default();
+                        let duration = end
+                            .timestamp
+                            .duration_since(start.timestamp)
+                            .unwrap_or_default();
+                        ui.label(format!("-{:.2}s", age.as_secs_f32()));
+                        ui.label(format!("{:?}", start.value.role));
+                        match start.value.chosen_action() {
+                            Some(action) => ui.label(format!("{action:?}")),
+                            None => ui.colored_label(Color32::RED, "none"),
+                        };
+                        ui.label(format!("for {:.2}s", duration.as_secs_f32()));
+                        ui.end_row();
+                    }
+                });
+            });
+        })
+        .response
+    }
+}
+
+impl BehaviorTracePanel {
+    /// Reads the current behavior inputs and parameters, best used while the replayer is paused
+    fn save_fixture(&self) -> Result<()> {
+        let nao = &self.nao;
+        let main_output = |name: &str| format!("Control.main_outputs.{name}");
+        let motion_commands = self.motion_command.get()?;
+        let [.., last_motion_command, motion_command] = motion_commands.as_slice() else {
+            bail!("no motion command of the previous cycle received yet");
+        };
+        let Some(Some(state)) = self.behavior_state.get_last_value()? else {
+            bail!("no behavior state received yet");
+        };
+        let mut parameter_overrides = serde_json::to_value(BehaviorFixtureParameters {
+            behavior: nao.blocking_read("parameters.behavior")?.1,
+            kick_selector: nao.blocking_read("parameters.kick_selector")?.1,
+            in_walk_kicks: nao.blocking_read("parameters.in_walk_kicks")?.1,
+            field_dimensions: nao.blocking_read("parameters.field_dimensions")?.1,
+            pose_detection: nao.blocking_read("parameters.pose_detection")?.1,
+            keeper_motion: nao.blocking_read("parameters.keeper_motion")?.1,
+            calibration_controller: nao.blocking_read("parameters.calibration_controller")?.1,
+            localization: nao.blocking_read("parameters.localization")?.1,
+            walk_speed: nao.blocking_read("parameters.walk_speed")?.1,
+        })
+        .wrap_err("failed to serialize behavior parameters")?;
+        prune_equal_branches(&mut parameter_overrides, &nao.default_parameters()?);
+        prune_empty_objects(&mut parameter_overrides);
+        let fixture = BehaviorFixture {
+            inputs: BehaviorInputs {
+                has_ground_contact: nao.blocking_read(main_output("has_ground_contact"))?.1,
+                world_state: nao.blocking_read(main_output("world_state"))?.1,
+                dribble_path_plan: nao.blocking_read(main_output("dribble_path_plan"))?.1,
+                cycle_time: nao.blocking_read(main_output("cycle_time"))?.1,
+                is_localization_converged: nao
+                    .blocking_read(main_output("is_localization_converged"))?
+                    .1,
+                expected_referee_position: nao
+                    .blocking_read(main_output("expected_referee_position"))?
+                    .1,
+                last_motion_command: last_motion_command.value.clone(),
+            },
+            state,
+            parameter_overrides,
+            expected_motion_command: motion_command.value.clone(),
+        };
+        let file = File::create(&self.fixture_path).wrap_err("failed to create fixture file")?;
+        serde_json::to_writer_pretty(file, &fixture).wrap_err("failed to write fixture")
+    }
+}
+
+/// Splits the traces into segments with the same role and chosen action
+fn changes(traces: &[Datum<DecisionTrace>]) -> Vec<(&Datum<DecisionTrace>, &Datum<DecisionTrace>)> {
+    let mut segments: Vec<(&Datum<DecisionTrace>, &Datum<DecisionTrace>)> = Vec::new();
+    for trace in traces {
+        match segments.last_mut() {
+            Some((start, end))
+                if start.value.role == trace.value.role
+                    && start.value.chosen_action() == trace.value.chosen_action() =>
+            {
+                *end = trace;
+            }
+            _ => segments.push((trace, trace)),
+        }
+    }
+    segments
+}
@@ -6,9 +6,9 @@ use color_eyre::{
 };
 
 use calibration::{
-    corrections::Corrections,
+    corrections::{correct_radial_distortion, Corrections},
     goal_and_penalty_box::{LineType, Measurement, Residuals},
-    problem::CalibrationProblem,
+    problem::{CalibrationProblem, EstimatedParameters},
 };
 use communication::messages::TextOrBinary;
 use coordinate_systems::Pixel;
@@ -18,7 +18,10 @@ use linear_algebra::Isometry2;
 use parameters::directory::Scope;
 use projection::camera_matrix::CameraMatrix;
 use serde_json::Value;
-use types::{camera_position::CameraPosition, field_dimensions::FieldDimensions};
+use types::{
+    camera_position::CameraPosition, field_dimensions::FieldDimensions,
+    lens_distortion::LensDistortion,
+};
 
 use crate::{nao::Nao, value_buffer::BufferHandle};
 
@@ -28,10 +31,15 @@ const CAMERA_TOP_CORRECTION_PATH: &str =
     "parameters.camera_matrix_parameters.calibration.correction_in_camera_top";
 const CAMERA_BOTTOM_CORRECTION_PATH: &str =
     "parameters.camera_matrix_parameters.calibration.correction_in_camera_bottom";
+const CAMERA_TOP_LENS_DISTORTION_PATH: &str =
+    "parameters.camera_matrix_parameters.vision_top.lens_distortion";
+const CAMERA_BOTTOM_LENS_DISTORTION_PATH: &str =
+    "parameters.camera_matrix_parameters.vision_bottom.lens_distortion";
 
 pub struct SemiAutomaticCalibrationContext {
     nao: Arc<Nao>,
     state: OptimizationState,
+    pub estimate_lens_distortion: bool,
 
     top_camera_correction: BufferHandle<nalgebra::Vector3<f32>>,
     bottom_camera_correction: BufferHandle<nalgebra::Vector3<f32>>,
@@ -52,11 +60,20 @@ pub struct SavedMeasurement {
     pub drawn_lines: Vec<DrawnLine>,
 }
 
+/// Lens distortion of one camera before and after the optimization
+#[derive(Clone, Copy, Debug)]
+struct LensDistortionUpdate {
+    path: &'static str,
+    original: LensDistortion,
+    calibrated: LensDistortion,
+}
+
 enum OptimizationState {
     NotOptimized,
     Optimized {
         corrections: Corrections,
         report: MinimizationReport<f32>,
+        lens_distortions: Vec<LensDistortionUpdate>,
     },
 }
 
@@ -70,6 +87,7 @@ impl SemiAutomaticCalibrationContext {
         Self {
             nao,
             state: OptimizationState::NotOptimized,
+            estimate_lens_distortion: false,
             top_camera_correction,
             bottom_camera_correction,
             robot_correction,
@@ -118,12 +136,14 @@ impl SemiAutomaticCalibrationContext {
             correction_in_robot,
             correction_in_camera_top,
             correction_in_camera_bottom,
+            ..Default::default()
         })
     }
 
     fn apply_corrections(
         &self,
         corrections: Corrections,
+        lens_distortions: impl IntoIterator<Item = (&'static str, LensDistortion)>,
         save_function: impl Fn(&str, Value) -> Result<()>,
     ) -> Result<()> {
         let (x, y, z) = corrections.correction_in_robot.euler_angles();
@@ -138,6 +158,10 @@ impl SemiAutomaticCalibrationContext {
             serde_json::to_value([x, y, z])?,
         )?;
 
+        for (path, lens_distortion) in lens_distortions {
+            save_function(path, serde_json::to_value(lens_distortion)?)?;
+        }
+
         Ok(())
     }
 
@@ -148,27 +172,90 @@ impl SemiAutomaticCalibrationContext {
             .get_last_value()?
             .wrap_err("failed to get field dimensions")?;
 
-        let (corrections, report) = optimize(initial_corrections, field_dimensions, measurements)
-            .wrap_err("failed to optimize")?;
+        let estimated_parameters = EstimatedParameters {
+            lens_distortion: self.estimate_lens_distortion,
+            ..Default::default()
+        };
+        // the estimated distortion corrects the distortion the measurements were captured with
+        let lens_distortions: Vec<_> = if self.estimate_lens_distortion {
+            [
+                (CameraPosition::Top, CAMERA_TOP_LENS_DISTORTION_PATH),
+                (CameraPosition::Bottom, CAMERA_BOTTOM_LENS_DISTORTION_PATH),
+            ]
+            .into_iter()
+            .filter_map(|(position, path)| {
+                let original = measurements
+                    .iter()
+                    .find(|measurement| measurement.camera_position == position)?
+                    .camera_matrix
+                    .intrinsics
+                    .distortion;
+                Some((position, path, original))
+            })
+            .collect()
+        } else {
+            Vec::new()
+        };
 
-        self.apply_corrections(corrections, |path, value| {
-            self.nao.write(path, TextOrBinary::Text(value));
-            Ok(())
-        })?;
+        let (corrections, report) = optimize(
+            initial_corrections,
+            field_dimensions,
+            measurements,
+            estimated_parameters,
+        )
+        .wrap_err("failed to optimize")?;
+        let lens_distortions: Vec<_> = lens_distortions
+            .into_iter()
+            .map(|(position, path, original)| LensDistortionUpdate {
+                path,
+                original,
+                calibrated: correct_radial_distortion(
+                    original,
+                    match position {
+                        CameraPosition::Top => corrections.radial_distortion_top,
+                        CameraPosition::Bottom => corrections.radial_distortion_bottom,
+                    },
+                ),
+            })
+            .collect();
+
+        self.apply_corrections(
+            corrections,
+            lens_distortions
+                .iter()
+                .map(|update| (update.path, update.calibrated)),
+            |path, value| {
+                self.nao.write(path, TextOrBinary::Text(value));
+                Ok(())
+            },
+        )?;
         self.state = OptimizationState::Optimized {
             corrections,
             report,
+            lens_distortions,
         };
         Ok(())
     }
 
     pub fn reset(&mut self) -> Result<()> {
+        let lens_distortions = match &self.state {
+            OptimizationState::NotOptimized => Vec::new(),
+            OptimizationState::Optimized {
+                lens_distortions, ..
+            } => lens_distortions.clone(),
+        };
         self.state = OptimizationState::NotOptimized;
 
-        self.apply_corrections(Corrections::default(), |path, value| {
-            self.nao.write(path, TextOrBinary::Text(value));
-            Ok(())
-        })
+        self.apply_corrections(
+            Corrections::default(),
+            lens_distortions
+                .iter()
+                .map(|update| (update.path, update.original)),
+            |path, value| {
+                self.nao.write(path, TextOrBinary::Text(value));
+                Ok(())
+            },
+        )
     }
 
     pub fn is_converged(&self) -> bool {
@@ -179,12 +266,24 @@ impl SemiAutomaticCalibrationContext {
     }
 
     pub fn save_to_head(&self) -> Result<()> {
-        if let OptimizationState::Optimized { corrections, .. } = &self.state {
-            return self.apply_corrections(*corrections, |path, value| {
-                let parameter_path = path.strip_prefix("parameters.").wrap_err("invalid path")?;
-                self.nao
-                    .store_parameters(parameter_path, value, Scope::default_head())
-            });
+        if let OptimizationState::Optimized {
+            corrections,
+            lens_distortions,
+            ..
+        } = &self.state
+        {
+            return self.apply_corrections(
+                *corrections,
+                lens_distortions
+                    .iter()
+                    .map(|update| (update.path, update.calibrated)),
+                |path, value| {
+                    let parameter_path =
+                        path.strip_prefix("parameters.").wrap_err("invalid path")?;
+                    self.nao
+                        .store_parameters(parameter_path, value, Scope::default_head())
+                },
+            );
         }
         bail!("optimization is not done yet")
     }
@@ -194,6 +293,7 @@ fn optimize(
     initial_corrections: Corrections,
     field_dimensions: FieldDimensions,
     measurements: Vec<SavedMeasurement>,
+    estimated_parameters: EstimatedParameters,
 ) -> Result<(Corrections, MinimizationReport<f32>)> {
     let measurements = measurements
         .into_iter()
@@ -211,8 +311,12 @@ fn optimize(
         })
         .collect();
 
-    let problem =
-        CalibrationProblem::<Residuals>::new(initial_corrections, measurements, field_dimensions);
+    let problem = CalibrationProblem::<Residuals>::new(
+        initial_corrections,
+        measurements,
+        field_dimensions,
+        estimated_parameters,
+    );
     let (result, report) = LevenbergMarquardt::new().minimize(problem);
     let optimized_corrections = result.get_corrections();
     Ok((optimized_corrections, report))
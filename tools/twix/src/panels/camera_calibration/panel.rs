@@ -153,6 +153,10 @@ impl Widget for &mut SemiAutomaticCameraCalibrationPanel {
             }
         });
         ui.horizontal(|ui| {
+            ui.checkbox(
+                &mut self.optimization.estimate_lens_distortion,
+                "Estimate lens distortion",
+            );
             ui.label(format!("# drawn lines: {}", self.drawn_lines.len()));
             ui.label(format!("# measurements: {}", self.saved_measurements.len()));
         });
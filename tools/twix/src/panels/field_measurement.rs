@@ -0,0 +1,309 @@
+use std::sync::Arc;
+
+use color_eyre::{eyre::WrapErr, Result};
+use eframe::egui::{Button, DragValue, Grid, Response, Slider, Ui, Widget};
+use log::error;
+use serde::Serialize;
+use serde_json::Value;
+
+use calibration::field_measurement::{estimate_field_dimensions, Estimate, Measurement};
+use coordinate_systems::{Field, Ground};
+use geometry::line_segment::LineSegment;
+use linear_algebra::{vector, Isometry2};
+use parameters::directory::Scope;
+use types::{field_dimensions::FieldDimensions, line_data::LineData};
+
+use crate::{log_error::LogError, nao::Nao, panel::Panel, value_buffer::BufferHandle};
+
+const FIELD_DIMENSIONS_PATH: &str = "field_dimensions";
+const REPORT_FILE_NAME: &str = "field_measurement.json";
+const DEFAULT_MAXIMUM_ASSOCIATION_DISTANCE: f32 = 0.3;
+const DEFAULT_FRAMES_PER_CAPTURE: usize = 10;
+
+pub struct FieldMeasurementPanel {
+    nao: Arc<Nao>,
+    field_dimensions: BufferHandle<FieldDimensions>,
+    line_data_top: BufferHandle<Option<LineData>>,
+    line_data_bottom: BufferHandle<Option<LineData>>,
+
+    spot: Spot,
+    /// Distance from the center mark to the penalty marks, measured on the field
+    penalty_mark_distance: f32,
+    frames_per_capture: usize,
+    pending_capture: Option<PendingCapture>,
+    maximum_association_distance: f32,
+    measurements: Vec<Measurement>,
+    estimate: Option<Estimate>,
+}
+
+/// Lines collected over several frames, all of them enter the estimate to average out detection
+/// noise of single frames
+struct PendingCapture {
+    ground_to_field: Isometry2<Ground, Field>,
+    lines: Vec<LineSegment<Ground>>,
+    frames: usize,
+}
+
+/// Pose of the robot on the field while capturing
+#[derive(Clone, Copy, Default)]
+struct Spot {
+    x: f32,
+    y: f32,
+    orientation_degrees: f32,
+}
+
+#[derive(Serialize)]
+struct Report<'a> {
+    initial_field_dimensions: FieldDimensions,
+    measurements: &'a [Measurement],
+    estimate: &'a Estimate,
+}
+
+impl Panel for FieldMeasurementPanel {
+    const NAME: &'static str = "Field Measurement";
+
+    fn new(nao: Arc<Nao>, _value: Option<&Value>) -> Self {
+        let field_dimensions = nao.subscribe_value("parameters.field_dimensions");
+        let line_data_top = nao.subscribe_value("VisionTop.main_outputs.line_data");
+        let line_data_bottom = nao.subscribe_value("VisionBottom.main_outputs.line_data");
+
+        Self {
+            nao,
+            field_dimensions,
+            line_data_top,
+            line_data_bottom,
+            spot: Spot::default(),
+            penalty_mark_distance: 0.0,
+            frames_per_capture: DEFAULT_FRAMES_PER_CAPTURE,
+            pending_capture: None,
+            maximum_association_distance: DEFAULT_MAXIMUM_ASSOCIATION_DISTANCE,
+            measurements: Vec::new(),
+            estimate: None,
+        }
+    }
+}
+
+impl Widget for &mut FieldMeasurementPanel {
+    fn ui(self, ui: &mut Ui) -> Response {
+        ui.vertical(|ui| {
+            let field_dimensions = self.field_dimensions.get_last_value().ok().flatten();
+
+            ui.label("Place the robot at a known spot facing the given orientation");
+            ui.horizontal(|ui| {
+                ui.label("Spot");
+                ui.add(DragValue::new(&mut self.spot.x).speed(0.01).suffix(" m"));
+                ui.add(DragValue::new(&mut self.spot.y).speed(0.01).suffix(" m"));
+                ui.add(
+                    DragValue::new(&mut self.spot.orientation_degrees)
+                        .speed(1.0)
+                        .suffix("°"),
+                );
+            });
+            // spots must not depend on the dimensions being estimated, measure them on the field
+            ui.horizontal(|ui| {
+                ui.label("Measured center to penalty mark distance");
+                ui.add(
+                    DragValue::new(&mut self.penalty_mark_distance)
+                        .speed(0.01)
+                        .range(0.0..=f32::MAX)
+                        .suffix(" m"),
+                );
+            });
+            ui.horizontal(|ui| {
+                if ui.button("Center").clicked() {
+                    self.spot = Spot::default();
+                }
+                let penalty_mark_measured = self.penalty_mark_distance > 0.0;
+                if ui
+                    .add_enabled(penalty_mark_measured, Button::new("Own penalty spot"))
+                    .clicked()
+                {
+                    self.spot = Spot {
+                        x: -self.penalty_mark_distance,
+                        ..Spot::default()
+                    };
+                }
+                if ui
+                    .add_enabled(penalty_mark_measured, Button::new("Opponent penalty spot"))
+                    .clicked()
+                {
+                    self.spot = Spot {
+                        x: self.penalty_mark_distance,
+                        orientation_degrees: 180.0,
+                        ..Spot::default()
+                    };
+                }
+            });
+
+            self.collect_pending_capture();
+            ui.horizontal(|ui| {
+                ui.add(
+                    DragValue::new(&mut self.frames_per_capture)
+                        .range(1..=100)
+                        .suffix(" frames"),
+                );
+                if let Some(pending_capture) = &self.pending_capture {
+                    ui.label(format!(
+                        "Capturing {}/{}",
+                        pending_capture.frames, self.frames_per_capture
+                    ));
+                    ui.ctx().request_repaint();
+                } else if ui.button("Capture").clicked() {
+                    self.start_capture();
+                }
+                if ui.button("Clear").clicked() {
+                    self.pending_capture = None;
+                    self.measurements.clear();
+                    self.estimate = None;
+                }
+                let lines: usize = self
+                    .measurements
+                    .iter()
+                    .map(|measurement| measurement.lines.len())
+                    .sum();
+                ui.label(format!(
+                    "{} captures, {lines} lines",
+                    self.measurements.len()
+                ));
+            });
+
+            ui.add(
+                Slider::new(&mut self.maximum_association_distance, 0.05..=1.0)
+                    .text("Maximum association distance [m]"),
+            );
+
+            let Some(field_dimensions) = field_dimensions else {
+                ui.label("Field dimensions are not available");
+                return;
+            };
+            if ui.button("Estimate").clicked() {
+                self.estimate = estimate_field_dimensions(
+                    field_dimensions,
+                    &self.measurements,
+                    self.maximum_association_distance,
+                );
+                if self.estimate.is_none() {
+                    error!("failed to estimate field dimensions");
+                }
+            }
+
+            let Some(estimate) = &self.estimate else {
+                return;
+            };
+            ui.separator();
+            draw_estimate(ui, &field_dimensions, estimate);
+            ui.horizontal(|ui| {
+                if ui.button("Save to location").clicked() {
+                    self.save(field_dimensions, estimate).log_err();
+                }
+            });
+        })
+        .response
+    }
+}
+
+impl FieldMeasurementPanel {
+    fn start_capture(&mut self) {
+        // only frames arriving after the click belong to this spot
+        self.line_data_top.mark_as_seen();
+        self.line_data_bottom.mark_as_seen();
+        self.pending_capture = Some(PendingCapture {
+            ground_to_field: Isometry2::from_parts(
+                vector![self.spot.x, self.spot.y],
+                self.spot.orientation_degrees.to_radians(),
+            ),
+            lines: Vec::new(),
+            frames: 0,
+        });
+    }
+
+    fn collect_pending_capture(&mut self) {
+        let Some(pending_capture) = &mut self.pending_capture else {
+            return;
+        };
+        // a frame is complete once the top camera delivered new lines
+        if !self.line_data_top.has_changed() {
+            return;
+        }
+        for line_data in [&mut self.line_data_top, &mut self.line_data_bottom] {
+            if !line_data.has_changed() {
+                continue;
+            }
+            line_data.mark_as_seen();
+            if let Some(line_data) = line_data.get_last_value().ok().flatten().flatten() {
+                pending_capture.lines.extend(line_data.lines);
+            }
+        }
+        pending_capture.frames += 1;
+
+        if pending_capture.frames >= self.frames_per_capture {
+            let PendingCapture {
+                ground_to_field,
+                lines,
+                ..
+            } = self.pending_capture.take().unwrap();
+            self.measurements.push(Measurement {
+                ground_to_field,
+                lines,
+            });
+            self.estimate = None;
+        }
+    }
+
+    fn save(&self, initial_field_dimensions: FieldDimensions, estimate: &Estimate) -> Result<()> {
+        let report = Report {
+            initial_field_dimensions,
+            measurements: &self.measurements,
+            estimate,
+        };
+        let report_path = self
+            .nao
+            .location_directory()
+            .wrap_err("failed to find location parameter directory")?
+            .join(REPORT_FILE_NAME);
+        std::fs::write(&report_path, serde_json::to_string_pretty(&report)?)
+            .wrap_err_with(|| format!("failed to write {}", report_path.display()))?;
+        let value = serde_json::to_value(estimate.field_dimensions)?;
+        self.nao
+            .store_parameters(FIELD_DIMENSIONS_PATH, value, Scope::current_location())
+            .wrap_err("failed to store field dimensions")
+    }
+}
+
+fn draw_estimate(ui: &mut Ui, current: &FieldDimensions, estimate: &Estimate) {
+    let estimated = &estimate.field_dimensions;
+    Grid::new("field_dimensions_estimate").show(ui, |ui| {
+        ui.label("");
+        ui.label("Current");
+        ui.label("Estimated");
+        ui.end_row();
+        for (name, current, estimated) in [
+            ("Length", current.length, estimated.length),
+            ("Width", current.width, estimated.width),
+            (
+                "Penalty area length",
+                current.penalty_area_length,
+                estimated.penalty_area_length,
+            ),
+            (
+                "Penalty area width",
+                current.penalty_area_width,
+                estimated.penalty_area_width,
+            ),
+            (
+                "Center circle diameter",
+                current.center_circle_diameter,
+                estimated.center_circle_diameter,
+            ),
+        ] {
+            ui.label(name);
+            ui.label(format!("{current:.3} m"));
+            ui.label(format!("{estimated:.3} m"));
+            ui.end_row();
+        }
+    });
+    ui.label(format!(
+        "RMS error: {:.3} m, {} lines associated, {} ignored",
+        estimate.root_mean_square_error, estimate.associated_lines, estimate.unassociated_lines
+    ));
+}
@@ -18,6 +18,7 @@ mod pose_detection;
 mod referee_position;
 mod robot_pose;
 mod walking;
+mod whistle;
 
 pub use ball_filter::BallFilter;
 pub use ball_measurements::BallMeasurement;
@@ -39,3 +40,4 @@ pub use pose_detection::PoseDetection;
 pub use referee_position::RefereePosition;
 pub use robot_pose::RobotPose;
 pub use walking::Walking;
+pub use whistle::Whistle;
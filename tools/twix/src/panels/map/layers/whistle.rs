@@ -0,0 +1,64 @@
+use std::sync::Arc;
+
+use color_eyre::Result;
+use eframe::epaint::{Color32, Stroke};
+
+use coordinate_systems::{Field, Ground, Robot};
+use linear_algebra::{point, Isometry2, Isometry3};
+use types::{field_dimensions::FieldDimensions, filtered_whistle::FilteredWhistle};
+
+use crate::{
+    nao::Nao, panels::map::layer::Layer, twix_painter::TwixPainter, value_buffer::BufferHandle,
+};
+
+const BEARING_LENGTH: f32 = 1.5;
+
+pub struct Whistle {
+    robot_to_ground: BufferHandle<Option<Isometry3<Robot, Ground>>>,
+    ground_to_field: BufferHandle<Option<Isometry2<Ground, Field>>>,
+    filtered_whistle: BufferHandle<FilteredWhistle>,
+}
+
+impl Layer<Field> for Whistle {
+    const NAME: &'static str = "Whistle";
+
+    fn new(nao: Arc<Nao>) -> Self {
+        let robot_to_ground = nao.subscribe_value("Control.main_outputs.robot_to_ground");
+        let ground_to_field = nao.subscribe_value("Control.main_outputs.ground_to_field");
+        let filtered_whistle = nao.subscribe_value("Control.main_outputs.filtered_whistle");
+        Self {
+            robot_to_ground,
+            ground_to_field,
+            filtered_whistle,
+        }
+    }
+
+    fn paint(
+        &self,
+        painter: &TwixPainter<Field>,
+        _field_dimensions: &FieldDimensions,
+    ) -> Result<()> {
+        let Some(robot_to_ground) = self.robot_to_ground.get_last_value()?.flatten() else {
+            return Ok(());
+        };
+        let Some(ground_to_field) = self.ground_to_field.get_last_value()?.flatten() else {
+            return Ok(());
+        };
+        let Some(filtered_whistle) = self.filtered_whistle.get_last_value()? else {
+            return Ok(());
+        };
+        let Some(bearing) = filtered_whistle.bearing else {
+            return Ok(());
+        };
+
+        let start = ground_to_field * (robot_to_ground * point![0.0, 0.0, 0.0]).xy();
+        let end = start + bearing.as_unit_vector() * BEARING_LENGTH;
+        let color = if filtered_whistle.is_detected {
+            Color32::RED
+        } else {
+            Color32::GRAY
+        };
+        painter.line_segment(start, end, Stroke::new(0.03, color));
+        Ok(())
+    }
+}
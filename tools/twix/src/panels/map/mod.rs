@@ -86,6 +86,7 @@ pub struct MapPanel {
     ball_filter: EnabledLayer<layers::BallFilter, Ground>,
     obstacle_filter: EnabledLayer<layers::ObstacleFilter, Ground>,
     walking: EnabledLayer<layers::Walking, Ground>,
+    whistle: EnabledLayer<layers::Whistle, Field>,
     localization: EnabledLayer<layers::Localization, Field>,
 }
 
@@ -112,6 +113,7 @@ impl Panel for MapPanel {
         let ball_filter = EnabledLayer::new(nao.clone(), value, false);
         let obstacle_filter = EnabledLayer::new(nao.clone(), value, false);
         let walking = EnabledLayer::new(nao.clone(), value, false);
+        let whistle = EnabledLayer::new(nao.clone(), value, false);
         let localization = EnabledLayer::new(nao.clone(), value, false);
 
         let field_dimensions = nao.subscribe_value("parameters.field_dimensions");
@@ -150,6 +152,7 @@ impl Panel for MapPanel {
             ball_filter,
             obstacle_filter,
             walking,
+            whistle,
             localization,
         }
     }
@@ -178,6 +181,7 @@ impl Panel for MapPanel {
             "ball_filter": self.ball_filter.save(),
             "obstacle_filter": self.obstacle_filter.save(),
             "walking": self.walking.save(),
+            "whistle": self.whistle.save(),
             "localization": self.localization.save(),
         })
     }
@@ -206,6 +210,7 @@ impl Widget for &mut MapPanel {
                 self.ball_filter.checkbox(ui);
                 self.obstacle_filter.checkbox(ui);
                 self.walking.checkbox(ui);
+                self.whistle.checkbox(ui);
                 self.localization.checkbox(ui);
             });
             ComboBox::from_id_salt("plot_type_selector")
@@ -300,6 +305,8 @@ impl Widget for &mut MapPanel {
             .generic_paint(&painter, ground_to_field, &field_dimensions);
         self.walking
             .generic_paint(&painter, ground_to_field, &field_dimensions);
+        self.whistle
+            .generic_paint(&painter, ground_to_field, &field_dimensions);
         self.localization
             .generic_paint(&painter, ground_to_field, &field_dimensions);
 
@@ -1,8 +1,10 @@
 mod automatic_camera_calibration_export;
 mod ball_candidates;
 mod behavior_simulator;
+mod behavior_trace;
 mod camera_calibration;
 mod enum_plot;
+mod field_measurement;
 mod image;
 mod image_color_select;
 mod image_segments;
@@ -21,8 +23,10 @@ pub use automatic_camera_calibration_export::{
 };
 pub use ball_candidates::BallCandidatePanel;
 pub use behavior_simulator::BehaviorSimulatorPanel;
+pub use behavior_trace::BehaviorTracePanel;
 pub use camera_calibration::SemiAutomaticCameraCalibrationPanel;
 pub use enum_plot::EnumPlotPanel;
+pub use field_measurement::FieldMeasurementPanel;
 pub use image::ImagePanel;
 pub use image_color_select::ImageColorSelectPanel;
 pub use image_segments::ImageSegmentsPanel;
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
 use crate::{log_error::LogError, nao::Nao, panel::Panel, value_buffer::BufferHandle};
 use color_eyre::{
@@ -6,10 +6,12 @@ use color_eyre::{
     Result,
 };
 use communication::messages::TextOrBinary;
-use eframe::egui::{Response, ScrollArea, TextEdit, Ui, Widget};
+use eframe::egui::{CollapsingHeader, Color32, Response, ScrollArea, TextEdit, Ui, Widget};
+use hula_types::hardware::Ids;
 use hulk_widgets::{NaoPathCompletionEdit, PathFilter};
 use log::error;
-use parameters::directory::Scope;
+use parameters::{directory::Scope, provenance::Provenance};
+use path_serde::schema::{validate, FieldSchema, Violation};
 use serde_json::{json, Value};
 
 pub struct ParameterPanel {
@@ -17,6 +19,8 @@ pub struct ParameterPanel {
     path: String,
     buffer: Option<BufferHandle<Value>>,
     parameter_value: Result<String>,
+    hardware_ids: BufferHandle<Ids>,
+    provenance: Option<Result<Provenance>>,
 }
 
 impl Panel for ParameterPanel {
@@ -28,12 +32,15 @@ impl Panel for ParameterPanel {
             .and_then(|path| path.as_str());
 
         let value_buffer = path.map(|path| nao.subscribe_json(path));
+        let hardware_ids = nao.subscribe_value("hardware_ids");
 
         Self {
             nao,
             path: path.unwrap_or("").to_string(),
             buffer: value_buffer,
             parameter_value: Err(eyre!("no subscription")),
+            hardware_ids,
+            provenance: None,
         }
     }
     fn save(&self) -> Value {
@@ -43,8 +50,72 @@ impl Panel for ParameterPanel {
     }
 }
 
+impl ParameterPanel {
+    fn update_provenance(&mut self) {
+        let Some(local_parameter_path) = self.path.strip_prefix("parameters.") else {
+            self.provenance = None;
+            return;
+        };
+        self.provenance = match self.hardware_ids.get_last_value() {
+            Ok(Some(ids)) => Some(self.nao.explain_parameter(local_parameter_path, &ids)),
+            Ok(None) => None,
+            Err(error) => Some(Err(error)),
+        };
+    }
+
+    /// Schemas of the selected path and its fields, relative to the selected path
+    fn schema(&self) -> BTreeMap<String, FieldSchema> {
+        let paths = self.nao.latest_paths();
+        let Some(Ok(paths)) = paths.as_ref() else {
+            return BTreeMap::new();
+        };
+        paths
+            .range(self.path.clone()..)
+            .take_while(|(path, _)| path.starts_with(&self.path))
+            .filter_map(|(path, entry)| {
+                let relative_path = if *path == self.path {
+                    ""
+                } else {
+                    path.strip_prefix(&format!("{}.", self.path))?
+                };
+                Some((relative_path.to_string(), entry.schema.clone()?))
+            })
+            .collect()
+    }
+
+    fn violations(&self, schema: &BTreeMap<String, FieldSchema>) -> Vec<Violation> {
+        let Some(value) = self
+            .parameter_value
+            .as_ref()
+            .ok()
+            .and_then(|value| serde_json::from_str::<Value>(value).ok())
+        else {
+            return Vec::new();
+        };
+        let absolute_path = |path: String| {
+            if path.is_empty() {
+                self.path.clone()
+            } else {
+                format!("{}.{path}", self.path)
+            }
+        };
+        validate(schema, &value)
+            .into_iter()
+            .map(|violation| Violation {
+                path: absolute_path(violation.path),
+                minimum_field: violation
+                    .minimum_field
+                    .map(|(path, minimum)| (absolute_path(path), minimum)),
+                ..violation
+            })
+            .collect()
+    }
+}
+
 impl Widget for &mut ParameterPanel {
     fn ui(self, ui: &mut Ui) -> Response {
+        let schema = self.schema();
+        let violations = self.violations(&schema);
         ui.vertical(|ui| {
             ui.horizontal(|ui| {
                 let path_edit = ui.add(NaoPathCompletionEdit::new(
@@ -56,13 +127,22 @@ impl Widget for &mut ParameterPanel {
                 if path_edit.changed() {
                     self.buffer = Some(self.nao.subscribe_json(&self.path));
                 }
+                if path_edit.changed() || self.hardware_ids.has_changed() {
+                    self.hardware_ids.mark_as_seen();
+                    self.update_provenance();
+                }
+                if let Some(provenance) = &self.provenance {
+                    path_edit.on_hover_ui(|ui| draw_provenance(ui, provenance));
+                }
                 let settable = self.buffer.is_some()
                     && self
                         .parameter_value
                         .as_ref()
-                        .is_ok_and(|value| !value.is_empty());
+                        .is_ok_and(|value| !value.is_empty())
+                    && violations.is_empty();
                 let local_parameter_path = self.path.strip_prefix("parameters.");
 
+                let mut stored = false;
                 ui.add_enabled_ui(settable && local_parameter_path.is_some(), |ui| {
                     if ui.button("Set").clicked() {
                         let serialized =
@@ -90,6 +170,7 @@ impl Widget for &mut ParameterPanel {
                                         Scope::current_head(),
                                     )
                                     .log_err();
+                                stored = true;
                             }
                             Err(error) => error!(
                                 "parameter panel: failed to serialize parameter value: {error:#?}"
@@ -110,6 +191,7 @@ impl Widget for &mut ParameterPanel {
                                         Scope::current_body(),
                                     )
                                     .log_err();
+                                stored = true;
                             }
                             Err(error) => error!(
                                 "parameter panel: failed to serialize parameter value: {error:#?}"
@@ -117,7 +199,14 @@ impl Widget for &mut ParameterPanel {
                         }
                     }
                 });
+                if stored {
+                    self.update_provenance();
+                }
             });
+            draw_schema(ui, &schema);
+            for violation in &violations {
+                ui.colored_label(Color32::RED, violation.to_string());
+            }
 
             if let Some(buffer) = &mut self.buffer {
                 if buffer.has_changed() {
@@ -154,3 +243,74 @@ impl Widget for &mut ParameterPanel {
         .response
     }
 }
+
+fn draw_provenance(ui: &mut Ui, provenance: &Result<Provenance>) {
+    let provenance = match provenance {
+        Ok(provenance) => provenance,
+        Err(error) => {
+            ui.colored_label(Color32::RED, format!("{error:#}"));
+            return;
+        }
+    };
+    if provenance.chain.is_empty() {
+        ui.label("Not set by any parameter file");
+    }
+    for layer_value in &provenance.chain {
+        let file = layer_value
+            .file
+            .file_name()
+            .map(|file_name| file_name.to_string_lossy())
+            .unwrap_or_default();
+        ui.label(format!(
+            "{:?} ({file}): {}",
+            layer_value.layer, layer_value.value
+        ));
+    }
+    if let Some(effective) = &provenance.effective {
+        ui.strong(format!("Effective: {effective}"));
+    }
+}
+
+fn draw_schema(ui: &mut Ui, schema: &BTreeMap<String, FieldSchema>) {
+    if let Some(field_schema) = schema.get("") {
+        ui.label(format_schema(field_schema));
+    }
+    let fields: Vec<_> = schema.iter().filter(|(path, _)| !path.is_empty()).collect();
+    if fields.is_empty() {
+        return;
+    }
+    CollapsingHeader::new("Units and ranges")
+        .id_salt("parameter_schema")
+        .show(ui, |ui| {
+            for (path, field_schema) in fields {
+                ui.label(format!("{path}: {}", format_schema(field_schema)));
+            }
+        });
+}
+
+fn format_schema(schema: &FieldSchema) -> String {
+    let unit = schema.unit.as_deref().unwrap_or_default();
+    let mut parts = Vec::new();
+    if !unit.is_empty() {
+        parts.push(format!("[{unit}]"));
+    }
+    if schema.minimum.is_some() || schema.maximum.is_some() {
+        let format_bound =
+            |bound: Option<f64>| bound.map_or(String::new(), |bound| format!("{bound}{unit}"));
+        parts.push(format!(
+            "range {}..={}",
+            format_bound(schema.minimum),
+            format_bound(schema.maximum)
+        ));
+    }
+    if let Some(minimum_field) = &schema.minimum_field {
+        parts.push(format!("≥ {minimum_field}"));
+    }
+    if schema.ordered {
+        parts.push("start ≤ end".to_string());
+    }
+    if let Some(description) = &schema.description {
+        parts.push(description.clone());
+    }
+    parts.join(" ")
+}
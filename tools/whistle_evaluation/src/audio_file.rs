@@ -0,0 +1,97 @@
+use std::path::Path;
+
+use claxon::FlacReader;
+use color_eyre::{
+    eyre::{bail, WrapErr},
+    Result,
+};
+use hound::{SampleFormat, WavReader};
+
+use audio::whistle_detection::{AUDIO_SAMPLE_RATE, NUMBER_OF_AUDIO_CHANNELS};
+
+/// Non-interleaved samples of a recording, normalized to [-1, 1]
+pub struct Recording {
+    pub channels: Vec<Vec<f32>>,
+}
+
+impl Recording {
+    pub fn load(path: &Path) -> Result<Self> {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(|extension| extension.to_lowercase());
+        let (sample_rate, number_of_channels, interleaved) = match extension.as_deref() {
+            Some("wav") => read_wav(path)?,
+            Some("flac") => read_flac(path)?,
+            _ => bail!("unsupported audio file {}", path.display()),
+        };
+        if sample_rate != AUDIO_SAMPLE_RATE {
+            bail!(
+                "{} has a sample rate of {sample_rate} Hz but the whistle detection expects {AUDIO_SAMPLE_RATE} Hz",
+                path.display()
+            );
+        }
+
+        let mut channels = vec![Vec::new(); number_of_channels];
+        for (index, sample) in interleaved.into_iter().enumerate() {
+            channels[index % number_of_channels].push(sample);
+        }
+        // mono recordings are fed into every microphone such that the filter sees as many
+        // detections per buffer as on the robot
+        match number_of_channels {
+            NUMBER_OF_AUDIO_CHANNELS => {}
+            1 => channels = vec![channels.remove(0); NUMBER_OF_AUDIO_CHANNELS],
+            _ => bail!(
+                "{} has {number_of_channels} channels, expected 1 or {NUMBER_OF_AUDIO_CHANNELS}",
+                path.display()
+            ),
+        }
+        Ok(Self { channels })
+    }
+
+    pub fn duration(&self) -> f32 {
+        self.channels.first().map_or(0, Vec::len) as f32 / AUDIO_SAMPLE_RATE as f32
+    }
+}
+
+fn read_wav(path: &Path) -> Result<(u32, usize, Vec<f32>)> {
+    let mut reader =
+        WavReader::open(path).wrap_err_with(|| format!("failed to open {}", path.display()))?;
+    let specification = reader.spec();
+    let samples = match specification.sample_format {
+        SampleFormat::Float => reader
+            .samples::<f32>()
+            .collect::<Result<Vec<_>, _>>()
+            .wrap_err("failed to read samples")?,
+        SampleFormat::Int => {
+            let scale = (1_i64 << (specification.bits_per_sample - 1)) as f32;
+            reader
+                .samples::<i32>()
+                .map(|sample| sample.map(|sample| sample as f32 / scale))
+                .collect::<Result<Vec<_>, _>>()
+                .wrap_err("failed to read samples")?
+        }
+    };
+    Ok((
+        specification.sample_rate,
+        specification.channels as usize,
+        samples,
+    ))
+}
+
+fn read_flac(path: &Path) -> Result<(u32, usize, Vec<f32>)> {
+    let mut reader =
+        FlacReader::open(path).wrap_err_with(|| format!("failed to open {}", path.display()))?;
+    let information = reader.streaminfo();
+    let scale = (1_i64 << (information.bits_per_sample - 1)) as f32;
+    let samples = reader
+        .samples()
+        .map(|sample| sample.map(|sample| sample as f32 / scale))
+        .collect::<Result<Vec<_>, _>>()
+        .wrap_err("failed to read samples")?;
+    Ok((
+        information.sample_rate,
+        information.channels as usize,
+        samples,
+    ))
+}
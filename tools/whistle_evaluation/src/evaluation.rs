@@ -0,0 +1,260 @@
+use std::{
+    collections::BTreeMap,
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, UNIX_EPOCH},
+};
+
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use audio::{
+    whistle_classifier::{self, WhistleClassifier},
+    whistle_detection::{self, WhistleDetection, AUDIO_SAMPLE_RATE, NUMBER_OF_AUDIO_SAMPLES},
+};
+use control::whistle_filter::{self, WhistleFilter};
+use coordinate_systems::{Field, Head};
+use framework::{AdditionalOutput, PerceptionInput};
+use hardware::PathsInterface;
+use hula_types::hardware::Paths;
+use linear_algebra::{Point3, Vector2};
+use types::{
+    cycle_time::CycleTime,
+    parameters::{WhistleClassifierParameters, WhistleDetectionParameters},
+    robot_kinematics::RobotKinematics,
+    samples::Samples,
+};
+
+use crate::audio_file::Recording;
+
+/// Parameters of `control::whistle_filter` as found in the parameter files
+#[derive(Clone, Debug, Deserialize)]
+pub struct WhistleFilterParameters {
+    pub buffer_length: usize,
+    pub minimum_detections: usize,
+    pub microphone_positions: Vec<Point3<Head>>,
+    pub speed_of_sound: f32,
+    pub minimum_coherence: f32,
+    pub neighbouring_field_directions: Vec<Vector2<Field>>,
+    pub neighbouring_field_rejection_angle: f32,
+}
+
+/// Provides the neural network directory to the whistle classifier
+struct EvaluationPaths {
+    neural_networks: PathBuf,
+}
+
+impl PathsInterface for EvaluationPaths {
+    fn get_paths(&self) -> Paths {
+        Paths {
+            motions: PathBuf::new(),
+            neural_networks: self.neural_networks.clone(),
+            sounds: PathBuf::new(),
+        }
+    }
+}
+
+/// Labeled whistle in seconds since the start of the recording
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct Interval {
+    pub start: f32,
+    pub end: f32,
+}
+
+/// Feeds the recording buffer by buffer through the whistle classifier, the whistle detection
+/// and the whistle filter
+///
+/// Returns the times in seconds at which the filter started reporting a whistle.
+pub fn detect_whistles(
+    recording: &Recording,
+    detection_parameters: &WhistleDetectionParameters,
+    classifier_parameters: &WhistleClassifierParameters,
+    filter_parameters: &WhistleFilterParameters,
+    neural_networks: PathBuf,
+) -> Result<Vec<f32>> {
+    let paths = Arc::new(EvaluationPaths { neural_networks });
+    let mut classifier = WhistleClassifier::new(whistle_classifier::CreationContext::new(&paths))?;
+    let mut detection = WhistleDetection::new(whistle_detection::CreationContext::new())?;
+    let mut filter = WhistleFilter::new(whistle_filter::CreationContext::new())?;
+    let robot_kinematics = RobotKinematics::default();
+    let mut ground_to_field = None;
+    let buffer_duration =
+        Duration::from_secs_f32(NUMBER_OF_AUDIO_SAMPLES as f32 / AUDIO_SAMPLE_RATE as f32);
+
+    let number_of_buffers = recording
+        .channels
+        .first()
+        .map_or(0, |channel| channel.len() / NUMBER_OF_AUDIO_SAMPLES);
+    let mut detections = Vec::new();
+    for buffer_index in 0..number_of_buffers {
+        let samples_of_buffer =
+            buffer_index * NUMBER_OF_AUDIO_SAMPLES..(buffer_index + 1) * NUMBER_OF_AUDIO_SAMPLES;
+        let samples = Samples {
+            rate: AUDIO_SAMPLE_RATE,
+            channels_of_samples: Arc::new(
+                recording
+                    .channels
+                    .iter()
+                    .map(|channel| channel[samples_of_buffer.clone()].to_vec())
+                    .collect(),
+            ),
+        };
+        let mut whistle_probabilities = None;
+        let classified_whistle = classifier
+            .cycle(whistle_classifier::CycleContext::new(
+                &detection_parameters.detector,
+                classifier_parameters,
+                &samples,
+                AdditionalOutput::new(false, &mut whistle_probabilities),
+            ))?
+            .classified_whistle
+            .value;
+        let mut audio_spectrums = None;
+        let mut detection_infos = None;
+        let whistle = detection
+            .cycle(whistle_detection::CycleContext::new(
+                detection_parameters,
+                &samples,
+                classified_whistle.as_ref(),
+                AdditionalOutput::new(false, &mut audio_spectrums),
+                AdditionalOutput::new(false, &mut detection_infos),
+            ))?
+            .detected_whistle
+            .value;
+
+        let now = UNIX_EPOCH + buffer_duration * (buffer_index as u32 + 1);
+        let cycle_time = CycleTime {
+            start_time: now,
+            last_cycle_duration: buffer_duration,
+        };
+        let detected_whistle = PerceptionInput {
+            persistent: BTreeMap::from([(now, vec![&whistle])]),
+            temporary: BTreeMap::new(),
+        };
+        let filtered_whistle = filter
+            .cycle(whistle_filter::CycleContext::new(
+                &cycle_time,
+                &robot_kinematics,
+                None,
+                &mut ground_to_field,
+                &filter_parameters.buffer_length,
+                &filter_parameters.minimum_detections,
+                &filter_parameters.microphone_positions,
+                &filter_parameters.speed_of_sound,
+                &filter_parameters.minimum_coherence,
+                &filter_parameters.neighbouring_field_directions,
+                &filter_parameters.neighbouring_field_rejection_angle,
+                detected_whistle,
+            ))?
+            .filtered_whistle
+            .value;
+        if filtered_whistle.is_detected && filtered_whistle.last_detection == Some(now) {
+            let seconds = now
+                .duration_since(UNIX_EPOCH)
+                .expect("time ran backwards")
+                .as_secs_f32();
+            detections.push(seconds);
+        }
+    }
+    Ok(detections)
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct Score {
+    pub whistles: usize,
+    pub hits: usize,
+    pub false_positives: usize,
+    /// Duration of all evaluated recordings in seconds
+    pub duration: f32,
+    /// Time from the labeled start of each detected whistle until the filter reported it
+    pub latencies: Vec<f32>,
+}
+
+impl Score {
+    /// Matches detections to labels, a detection up to `tolerance` seconds after the end of a
+    /// whistle still counts as hit
+    pub fn new(detections: &[f32], labels: &[Interval], tolerance: f32, duration: f32) -> Self {
+        let mut is_hit = vec![false; labels.len()];
+        let mut score = Self {
+            whistles: labels.len(),
+            duration,
+            ..Default::default()
+        };
+        for &detection in detections {
+            let matching_label = labels
+                .iter()
+                .position(|label| (label.start..=label.end + tolerance).contains(&detection));
+            match matching_label {
+                Some(index) if !is_hit[index] => {
+                    is_hit[index] = true;
+                    score.hits += 1;
+                    score.latencies.push(detection - labels[index].start);
+                }
+                Some(_) => {}
+                None => score.false_positives += 1,
+            }
+        }
+        score
+    }
+
+    pub fn merge(mut self, other: Self) -> Self {
+        self.whistles += other.whistles;
+        self.hits += other.hits;
+        self.false_positives += other.false_positives;
+        self.duration += other.duration;
+        self.latencies.extend(other.latencies);
+        self
+    }
+
+    pub fn misses(&self) -> usize {
+        self.whistles - self.hits
+    }
+
+    pub fn false_positives_per_minute(&self) -> f32 {
+        if self.duration <= 0.0 {
+            return 0.0;
+        }
+        self.false_positives as f32 / (self.duration / 60.0)
+    }
+
+    pub fn mean_latency(&self) -> Option<f32> {
+        (!self.latencies.is_empty())
+            .then(|| self.latencies.iter().sum::<f32>() / self.latencies.len() as f32)
+    }
+
+    pub fn maximum_latency(&self) -> Option<f32> {
+        self.latencies.iter().copied().reduce(f32::max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detections_are_matched_to_labels() {
+        let labels = [
+            Interval {
+                start: 1.0,
+                end: 2.0,
+            },
+            Interval {
+                start: 10.0,
+                end: 11.0,
+            },
+            Interval {
+                start: 20.0,
+                end: 21.0,
+            },
+        ];
+        let detections = [1.3, 1.5, 5.0, 11.5];
+
+        let score = Score::new(&detections, &labels, 1.0, 60.0);
+
+        assert_eq!(score.hits, 2);
+        assert_eq!(score.misses(), 1);
+        assert_eq!(score.false_positives, 1);
+        assert_eq!(score.false_positives_per_minute(), 1.0);
+        assert_eq!(score.maximum_latency(), Some(1.5));
+    }
+}
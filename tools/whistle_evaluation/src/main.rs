@@ -0,0 +1,245 @@
+use std::{
+    env::current_dir,
+    fs::File,
+    path::{Path, PathBuf},
+};
+
+use clap::Parser;
+use color_eyre::{
+    eyre::{bail, WrapErr},
+    Result,
+};
+use itertools::Itertools;
+use path_serde::PathDeserialize;
+use serde::Serialize;
+use serde_json::{from_reader, from_value, Value};
+
+use repository::Repository;
+use types::parameters::{WhistleClassifierParameters, WhistleDetectionParameters};
+
+use crate::{
+    audio_file::Recording,
+    evaluation::{detect_whistles, Interval, Score, WhistleFilterParameters},
+};
+
+mod audio_file;
+mod evaluation;
+
+/// Evaluate the whistle detection and whistle filter on labeled recordings
+///
+/// Each recording `<name>.wav` or `<name>.flac` needs a label file `<name>.json` next to it
+/// containing a list of whistles, e.g. `[{ "start": 3.2, "end": 4.0 }]` in seconds.
+#[derive(Parser)]
+struct Arguments {
+    /// WAV or FLAC recordings with 44.1 kHz and either one or four channels
+    #[arg(required = true)]
+    recordings: Vec<PathBuf>,
+    /// Parameter file to read `whistle_detection`, `whistle_classifier` and `whistle_filter` from
+    #[arg(long)]
+    parameters: Option<PathBuf>,
+    /// Directory containing the network of the learned whistle classifier
+    #[arg(long)]
+    neural_networks: Option<PathBuf>,
+    /// Sweep a whistle detection parameter, e.g. `whistle_scaling=3.0,3.5,4.0` or
+    /// `detector="Spectral","Learned"`
+    #[arg(long, value_parser = parse_sweep)]
+    sweep: Vec<Sweep>,
+    /// Seconds after the end of a whistle in which a detection still counts
+    #[arg(long, default_value = "1.0")]
+    tolerance: f32,
+    /// Output results as json
+    #[arg(long, short = 'j')]
+    json: bool,
+}
+
+#[derive(Clone)]
+struct Sweep {
+    path: String,
+    values: Vec<Value>,
+}
+
+fn parse_sweep(argument: &str) -> Result<Sweep, String> {
+    let (path, values) = argument
+        .split_once('=')
+        .ok_or("expected sweep in the form `path=value,value,...`")?;
+    let values = values
+        .split(',')
+        .map(|value| serde_json::from_str(value.trim()))
+        .collect::<Result<_, _>>()
+        .map_err(|error| format!("failed to parse sweep values: {error}"))?;
+    Ok(Sweep {
+        path: path.to_string(),
+        values,
+    })
+}
+
+struct LabeledRecording {
+    path: PathBuf,
+    recording: Recording,
+    labels: Vec<Interval>,
+}
+
+#[derive(Serialize)]
+struct Evaluation {
+    assignments: Vec<(String, Value)>,
+    score: Score,
+}
+
+fn main() -> Result<()> {
+    color_eyre::install()?;
+    let arguments = Arguments::parse();
+
+    let repository_root = || -> Result<PathBuf> {
+        let current_directory = current_dir().wrap_err("failed to get current directory")?;
+        let repository =
+            Repository::find_root(current_directory).wrap_err("failed to find repository root")?;
+        Ok(repository.root)
+    };
+    let parameters_path = match arguments.parameters {
+        Some(path) => path,
+        None => repository_root()?.join("etc/parameters/default.json"),
+    };
+    let neural_networks = match arguments.neural_networks {
+        Some(path) => path,
+        None => repository_root()?.join("etc/neural_networks"),
+    };
+    let (detection_parameters, classifier_parameters, filter_parameters) =
+        read_parameters(&parameters_path)?;
+
+    let recordings = arguments
+        .recordings
+        .iter()
+        .map(|path| {
+            let recording = Recording::load(path)?;
+            let labels = read_labels(path)?;
+            Ok(LabeledRecording {
+                path: path.clone(),
+                recording,
+                labels,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let combinations: Vec<Vec<(String, Value)>> = if arguments.sweep.is_empty() {
+        vec![Vec::new()]
+    } else {
+        arguments
+            .sweep
+            .iter()
+            .map(|sweep| {
+                sweep
+                    .values
+                    .iter()
+                    .map(|value| (sweep.path.clone(), value.clone()))
+            })
+            .multi_cartesian_product()
+            .collect()
+    };
+
+    let mut evaluations = Vec::new();
+    for assignments in combinations {
+        let mut detection_parameters = detection_parameters.clone();
+        for (path, value) in &assignments {
+            detection_parameters
+                .deserialize_path(path, value.clone())
+                .wrap_err_with(|| format!("failed to set {path} to {value}"))?;
+        }
+        let mut score = Score::default();
+        for labeled_recording in &recordings {
+            let detections = detect_whistles(
+                &labeled_recording.recording,
+                &detection_parameters,
+                &classifier_parameters,
+                &filter_parameters,
+                neural_networks.clone(),
+            )
+            .wrap_err_with(|| format!("failed to evaluate {}", labeled_recording.path.display()))?;
+            score = score.merge(Score::new(
+                &detections,
+                &labeled_recording.labels,
+                arguments.tolerance,
+                labeled_recording.recording.duration(),
+            ));
+        }
+        evaluations.push(Evaluation { assignments, score });
+    }
+
+    if arguments.json {
+        let json = serde_json::to_string(&evaluations).wrap_err("failed to serialize results")?;
+        println!("{json}");
+    } else {
+        print_evaluations(&evaluations);
+    }
+    Ok(())
+}
+
+fn read_parameters(
+    path: &Path,
+) -> Result<(
+    WhistleDetectionParameters,
+    WhistleClassifierParameters,
+    WhistleFilterParameters,
+)> {
+    let file = File::open(path).wrap_err_with(|| format!("failed to open {}", path.display()))?;
+    let mut parameters: Value =
+        from_reader(file).wrap_err_with(|| format!("failed to parse {}", path.display()))?;
+    let detection_parameters = from_value(parameters["whistle_detection"].take())
+        .wrap_err("failed to parse whistle_detection parameters")?;
+    let classifier_parameters = from_value(parameters["whistle_classifier"].take())
+        .wrap_err("failed to parse whistle_classifier parameters")?;
+    let filter_parameters = from_value(parameters["whistle_filter"].take())
+        .wrap_err("failed to parse whistle_filter parameters")?;
+    Ok((
+        detection_parameters,
+        classifier_parameters,
+        filter_parameters,
+    ))
+}
+
+fn read_labels(recording_path: &Path) -> Result<Vec<Interval>> {
+    let path = recording_path.with_extension("json");
+    let file =
+        File::open(&path).wrap_err_with(|| format!("failed to open labels {}", path.display()))?;
+    let labels: Vec<Interval> =
+        from_reader(file).wrap_err_with(|| format!("failed to parse labels {}", path.display()))?;
+    if let Some(label) = labels.iter().find(|label| label.end < label.start) {
+        bail!(
+            "whistle in {} ends at {} before it starts at {}",
+            path.display(),
+            label.end,
+            label.start
+        );
+    }
+    Ok(labels)
+}
+
+fn print_evaluations(evaluations: &[Evaluation]) {
+    println!(
+        "{:<40} {:>9} {:>7} {:>8} {:>13} {:>12}",
+        "parameters", "hits", "misses", "FP/min", "mean latency", "max latency"
+    );
+    for evaluation in evaluations {
+        let assignments = if evaluation.assignments.is_empty() {
+            "defaults".to_string()
+        } else {
+            evaluation
+                .assignments
+                .iter()
+                .map(|(path, value)| format!("{path}={value}"))
+                .join(" ")
+        };
+        let score = &evaluation.score;
+        let format_latency = |latency: Option<f32>| {
+            latency.map_or("-".to_string(), |latency| format!("{latency:.2}s"))
+        };
+        println!(
+            "{:<40} {:>9} {:>7} {:>8.2} {:>13} {:>12}",
+            assignments,
+            format!("{}/{}", score.hits, score.whistles),
+            score.misses(),
+            score.false_positives_per_minute(),
+            format_latency(score.mean_latency()),
+            format_latency(score.maximum_latency()),
+        );
+    }
+}